@@ -0,0 +1,28 @@
+//! Embeds the git commit the contract was built from as `PIFP_GIT_COMMIT`,
+//! read back by `PifpProtocol::version`.
+//!
+//! An explicit `PIFP_GIT_COMMIT` in the build environment wins, so
+//! reproducible builds outside a checkout can still record their source.
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=PIFP_GIT_COMMIT");
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    println!("cargo:rerun-if-changed=../../.git/refs/heads");
+
+    let commit = std::env::var("PIFP_GIT_COMMIT").ok().or_else(|| {
+        let output = Command::new("git")
+            .args(["rev-parse", "--short=12", "HEAD"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+    });
+    println!(
+        "cargo:rustc-env=PIFP_GIT_COMMIT={}",
+        commit.unwrap_or_else(|| "unknown".to_string())
+    );
+}
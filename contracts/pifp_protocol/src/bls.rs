@@ -0,0 +1,92 @@
+//! # BLS aggregate attestations
+//!
+//! Lets a set of oracles attest a project with a single aggregated BLS12-381
+//! signature instead of one `verify_and_release` call each.
+//!
+//! Oracle public keys are G1 points (96-byte uncompressed encoding) registered
+//! by an Admin; signatures are G2 points (192 bytes) over the message returned
+//! by [`attestation_payload`], hashed to G2 with [`DST`]. An aggregate
+//! signature is valid when
+//!
+//! ```text
+//! e(pk_1 + … + pk_n, H(m)) == e(G1, sig)
+//! ```
+//!
+//! which is checked as a single two-pair `pairing_check` against the negated
+//! G1 generator.
+//!
+//! The payload binds the network (its passphrase hash, `network_id`) and
+//! this contract's address, so an aggregate produced for a testnet or a
+//! forked deployment never verifies against another deployment's escrow.
+//!
+//! Aggregating public keys is only safe against rogue-key attacks when every
+//! registered key has a proven proof-of-possession. Admins must verify that
+//! off-chain before calling `set_oracle_bls_key`.
+
+use soroban_sdk::{
+    crypto::bls12_381::{G1Affine, G2Affine},
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Vec,
+};
+
+/// Domain separation tag for hashing attestation payloads to G2.
+pub const DST: &[u8] = b"PIFP-V01-CS01-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+/// Uncompressed encoding of the negated BLS12-381 G1 generator.
+const NEG_G1_GENERATOR: [u8; 96] = [
+    0x17, 0xf1, 0xd3, 0xa7, 0x31, 0x97, 0xd7, 0x94, 0x26, 0x95, 0x63, 0x8c, 0x4f, 0xa9, 0xac, 0x0f,
+    0xc3, 0x68, 0x8c, 0x4f, 0x97, 0x74, 0xb9, 0x05, 0xa1, 0x4e, 0x3a, 0x3f, 0x17, 0x1b, 0xac, 0x58,
+    0x6c, 0x55, 0xe8, 0x3f, 0xf9, 0x7a, 0x1a, 0xef, 0xfb, 0x3a, 0xf0, 0x0a, 0xdb, 0x22, 0xc6, 0xbb,
+    0x11, 0x4d, 0x1d, 0x68, 0x55, 0xd5, 0x45, 0xa8, 0xaa, 0x7d, 0x76, 0xc8, 0xcf, 0x2e, 0x21, 0xf2,
+    0x67, 0x81, 0x6a, 0xef, 0x1d, 0xb5, 0x07, 0xc9, 0x66, 0x55, 0xb9, 0xd5, 0xca, 0xac, 0x42, 0x36,
+    0x4e, 0x6f, 0x38, 0xba, 0x0e, 0xcb, 0x75, 0x1b, 0xad, 0x54, 0xdc, 0xd6, 0xb9, 0x39, 0xc2, 0xca,
+];
+
+/// The message oracles sign to attest `proof_hash` for `project_id` on the
+/// deployment at `contract`: the 32-byte network ID, the XDR of `contract`,
+/// the big-endian project ID and the 32-byte proof hash.
+pub fn attestation_payload(
+    env: &Env,
+    contract: &Address,
+    project_id: u64,
+    proof_hash: &BytesN<32>,
+) -> Bytes {
+    let mut payload = Bytes::from(env.ledger().network_id());
+    payload.append(&contract.clone().to_xdr(env));
+    payload.extend_from_array(&project_id.to_be_bytes());
+    payload.append(&Bytes::from(proof_hash.clone()));
+    payload
+}
+
+/// Verify `signature` as an aggregate over `payload` by every key in
+/// `public_keys`. Returns `false` for an empty key set.
+pub fn verify_aggregate(
+    env: &Env,
+    public_keys: &Vec<BytesN<96>>,
+    payload: &Bytes,
+    signature: &BytesN<192>,
+) -> bool {
+    let bls = env.crypto().bls12_381();
+
+    let mut aggregate: Option<G1Affine> = None;
+    for key in public_keys.iter() {
+        let point = G1Affine::from_bytes(key);
+        aggregate = Some(match aggregate {
+            Some(acc) => bls.g1_add(&acc, &point),
+            None => point,
+        });
+    }
+    let aggregate = match aggregate {
+        Some(aggregate) => aggregate,
+        None => return false,
+    };
+
+    let message = bls.hash_to_g2(payload, &Bytes::from_slice(env, DST));
+    let neg_generator = G1Affine::from_bytes(BytesN::from_array(env, &NEG_G1_GENERATOR));
+    let signature = G2Affine::from_bytes(signature.clone());
+
+    bls.pairing_check(
+        soroban_sdk::vec![env, aggregate, neg_generator],
+        soroban_sdk::vec![env, message, signature],
+    )
+}
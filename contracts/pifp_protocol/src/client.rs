@@ -0,0 +1,198 @@
+//! # Off-chain client helpers
+//!
+//! Enabled with the `client` cargo feature. Intended for indexers, bots and
+//! other Rust services that consume PIFP events from RPC (`getEvents`) or
+//! ledger meta and would otherwise hand-roll `contracttype` decoding.
+//!
+//! Everything here works on plain XDR values and needs no [`soroban_sdk::Env`]:
+//!
+//! - [`decode_event`] / [`decode_contract_event`] turn a raw event into a
+//!   typed [`PifpEvent`] (`created`, `funded`, `verified`).
+//! - [`format_amount`] / [`parse_amount`] convert between raw token units
+//!   and decimal strings.
+
+extern crate std;
+
+use std::string::String;
+use std::vec::Vec;
+
+use soroban_sdk::xdr::{
+    ContractEvent, ContractEventBody, Int128Parts, Limits, ReadXdr, ScAddress, ScVal,
+};
+
+/// Decoded `created` event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatedEvent {
+    pub project_id: u64,
+    pub creator: ScAddress,
+    pub token: ScAddress,
+    pub goal: i128,
+}
+
+/// Decoded `funded` event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundedEvent {
+    pub project_id: u64,
+    pub donator: ScAddress,
+    pub amount: i128,
+}
+
+/// Decoded `verified` event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifiedEvent {
+    pub project_id: u64,
+    pub oracle: ScAddress,
+    pub proof_hash: [u8; 32],
+}
+
+/// A PIFP event this module knows how to decode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PifpEvent {
+    Created(CreatedEvent),
+    Funded(FundedEvent),
+    Verified(VerifiedEvent),
+}
+
+/// Decode an event from its topics and data.
+///
+/// Returns `None` for events this module does not model, or whose payload
+/// does not match the expected shape.
+pub fn decode_event(topics: &[ScVal], data: &ScVal) -> Option<PifpEvent> {
+    let name = symbol(topics.first()?)?;
+    match name {
+        "created" => Some(PifpEvent::Created(CreatedEvent {
+            project_id: u64_of(field(data, "project_id")?)?,
+            creator: address(field(data, "creator")?)?,
+            token: address(field(data, "token")?)?,
+            goal: i128_of(field(data, "goal")?)?,
+        })),
+        "funded" => Some(PifpEvent::Funded(FundedEvent {
+            project_id: u64_of(field(data, "project_id")?)?,
+            donator: address(field(data, "donator")?)?,
+            amount: i128_of(field(data, "amount")?)?,
+        })),
+        "verified" => Some(PifpEvent::Verified(VerifiedEvent {
+            project_id: u64_of(field(data, "project_id")?)?,
+            oracle: address(field(data, "oracle")?)?,
+            proof_hash: bytes32(field(data, "proof_hash")?)?,
+        })),
+        _ => None,
+    }
+}
+
+/// Decode a [`ContractEvent`] as found in transaction meta.
+///
+/// The emitting contract is not checked; callers should filter by contract
+/// ID first.
+pub fn decode_contract_event(event: &ContractEvent) -> Option<PifpEvent> {
+    let ContractEventBody::V0(body) = &event.body;
+    decode_event(&body.topics, &body.data)
+}
+
+/// Decode a [`ContractEvent`] from its binary XDR encoding.
+pub fn decode_contract_event_xdr(xdr: &[u8]) -> Option<PifpEvent> {
+    let event = ContractEvent::from_xdr(xdr, Limits::none()).ok()?;
+    decode_contract_event(&event)
+}
+
+/// Render `amount` raw token units as a decimal string with `decimals`
+/// fractional digits, e.g. `format_amount(12_345_000, 7) == "1.2345"`.
+/// Trailing fractional zeros are dropped.
+pub fn format_amount(amount: i128, decimals: u32) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    let abs = amount.unsigned_abs();
+    let scale = 10u128.pow(decimals);
+    let whole = abs / scale;
+    let frac = abs % scale;
+    if frac == 0 {
+        return std::format!("{sign}{whole}");
+    }
+    let frac = std::format!("{:0width$}", frac, width = decimals as usize);
+    std::format!("{sign}{whole}.{}", frac.trim_end_matches('0'))
+}
+
+/// Parse a decimal string into raw token units with `decimals` fractional
+/// digits. Returns `None` on malformed input, excess precision or overflow.
+pub fn parse_amount(s: &str, decimals: u32) -> Option<i128> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (whole, frac) = match digits.split_once('.') {
+        Some((whole, frac)) => (whole, frac),
+        None => (digits, ""),
+    };
+    if whole.is_empty() || frac.len() > decimals as usize {
+        return None;
+    }
+    if !whole
+        .bytes()
+        .chain(frac.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let mut padded: Vec<u8> = frac.bytes().collect();
+    padded.resize(decimals as usize, b'0');
+    let scale = 10i128.checked_pow(decimals)?;
+    let whole: i128 = whole.parse().ok()?;
+    let frac: i128 = if padded.is_empty() {
+        0
+    } else {
+        std::str::from_utf8(&padded).ok()?.parse().ok()?
+    };
+    let value = whole.checked_mul(scale)?.checked_add(frac)?;
+    Some(if negative { -value } else { value })
+}
+
+// ─────────────────────────────────────────────────────────
+// ScVal accessors
+// ─────────────────────────────────────────────────────────
+
+fn symbol(val: &ScVal) -> Option<&str> {
+    match val {
+        ScVal::Symbol(sym) => std::str::from_utf8(sym.0.as_slice()).ok(),
+        _ => None,
+    }
+}
+
+/// Look up `name` in a `contracttype` struct, which is encoded as a map
+/// keyed by field-name symbols.
+fn field<'a>(val: &'a ScVal, name: &str) -> Option<&'a ScVal> {
+    match val {
+        ScVal::Map(Some(map)) => map
+            .0
+            .iter()
+            .find(|entry| symbol(&entry.key) == Some(name))
+            .map(|entry| &entry.val),
+        _ => None,
+    }
+}
+
+fn u64_of(val: &ScVal) -> Option<u64> {
+    match val {
+        ScVal::U64(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn i128_of(val: &ScVal) -> Option<i128> {
+    match val {
+        ScVal::I128(Int128Parts { hi, lo }) => Some(((*hi as i128) << 64) | (*lo as i128)),
+        _ => None,
+    }
+}
+
+fn address(val: &ScVal) -> Option<ScAddress> {
+    match val {
+        ScVal::Address(addr) => Some(addr.clone()),
+        _ => None,
+    }
+}
+
+fn bytes32(val: &ScVal) -> Option<[u8; 32]> {
+    match val {
+        ScVal::Bytes(bytes) => bytes.0.as_slice().try_into().ok(),
+        _ => None,
+    }
+}
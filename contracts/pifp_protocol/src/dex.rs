@@ -0,0 +1,51 @@
+//! # Fee conversion
+//!
+//! Optional conversion of protocol fee revenue into a single reserve token.
+//!
+//! When `ProtocolConfig::reserve_token` is set, fees paid in any other token
+//! are held by the contract instead of being sent to the treasury. Once a
+//! token's held fees reach `ProtocolConfig::conversion_threshold`, anyone
+//! may call `convert_fees` to swap them through `ProtocolConfig::dex_router`
+//! and pay the proceeds to the treasury. The swap is bounded by the price
+//! feed's quote less `ProtocolConfig::max_slippage_bps`; a worse fill
+//! reverts and the fees stay held.
+//!
+//! The router is expected to be a thin adapter in front of a specific DEX.
+//! The input tokens are transferred to it before [`DexRouterInterface::swap`]
+//! is called, and what the treasury actually received is measured rather
+//! than taken from the router's return value.
+
+use soroban_sdk::{contractclient, token, Address, Env};
+
+/// Swap interface a DEX adapter implements for PIFP.
+#[contractclient(name = "DexRouterClient")]
+pub trait DexRouterInterface {
+    /// Swap `amount_in` of `token_in`, already transferred to the router,
+    /// for at least `min_out` of `token_out` paid to `to`. Returns the
+    /// amount paid.
+    fn swap(
+        env: Env,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_out: i128,
+        to: Address,
+    ) -> i128;
+}
+
+/// Call `router` to swap `amount_in` of `token_in` it already holds into
+/// `token_out` for `to`. Returns the amount of `token_out` `to` received.
+pub fn swap(
+    env: &Env,
+    router: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: i128,
+    min_out: i128,
+    to: &Address,
+) -> i128 {
+    let out = token::Client::new(env, token_out);
+    let before = out.balance(to);
+    DexRouterClient::new(env, router).swap(token_in, token_out, &amount_in, &min_out, to);
+    out.balance(to) - before
+}
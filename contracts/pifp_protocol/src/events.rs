@@ -1,4 +1,9 @@
-use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::types::{
+    ContractVersion, Council, DeadManSwitch, DonorPreferences, GrantRoute, MaintenanceWindow,
+    Milestone, ProjectMetadata, ProjectStatus, RecoveryRequest, TimelockedAction, TokenBalance,
+};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -25,6 +30,75 @@ pub struct ProjectVerified {
     pub proof_hash: BytesN<32>,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectAttested {
+    pub project_id: u64,
+    pub oracle: Address,
+    pub attestations: u32,
+    pub quorum: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifierSet {
+    pub project_id: u64,
+    pub verifier: Address,
+    pub vk_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectReattested {
+    pub project_id: u64,
+    pub oracle: Address,
+    pub next_due: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RentPaid {
+    pub project_id: u64,
+    pub keeper: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusForceQueued {
+    pub project_id: u64,
+    pub new_status: ProjectStatus,
+    pub reason_hash: BytesN<32>,
+    pub eta: u64,
+    pub by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusForced {
+    pub project_id: u64,
+    pub from: ProjectStatus,
+    pub to: ProjectStatus,
+    pub reason_hash: BytesN<32>,
+    pub by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectCleaned {
+    pub project_id: u64,
+    pub entries_removed: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatorFlagged {
+    pub creator: Address,
+    pub flagged_by: Address,
+    pub fraud_flags: u32,
+}
+
 pub fn emit_project_created(
     env: &Env,
     project_id: u64,
@@ -61,3 +135,817 @@ pub fn emit_project_verified(env: &Env, project_id: u64, oracle: Address, proof_
     };
     env.events().publish(topics, data);
 }
+
+/// Emitted when a creator submits proof of impact for oracle review.
+pub fn emit_proof_submitted(env: &Env, project_id: u64, creator: Address, proof_hash: BytesN<32>) {
+    let topics = (symbol_short!("proof_sub"), project_id);
+    env.events().publish(topics, (creator, proof_hash));
+}
+
+/// Emitted when an oracle rejects a submitted proof.
+pub fn emit_proof_rejected(
+    env: &Env,
+    project_id: u64,
+    oracle: Address,
+    proof_hash: BytesN<32>,
+    reason_hash: BytesN<32>,
+) {
+    let topics = (symbol_short!("proof_rej"), project_id);
+    env.events()
+        .publish(topics, (oracle, proof_hash, reason_hash));
+}
+
+pub fn emit_project_attested(
+    env: &Env,
+    project_id: u64,
+    oracle: Address,
+    attestations: u32,
+    quorum: u32,
+) {
+    let topics = (symbol_short!("attested"), project_id);
+    let data = ProjectAttested {
+        project_id,
+        oracle,
+        attestations,
+        quorum,
+    };
+    env.events().publish(topics, data);
+}
+
+/// Emitted when an admin assigns oracles to a project; an empty list means
+/// any oracle may attest again.
+pub fn emit_project_oracles_set(env: &Env, project_id: u64, oracles: Vec<Address>) {
+    let topics = (symbol_short!("oracles"), project_id);
+    env.events().publish(topics, oracles);
+}
+
+pub fn emit_verifier_set(env: &Env, project_id: u64, verifier: Address, vk_hash: BytesN<32>) {
+    let topics = (symbol_short!("vrf_set"), project_id);
+    let data = VerifierSet {
+        project_id,
+        verifier,
+        vk_hash,
+    };
+    env.events().publish(topics, data);
+}
+
+/// Emitted when a verifier contract is added to or removed from the allowlist.
+pub fn emit_verifier_approval(env: &Env, verifier: Address, approved: bool, by: Address) {
+    let topics = (symbol_short!("vrf_appr"), verifier);
+    env.events().publish(topics, (approved, by));
+}
+
+/// Emitted when the protocol-wide pause switch is flipped. `reason_hash`
+/// and `expected_resume` are only set when pausing.
+pub fn emit_protocol_paused(
+    env: &Env,
+    paused: bool,
+    by: Address,
+    reason_hash: Option<BytesN<32>>,
+    expected_resume: Option<u64>,
+) {
+    let topics = (symbol_short!("pause"),);
+    env.events()
+        .publish(topics, (paused, by, reason_hash, expected_resume));
+}
+
+/// Emitted when the SuperAdmin role moves to a new address. Published in
+/// addition to the generic `role_del`/`role_set` pair so monitors can alert
+/// on this action specifically.
+pub fn emit_super_admin_transferred(env: &Env, old: Address, new: Address) {
+    let topics = (symbol_short!("sa_xfer"),);
+    env.events().publish(topics, (old, new));
+}
+
+/// Emitted when the SuperAdmin swaps out a compromised oracle. High
+/// severity: monitors should alert on it. `cancelled` counts the pending
+/// attestations of `old` that were discarded.
+pub fn emit_oracle_replaced(env: &Env, old: Address, new: Address, cancelled: u32) {
+    let topics = (symbol_short!("ora_emerg"),);
+    env.events().publish(topics, (old, new, cancelled));
+}
+
+/// Emitted when the SuperAdmin configures the recovery guardians.
+pub fn emit_guardians_set(env: &Env, guardians: Vec<Address>, threshold: u32, delay: u64) {
+    let topics = (symbol_short!("guardians"),);
+    env.events().publish(topics, (guardians, threshold, delay));
+}
+
+/// Emitted when a guardian approves a SuperAdmin recovery. The data carries
+/// the request including every approval so far.
+pub fn emit_recovery_approved(env: &Env, guardian: Address, request: RecoveryRequest) {
+    let topics = (symbol_short!("recovery"), guardian);
+    env.events().publish(topics, request);
+}
+
+/// Emitted when the SuperAdmin cancels a pending recovery.
+pub fn emit_recovery_cancelled(env: &Env, by: Address) {
+    let topics = (symbol_short!("recov_del"),);
+    env.events().publish(topics, by);
+}
+
+/// Emitted when a pending recovery is executed. A `sa_xfer` event for the
+/// rotation itself precedes it.
+pub fn emit_recovered(env: &Env, new_super_admin: Address, by: Address) {
+    let topics = (symbol_short!("recovered"),);
+    env.events().publish(topics, (new_super_admin, by));
+}
+
+/// Emitted when the SuperAdmin configures or removes the dead-man switch.
+pub fn emit_dead_man_set(env: &Env, switch: Option<DeadManSwitch>) {
+    let topics = (symbol_short!("deadman"),);
+    env.events().publish(topics, switch);
+}
+
+/// Emitted when the successor announces a claim on a lapsed dead-man
+/// switch. Any SuperAdmin heartbeat before `eta` voids the claim.
+pub fn emit_claim_announced(env: &Env, successor: Address, eta: u64) {
+    let topics = (symbol_short!("dm_claim"), successor);
+    env.events().publish(topics, eta);
+}
+
+/// Emitted when the successor takes over the SuperAdmin role. A `sa_xfer`
+/// event for the rotation itself precedes it.
+pub fn emit_dead_man_claimed(env: &Env, old: Address, successor: Address) {
+    let topics = (symbol_short!("dm_taken"),);
+    env.events().publish(topics, (old, successor));
+}
+
+/// Emitted when a maintenance window is scheduled, well before it starts.
+pub fn emit_maintenance_scheduled(env: &Env, window: MaintenanceWindow) {
+    let topics = (symbol_short!("maint"),);
+    env.events().publish(topics, window);
+}
+
+/// Emitted when a scheduled maintenance window is cancelled.
+pub fn emit_maintenance_cancelled(env: &Env, by: Address) {
+    let topics = (symbol_short!("maint_del"),);
+    env.events().publish(topics, by);
+}
+
+/// Emitted when a single project is paused or unpaused.
+pub fn emit_project_paused(env: &Env, project_id: u64, paused: bool, by: Address) {
+    let topics = (symbol_short!("prj_pause"), project_id);
+    env.events().publish(topics, (paused, by));
+}
+
+/// Emitted when deposits of `token` are suspended or resumed.
+pub fn emit_token_paused(env: &Env, token: Address, paused: bool, by: Address) {
+    let topics = (symbol_short!("tok_pause"), token);
+    env.events().publish(topics, (paused, by));
+}
+
+/// Emitted when a token is added to (`allowed = true`) or removed from the
+/// registration allowlist.
+pub fn emit_token_allowed(env: &Env, token: Address, allowed: bool, by: Address) {
+    let topics = (symbol_short!("tok_allow"), token);
+    env.events().publish(topics, (allowed, by));
+}
+
+/// Emitted when an address is frozen pending compliance review.
+pub fn emit_address_frozen(env: &Env, address: Address, reason_hash: BytesN<32>, by: Address) {
+    let topics = (symbol_short!("frozen"), address);
+    env.events().publish(topics, (reason_hash, by));
+}
+
+/// Emitted when a compliance freeze is lifted.
+pub fn emit_address_unfrozen(env: &Env, address: Address, by: Address) {
+    let topics = (symbol_short!("unfrozen"), address);
+    env.events().publish(topics, by);
+}
+
+/// Emitted when the SuperAdmin queues a forced status change.
+pub fn emit_status_force_queued(
+    env: &Env,
+    project_id: u64,
+    new_status: ProjectStatus,
+    reason_hash: BytesN<32>,
+    eta: u64,
+    by: Address,
+) {
+    let topics = (symbol_short!("force_req"), project_id);
+    let data = StatusForceQueued {
+        project_id,
+        new_status,
+        reason_hash,
+        eta,
+        by,
+    };
+    env.events().publish(topics, data);
+}
+
+/// Emitted when a forced status change takes effect.
+pub fn emit_status_forced(
+    env: &Env,
+    project_id: u64,
+    from: ProjectStatus,
+    to: ProjectStatus,
+    reason_hash: BytesN<32>,
+    by: Address,
+) {
+    let topics = (symbol_short!("forced"), project_id);
+    let data = StatusForced {
+        project_id,
+        from,
+        to,
+        reason_hash,
+        by,
+    };
+    env.events().publish(topics, data);
+}
+
+/// Emitted when a queued forced status change is withdrawn.
+pub fn emit_status_force_cancelled(env: &Env, project_id: u64, by: Address) {
+    let topics = (symbol_short!("force_del"), project_id);
+    env.events().publish(topics, by);
+}
+
+/// Emitted when the SuperAdmin queues moving a project to `successor`.
+pub fn emit_migration_queued(env: &Env, project_id: u64, successor: Address, eta: u64) {
+    let topics = (symbol_short!("migr_req"), project_id);
+    env.events().publish(topics, (successor, eta));
+}
+
+/// Emitted when a queued escrow migration is withdrawn.
+pub fn emit_migration_cancelled(env: &Env, project_id: u64, by: Address) {
+    let topics = (symbol_short!("migr_del"), project_id);
+    env.events().publish(topics, by);
+}
+
+/// Emitted when a project's escrow has been moved to `successor`.
+pub fn emit_escrow_migrated(
+    env: &Env,
+    project_id: u64,
+    successor: Address,
+    balances: Vec<TokenBalance>,
+) {
+    let topics = (symbol_short!("migrated"), project_id);
+    env.events().publish(topics, (successor, balances));
+}
+
+/// Emitted when the SuperAdmin starts winding the deployment down.
+pub fn emit_sunset_begun(env: &Env, by: Address) {
+    let topics = (symbol_short!("sunset"),);
+    env.events().publish(topics, by);
+}
+
+/// Emitted when residual, non-escrowed funds are swept during wind-down.
+pub fn emit_residual_swept(env: &Env, token: Address, to: Address, amount: i128) {
+    let topics = (symbol_short!("swept"), token);
+    env.events().publish(topics, (to, amount));
+}
+
+/// Transfer-shaped companion event for every escrow inflow and outflow.
+///
+/// Mirrors the Stellar Asset Contract `transfer` event — topics
+/// `("transfer", from, to, token)`, data `amount` — so generic analytics
+/// pipelines pick up PIFP flows. The fourth topic is the token contract
+/// address rather than the SEP-11 asset string, which would cost a
+/// cross-contract `name()` call per transfer.
+pub fn emit_escrow_transfer(env: &Env, token: Address, from: Address, to: Address, amount: i128) {
+    let topics = (symbol_short!("transfer"), from, to, token);
+    env.events().publish(topics, amount);
+}
+
+/// Emitted when a project's controlling owner set changes.
+pub fn emit_owners_set(env: &Env, project_id: u64, owners: Vec<Address>, threshold: u32) {
+    let topics = (symbol_short!("owners"), project_id);
+    env.events().publish(topics, (owners, threshold));
+}
+
+/// Emitted for each token paid to the creator when a verified project
+/// releases escrow.
+/// Emitted when a creator withdraws released funds held for them.
+pub fn emit_funds_withdrawn(
+    env: &Env,
+    project_id: u64,
+    creator: Address,
+    token: Address,
+    amount: i128,
+) {
+    let topics = (symbol_short!("withdrawn"), project_id, token);
+    env.events().publish(topics, (creator, amount));
+}
+
+pub fn emit_funds_released(
+    env: &Env,
+    project_id: u64,
+    creator: Address,
+    token: Address,
+    amount: i128,
+) {
+    let topics = (symbol_short!("released"), project_id);
+    env.events().publish(topics, (creator, token, amount));
+}
+
+/// Emitted when a project's milestone `index` is verified and its share of
+/// the escrow released.
+pub fn emit_milestone_released(env: &Env, project_id: u64, index: u32) {
+    let topics = (symbol_short!("milestone"), project_id);
+    env.events().publish(topics, index);
+}
+
+/// Emitted when a project's disbursement schedule releases one or more
+/// tranches; `released_bps` is the cumulative share released.
+pub fn emit_tranche_released(env: &Env, project_id: u64, released_bps: u32) {
+    let topics = (symbol_short!("tranche"), project_id);
+    env.events().publish(topics, released_bps);
+}
+
+/// Emitted when a project's config and state are extended, either by
+/// `bump_project` or automatically once their TTL falls below the threshold.
+/// `live_until` is the ledger sequence they now live until.
+pub fn emit_ttl_extended(env: &Env, project_id: u64, live_until: u32) {
+    let topics = (symbol_short!("ttl_bump"), project_id);
+    env.events().publish(topics, live_until);
+}
+
+/// Emitted once when the contract is initialised, recording the semantic
+/// version and storage schema that were deployed. The git commit is left
+/// to the `version` query.
+pub fn emit_initialized(env: &Env, super_admin: Address, version: ContractVersion) {
+    let topics = (symbol_short!("init"),);
+    env.events()
+        .publish(topics, (super_admin, version.version, version.schema));
+}
+
+/// Emitted when the SuperAdmin or a council member queues a timelocked action.
+pub fn emit_action_queued(
+    env: &Env,
+    action_id: u64,
+    action: TimelockedAction,
+    eta: u64,
+    by: Address,
+) {
+    let topics = (symbol_short!("act_queue"), action_id);
+    env.events().publish(topics, (action, eta, by));
+}
+
+/// Emitted when a timelocked action is applied.
+pub fn emit_action_executed(env: &Env, action_id: u64, action: TimelockedAction) {
+    let topics = (symbol_short!("act_exec"), action_id);
+    env.events().publish(topics, action);
+}
+
+/// Emitted when the SuperAdmin or a council member withdraws a queued action.
+pub fn emit_action_cancelled(env: &Env, action_id: u64, by: Address) {
+    let topics = (symbol_short!("act_cncl"), action_id);
+    env.events().publish(topics, by);
+}
+
+/// Emitted when a council member approves a queued action; carries the
+/// approvals collected so far.
+pub fn emit_action_approved(env: &Env, action_id: u64, by: Address, approvals: u32) {
+    let topics = (symbol_short!("act_appr"), action_id);
+    env.events().publish(topics, (by, approvals));
+}
+
+/// Emitted when the council holding SuperAdmin is set or replaced.
+pub fn emit_council_set(env: &Env, council: Council) {
+    let topics = (symbol_short!("council"),);
+    env.events()
+        .publish(topics, (council.members, council.threshold));
+}
+
+/// Emitted when the SuperAdmin replaces the contract's Wasm.
+pub fn emit_upgraded(env: &Env, by: Address, new_wasm_hash: BytesN<32>) {
+    let topics = (symbol_short!("upgraded"),);
+    env.events().publish(topics, (by, new_wasm_hash));
+}
+
+/// Emitted when storage has been migrated from schema `from` to `version`.
+pub fn emit_schema_migrated(env: &Env, from: u32, version: ContractVersion) {
+    let topics = (symbol_short!("schema"),);
+    env.events().publish(topics, (from, version));
+}
+
+/// Emitted when a project's dependencies are declared or cleared.
+pub fn emit_dependencies_set(env: &Env, project_id: u64, deps: Vec<u64>) {
+    let topics = (symbol_short!("depends"), project_id);
+    env.events().publish(topics, deps);
+}
+
+/// Emitted when a creator registers a handle for a project.
+pub fn emit_handle_set(env: &Env, project_id: u64, creator: Address, handle: Symbol) {
+    let topics = (symbol_short!("handle"), project_id);
+    env.events().publish(topics, (creator, handle));
+}
+
+/// Emitted when a project's donor allowlist is turned on or off.
+pub fn emit_donor_allowlist_set(env: &Env, project_id: u64, enabled: bool, by: Address) {
+    let topics = (symbol_short!("allowlist"), project_id);
+    env.events().publish(topics, (enabled, by));
+}
+
+/// Emitted when `donor` is added to or removed from a project's donor
+/// allowlist.
+pub fn emit_allowed_donor_set(
+    env: &Env,
+    project_id: u64,
+    donor: Address,
+    allowed: bool,
+    by: Address,
+) {
+    let topics = (symbol_short!("donor_acl"), project_id, donor);
+    env.events().publish(topics, (allowed, by));
+}
+
+/// Emitted when a project's creator sets or changes its display metadata.
+pub fn emit_metadata_updated(env: &Env, project_id: u64, metadata: ProjectMetadata) {
+    let topics = (symbol_short!("metadata"), project_id);
+    env.events()
+        .publish(topics, (metadata.name, metadata.metadata_hash));
+}
+
+/// Emitted when a currency-locked project's first deposit fixes `token` as
+/// its only accepted token.
+pub fn emit_currency_locked(env: &Env, project_id: u64, token: Address) {
+    let topics = (symbol_short!("cur_lock"), project_id);
+    env.events().publish(topics, token);
+}
+
+/// Emitted when a project opts into donor protection.
+pub fn emit_project_insured(env: &Env, project_id: u64, premium_bps: u32) {
+    let topics = (symbol_short!("insured"), project_id);
+    env.events().publish(topics, premium_bps);
+}
+
+/// Emitted when a deposit into an insured project pays its premium into the
+/// insurance pool.
+pub fn emit_premium_paid(env: &Env, project_id: u64, token: Address, premium: i128) {
+    let topics = (symbol_short!("premium"), project_id);
+    env.events().publish(topics, (token, premium));
+}
+
+/// Emitted when a project's fee override is set (`Some`) or cleared (`None`).
+pub fn emit_fee_override_set(env: &Env, project_id: u64, fee_bps: Option<u32>) {
+    let topics = (symbol_short!("fee_ovr"), project_id);
+    env.events().publish(topics, fee_bps);
+}
+
+/// Emitted when a project's per-donor match cap changes; `0` turns
+/// matching off.
+pub fn emit_match_cap_set(env: &Env, project_id: u64, cap: i128) {
+    let topics = (symbol_short!("match_cap"), project_id);
+    env.events().publish(topics, cap);
+}
+
+/// Emitted when part of a deposit is tagged for matching. `eligible` is the
+/// portion of this deposit that will be matched.
+pub fn emit_match_eligible(env: &Env, project_id: u64, donor: Address, eligible: i128) {
+    let topics = (symbol_short!("matchable"), project_id);
+    env.events().publish(topics, (donor, eligible));
+}
+
+/// Emitted when a sponsor opens a matching pool for a project.
+pub fn emit_match_pool_created(
+    env: &Env,
+    pool_id: u64,
+    project_id: u64,
+    sponsor: Address,
+    token: Address,
+    ratio_bps: u32,
+    cap: i128,
+) {
+    let topics = (symbol_short!("mpool_new"), project_id, pool_id);
+    env.events()
+        .publish(topics, (sponsor, token, ratio_bps, cap));
+}
+
+/// Emitted when a sponsor adds funds to a matching pool.
+pub fn emit_match_pool_funded(env: &Env, pool_id: u64, sponsor: Address, amount: i128) {
+    let topics = (symbol_short!("mpool_fnd"), pool_id);
+    env.events().publish(topics, (sponsor, amount));
+}
+
+/// Emitted when a matching pool adds `amount` to a donor's deposit.
+pub fn emit_donation_matched(
+    env: &Env,
+    project_id: u64,
+    pool_id: u64,
+    donor: Address,
+    amount: i128,
+) {
+    let topics = (symbol_short!("matched"), project_id, pool_id);
+    env.events().publish(topics, (donor, amount));
+}
+
+/// Emitted when a sponsor takes back a matching pool's unspent funds.
+pub fn emit_match_pool_closed(env: &Env, pool_id: u64, sponsor: Address, amount: i128) {
+    let topics = (symbol_short!("mpool_end"), pool_id);
+    env.events().publish(topics, (sponsor, amount));
+}
+
+/// Emitted when a deposit pays the protocol fee, whether sent to the
+/// treasury or held for conversion.
+pub fn emit_fee_paid(env: &Env, project_id: u64, token: Address, fee: i128) {
+    let topics = (symbol_short!("fee"), project_id);
+    env.events().publish(topics, (token, fee));
+}
+
+/// Emitted when a release pays the protocol fee, held for the treasury.
+pub fn emit_release_fee_paid(env: &Env, project_id: u64, token: Address, fee: i128) {
+    let topics = (symbol_short!("rel_fee"), project_id);
+    env.events().publish(topics, (token, fee));
+}
+
+/// Emitted when an admin pays held fees of `token` out to the treasury.
+pub fn emit_treasury_withdrawn(env: &Env, token: Address, treasury: Address, amount: i128) {
+    let topics = (symbol_short!("treas_wd"), token);
+    env.events().publish(topics, (treasury, amount));
+}
+
+/// Emitted when held fees of `token` are paid to the treasury as
+/// `amount_out` of `reserve`. `reserve` equals `token` when conversion was
+/// switched off and the fees were paid out unconverted.
+pub fn emit_fees_converted(
+    env: &Env,
+    token: Address,
+    reserve: Address,
+    amount_in: i128,
+    amount_out: i128,
+) {
+    let topics = (symbol_short!("converted"), token);
+    env.events()
+        .publish(topics, (reserve, amount_in, amount_out));
+}
+
+/// Emitted when quorum is met on a project that opted into a donor vote.
+pub fn emit_vote_opened(env: &Env, project_id: u64, ends_at: u64, total_weight: i128) {
+    let topics = (symbol_short!("vote_open"), project_id);
+    env.events().publish(topics, (ends_at, total_weight));
+}
+
+/// Emitted when a donor objects to a pending release.
+pub fn emit_release_objected(env: &Env, project_id: u64, donor: Address, weight: i128) {
+    let topics = (symbol_short!("objected"), project_id);
+    env.events().publish(topics, (donor, weight));
+}
+
+/// Emitted when a donor vote closes; `contested` routes it to an admin.
+pub fn emit_vote_closed(env: &Env, project_id: u64, contested: bool, objections: i128) {
+    let topics = (symbol_short!("vote_end"), project_id);
+    env.events().publish(topics, (contested, objections));
+}
+
+/// Emitted when a verified release enters its challenge window.
+pub fn emit_challenge_opened(env: &Env, project_id: u64, released_bps: u32, ends_at: u64) {
+    let topics = (symbol_short!("chal_open"), project_id);
+    env.events().publish(topics, (released_bps, ends_at));
+}
+
+/// Emitted when an auditor disputes a release during its challenge window.
+pub fn emit_dispute_raised(
+    env: &Env,
+    project_id: u64,
+    auditor: Address,
+    evidence_hash: BytesN<32>,
+) {
+    let topics = (symbol_short!("disputed"), project_id);
+    env.events().publish(topics, (auditor, evidence_hash));
+}
+
+/// Emitted when an admin resolves a disputed release.
+pub fn emit_dispute_resolved(env: &Env, project_id: u64, release: bool, by: Address) {
+    let topics = (symbol_short!("disp_res"), project_id);
+    env.events().publish(topics, (release, by));
+}
+
+/// Emitted when an admin resolves a contested release.
+pub fn emit_contest_resolved(env: &Env, project_id: u64, release: bool, by: Address) {
+    let topics = (symbol_short!("resolved"), project_id);
+    env.events().publish(topics, (release, by));
+}
+
+/// Emitted when a donor backs extending a project's missed deadline.
+pub fn emit_extension_backed(
+    env: &Env,
+    project_id: u64,
+    donor: Address,
+    new_deadline: u64,
+    weight: i128,
+) {
+    let topics = (symbol_short!("ext_vote"), project_id);
+    env.events().publish(topics, (donor, new_deadline, weight));
+}
+
+/// Emitted when a project's deadline is extended.
+pub fn emit_deadline_extended(env: &Env, project_id: u64, old_deadline: u64, new_deadline: u64) {
+    let topics = (symbol_short!("extended"), project_id);
+    env.events().publish(topics, (old_deadline, new_deadline));
+}
+
+/// Emitted when a creator proposes replacing a project's milestones.
+pub fn emit_amendment_proposed(
+    env: &Env,
+    project_id: u64,
+    milestones: Vec<Milestone>,
+    ends_at: u64,
+) {
+    let topics = (symbol_short!("amend_req"), project_id);
+    env.events().publish(topics, (milestones, ends_at));
+}
+
+/// Emitted when a donor backs a project's milestone amendment.
+pub fn emit_amendment_backed(env: &Env, project_id: u64, donor: Address, weight: i128) {
+    let topics = (symbol_short!("amend_vot"), project_id);
+    env.events().publish(topics, (donor, weight));
+}
+
+/// Emitted when a milestone amendment is approved or rejected. `by` is the
+/// deciding admin, or `None` when donor backing carried it.
+pub fn emit_amendment_resolved(env: &Env, project_id: u64, approved: bool, by: Option<Address>) {
+    let topics = (symbol_short!("amended"), project_id);
+    env.events().publish(topics, (approved, by));
+}
+
+/// Emitted alongside `funded` when a price feed valued the deposit.
+pub fn emit_deposit_valued(
+    env: &Env,
+    project_id: u64,
+    donor: Address,
+    token: Address,
+    amount: i128,
+    value: i128,
+) {
+    let topics = (symbol_short!("valued"), project_id);
+    env.events().publish(topics, (donor, token, amount, value));
+}
+
+pub fn emit_project_reattested(env: &Env, project_id: u64, oracle: Address, next_due: u64) {
+    let topics = (symbol_short!("reattest"), project_id);
+    let data = ProjectReattested {
+        project_id,
+        oracle,
+        next_due,
+    };
+    env.events().publish(topics, data);
+}
+
+/// Emitted when a settled project's leftover balance in `token` is moved to
+/// the dust ledger.
+pub fn emit_dust_recorded(env: &Env, project_id: u64, token: Address, amount: i128) {
+    let topics = (symbol_short!("dust"), project_id);
+    env.events().publish(topics, (token, amount));
+}
+
+/// Emitted when the dust ledger of `token` is swept to the treasury.
+pub fn emit_dust_swept(env: &Env, token: Address, treasury: Address, amount: i128) {
+    let topics = (symbol_short!("dust_swp"), token);
+    env.events().publish(topics, (treasury, amount));
+}
+
+/// Emitted when a donor updates their privacy preferences.
+pub fn emit_donor_preferences_set(env: &Env, donor: Address, prefs: DonorPreferences) {
+    let topics = (symbol_short!("prefs"), donor);
+    env.events().publish(topics, prefs);
+}
+
+/// Emitted when a donor contract registers (`true`) or removes (`false`)
+/// its deposit hook.
+pub fn emit_deposit_hook_set(env: &Env, donor: Address, enabled: bool) {
+    let topics = (symbol_short!("hook"), donor);
+    env.events().publish(topics, enabled);
+}
+
+/// Emitted when the release throttle holds back a release of `project_id`
+/// up to `released_bps`.
+pub fn emit_release_queued(env: &Env, project_id: u64, released_bps: u32) {
+    let topics = (symbol_short!("rel_queue"), project_id);
+    env.events().publish(topics, released_bps);
+}
+
+/// Emitted when someone tops up the refund sponsorship pool.
+pub fn emit_sponsor_pool_funded(env: &Env, funder: Address, token: Address, amount: i128) {
+    let topics = (symbol_short!("sp_fund"), token);
+    env.events().publish(topics, (funder, amount));
+}
+
+/// Emitted when a donor flags `amount` of a deposit as non-refundable.
+pub fn emit_grant_pledged(
+    env: &Env,
+    project_id: u64,
+    donor: Address,
+    token: Address,
+    amount: i128,
+    route: GrantRoute,
+) {
+    let topics = (symbol_short!("granted"), project_id);
+    env.events().publish(topics, (donor, token, amount, route));
+}
+
+/// Emitted when an expired project pays out a donor's grant per its route.
+pub fn emit_grant_routed(
+    env: &Env,
+    project_id: u64,
+    donor: Address,
+    token: Address,
+    amount: i128,
+    route: GrantRoute,
+) {
+    let topics = (symbol_short!("routed"), project_id);
+    env.events().publish(topics, (donor, token, amount, route));
+}
+
+/// Emitted when admins grant `amount` of `token` from the commons pool.
+pub fn emit_commons_disbursed(env: &Env, token: Address, recipient: Address, amount: i128) {
+    let topics = (symbol_short!("commons"), token);
+    env.events().publish(topics, (recipient, amount));
+}
+
+/// Emitted when a donor's small refund claim on `project_id` is reimbursed.
+pub fn emit_refund_sponsored(
+    env: &Env,
+    project_id: u64,
+    donor: Address,
+    token: Address,
+    amount: i128,
+) {
+    let topics = (symbol_short!("sponsored"), project_id);
+    env.events().publish(topics, (donor, token, amount));
+}
+
+/// Emitted when a project passes its deadline without completing.
+pub fn emit_project_expired(env: &Env, project_id: u64) {
+    let topics = (symbol_short!("expired"), project_id);
+    env.events().publish(topics, ());
+}
+
+/// Emitted when a donor reclaims `amount` of `token` from an expired project.
+pub fn emit_refunded(env: &Env, project_id: u64, donor: Address, token: Address, amount: i128) {
+    let topics = (symbol_short!("refunded"), project_id);
+    env.events().publish(topics, (donor, token, amount));
+}
+
+/// Emitted when someone tops up the keeper reward pool.
+pub fn emit_keeper_pool_funded(env: &Env, funder: Address, token: Address, amount: i128) {
+    let topics = (symbol_short!("kp_fund"), token);
+    env.events().publish(topics, (funder, amount));
+}
+
+/// Emitted when a keeper is rewarded for a crank call on `project_id`.
+pub fn emit_keeper_rewarded(
+    env: &Env,
+    project_id: u64,
+    keeper: Address,
+    token: Address,
+    amount: i128,
+) {
+    let topics = (symbol_short!("keeper"), project_id);
+    env.events().publish(topics, (keeper, token, amount));
+}
+
+/// Emitted when `sponsor` adds to a project's rent fund.
+pub fn emit_rent_sponsored(
+    env: &Env,
+    project_id: u64,
+    sponsor: Address,
+    token: Address,
+    amount: i128,
+) {
+    let topics = (symbol_short!("rent_spon"), project_id);
+    env.events().publish(topics, (sponsor, token, amount));
+}
+
+/// Emitted when a sponsor takes back its share of a settled project's
+/// unspent rent fund.
+pub fn emit_rent_reclaimed(
+    env: &Env,
+    project_id: u64,
+    sponsor: Address,
+    token: Address,
+    amount: i128,
+) {
+    let topics = (symbol_short!("rent_back"), project_id);
+    env.events().publish(topics, (sponsor, token, amount));
+}
+
+pub fn emit_rent_paid(env: &Env, project_id: u64, keeper: Address, token: Address, amount: i128) {
+    let topics = (symbol_short!("rent_paid"), project_id);
+    let data = RentPaid {
+        project_id,
+        keeper,
+        token,
+        amount,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_project_cleaned(env: &Env, project_id: u64, entries_removed: u32) {
+    let topics = (symbol_short!("cleaned"), project_id);
+    let data = ProjectCleaned {
+        project_id,
+        entries_removed,
+    };
+    env.events().publish(topics, data);
+}
+
+pub fn emit_creator_flagged(env: &Env, creator: Address, flagged_by: Address, fraud_flags: u32) {
+    let topics = (symbol_short!("flagged"), creator.clone());
+    let data = CreatorFlagged {
+        creator,
+        flagged_by,
+        fraud_flags,
+    };
+    env.events().publish(topics, data);
+}
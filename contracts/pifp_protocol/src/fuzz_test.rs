@@ -1,22 +1,29 @@
-
 extern crate std;
 use std::vec::Vec;
 
 use proptest::prelude::*;
-use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env};
+use soroban_sdk::{
+    testutils::{Address as _, EnvTestConfig, Ledger},
+    token, vec, Address, BytesN, Env,
+};
 
 use crate::invariants::*;
-use crate::types::ProjectStatus;
-use crate::{PifpProtocol, PifpProtocolClient};
+use crate::types::{Project, ProjectStatus};
+use crate::{PifpProtocol, PifpProtocolClient, Role};
 
 // ── Helpers ─────────────────────────────────────────────────────────
 
-fn setup_env() -> (Env, PifpProtocolClient<'static>) {
-    let env = Env::default();
+fn setup_env() -> (Env, PifpProtocolClient<'static>, Address) {
+    // Generated cases differ run to run; don't write a snapshot per case.
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
     env.mock_all_auths();
     let contract_id = env.register(PifpProtocol, ());
     let client = PifpProtocolClient::new(&env, &contract_id);
-    (env, client)
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
 }
 
 fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
@@ -24,6 +31,39 @@ fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
     token::Client::new(env, &addr.address())
 }
 
+/// Register a single-token, single-oracle project for a fresh creator.
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    super_admin: &Address,
+    token: &Address,
+    goal: i128,
+    proof_hash: &BytesN<32>,
+    deadline: u64,
+) -> Project {
+    let creator = Address::generate(env);
+    client.grant_role(super_admin, &creator, &Role::ProjectManager, &None);
+    client.register_project(
+        &creator,
+        &vec![env, token.clone()],
+        &goal,
+        proof_hash,
+        &deadline,
+        &1,
+        &vec![env],
+    )
+}
+
+/// Escrowed balance of a single-token project.
+fn escrowed(client: &PifpProtocolClient<'static>, project_id: u64) -> i128 {
+    client
+        .get_balances(&project_id)
+        .balances
+        .get(0)
+        .unwrap()
+        .balance
+}
+
 // ── 1. Registration Fuzz Tests ──────────────────────────────────────
 
 proptest! {
@@ -31,43 +71,27 @@ proptest! {
 
     #[test]
     fn fuzz_register_valid_goal(goal in 1i128..=1_000_000_000_000i128) {
-        let (env, client) = setup_env();
-        let creator = Address::generate(&env);
-        let token_admin = Address::generate(&env);
-        let token = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &[7u8; 32]);
         let deadline = env.ledger().timestamp() + 86_400;
 
-        let project = client.register_project(
-            &creator,
-            &token.address,
-            &goal,
-            &proof_hash,
-            &deadline,
-        );
+        let project = register(&env, &client, &super_admin, &token.address, goal, &proof_hash, deadline);
 
         assert_all_project_invariants(&project);
         assert_eq!(project.goal, goal);
-        assert_eq!(project.balance, 0);
+        assert_eq!(escrowed(&client, project.id), 0);
         assert_eq!(project.status, ProjectStatus::Funding);
     }
 
     #[test]
     fn fuzz_register_valid_deadline(offset in 1u64..=10_000_000u64) {
-        let (env, client) = setup_env();
-        let creator = Address::generate(&env);
-        let token_admin = Address::generate(&env);
-        let token = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &[8u8; 32]);
         let deadline = env.ledger().timestamp() + offset;
 
-        let project = client.register_project(
-            &creator,
-            &token.address,
-            &100,
-            &proof_hash,
-            &deadline,
-        );
+        let project = register(&env, &client, &super_admin, &token.address, 100, &proof_hash, deadline);
 
         assert_all_project_invariants(&project);
         assert_eq!(project.deadline, deadline);
@@ -75,20 +99,12 @@ proptest! {
 
     #[test]
     fn fuzz_register_random_proof_hash(hash_bytes in prop::array::uniform32(any::<u8>())) {
-        let (env, client) = setup_env();
-        let creator = Address::generate(&env);
-        let token_admin = Address::generate(&env);
-        let token = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &hash_bytes);
         let deadline = env.ledger().timestamp() + 86_400;
 
-        let project = client.register_project(
-            &creator,
-            &token.address,
-            &1000,
-            &proof_hash,
-            &deadline,
-        );
+        let project = register(&env, &client, &super_admin, &token.address, 1000, &proof_hash, deadline);
 
         assert_all_project_invariants(&project);
         assert_eq!(project.proof_hash, proof_hash);
@@ -102,30 +118,24 @@ proptest! {
 
     #[test]
     fn fuzz_deposit_single(amount in 1i128..=100_000i128) {
-        let (env, client) = setup_env();
-        let creator = Address::generate(&env);
-        let token_admin = Address::generate(&env);
-        let token_client = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token_client = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &[1u8; 32]);
         let deadline = env.ledger().timestamp() + 86_400;
 
-        let project = client.register_project(
-            &creator,
-            &token_client.address,
-            &100_000,
-            &proof_hash,
-            &deadline,
+        let project = register(
+            &env, &client, &super_admin, &token_client.address, 100_000, &proof_hash, deadline,
         );
 
         let donator = Address::generate(&env);
         let sac = token::StellarAssetClient::new(&env, &token_client.address);
         sac.mint(&donator, &amount);
 
-        let balance_before = project.balance;
-        client.deposit(&project.id, &donator, &amount);
+        let balance_before = escrowed(&client, project.id);
+        client.deposit(&project.id, &donator, &token_client.address, &amount, &false);
 
         let updated = client.get_project(&project.id);
-        assert_deposit_invariant(balance_before, updated.balance, amount);
+        assert_deposit_invariant(balance_before, escrowed(&client, project.id), amount);
         assert_all_project_invariants(&updated);
     }
 
@@ -133,19 +143,13 @@ proptest! {
     fn fuzz_deposit_multiple(
         amounts in prop::collection::vec(1i128..=10_000i128, 2..=8)
     ) {
-        let (env, client) = setup_env();
-        let creator = Address::generate(&env);
-        let token_admin = Address::generate(&env);
-        let token_client = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token_client = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &[2u8; 32]);
         let deadline = env.ledger().timestamp() + 86_400;
 
-        let project = client.register_project(
-            &creator,
-            &token_client.address,
-            &1_000_000,
-            &proof_hash,
-            &deadline,
+        let project = register(
+            &env, &client, &super_admin, &token_client.address, 1_000_000, &proof_hash, deadline,
         );
 
         let sac = token::StellarAssetClient::new(&env, &token_client.address);
@@ -155,18 +159,17 @@ proptest! {
             let donator = Address::generate(&env);
             sac.mint(&donator, amount);
 
-            let before = client.get_project(&project.id).balance;
-            client.deposit(&project.id, &donator, amount);
+            let before = escrowed(&client, project.id);
+            client.deposit(&project.id, &donator, &token_client.address, amount, &false);
             let after = client.get_project(&project.id);
 
-            assert_deposit_invariant(before, after.balance, *amount);
+            assert_deposit_invariant(before, escrowed(&client, project.id), *amount);
             assert_all_project_invariants(&after);
 
             expected_balance += amount;
         }
 
-        let final_project = client.get_project(&project.id);
-        assert_eq!(final_project.balance, expected_balance);
+        assert_eq!(escrowed(&client, project.id), expected_balance);
     }
 }
 
@@ -182,27 +185,18 @@ proptest! {
     ) {
         prop_assume!(stored_bytes != submitted_bytes);
 
-        let (env, client) = setup_env();
-        let creator = Address::generate(&env);
-        let token_admin = Address::generate(&env);
-        let token = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &stored_bytes);
         let deadline = env.ledger().timestamp() + 86_400;
 
-        let project = client.register_project(
-            &creator,
-            &token.address,
-            &500,
-            &proof_hash,
-            &deadline,
-        );
+        let project = register(&env, &client, &super_admin, &token.address, 500, &proof_hash, deadline);
 
-        let oracle_admin = Address::generate(&env);
         let oracle = Address::generate(&env);
-        client.set_oracle(&oracle_admin, &oracle);
+        client.set_oracle(&super_admin, &oracle);
 
         let wrong_hash = BytesN::from_array(&env, &submitted_bytes);
-        let result = client.try_verify_and_release(&project.id, &wrong_hash);
+        let result = client.try_verify_and_release(&oracle, &project.id, &wrong_hash);
         prop_assert!(result.is_err(), "verify_and_release should fail with wrong hash");
     }
 
@@ -210,26 +204,17 @@ proptest! {
     fn fuzz_verify_correct_hash_always_succeeds(
         hash_bytes in prop::array::uniform32(any::<u8>()),
     ) {
-        let (env, client) = setup_env();
-        let creator = Address::generate(&env);
-        let token_admin = Address::generate(&env);
-        let token = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &hash_bytes);
         let deadline = env.ledger().timestamp() + 86_400;
 
-        let project = client.register_project(
-            &creator,
-            &token.address,
-            &500,
-            &proof_hash,
-            &deadline,
-        );
+        let project = register(&env, &client, &super_admin, &token.address, 500, &proof_hash, deadline);
 
-        let oracle_admin = Address::generate(&env);
         let oracle = Address::generate(&env);
-        client.set_oracle(&oracle_admin, &oracle);
+        client.set_oracle(&super_admin, &oracle);
 
-        client.verify_and_release(&project.id, &proof_hash);
+        client.verify_and_release(&oracle, &project.id, &proof_hash);
 
         let updated = client.get_project(&project.id);
         assert_valid_status_transition(&ProjectStatus::Funding, &updated.status);
@@ -244,23 +229,16 @@ proptest! {
 
     #[test]
     fn fuzz_sequential_ids(n in 2u32..=10u32) {
-        let (env, client) = setup_env();
-        let token_admin = Address::generate(&env);
-        let token = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &[1u8; 32]);
         let deadline = env.ledger().timestamp() + 86_400;
 
         let mut projects = Vec::new();
         for _ in 0..n {
-            let creator = Address::generate(&env);
-            let p = client.register_project(
-                &creator,
-                &token.address,
-                &1000,
-                &proof_hash,
-                &deadline,
-            );
-            projects.push(p);
+            projects.push(register(
+                &env, &client, &super_admin, &token.address, 1000, &proof_hash, deadline,
+            ));
         }
 
         assert_sequential_ids(&projects);
@@ -274,25 +252,19 @@ proptest! {
 
     #[test]
     fn fuzz_immutability_after_deposit(amount in 1i128..=50_000i128) {
-        let (env, client) = setup_env();
-        let creator = Address::generate(&env);
-        let token_admin = Address::generate(&env);
-        let token_client = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token_client = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &[5u8; 32]);
         let deadline = env.ledger().timestamp() + 86_400;
 
-        let original = client.register_project(
-            &creator,
-            &token_client.address,
-            &100_000,
-            &proof_hash,
-            &deadline,
+        let original = register(
+            &env, &client, &super_admin, &token_client.address, 100_000, &proof_hash, deadline,
         );
 
         let donator = Address::generate(&env);
         let sac = token::StellarAssetClient::new(&env, &token_client.address);
         sac.mint(&donator, &amount);
-        client.deposit(&original.id, &donator, &amount);
+        client.deposit(&original.id, &donator, &token_client.address, &amount, &false);
 
         let after = client.get_project(&original.id);
         assert_project_immutable_fields(&original, &after);
@@ -302,25 +274,16 @@ proptest! {
     fn fuzz_immutability_after_verify(
         hash_bytes in prop::array::uniform32(any::<u8>()),
     ) {
-        let (env, client) = setup_env();
-        let creator = Address::generate(&env);
-        let token_admin = Address::generate(&env);
-        let token = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &hash_bytes);
         let deadline = env.ledger().timestamp() + 86_400;
 
-        let original = client.register_project(
-            &creator,
-            &token.address,
-            &500,
-            &proof_hash,
-            &deadline,
-        );
+        let original = register(&env, &client, &super_admin, &token.address, 500, &proof_hash, deadline);
 
-        let oracle_admin = Address::generate(&env);
         let oracle = Address::generate(&env);
-        client.set_oracle(&oracle_admin, &oracle);
-        client.verify_and_release(&original.id, &proof_hash);
+        client.set_oracle(&super_admin, &oracle);
+        client.verify_and_release(&oracle, &original.id, &proof_hash);
 
         let after = client.get_project(&original.id);
         assert_project_immutable_fields(&original, &after);
@@ -339,20 +302,14 @@ proptest! {
         hash_bytes in prop::array::uniform32(any::<u8>()),
         deadline_offset in 1000u64..=10_000_000u64,
     ) {
-        let (env, client) = setup_env();
-        let creator = Address::generate(&env);
-        let token_admin = Address::generate(&env);
-        let token_client = create_token(&env, &token_admin);
+        let (env, client, super_admin) = setup_env();
+        let token_client = create_token(&env, &Address::generate(&env));
         let proof_hash = BytesN::from_array(&env, &hash_bytes);
         let deadline = env.ledger().timestamp() + deadline_offset;
 
         // Phase 1: Register project.
-        let project = client.register_project(
-            &creator,
-            &token_client.address,
-            &goal,
-            &proof_hash,
-            &deadline,
+        let project = register(
+            &env, &client, &super_admin, &token_client.address, goal, &proof_hash, deadline,
         );
         assert_all_project_invariants(&project);
         assert_eq!(project.status, ProjectStatus::Funding);
@@ -365,35 +322,180 @@ proptest! {
             let donator = Address::generate(&env);
             sac.mint(&donator, amount);
 
-            let before = client.get_project(&project.id);
-            client.deposit(&project.id, &donator, amount);
+            let before = escrowed(&client, project.id);
+            client.deposit(&project.id, &donator, &token_client.address, amount, &false);
             let after = client.get_project(&project.id);
 
-            assert_deposit_invariant(before.balance, after.balance, *amount);
+            assert_deposit_invariant(before, escrowed(&client, project.id), *amount);
             assert_project_immutable_fields(&project, &after);
             assert_all_project_invariants(&after);
 
             total_deposited += amount;
         }
 
-        let after_deposits = client.get_project(&project.id);
-        assert_eq!(after_deposits.balance, total_deposited);
+        assert_eq!(escrowed(&client, project.id), total_deposited);
 
         // Phase 3: Oracle verification.
-        let oracle_admin = Address::generate(&env);
         let oracle = Address::generate(&env);
-        client.set_oracle(&oracle_admin, &oracle);
-        client.verify_and_release(&project.id, &proof_hash);
+        client.set_oracle(&super_admin, &oracle);
+        client.verify_and_release(&oracle, &project.id, &proof_hash);
 
         let final_project = client.get_project(&project.id);
         assert_valid_status_transition(&ProjectStatus::Funding, &final_project.status);
         assert_project_immutable_fields(&project, &final_project);
         assert_eq!(final_project.status, ProjectStatus::Completed);
-        // Balance should be unchanged after verification.
-        assert_eq!(final_project.balance, total_deposited);
+        // Verification releases the whole escrow to the creator.
+        assert_eq!(escrowed(&client, project.id), 0);
+        assert_eq!(token_client.balance(&project.creator), total_deposited);
 
         // Phase 4: Double-verify should fail.
-        let result = client.try_verify_and_release(&project.id, &proof_hash);
+        let result = client.try_verify_and_release(&oracle, &project.id, &proof_hash);
         prop_assert!(result.is_err(), "double verification should fail");
     }
 }
+
+// ── 7. Interleaved Multi-Project Conservation ───────────────────────
+
+/// One randomly chosen operation against project `project` (an index into
+/// the registered set).
+#[derive(Clone, Debug)]
+enum Op {
+    Deposit {
+        project: usize,
+        donor: usize,
+        token: usize,
+        amount: i128,
+    },
+    Verify {
+        project: usize,
+    },
+    Expire {
+        project: usize,
+    },
+    Refund {
+        project: usize,
+        donor: usize,
+        token: usize,
+    },
+}
+
+fn op_strategy(projects: usize, donors: usize, tokens: usize) -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => (0..projects, 0..donors, 0..tokens, 1i128..=5_000i128).prop_map(
+            |(project, donor, token, amount)| Op::Deposit { project, donor, token, amount }
+        ),
+        1 => (0..projects).prop_map(|project| Op::Verify { project }),
+        1 => (0..projects).prop_map(|project| Op::Expire { project }),
+        1 => (0..projects, 0..donors, 0..tokens).prop_map(
+            |(project, donor, token)| Op::Refund { project, donor, token }
+        ),
+    ]
+}
+
+/// Everything observable about one project: its mutable state and every
+/// per-token balance.
+fn snapshot(
+    client: &PifpProtocolClient<'static>,
+    ids: &[u64],
+) -> Vec<(crate::ProjectState, soroban_sdk::Vec<crate::TokenBalance>)> {
+    ids.iter()
+        .map(|id| {
+            (
+                client.get_project_state(id),
+                client.get_balances(id).balances,
+            )
+        })
+        .collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    /// Several projects share donors and tokens. After every operation —
+    /// successful or rejected — every project other than its target must be
+    /// byte-for-byte unchanged, and escrowed totals must match the token
+    /// balance the contract actually holds.
+    #[test]
+    fn fuzz_interleaved_projects_are_isolated(
+        ops in prop::collection::vec(op_strategy(4, 3, 2), 1..=40),
+    ) {
+        let (env, client, super_admin) = setup_env();
+        let creator = Address::generate(&env);
+        client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+        let oracle = Address::generate(&env);
+        client.set_oracle(&super_admin, &oracle);
+
+        let tokens: Vec<Address> = (0..2)
+            .map(|_| create_token(&env, &Address::generate(&env)).address)
+            .collect();
+        let donors: Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+        for token in &tokens {
+            let sac = token::StellarAssetClient::new(&env, token);
+            for donor in &donors {
+                sac.mint(donor, &1_000_000);
+            }
+        }
+
+        let accepted = vec![&env, tokens[0].clone(), tokens[1].clone()];
+        let proof_hash = BytesN::from_array(&env, &[9u8; 32]);
+        let deadline = env.ledger().timestamp() + 86_400;
+        let ids: Vec<u64> = (0..4)
+            .map(|_| {
+                client
+                    .register_project(
+                        &creator, &accepted, &50_000, &proof_hash, &deadline, &1, &vec![&env],
+                    )
+                    .id
+            })
+            .collect();
+
+        for op in ops {
+            let before = snapshot(&client, &ids);
+            let target = match op {
+                Op::Deposit { project, donor, token, amount } => {
+                    let _ = client.try_deposit(
+                        &ids[project], &donors[donor], &tokens[token], &amount, &false,
+                    );
+                    project
+                }
+                Op::Verify { project } => {
+                    let _ = client.try_verify_and_release(&oracle, &ids[project], &proof_hash);
+                    project
+                }
+                Op::Expire { project } => {
+                    // Queue, wait out the timelock, then apply the override.
+                    let reason = BytesN::from_array(&env, &[3u8; 32]);
+                    let expired = ProjectStatus::Expired;
+                    if client
+                        .try_force_status(&super_admin, &ids[project], &expired, &reason)
+                        .is_ok()
+                    {
+                        let delay = client.get_protocol_config().force_status_delay;
+                        env.ledger().with_mut(|l| l.timestamp += delay);
+                        client.force_status(&super_admin, &ids[project], &expired, &reason);
+                    }
+                    project
+                }
+                Op::Refund { project, donor, token } => {
+                    let _ = client.try_claim_refund(&ids[project], &donors[donor], &tokens[token]);
+                    project
+                }
+            };
+
+            let after = snapshot(&client, &ids);
+            for (i, (b, a)) in before.iter().zip(after.iter()).enumerate() {
+                if i != target {
+                    prop_assert_eq!(b, a, "op on project {} changed project {}", target, i);
+                }
+            }
+            for (i, token) in tokens.iter().enumerate() {
+                let escrowed: i128 = after
+                    .iter()
+                    .map(|(_, balances)| balances.get(i as u32).unwrap().balance)
+                    .sum();
+                let held = token::Client::new(&env, token).balance(&client.address);
+                prop_assert!(escrowed <= held, "token {} over-committed", i);
+            }
+        }
+    }
+}
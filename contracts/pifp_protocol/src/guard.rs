@@ -0,0 +1,38 @@
+//! # Reentrancy guard
+//!
+//! Entry points that move tokens or call out to donor hooks hold an
+//! instance-storage lock for their whole duration. A token, hook or
+//! verifier contract that calls back into any guarded entry point while
+//! the lock is held fails with `Error::ReentrantCall`, so a malicious
+//! contract can never observe or update balances mid-transfer.
+//!
+//! The Soroban host already refuses direct re-entry into a contract on the
+//! call stack; the lock makes the invariant explicit and keeps it if that
+//! host rule is ever relaxed or routed around through another contract.
+
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::storage;
+use crate::Error;
+
+/// Holds the reentrancy lock until dropped.
+pub struct ReentrancyGuard {
+    env: Env,
+}
+
+impl ReentrancyGuard {
+    /// Take the lock, panicking with `Error::ReentrantCall` if it is held.
+    pub fn acquire(env: &Env) -> Self {
+        if storage::is_locked(env) {
+            panic_with_error!(env, Error::ReentrantCall);
+        }
+        storage::set_locked(env, true);
+        ReentrancyGuard { env: env.clone() }
+    }
+}
+
+impl Drop for ReentrancyGuard {
+    fn drop(&mut self) {
+        storage::set_locked(&self.env, false);
+    }
+}
@@ -0,0 +1,29 @@
+//! # Deposit hooks
+//!
+//! Donors that are themselves contracts (DAO treasuries, vaults) can opt in
+//! to a callback after each successful deposit, so their internal accounting
+//! updates in the same transaction as the donation.
+//!
+//! A donor registers with `set_deposit_hook`; from then on `deposit` calls
+//! [`DepositReceiverInterface::on_deposit_receipt`] on the donor address.
+//! The call is not isolated: if the hook fails, the deposit fails with it,
+//! so the donor's books and the escrow can never disagree.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::storage;
+
+/// Interface a donor contract implements to receive deposit receipts.
+#[contractclient(name = "DepositReceiverClient")]
+pub trait DepositReceiverInterface {
+    /// Called after `amount` of `token` from this contract has been
+    /// deposited into `project_id`.
+    fn on_deposit_receipt(env: Env, project_id: u64, token: Address, amount: i128);
+}
+
+/// Notify `donor` of a deposit if it registered a hook.
+pub fn notify_deposit(env: &Env, donor: &Address, project_id: u64, token: &Address, amount: i128) {
+    if storage::has_deposit_hook(env, donor) {
+        DepositReceiverClient::new(env, donor).on_deposit_receipt(&project_id, token, &amount);
+    }
+}
@@ -0,0 +1,34 @@
+//! # Crowdfunding interface
+//!
+//! A minimal, protocol-agnostic surface for funding and tracking a campaign.
+//!
+//! DAOs, aggregators and routers can integrate any contract implementing
+//! [`CrowdfundingInterface`] through the generated [`CrowdfundingClient`]
+//! without depending on the rest of the PIFP API. `PifpProtocol` implements
+//! it on top of `deposit`, `get_project_state` and `get_token_flows`.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+use crate::types::ProjectStatus;
+
+/// Funding progress towards a project's goal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GoalProgress {
+    /// Amount raised to date, in the goal's unit; releases do not reduce it.
+    pub raised: i128,
+    pub goal: i128,
+}
+
+/// Interface shared by crowdfunding contracts.
+#[contractclient(name = "CrowdfundingClient")]
+pub trait CrowdfundingInterface {
+    /// Contribute `amount` of `token` from `funder` to `project_id`.
+    fn fund(env: Env, funder: Address, project_id: u64, token: Address, amount: i128);
+
+    /// Current lifecycle status of `project_id`.
+    fn status(env: Env, project_id: u64) -> ProjectStatus;
+
+    /// Amount raised so far against the project's goal.
+    fn goal_progress(env: Env, project_id: u64) -> GoalProgress;
+}
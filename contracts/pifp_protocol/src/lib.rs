@@ -6,12 +6,45 @@
 //!
 //! | Phase        | Entry Point(s)                              |
 //! |--------------|---------------------------------------------|
-//! | Bootstrap    | [`PifpProtocol::init`]                      |
-//! | Role admin   | `grant_role`, `revoke_role`, `transfer_super_admin`, `set_oracle` |
-//! | Registration | [`PifpProtocol::register_project`]          |
-//! | Funding      | [`PifpProtocol::deposit`]                   |
-//! | Verification | [`PifpProtocol::verify_and_release`]        |
-//! | Queries      | `get_project`, `role_of`, `has_role`        |
+//! | Bootstrap    | [`PifpProtocol::init`], `init_council`, `get_council`, `version` |
+//! | Upgrades     | `upgrade`, `migrate`, `get_version`         |
+//! | Timelock     | `queue_action`, `approve_action`, `execute_action`, `cancel_action`, `get_queued_action` |
+//! | Role admin   | `grant_role`, `grant_role_until`, `revoke_role`, `transfer_super_admin`, `set_oracle`, `replace_oracle_emergency`, `get_admin_log` |
+//! | Config       | `set_protocol_config`, `get_protocol_config`, `error_message` |
+//! | Pausing      | `pause`, `unpause`, `get_pause_info`, `schedule_maintenance`, `cancel_maintenance`, `pause_project`, `unpause_project`, `pause_token`, `unpause_token`, `is_token_paused` |
+//! | Wind-down    | `begin_sunset`, `finalize_sunset`, `is_sunset` |
+//! | Tokens       | `add_allowed_token`, `remove_allowed_token`, `list_allowed_tokens`, `is_token_allowed` |
+//! | Compliance   | `freeze_address`, `unfreeze_address`, `get_freeze` |
+//! | Donor gates  | `set_donor_allowlist`, `add_allowed_donor`, `remove_allowed_donor`, `is_donor_allowed` |
+//! | Recovery     | `force_status`, `cancel_force_status`, `get_pending_force_status` |
+//! | Migration    | `migrate_escrow`, `cancel_migration`, `get_pending_migration` |
+//! | Key recovery | `set_guardians`, `approve_recovery`, `execute_recovery`, `cancel_recovery`, `get_guardians`, `get_recovery` |
+//! | Continuity   | `set_dead_man_switch`, `clear_dead_man_switch`, `heartbeat`, `announce_claim`, `claim_super_admin`, `get_dead_man_switch` |
+//! | Registration | [`PifpProtocol::register_project`], `register_project_at`, `derive_project_id`, `get_milestones`, `set_reference_goal`, `set_currency_lock`, `set_project_handle`, `get_project_by_handle`, `get_project_handle`, `update_metadata`, `get_project_metadata` |
+//! | Funding      | [`PifpProtocol::deposit`], `deposit_grant`, `get_grant`, `accepts_token` |
+//! | Donors       | `get_donor_history`, `get_donor_projects`, `get_donation`, `get_donation_count`, `get_donation_value`, `get_donation_weight`, `get_donor_stats`, `get_donor_statement`, `get_project_donors`, `set_donor_preferences`, `get_donor_preferences`, `set_deposit_hook`, `has_deposit_hook` |
+//! | Ownership    | `set_project_owners`, `get_project_owners`  |
+//! | Verification | [`PifpProtocol::verify_and_release`], `submit_proof`, `approve_proof`, `reject_proof`, `get_pending_proof`, `set_project_verifier`, `set_project_oracles`, `get_project_oracles`, `set_dependencies`, `get_dependencies` |
+//! | Payouts      | `withdraw`, `get_claimable`                 |
+//! | Verifiers    | `approve_verifier`, `revoke_verifier`, `is_verifier_approved` |
+//! | BLS quorum   | `set_oracle_bls_key`, `verify_aggregate`, `get_attestation_payload` |
+//! | Insurance    | `set_insured`, `get_insurance`, `get_insurance_pool` |
+//! | Fees         | `set_fee_override`, `get_fee_bps`, `set_release_fee_bps`, `get_fee_config`, `withdraw_treasury`, `convert_fees`, `get_fee_vault` |
+//! | Matching     | `set_match_cap`, `get_match_cap`, `get_match_eligible`, `create_match_pool`, `fund_match_pool`, `close_match_pool`, `get_match_pool`, `get_match_pools` |
+//! | Expiry       | `expire_project`, `claim_refund`, `get_refunded` |
+//! | Sponsorship  | `fund_sponsor_pool`, `get_sponsor_pool`     |
+//! | Commons      | `disburse_commons`, `get_commons_pool`      |
+//! | Schedules    | `set_disbursement_template`, `use_disbursement_template`, `get_disbursement_schedule` |
+//! | Donor votes  | `set_donor_vote`, `object_to_release`, `close_donor_vote`, `resolve_contest` |
+//! | Challenges   | `raise_dispute`, `close_challenge`, `resolve_dispute`, `get_challenge` |
+//! | Throttling   | `process_release_queue`, `get_release_queue` |
+//! | Extensions   | `extend_deadline`, `get_extension_count`, `back_extension`, `get_extension_vote` |
+//! | Amendments   | `propose_amendment`, `back_amendment`, `resolve_amendment`, `get_amendment` |
+//! | Ongoing      | `set_ongoing`, `get_cadence`, `is_stream_active` |
+//! | Maintenance  | `bump_project`, `sponsor_rent`, `reclaim_rent_sponsorship`, `get_rent_fund`, `cleanup`, `storage_report`, `fund_keeper_pool`, `get_keeper_pool`, `sweep_dust`, `get_dust` |
+//! | Reputation   | `flag_creator`, `get_creator_record`, `get_creator_released`, `get_projects_by_creator` |
+//! | Interface    | `fund`, `status`, `goal_progress` ([`interface::CrowdfundingInterface`]) |
+//! | Queries      | `get_project`, `get_project_config`, `get_project_state`, `get_balances`, `get_balances_page`, `get_token_flows`, `get_escrow_proof`, `get_project_report`, `get_actionable_projects`, `health_check`, `get_project_count`, `list_projects`, `list_project_ids_by_status`, `role_of`, `has_role`, `role_expiry`, `list_role_members`, `get_role_history`, `capabilities_of` |
 //!
 //! ## Architecture
 //!
@@ -23,30 +56,107 @@
 //! architecture and threat model.
 
 #![no_std]
+// Registration entry points take more arguments than clippy allows, and
+// the argument helpers `#[contractimpl]` generates for them cannot be
+// annotated individually.
+#![allow(clippy::too_many_arguments)]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, panic_with_error, token, Address, BytesN, Env, Vec,
+    contract, contracterror, contractimpl, panic_with_error, symbol_short, token, Address, Bytes,
+    BytesN, Env, String, Symbol, Vec,
 };
 
+pub mod bls;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod dex;
 pub mod events;
+pub mod fsm;
+mod guard;
+pub mod hooks;
+pub mod interface;
+pub mod migration;
+pub mod price;
 pub mod rbac;
 mod storage;
 mod types;
+pub mod upgrade;
+pub mod verifier;
 
-// #[cfg(test)]
-// mod fuzz_test;
+#[cfg(test)]
+mod fuzz_test;
 #[cfg(test)]
 mod invariants;
 // #[cfg(test)]
 // mod test;
 #[cfg(test)]
+mod test_audit;
+#[cfg(test)]
+mod test_auth;
+#[cfg(all(test, feature = "client"))]
+mod test_client;
+#[cfg(test)]
+mod test_donors;
+#[cfg(test)]
 mod test_events;
+#[cfg(test)]
+mod test_fees;
+#[cfg(test)]
+mod test_fsm;
+#[cfg(test)]
+mod test_insurance;
+#[cfg(test)]
+mod test_interface;
+#[cfg(test)]
+mod test_maintenance;
+#[cfg(test)]
+mod test_matching;
+#[cfg(test)]
+mod test_migration;
+#[cfg(test)]
+mod test_ownership;
+#[cfg(test)]
+mod test_pause;
+#[cfg(test)]
+mod test_queries;
+#[cfg(test)]
+mod test_recovery;
+#[cfg(test)]
+mod test_reentrancy;
+#[cfg(test)]
+mod test_refunds;
+#[cfg(test)]
+mod test_reputation;
+#[cfg(test)]
+mod test_schedules;
+#[cfg(test)]
+mod test_timelock;
+#[cfg(test)]
+mod test_upgrade;
+#[cfg(test)]
+mod test_verification;
+#[cfg(test)]
+mod test_votes;
 
-pub use rbac::Role;
+use fsm::StatusEvent;
+use guard::ReentrancyGuard;
+pub use interface::{CrowdfundingClient, CrowdfundingInterface, GoalProgress};
+pub use rbac::{Role, RoleChange};
 use storage::{
     get_and_increment_project_id, load_project, load_project_pair, save_project, save_project_state,
 };
-pub use types::{Project, ProjectStatus};
+use types::ProjectConfig;
+pub use types::{
+    ActionableKind, AdminAction, Challenge, ContractVersion, Council, CreatorRecord, DeadManSwitch,
+    DepositReceipt, DisbursementTemplate, DonorPreferences, DonorStatement, DonorStats, DonorVote,
+    DonorVoteConfig, EscrowProof, ExtensionVote, FeeConfig, GoalUnit, Grant, GrantRoute,
+    GuardianSet, LedgerEntry, LedgerKind, MaintenanceWindow, MatchPool, MigrationRecord, Milestone,
+    MilestoneAmendment, OngoingCadence, PauseInfo, PendingMigration, PendingProof,
+    PendingStatusChange, Project, ProjectBalances, ProjectMetadata, ProjectOwners, ProjectReport,
+    ProjectState, ProjectStatus, ProjectVerifier, ProtocolConfig, ProtocolHealth, QueuedAction,
+    QueuedRelease, RecoveryRequest, StatementLine, StorageEntry, StorageReport, TimelockedAction,
+    TokenBalance, TokenFlows, TokenReport, Tranche,
+};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -62,6 +172,117 @@ pub enum Error {
     AlreadyInitialized = 8,
     RoleNotFound = 9,
     TooManyTokens = 10,
+    InvalidQuorum = 11,
+    AlreadyAttested = 12,
+    VerifierNotApproved = 13,
+    ProjectAlreadyFunded = 14,
+    InvalidAggregateSignature = 15,
+    InvalidConfig = 16,
+    ProjectNotSettled = 17,
+    ProtocolPaused = 18,
+    ProjectPaused = 19,
+    AddressFrozen = 20,
+    InvalidStatusTransition = 21,
+    TimelockNotElapsed = 22,
+    ProtocolSunset = 23,
+    VoteNotOpen = 24,
+    ReleasePending = 25,
+    AdminLimitReached = 26,
+    TokenPaused = 27,
+    OutflowExceedsInflow = 28,
+    DependencyNotMet = 29,
+    InvalidToken = 30,
+    ReentrantCall = 31,
+    VersionMismatch = 32,
+    SlippageExceeded = 33,
+    ChallengeNotOpen = 34,
+    AmendmentPending = 35,
+}
+
+impl Error {
+    /// Return the variant with contract error code `code`, if any.
+    pub fn from_code(code: u32) -> Option<Error> {
+        Some(match code {
+            1 => Error::ProjectNotFound,
+            2 => Error::MilestoneNotFound,
+            3 => Error::MilestoneAlreadyReleased,
+            4 => Error::InsufficientBalance,
+            5 => Error::InvalidMilestones,
+            6 => Error::NotAuthorized,
+            7 => Error::GoalMismatch,
+            8 => Error::AlreadyInitialized,
+            9 => Error::RoleNotFound,
+            10 => Error::TooManyTokens,
+            11 => Error::InvalidQuorum,
+            12 => Error::AlreadyAttested,
+            13 => Error::VerifierNotApproved,
+            14 => Error::ProjectAlreadyFunded,
+            15 => Error::InvalidAggregateSignature,
+            16 => Error::InvalidConfig,
+            17 => Error::ProjectNotSettled,
+            18 => Error::ProtocolPaused,
+            19 => Error::ProjectPaused,
+            20 => Error::AddressFrozen,
+            21 => Error::InvalidStatusTransition,
+            22 => Error::TimelockNotElapsed,
+            23 => Error::ProtocolSunset,
+            24 => Error::VoteNotOpen,
+            25 => Error::ReleasePending,
+            26 => Error::AdminLimitReached,
+            27 => Error::TokenPaused,
+            28 => Error::OutflowExceedsInflow,
+            29 => Error::DependencyNotMet,
+            30 => Error::InvalidToken,
+            31 => Error::ReentrantCall,
+            32 => Error::VersionMismatch,
+            33 => Error::SlippageExceeded,
+            34 => Error::ChallengeNotOpen,
+            35 => Error::AmendmentPending,
+            _ => return None,
+        })
+    }
+
+    /// Stable snake_case identifier for this error. Never changes once
+    /// published, so clients may key translations on it.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Error::ProjectNotFound => "project_not_found",
+            Error::MilestoneNotFound => "milestone_not_found",
+            Error::MilestoneAlreadyReleased => "milestone_already_released",
+            Error::InsufficientBalance => "insufficient_balance",
+            Error::InvalidMilestones => "invalid_milestones",
+            Error::NotAuthorized => "not_authorized",
+            Error::GoalMismatch => "goal_mismatch",
+            Error::AlreadyInitialized => "already_initialized",
+            Error::RoleNotFound => "role_not_found",
+            Error::TooManyTokens => "too_many_tokens",
+            Error::InvalidQuorum => "invalid_quorum",
+            Error::AlreadyAttested => "already_attested",
+            Error::VerifierNotApproved => "verifier_not_approved",
+            Error::ProjectAlreadyFunded => "project_already_funded",
+            Error::InvalidAggregateSignature => "invalid_aggregate_signature",
+            Error::InvalidConfig => "invalid_config",
+            Error::ProjectNotSettled => "project_not_settled",
+            Error::ProtocolPaused => "protocol_paused",
+            Error::ProjectPaused => "project_paused",
+            Error::AddressFrozen => "address_frozen",
+            Error::InvalidStatusTransition => "invalid_status_transition",
+            Error::TimelockNotElapsed => "timelock_not_elapsed",
+            Error::ProtocolSunset => "protocol_sunset",
+            Error::VoteNotOpen => "vote_not_open",
+            Error::ReleasePending => "release_pending",
+            Error::AdminLimitReached => "admin_limit_reached",
+            Error::TokenPaused => "token_paused",
+            Error::OutflowExceedsInflow => "outflow_exceeds_inflow",
+            Error::DependencyNotMet => "dependency_not_met",
+            Error::InvalidToken => "invalid_token",
+            Error::ReentrantCall => "reentrant_call",
+            Error::VersionMismatch => "version_mismatch",
+            Error::SlippageExceeded => "slippage_exceeded",
+            Error::ChallengeNotOpen => "challenge_not_open",
+            Error::AmendmentPending => "amendment_pending",
+        }
+    }
 }
 
 #[contract]
@@ -82,6 +303,294 @@ impl PifpProtocol {
     pub fn init(env: Env, super_admin: Address) {
         super_admin.require_auth();
         rbac::init_super_admin(&env, &super_admin);
+        let version = ContractVersion::current(&env);
+        storage::set_stored_version(&env, &version);
+        events::emit_initialized(&env, super_admin, version);
+    }
+
+    /// Initialise the contract under a council of `members` instead of a
+    /// single SuperAdmin.
+    ///
+    /// The contract itself holds the `SuperAdmin` role, so SuperAdmin
+    /// powers are exercised only through timelocked actions that
+    /// `threshold` members have approved. Every member must sign.
+    ///
+    /// - Fails with `AlreadyInitialized` if `init` or `init_council` ran.
+    /// - Fails with `InvalidConfig` unless 1 ≤ `threshold` ≤ member count
+    ///   ≤ `Council::MAX_MEMBERS`, with no duplicate members.
+    pub fn init_council(env: Env, members: Vec<Address>, threshold: u32) {
+        for member in members.iter() {
+            member.require_auth();
+        }
+        let super_admin = env.current_contract_address();
+        rbac::init_super_admin(&env, &super_admin);
+        rbac::set_council(&env, &Council { members, threshold });
+        let version = ContractVersion::current(&env);
+        storage::set_stored_version(&env, &version);
+        events::emit_initialized(&env, super_admin, version);
+    }
+
+    /// Return the council holding SuperAdmin, or `None` for a single-key
+    /// deployment.
+    pub fn get_council(env: Env) -> Option<Council> {
+        rbac::get_council(&env)
+    }
+
+    /// Return the semantic version and git commit this contract was built
+    /// from, so operators can tell which build a deployment is running.
+    pub fn version(env: Env) -> ContractVersion {
+        ContractVersion::current(&env)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Upgrades
+    // ─────────────────────────────────────────────────────────
+
+    /// Replace the contract's Wasm with the uploaded `new_wasm_hash`.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    /// - Fails with `VersionMismatch` until `migrate` has brought storage
+    ///   up to this build's schema, so migrations are never skipped.
+    ///
+    /// - Fails with `TimelockNotElapsed` while `ProtocolConfig::action_delay`
+    ///   is set; queue a `TimelockedAction::Upgrade` instead.
+    ///
+    /// The new code takes effect after this invocation; call `migrate`
+    /// next if it raised the schema.
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) {
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        require_no_action_delay(&env);
+        apply_upgrade(&env, &caller, new_wasm_hash);
+    }
+
+    /// Run the storage migrations between the recorded schema and this
+    /// build's, then record this build. Returns the schema storage has
+    /// reached.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    /// - Fails with `VersionMismatch` if storage was written by a newer
+    ///   schema than this build knows.
+    ///
+    /// Steps that rewrite projects handle `PROJECT_PAGE` (25) per call; while
+    /// the returned schema is below `version().schema`, call again. The
+    /// schema event is emitted once the last step finishes. Safe to call
+    /// when nothing is pending: it only refreshes the recorded build
+    /// metadata.
+    pub fn migrate(env: Env, caller: Address) -> u32 {
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        let from = stored_schema(&env);
+        if from > ContractVersion::SCHEMA {
+            panic_with_error!(&env, Error::VersionMismatch);
+        }
+        let reached = upgrade::run(&env, from);
+        let mut version = ContractVersion::current(&env);
+        version.schema = reached;
+        storage::set_stored_version(&env, &version);
+        if from != ContractVersion::SCHEMA && reached == ContractVersion::SCHEMA {
+            log_admin(&env, &caller, symbol_short!("schema"), None, None);
+            events::emit_schema_migrated(&env, from, version);
+        }
+        reached
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Timelocked actions
+    // ─────────────────────────────────────────────────────────
+
+    /// Queue `action` for execution once `ProtocolConfig::action_delay`
+    /// has elapsed. Returns its ID.
+    ///
+    /// - `caller` must be the SuperAdmin, or a council member if the
+    ///   deployment has a council; the queueing counts as their approval.
+    /// - The action is validated now as well as on execution, so a fee
+    ///   above `MAX_FEE_BPS`, a non-positive withdrawal, a `SuperAdmin`
+    ///   grant or an unusable council fails with `InvalidConfig` here.
+    ///
+    /// Emits `act_queue` so monitors see the change coming.
+    pub fn queue_action(env: Env, caller: Address, action: TimelockedAction) -> u64 {
+        caller.require_auth();
+        rbac::require_proposer(&env, &caller);
+        validate_action(&env, &action);
+        let id = storage::next_action_id(&env);
+        let eta = env
+            .ledger()
+            .timestamp()
+            .saturating_add(storage::get_protocol_config(&env).action_delay);
+        storage::set_action(
+            &env,
+            id,
+            &QueuedAction {
+                action: action.clone(),
+                proposer: caller.clone(),
+                eta,
+                approvals: Vec::from_array(&env, [caller.clone()]),
+            },
+        );
+        log_admin(&env, &caller, symbol_short!("act_queue"), None, None);
+        events::emit_action_queued(&env, id, action, eta, caller);
+        id
+    }
+
+    /// Approve queued action `action_id` as a council member. Returns the
+    /// approvals counted so far.
+    ///
+    /// - `caller` must be a council member.
+    /// - Fails with `ProjectNotFound` if no such action is queued and with
+    ///   `AlreadyAttested` if `caller` already approved it.
+    ///
+    /// Emits `act_appr`.
+    pub fn approve_action(env: Env, caller: Address, action_id: u64) -> u32 {
+        caller.require_auth();
+        if rbac::get_council(&env).is_none() {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        rbac::require_proposer(&env, &caller);
+        let mut queued = match storage::get_action(&env, action_id) {
+            Some(queued) => queued,
+            None => panic_with_error!(&env, Error::ProjectNotFound),
+        };
+        if queued.approvals.contains(&caller) {
+            panic_with_error!(&env, Error::AlreadyAttested);
+        }
+        queued.approvals.push_back(caller.clone());
+        storage::set_action(&env, action_id, &queued);
+        let approvals = rbac::count_approvals(&env, &queued.approvals);
+        events::emit_action_approved(&env, action_id, caller, approvals);
+        approvals
+    }
+
+    /// Apply queued action `action_id`. Anyone may call it once its delay
+    /// has elapsed and it has enough approvals; the address that queued it
+    /// is recorded as the actor.
+    ///
+    /// - Fails with `TimelockNotElapsed` before the action's `eta`, and
+    ///   with `ProjectNotFound` if no such action is queued.
+    /// - Fails with `InvalidQuorum` until approvals from current council
+    ///   members reach the threshold.
+    /// - An `EmergencyWithdraw` fails with `InsufficientBalance` if the
+    ///   contract no longer holds that much outside escrows and pools.
+    ///
+    /// Emits `act_exec`.
+    pub fn execute_action(env: Env, action_id: u64) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        let queued = match storage::get_action(&env, action_id) {
+            Some(queued) => queued,
+            None => panic_with_error!(&env, Error::ProjectNotFound),
+        };
+        if env.ledger().timestamp() < queued.eta {
+            panic_with_error!(&env, Error::TimelockNotElapsed);
+        }
+        if rbac::count_approvals(&env, &queued.approvals) < rbac::approval_threshold(&env) {
+            panic_with_error!(&env, Error::InvalidQuorum);
+        }
+        validate_action(&env, &queued.action);
+        storage::remove_action(&env, action_id);
+
+        let proposer = queued.proposer;
+        match queued.action.clone() {
+            TimelockedAction::SetFees(deposit_bps, release_bps) => {
+                let mut config = storage::get_protocol_config(&env);
+                config.fee_bps = deposit_bps;
+                config.release_fee_bps = release_bps;
+                storage::set_protocol_config(&env, &config);
+                log_admin(&env, &proposer, symbol_short!("config"), None, None);
+            }
+            TimelockedAction::Upgrade(hash) => apply_upgrade(&env, &proposer, hash),
+            TimelockedAction::EmergencyWithdraw(token, recipient, amount) => {
+                if amount > untracked_balance(&env, &token) {
+                    panic_with_error!(&env, Error::InsufficientBalance);
+                }
+                let contract = env.current_contract_address();
+                token::Client::new(&env, &token).transfer(&contract, &recipient, &amount);
+                events::emit_escrow_transfer(
+                    &env,
+                    token.clone(),
+                    contract,
+                    recipient.clone(),
+                    amount,
+                );
+                log_admin(
+                    &env,
+                    &proposer,
+                    symbol_short!("swept"),
+                    Some(recipient.clone()),
+                    None,
+                );
+                events::emit_residual_swept(&env, token, recipient, amount);
+            }
+            TimelockedAction::SetDelay(delay) => {
+                let mut config = storage::get_protocol_config(&env);
+                config.action_delay = delay;
+                storage::set_protocol_config(&env, &config);
+                log_admin(&env, &proposer, symbol_short!("config"), None, None);
+            }
+            TimelockedAction::GrantRole(target, role) => {
+                let super_admin = rbac::get_super_admin(&env).expect("SuperAdmin is set at init");
+                rbac::grant_role(&env, &super_admin, &target, role, None, None);
+                log_admin(
+                    &env,
+                    &proposer,
+                    symbol_short!("role_set"),
+                    Some(target),
+                    None,
+                );
+            }
+            TimelockedAction::RevokeRole(target) => {
+                let super_admin = rbac::get_super_admin(&env).expect("SuperAdmin is set at init");
+                rbac::revoke_role(&env, &super_admin, &target, None);
+                log_admin(
+                    &env,
+                    &proposer,
+                    symbol_short!("role_del"),
+                    Some(target),
+                    None,
+                );
+            }
+            TimelockedAction::SetCouncil(members, threshold) => {
+                let contract = env.current_contract_address();
+                if rbac::get_council(&env).is_none() {
+                    rbac::recover_super_admin(&env, &contract, &proposer);
+                }
+                rbac::set_council(&env, &Council { members, threshold });
+                log_admin(&env, &proposer, symbol_short!("council"), None, None);
+            }
+        }
+        events::emit_action_executed(&env, action_id, queued.action);
+    }
+
+    /// Withdraw queued action `action_id`.
+    ///
+    /// - `caller` must be the SuperAdmin, or any council member: a single
+    ///   member can delay a change but never force one through.
+    pub fn cancel_action(env: Env, caller: Address, action_id: u64) {
+        caller.require_auth();
+        rbac::require_proposer(&env, &caller);
+        if storage::get_action(&env, action_id).is_some() {
+            storage::remove_action(&env, action_id);
+            log_admin(&env, &caller, symbol_short!("act_cncl"), None, None);
+            events::emit_action_cancelled(&env, action_id, caller);
+        }
+    }
+
+    /// Return queued action `action_id`, or `None` once executed or
+    /// cancelled.
+    pub fn get_queued_action(env: Env, action_id: u64) -> Option<QueuedAction> {
+        storage::get_action(&env, action_id)
+    }
+
+    /// Return the build that last initialised or migrated storage.
+    ///
+    /// Differs from `version` between an `upgrade` and the `migrate` that
+    /// follows it. Deployments that predate the record report schema 1 with
+    /// unknown version and commit.
+    pub fn get_version(env: Env) -> ContractVersion {
+        storage::get_stored_version(&env).unwrap_or(ContractVersion {
+            version: String::from_str(&env, "unknown"),
+            commit: String::from_str(&env, "unknown"),
+            schema: 1,
+        })
     }
 
     // ─────────────────────────────────────────────────────────
@@ -92,24 +601,99 @@ impl PifpProtocol {
     ///
     /// - `caller` must hold `SuperAdmin` or `Admin`.
     /// - Only `SuperAdmin` can grant `SuperAdmin`.
-    pub fn grant_role(env: Env, caller: Address, target: Address, role: Role) {
-        rbac::grant_role(&env, &caller, &target, role);
+    /// - `reason_hash` optionally references the off-chain approval; it is
+    ///   recorded in the `role_set` event and the role history.
+    pub fn grant_role(
+        env: Env,
+        caller: Address,
+        target: Address,
+        role: Role,
+        reason_hash: Option<BytesN<32>>,
+    ) {
+        caller.require_auth();
+        rbac::grant_role(&env, &caller, &target, role, None, reason_hash);
+        log_admin(&env, &caller, symbol_short!("role_set"), Some(target), None);
+    }
+
+    /// Grant `role` to `target` until `expires_at`, after which every guard
+    /// treats the role as absent without an explicit revoke.
+    ///
+    /// - Same rules as `grant_role`.
+    /// - `expires_at` must be in the future; `SuperAdmin` cannot be granted
+    ///   this way.
+    ///
+    /// A later `grant_role` to the same address replaces the expiry.
+    pub fn grant_role_until(
+        env: Env,
+        caller: Address,
+        target: Address,
+        role: Role,
+        expires_at: u64,
+        reason_hash: Option<BytesN<32>>,
+    ) {
+        caller.require_auth();
+        rbac::grant_role(&env, &caller, &target, role, Some(expires_at), reason_hash);
+        log_admin(&env, &caller, symbol_short!("role_set"), Some(target), None);
     }
 
     /// Revoke any role from `target`.
     ///
     /// - `caller` must hold `SuperAdmin` or `Admin`.
     /// - Cannot be used to remove the SuperAdmin; use `transfer_super_admin`.
-    pub fn revoke_role(env: Env, caller: Address, target: Address) {
-        rbac::revoke_role(&env, &caller, &target);
+    /// - `reason_hash` is recorded as for `grant_role`.
+    pub fn revoke_role(
+        env: Env,
+        caller: Address,
+        target: Address,
+        reason_hash: Option<BytesN<32>>,
+    ) {
+        caller.require_auth();
+        rbac::revoke_role(&env, &caller, &target, reason_hash);
+        log_admin(&env, &caller, symbol_short!("role_del"), Some(target), None);
     }
 
     /// Transfer SuperAdmin to `new_super_admin`.
     ///
     /// - `current_super_admin` must authorize and hold the `SuperAdmin` role.
     /// - The previous SuperAdmin loses the role immediately.
+    /// - Any pending guardian recovery and the dead-man switch are discarded.
     pub fn transfer_super_admin(env: Env, current_super_admin: Address, new_super_admin: Address) {
+        current_super_admin.require_auth();
         rbac::transfer_super_admin(&env, &current_super_admin, &new_super_admin);
+        storage::set_recovery(&env, None);
+        storage::set_dead_man(&env, None);
+        log_admin(
+            &env,
+            &current_super_admin,
+            symbol_short!("sa_xfer"),
+            Some(new_super_admin),
+            None,
+        );
+    }
+
+    /// Return at most `limit` audit log entries starting at sequence number
+    /// `offset`, oldest first.
+    ///
+    /// Only the most recent `ADMIN_LOG_CAPACITY` (128) actions are retained;
+    /// an `offset` older than that starts at the oldest retained entry.
+    /// `limit` is capped at `ADMIN_LOG_PAGE` (50) to stay within the
+    /// per-call ledger read limit.
+    /// Recorded actions are role changes, protocol config and template
+    /// changes, pauses and maintenance windows, freezes, forced transitions,
+    /// contest resolutions, verifier approvals, fraud flags and wind-down.
+    pub fn get_admin_log(env: Env, offset: u64, limit: u32) -> Vec<AdminAction> {
+        let count = storage::get_admin_log_count(&env);
+        let oldest = count.saturating_sub(storage::ADMIN_LOG_CAPACITY);
+        let start = offset.max(oldest);
+        let limit = limit.min(storage::ADMIN_LOG_PAGE) as u64;
+        let end = start.saturating_add(limit).min(count);
+        let mut entries = Vec::new(&env);
+        for seq in start..end {
+            if let Some(entry) = storage::get_admin_log_entry(&env, seq) {
+                entries.push_back(entry);
+            }
+        }
+        entries
     }
 
     /// Return the role held by `address`, or `None`.
@@ -117,166 +701,5391 @@ impl PifpProtocol {
         rbac::role_of(&env, address)
     }
 
+    /// Return the last `MAX_ROLE_HISTORY` role grants and revocations that
+    /// affected `address`, oldest first.
+    pub fn get_role_history(env: Env, address: Address) -> Vec<RoleChange> {
+        rbac::role_history(&env, address)
+    }
+
     /// Return `true` if `address` holds `role`.
     pub fn has_role(env: Env, address: Address, role: Role) -> bool {
         rbac::has_role(&env, address, role)
     }
 
-    // ─────────────────────────────────────────────────────────
-    // Existing entry points — updated to use RBAC
-    // ─────────────────────────────────────────────────────────
+    /// Return the timestamp the role of `address` lapses at, or `None` if
+    /// it was granted without an expiry.
+    pub fn role_expiry(env: Env, address: Address) -> Option<u64> {
+        rbac::role_expiry(&env, &address)
+    }
 
-    /// Register a new funding project.
+    /// Return every address holding `role`, in order of their grant.
     ///
-    /// `creator` must hold the `ProjectManager`, `Admin`, or `SuperAdmin` role.
-    pub fn register_project(
-        env: Env,
-        creator: Address,
-        accepted_tokens: Vec<Address>,
-        goal: i128,
-        proof_hash: BytesN<32>,
-        deadline: u64,
-    ) -> Project {
-        creator.require_auth();
-        // RBAC gate: only authorised roles may create projects.
-        rbac::require_can_register(&env, &creator);
+    /// Expired grants are left out. Holders granted before this index was
+    /// introduced are only listed once their role is granted again.
+    pub fn list_role_members(env: Env, role: Role) -> Vec<Address> {
+        rbac::list_members(&env, &role)
+    }
 
-        if accepted_tokens.is_empty() {
-            panic_with_error!(&env, Error::InvalidMilestones);
-        }
-        if accepted_tokens.len() > 10 {
-            panic_with_error!(&env, Error::TooManyTokens);
+    /// Return the actions `address` may currently take, so frontends can
+    /// render the right controls without encoding the RBAC matrix.
+    ///
+    /// Unfrozen addresses may `deposit` and `refund`; creators of at least
+    /// one project may `manage` their own projects; the rest follow from
+    /// the address's role, see [`rbac::capabilities`].
+    pub fn capabilities_of(env: Env, address: Address) -> Vec<Symbol> {
+        let mut caps = Vec::new(&env);
+        if storage::get_freeze(&env, &address).is_none() {
+            caps.push_back(symbol_short!("deposit"));
+            caps.push_back(symbol_short!("refund"));
         }
-        if goal <= 0 {
-            panic_with_error!(&env, Error::InvalidMilestones);
+        if storage::get_creator_record(&env, &address).projects_created > 0 {
+            caps.push_back(symbol_short!("manage"));
         }
-        if deadline <= env.ledger().timestamp() {
-            panic_with_error!(&env, Error::InvalidMilestones);
+        if let Some(role) = rbac::get_role(&env, &address) {
+            caps.append(&rbac::capabilities(&env, &role));
         }
+        caps
+    }
 
-        let id = get_and_increment_project_id(&env);
+    // ─────────────────────────────────────────────────────────
+    // SuperAdmin key recovery
+    // ─────────────────────────────────────────────────────────
 
-        let project = Project {
-            id,
-            creator: creator.clone(),
-            accepted_tokens: accepted_tokens.clone(),
-            goal,
-            proof_hash,
-            deadline,
-            status: ProjectStatus::Funding,
-            donation_count: 0,
+    /// Configure the guardians able to rotate the SuperAdmin if its key is
+    /// lost.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    /// - `guardians` must be distinct, at most `GuardianSet::MAX_GUARDIANS`,
+    ///   with `threshold` in `1..=guardians.len()`; otherwise
+    ///   `Error::InvalidQuorum`.
+    /// - `delay` must be at least `GuardianSet::MIN_DELAY` (7 days);
+    ///   otherwise `Error::InvalidConfig`.
+    ///
+    /// Replacing the set discards any pending recovery.
+    pub fn set_guardians(
+        env: Env,
+        caller: Address,
+        guardians: Vec<Address>,
+        threshold: u32,
+        delay: u64,
+    ) {
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        let set = GuardianSet {
+            guardians,
+            threshold,
+            delay,
         };
-
-        save_project(&env, &project);
-
-        // Standardized event emission (using the first token as a reference for the created event)
-        if let Some(token) = accepted_tokens.get(0) {
-            events::emit_project_created(&env, id, creator, token, goal);
+        if set.delay < GuardianSet::MIN_DELAY {
+            panic_with_error!(&env, Error::InvalidConfig);
         }
-
-        project
+        if !set.is_valid() {
+            panic_with_error!(&env, Error::InvalidQuorum);
+        }
+        storage::set_guardians(&env, &set);
+        storage::set_recovery(&env, None);
+        log_admin(&env, &caller, symbol_short!("guardians"), None, None);
+        events::emit_guardians_set(&env, set.guardians, set.threshold, set.delay);
     }
 
-    /// Retrieve a project by its ID.
-    pub fn get_project(env: Env, id: u64) -> Project {
-        load_project(&env, id)
+    /// Return the SuperAdmin recovery guardians, if configured.
+    pub fn get_guardians(env: Env) -> Option<GuardianSet> {
+        storage::get_guardians(&env)
     }
 
-    /// Deposit funds into a project.
+    /// Approve rotating the SuperAdmin to `new_super_admin`.
     ///
-    /// The `token` must be one of the project's accepted tokens.
-    pub fn deposit(env: Env, project_id: u64, donator: Address, token: Address, amount: i128) {
-        donator.require_auth();
-
-        // Read both config and state with a single helper that bumps TTLs
-        // atomically. This is the optimized retrieval pattern; it also returns
-        // the state needed for the subsequent checks.
-        let (config, state) = load_project_pair(&env, project_id);
+    /// - `guardian` must authorize and belong to the guardian set.
+    ///
+    /// The first approval opens a request whose delay starts immediately.
+    /// Approving a different address replaces the pending request, resetting
+    /// its approvals and delay. Returns the number of approvals so far.
+    pub fn approve_recovery(env: Env, guardian: Address, new_super_admin: Address) -> u32 {
+        guardian.require_auth();
+        let set = match storage::get_guardians(&env) {
+            Some(set) if set.guardians.contains(&guardian) => set,
+            _ => panic_with_error!(&env, Error::NotAuthorized),
+        };
 
-        // Basic status check: must be Funding or Active.
-        match state.status {
-            ProjectStatus::Funding | ProjectStatus::Active => {}
-            _ => panic!("project not accepting deposits"),
+        let mut request = match storage::get_recovery(&env) {
+            Some(pending) if pending.new_super_admin == new_super_admin => pending,
+            _ => RecoveryRequest {
+                new_super_admin,
+                approvals: Vec::new(&env),
+                eta: env.ledger().timestamp().saturating_add(set.delay),
+            },
+        };
+        if request.approvals.contains(&guardian) {
+            panic_with_error!(&env, Error::AlreadyAttested);
         }
+        request.approvals.push_back(guardian.clone());
+        storage::set_recovery(&env, Some(&request));
+        let approvals = request.approvals.len();
+        events::emit_recovery_approved(&env, guardian, request);
+        approvals
+    }
 
-        // Verify token is accepted.
-        let mut found = false;
-        for t in config.accepted_tokens.iter() {
-            if t == token {
-                found = true;
-                break;
-            }
+    /// Rotate the SuperAdmin to the address approved by the guardians.
+    ///
+    /// Callable by anyone once the pending request has `threshold`
+    /// approvals (`Error::InvalidQuorum` otherwise) and its delay has elapsed
+    /// (`Error::TimelockNotElapsed` otherwise). Fails with
+    /// `Error::InvalidStatusTransition` if no recovery is pending.
+    pub fn execute_recovery(env: Env, caller: Address) {
+        caller.require_auth();
+        let request = match storage::get_recovery(&env) {
+            Some(request) => request,
+            None => panic_with_error!(&env, Error::InvalidStatusTransition),
+        };
+        let set = storage::get_guardians(&env).expect("guardians set with recovery");
+        if request.approvals.len() < set.threshold {
+            panic_with_error!(&env, Error::InvalidQuorum);
         }
-        if !found {
-            panic!("token not accepted by this project");
+        if env.ledger().timestamp() < request.eta {
+            panic_with_error!(&env, Error::TimelockNotElapsed);
         }
 
-        // Transfer tokens from donator to contract.
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&donator, &env.current_contract_address(), &amount);
-
-        // Update the per-token balance.
-        storage::add_to_token_balance(&env, project_id, &token, amount);
-
-        // Standardized event emission
-        events::emit_project_funded(&env, project_id, donator, amount);
+        storage::set_recovery(&env, None);
+        storage::set_dead_man(&env, None);
+        rbac::recover_super_admin(&env, &request.new_super_admin, &caller);
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("recovered"),
+            Some(request.new_super_admin.clone()),
+            None,
+        );
+        events::emit_recovered(&env, request.new_super_admin, caller);
     }
 
-    /// Grant the Oracle role to `oracle`.
-    ///
-    /// Replaces the original `set_oracle(admin, oracle)`.
-    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// Discard the pending guardian recovery.
     ///
-    /// If an address already holds the Oracle role, calling this with a new
-    /// address will grant Oracle to the new one; the old one retains its role
-    /// unless explicitly revoked. If you want a single oracle, revoke the old
-    /// one first, then call `set_oracle`.
-    pub fn set_oracle(env: Env, caller: Address, oracle: Address) {
+    /// - `caller` must be the SuperAdmin.
+    /// - Fails with `Error::InvalidStatusTransition` if none is pending.
+    pub fn cancel_recovery(env: Env, caller: Address) {
         caller.require_auth();
-        rbac::require_admin_or_above(&env, &caller);
-        rbac::grant_role(&env, &caller, &oracle, Role::Oracle);
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        if storage::get_recovery(&env).is_none() {
+            panic_with_error!(&env, Error::InvalidStatusTransition);
+        }
+        storage::set_recovery(&env, None);
+        log_admin(&env, &caller, symbol_short!("recov_del"), None, None);
+        events::emit_recovery_cancelled(&env, caller);
     }
 
-    /// Verify proof of impact and release funds to the creator.
-    ///
-    /// The registered oracle submits a proof hash. If it matches the project's
-    /// stored `proof_hash`, the project status transitions to `Completed`.
+    /// Return the pending guardian recovery, if any.
+    pub fn get_recovery(env: Env) -> Option<RecoveryRequest> {
+        storage::get_recovery(&env)
+    }
+
+    /// Name `successor` to take over the SuperAdmin role if the current
+    /// holder goes silent.
     ///
-    /// NOTE: This is a mocked verification (hash equality).
-    /// The structure is prepared for future ZK-STARK verification.
+    /// - `caller` must be the SuperAdmin.
+    /// - `period` and `announce_delay` must be non-zero; otherwise
+    ///   `Error::InvalidConfig`.
     ///
-    /// Reads the immutable config (for proof_hash) and mutable state (for status),
-    /// then writes back only the small state entry.
-    pub fn verify_and_release(
+    /// Every SuperAdmin-signed administrative call (and `heartbeat`) counts
+    /// as a heartbeat. Once `period` seconds pass without one, `successor`
+    /// may `announce_claim`, then `claim_super_admin` after `announce_delay`.
+    pub fn set_dead_man_switch(
         env: Env,
-        oracle: Address,
-        project_id: u64,
-        submitted_proof_hash: BytesN<32>,
+        caller: Address,
+        successor: Address,
+        period: u64,
+        announce_delay: u64,
     ) {
-        oracle.require_auth();
-        // RBAC gate: caller must hold the Oracle role.
-        rbac::require_oracle(&env, &oracle);
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        if period == 0 || announce_delay == 0 {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        let switch = DeadManSwitch {
+            successor: successor.clone(),
+            period,
+            announce_delay,
+            last_heartbeat: env.ledger().timestamp(),
+            claim_eta: None,
+        };
+        storage::set_dead_man(&env, Some(&switch));
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("deadman"),
+            Some(successor),
+            None,
+        );
+        events::emit_dead_man_set(&env, Some(switch));
+    }
 
-        // Optimised dual-read helper
-        let (config, mut state) = load_project_pair(&env, project_id);
+    /// Remove the dead-man switch.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    pub fn clear_dead_man_switch(env: Env, caller: Address) {
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        storage::set_dead_man(&env, None);
+        log_admin(&env, &caller, symbol_short!("deadman"), None, None);
+        events::emit_dead_man_set(&env, None);
+    }
 
-        // Ensure the project is in a verifiable state.
-        match state.status {
-            ProjectStatus::Funding | ProjectStatus::Active => {}
-            ProjectStatus::Completed => panic_with_error!(&env, Error::MilestoneAlreadyReleased),
-            ProjectStatus::Expired => panic_with_error!(&env, Error::ProjectNotFound),
+    /// Prove the SuperAdmin key is still in use without changing anything
+    /// else. Voids any announced successor claim.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    pub fn heartbeat(env: Env, caller: Address) {
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        record_heartbeat(&env);
+    }
+
+    /// Announce the successor's claim on a lapsed dead-man switch.
+    ///
+    /// - `successor` must authorize and match the configured successor.
+    /// - Fails with `Error::TimelockNotElapsed` unless `period` has passed
+    ///   since the last heartbeat.
+    ///
+    /// Returns the timestamp from which `claim_super_admin` succeeds.
+    pub fn announce_claim(env: Env, successor: Address) -> u64 {
+        successor.require_auth();
+        let mut switch = require_successor(&env, &successor);
+        let now = env.ledger().timestamp();
+        if !switch.is_lapsed(now) {
+            panic_with_error!(&env, Error::TimelockNotElapsed);
         }
+        let eta = now.saturating_add(switch.announce_delay);
+        switch.claim_eta = Some(eta);
+        storage::set_dead_man(&env, Some(&switch));
+        events::emit_claim_announced(&env, successor, eta);
+        eta
+    }
 
-        // Mocked ZK verification: compare submitted hash to stored hash.
-        if submitted_proof_hash != config.proof_hash {
-            panic!("proof verification failed: hash mismatch");
+    /// Complete an announced claim, making `successor` the SuperAdmin.
+    ///
+    /// - `successor` must authorize and match the configured successor.
+    /// - Fails with `Error::InvalidStatusTransition` if no claim is announced
+    ///   and `Error::TimelockNotElapsed` before its delay has passed.
+    ///
+    /// The switch is removed; the new SuperAdmin may configure their own.
+    pub fn claim_super_admin(env: Env, successor: Address) {
+        successor.require_auth();
+        let switch = require_successor(&env, &successor);
+        let eta = match switch.claim_eta {
+            Some(eta) => eta,
+            None => panic_with_error!(&env, Error::InvalidStatusTransition),
+        };
+        if env.ledger().timestamp() < eta {
+            panic_with_error!(&env, Error::TimelockNotElapsed);
         }
 
-        // Transition to Completed — only write the state entry.
-        state.status = ProjectStatus::Completed;
-        save_project_state(&env, project_id, &state);
+        let old = rbac::get_super_admin(&env).expect("SuperAdmin is set at init");
+        storage::set_dead_man(&env, None);
+        storage::set_recovery(&env, None);
+        rbac::recover_super_admin(&env, &successor, &successor);
+        log_admin(
+            &env,
+            &successor,
+            symbol_short!("dm_taken"),
+            Some(old.clone()),
+            None,
+        );
+        events::emit_dead_man_claimed(&env, old, successor);
+    }
 
-        // Standardized event emission
-        events::emit_project_verified(&env, project_id, oracle.clone(), submitted_proof_hash);
+    /// Return the dead-man switch, if configured.
+    pub fn get_dead_man_switch(env: Env) -> Option<DeadManSwitch> {
+        storage::get_dead_man(&env)
     }
+
+    // ─────────────────────────────────────────────────────────
+    // Protocol configuration
+    // ─────────────────────────────────────────────────────────
+
+    /// Replace the protocol-wide configuration.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - `config.max_quorum` must be at least 1.
+    /// - `config.max_accepted_tokens` must lie in
+    ///   `1..=ProtocolConfig::MAX_ACCEPTED_TOKENS_LIMIT`.
+    /// - `config.rent_slice_bps` may not exceed
+    ///   `ProtocolConfig::MAX_RENT_SLICE_BPS`.
+    /// - `config.streak_period` must be non-zero.
+    /// - `config.insurance_premium_bps` may not exceed
+    ///   `ProtocolConfig::MAX_INSURANCE_PREMIUM_BPS`.
+    /// - `config.max_admins` must be at least 1, and only the SuperAdmin may
+    ///   raise it.
+    /// - `config.keeper_reward` and `config.dust_threshold` must not be
+    ///   negative.
+    /// - `config.fee_bps` may not exceed `ProtocolConfig::MAX_FEE_BPS`.
+    /// - `config.release_cap` must not be negative and
+    ///   `config.release_window` must be non-zero.
+    /// - `config.sponsor_threshold`, `config.sponsor_amount` and
+    ///   `config.sponsor_budget` must not be negative, and
+    ///   `config.sponsor_period` must be non-zero.
+    /// - Only the SuperAdmin may lower `config.retention_period` or change
+    ///   `config.successor`.
+    ///
+    /// Existing projects keep the quorum and tokens they were registered with.
+    pub fn set_protocol_config(env: Env, caller: Address, config: ProtocolConfig) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+
+        if config.max_quorum == 0 {
+            panic_with_error!(&env, Error::InvalidQuorum);
+        }
+        if config.max_accepted_tokens == 0
+            || config.max_accepted_tokens > ProtocolConfig::MAX_ACCEPTED_TOKENS_LIMIT
+        {
+            panic_with_error!(&env, Error::TooManyTokens);
+        }
+        if config.rent_slice_bps > ProtocolConfig::MAX_RENT_SLICE_BPS
+            || config.streak_period == 0
+            || config.insurance_premium_bps > ProtocolConfig::MAX_INSURANCE_PREMIUM_BPS
+            || config.max_admins == 0
+            || config.keeper_reward < 0
+            || config.dust_threshold < 0
+            || config.fee_bps > ProtocolConfig::MAX_FEE_BPS
+            || config.release_fee_bps > ProtocolConfig::MAX_FEE_BPS
+            || config.release_cap < 0
+            || config.release_window == 0
+            || config.sponsor_threshold < 0
+            || config.sponsor_amount < 0
+            || config.sponsor_budget < 0
+            || config.sponsor_period == 0
+            || config.conversion_threshold < 0
+            || config.max_slippage_bps > ProtocolConfig::MAX_SLIPPAGE_BPS
+            || (config.reserve_token.is_some()
+                && (config.dex_router.is_none() || config.price_feed.is_none()))
+        {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        let current = storage::get_protocol_config(&env);
+        if current.action_delay > 0
+            && (config.fee_bps != current.fee_bps
+                || config.release_fee_bps != current.release_fee_bps)
+            || config.action_delay < current.action_delay
+        {
+            panic_with_error!(&env, Error::TimelockNotElapsed);
+        }
+        if config.max_admins > current.max_admins
+            || config.retention_period < current.retention_period
+            || config.successor != current.successor
+            || config.release_fee_bps != current.release_fee_bps
+            || config.action_delay != current.action_delay
+        {
+            rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        }
+        storage::set_protocol_config(&env, &config);
+        log_admin(&env, &caller, symbol_short!("config"), None, None);
+    }
+
+    /// Return the protocol-wide configuration currently in effect.
+    pub fn get_protocol_config(env: Env) -> ProtocolConfig {
+        storage::get_protocol_config(&env)
+    }
+
+    /// Return the stable identifier of contract error `code`, e.g.
+    /// `project_not_found` for `Error(Contract, #1)`, or `unknown` for a code
+    /// this version does not define.
+    pub fn error_message(env: Env, code: u32) -> Symbol {
+        let name = Error::from_code(code)
+            .map(|e| e.name())
+            .unwrap_or("unknown");
+        Symbol::new(&env, name)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Pausing
+    // ─────────────────────────────────────────────────────────
+
+    /// Halt registrations, deposits and releases protocol-wide.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - `reason_hash` references the incident report or announcement.
+    /// - `expected_resume`, if given, must be in the future. It is advisory:
+    ///   the pause lasts until `unpause` is called.
+    ///
+    /// Pausing while already paused replaces the recorded details.
+    pub fn pause(env: Env, caller: Address, reason_hash: BytesN<32>, expected_resume: Option<u64>) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        let now = env.ledger().timestamp();
+        if let Some(resume) = expected_resume {
+            if resume <= now {
+                panic_with_error!(&env, Error::InvalidConfig);
+            }
+        }
+        let info = PauseInfo {
+            by: caller.clone(),
+            reason_hash: reason_hash.clone(),
+            paused_at: now,
+            expected_resume,
+        };
+        storage::set_pause_info(&env, Some(&info));
+        log_admin(&env, &caller, symbol_short!("pause"), None, None);
+        events::emit_protocol_paused(&env, true, caller, Some(reason_hash), expected_resume);
+    }
+
+    /// Lift the global pause.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn unpause(env: Env, caller: Address) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        storage::set_pause_info(&env, None);
+        log_admin(&env, &caller, symbol_short!("unpause"), None, None);
+        events::emit_protocol_paused(&env, false, caller, None, None);
+    }
+
+    /// Return `true` if the protocol is globally paused, either by `pause`
+    /// or by an active maintenance window.
+    pub fn is_paused(env: Env) -> bool {
+        protocol_paused(&env)
+    }
+
+    /// Return who paused the protocol, why, and when they expect to resume,
+    /// or `None` while it is running.
+    pub fn get_pause_info(env: Env) -> Option<PauseInfo> {
+        storage::get_pause_info(&env)
+    }
+
+    /// Announce a planned pause from `start` to `end`.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - `start` must be in the future and before `end`.
+    ///
+    /// The protocol pauses and resumes on its own as ledger time crosses the
+    /// window; no `pause`/`unpause` calls are needed. Scheduling again
+    /// replaces the previous window.
+    pub fn schedule_maintenance(
+        env: Env,
+        caller: Address,
+        start: u64,
+        end: u64,
+        reason_hash: BytesN<32>,
+    ) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        if start <= env.ledger().timestamp() || end <= start {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        let window = MaintenanceWindow {
+            by: caller,
+            start,
+            end,
+            reason_hash,
+        };
+        storage::set_maintenance(&env, Some(&window));
+        log_admin(&env, &window.by, symbol_short!("maint"), None, None);
+        events::emit_maintenance_scheduled(&env, window);
+    }
+
+    /// Cancel the scheduled maintenance window, ending it early if it is
+    /// already running.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn cancel_maintenance(env: Env, caller: Address) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        if storage::get_maintenance(&env).is_none() {
+            panic_with_error!(&env, Error::InvalidStatusTransition);
+        }
+        storage::set_maintenance(&env, None);
+        log_admin(&env, &caller, symbol_short!("maint_del"), None, None);
+        events::emit_maintenance_cancelled(&env, caller);
+    }
+
+    /// Return the scheduled maintenance window, if any. A window whose `end`
+    /// has passed is kept until replaced or cancelled but no longer pauses.
+    pub fn get_maintenance(env: Env) -> Option<MaintenanceWindow> {
+        storage::get_maintenance(&env)
+    }
+
+    /// Freeze deposits and releases for a single project, e.g. while it is
+    /// under investigation. Other projects are unaffected.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn pause_project(env: Env, caller: Address, project_id: u64) {
+        set_project_paused(&env, caller, project_id, true);
+    }
+
+    /// Lift a per-project pause.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn unpause_project(env: Env, caller: Address, project_id: u64) {
+        set_project_paused(&env, caller, project_id, false);
+    }
+
+    /// Return `true` if `project_id` is individually paused. Does not reflect
+    /// the global pause; see `is_paused`.
+    pub fn is_project_paused(env: Env, project_id: u64) -> bool {
+        storage::load_project_state(&env, project_id).has_flag(ProjectState::FLAG_PAUSED)
+    }
+
+    /// Suspend deposits of `token` into every project, e.g. during a
+    /// stablecoin depeg. Other tokens, and every other operation on
+    /// balances already held in `token`, are unaffected.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn pause_token(env: Env, caller: Address, token: Address) {
+        set_token_paused(&env, caller, token, true);
+    }
+
+    /// Resume deposits of `token`.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn unpause_token(env: Env, caller: Address, token: Address) {
+        set_token_paused(&env, caller, token, false);
+    }
+
+    /// Return `true` if deposits of `token` are suspended.
+    pub fn is_token_paused(env: Env, token: Address) -> bool {
+        storage::is_token_paused(&env, &token)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Token allowlist
+    // ─────────────────────────────────────────────────────────
+
+    /// Allow projects to register with `token` while
+    /// `ProtocolConfig::token_allowlist` is set. Adding a listed token is a
+    /// no-op.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - Fails with `InvalidToken` unless `token` answers like a token
+    ///   contract, and with `TooManyTokens` once
+    ///   `ProtocolConfig::MAX_ALLOWED_TOKENS` are listed.
+    pub fn add_allowed_token(env: Env, caller: Address, token: Address) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        let mut tokens = storage::get_allowed_tokens(&env);
+        if tokens.contains(&token) {
+            return;
+        }
+        if tokens.len() >= ProtocolConfig::MAX_ALLOWED_TOKENS {
+            panic_with_error!(&env, Error::TooManyTokens);
+        }
+        require_token_contract(&env, &token);
+        tokens.push_back(token.clone());
+        storage::set_allowed_tokens(&env, &tokens);
+        log_admin(&env, &caller, symbol_short!("tok_allow"), None, None);
+        events::emit_token_allowed(&env, token, true, caller);
+    }
+
+    /// Take `token` off the registration allowlist. Projects already
+    /// accepting it are unaffected.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn remove_allowed_token(env: Env, caller: Address, token: Address) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        let mut tokens = storage::get_allowed_tokens(&env);
+        if let Some(index) = tokens.first_index_of(&token) {
+            tokens.remove(index);
+            storage::set_allowed_tokens(&env, &tokens);
+            log_admin(&env, &caller, symbol_short!("tok_deny"), None, None);
+            events::emit_token_allowed(&env, token, false, caller);
+        }
+    }
+
+    /// Return the tokens on the registration allowlist, in the order they
+    /// were added.
+    pub fn list_allowed_tokens(env: Env) -> Vec<Address> {
+        storage::get_allowed_tokens(&env)
+    }
+
+    /// Return `true` if new projects may register with `token`: always
+    /// while `ProtocolConfig::token_allowlist` is off.
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        !storage::get_protocol_config(&env).token_allowlist
+            || storage::get_allowed_tokens(&env).contains(&token)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Wind-down
+    // ─────────────────────────────────────────────────────────
+
+    /// Start retiring this deployment.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    ///
+    /// New registrations and deposits are rejected from then on, while
+    /// verification and releases on existing projects keep working. Unlike
+    /// `pause`, this cannot be undone.
+    pub fn begin_sunset(env: Env, caller: Address) {
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        if !storage::is_sunset(&env) {
+            storage::set_sunset(&env);
+            log_admin(&env, &caller, symbol_short!("sunset"), None, None);
+            events::emit_sunset_begun(&env, caller);
+        }
+    }
+
+    /// Sweep this contract's `token` holdings that are not escrowed for any
+    /// project to `recipient`. Returns the amount swept.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    /// - Only allowed after `begin_sunset`.
+    ///
+    /// Project escrow, the insurance, keeper, sponsorship and commons pools
+    /// and the dust ledger are never touched, so this may be called again as
+    /// projects settle or per token as needed.
+    pub fn finalize_sunset(env: Env, caller: Address, token: Address, recipient: Address) -> i128 {
+        let _guard = ReentrancyGuard::acquire(&env);
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        if !storage::is_sunset(&env) {
+            panic_with_error!(&env, Error::InvalidStatusTransition);
+        }
+
+        let residual = untracked_balance(&env, &token);
+        if residual <= 0 {
+            return 0;
+        }
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &residual,
+        );
+        events::emit_escrow_transfer(
+            &env,
+            token.clone(),
+            env.current_contract_address(),
+            recipient.clone(),
+            residual,
+        );
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("swept"),
+            Some(recipient.clone()),
+            None,
+        );
+        events::emit_residual_swept(&env, token, recipient, residual);
+        residual
+    }
+
+    /// Return `true` once wind-down mode has begun.
+    pub fn is_sunset(env: Env) -> bool {
+        storage::is_sunset(&env)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Compliance freezes
+    // ─────────────────────────────────────────────────────────
+
+    /// Block deposits from `address` while a compliance review runs.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - `reason_hash` references the off-chain case; re-freezing replaces it.
+    ///
+    /// Unlike a permanent denylist, a freeze is expected to be lifted with
+    /// `unfreeze_address` once the review concludes.
+    pub fn freeze_address(env: Env, caller: Address, address: Address, reason_hash: BytesN<32>) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        storage::set_freeze(&env, &address, &reason_hash);
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("frozen"),
+            Some(address.clone()),
+            None,
+        );
+        events::emit_address_frozen(&env, address, reason_hash, caller);
+    }
+
+    /// Lift the compliance freeze on `address`. No-op if it is not frozen.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn unfreeze_address(env: Env, caller: Address, address: Address) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        if storage::remove_freeze(&env, &address) {
+            log_admin(
+                &env,
+                &caller,
+                symbol_short!("unfrozen"),
+                Some(address.clone()),
+                None,
+            );
+            events::emit_address_unfrozen(&env, address, caller);
+        }
+    }
+
+    /// Return the reason hash of the active freeze on `address`, if any.
+    pub fn get_freeze(env: Env, address: Address) -> Option<BytesN<32>> {
+        storage::get_freeze(&env, &address)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Donor allowlists
+    // ─────────────────────────────────────────────────────────
+
+    /// Turn a project's donor allowlist on or off. While it is on, only
+    /// donors added with `add_allowed_donor` may deposit; others fail with
+    /// `NotAuthorized`. Earlier deposits are unaffected.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) or be a single `SuperAdmin` or `Admin`, and must
+    ///   authorize.
+    ///
+    /// For grantees legally restricted in who may fund them. Emits
+    /// `allowlist`.
+    pub fn set_donor_allowlist(env: Env, signers: Vec<Address>, project_id: u64, enabled: bool) {
+        let config = storage::load_project_config(&env, project_id);
+        let caller = require_control_or_admin(&env, &config, &signers, symbol_short!("allowlist"));
+        let mut state = storage::load_project_state(&env, project_id);
+        state.set_flag(ProjectState::FLAG_DONOR_ALLOWLIST, enabled);
+        save_project_state(&env, project_id, &state);
+        events::emit_donor_allowlist_set(&env, project_id, enabled, caller);
+    }
+
+    /// Allow `donor` to deposit into a project while its donor allowlist is
+    /// on. The list may be filled before the allowlist is turned on.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) or be a single `SuperAdmin` or `Admin`, and must
+    ///   authorize.
+    ///
+    /// Emits `donor_acl`.
+    pub fn add_allowed_donor(env: Env, signers: Vec<Address>, project_id: u64, donor: Address) {
+        let config = storage::load_project_config(&env, project_id);
+        let caller = require_control_or_admin(&env, &config, &signers, symbol_short!("donor_acl"));
+        storage::set_allowed_donor(&env, project_id, &donor, true);
+        events::emit_allowed_donor_set(&env, project_id, donor, true, caller);
+    }
+
+    /// Remove `donor` from a project's donor allowlist. Their earlier
+    /// deposits stay in escrow.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) or be a single `SuperAdmin` or `Admin`, and must
+    ///   authorize.
+    ///
+    /// Emits `donor_acl`.
+    pub fn remove_allowed_donor(env: Env, signers: Vec<Address>, project_id: u64, donor: Address) {
+        let config = storage::load_project_config(&env, project_id);
+        let caller = require_control_or_admin(&env, &config, &signers, symbol_short!("donor_acl"));
+        storage::set_allowed_donor(&env, project_id, &donor, false);
+        events::emit_allowed_donor_set(&env, project_id, donor, false, caller);
+    }
+
+    /// Return `true` if `donor` passes a project's donor allowlist: the
+    /// allowlist is off, or `donor` is on it.
+    pub fn is_donor_allowed(env: Env, project_id: u64, donor: Address) -> bool {
+        donor_allowed(
+            &env,
+            project_id,
+            &storage::load_project_state(&env, project_id),
+            &donor,
+        )
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Recovery
+    // ─────────────────────────────────────────────────────────
+
+    /// Move a stuck project to `new_status`, bypassing the normal lifecycle.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    /// - `new_status` must be a forward transition from the current status.
+    ///
+    /// The first call queues the change and emits `force_req`; it can be
+    /// applied by calling again with the same arguments once
+    /// `ProtocolConfig::force_status_delay` has elapsed, which emits `forced`.
+    /// Calling with different arguments replaces the queued change and
+    /// restarts the delay. Returns `true` once the change has been applied.
+    pub fn force_status(
+        env: Env,
+        caller: Address,
+        project_id: u64,
+        new_status: ProjectStatus,
+        reason_hash: BytesN<32>,
+    ) -> bool {
+        let _guard = ReentrancyGuard::acquire(&env);
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+
+        let mut state = storage::load_project_state(&env, project_id);
+        if !state.status.can_advance_to(&new_status) {
+            panic_with_error!(&env, Error::InvalidStatusTransition);
+        }
+
+        let now = env.ledger().timestamp();
+        if let Some(pending) = storage::get_pending_force(&env, project_id) {
+            if pending.new_status == new_status && pending.reason_hash == reason_hash {
+                if now < pending.eta {
+                    panic_with_error!(&env, Error::TimelockNotElapsed);
+                }
+                let from = state.status.clone();
+                state.status = new_status.clone();
+                save_project_state(&env, project_id, &state);
+                if matches!(
+                    new_status,
+                    ProjectStatus::Completed | ProjectStatus::Expired
+                ) {
+                    storage::set_settled_at(&env, project_id, now);
+                }
+                if new_status == ProjectStatus::Expired {
+                    route_grants(&env, &storage::load_project_config(&env, project_id));
+                }
+                storage::remove_pending_force(&env, project_id);
+                log_admin(
+                    &env,
+                    &caller,
+                    symbol_short!("forced"),
+                    None,
+                    Some(project_id),
+                );
+                events::emit_status_forced(&env, project_id, from, new_status, reason_hash, caller);
+                return true;
+            }
+        }
+
+        let eta = now.saturating_add(storage::get_protocol_config(&env).force_status_delay);
+        storage::set_pending_force(
+            &env,
+            project_id,
+            &PendingStatusChange {
+                new_status: new_status.clone(),
+                reason_hash: reason_hash.clone(),
+                eta,
+            },
+        );
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("force_req"),
+            None,
+            Some(project_id),
+        );
+        events::emit_status_force_queued(&env, project_id, new_status, reason_hash, eta, caller);
+        false
+    }
+
+    /// Withdraw a queued `force_status` request.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    pub fn cancel_force_status(env: Env, caller: Address, project_id: u64) {
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        if storage::get_pending_force(&env, project_id).is_some() {
+            storage::remove_pending_force(&env, project_id);
+            log_admin(
+                &env,
+                &caller,
+                symbol_short!("force_del"),
+                None,
+                Some(project_id),
+            );
+            events::emit_status_force_cancelled(&env, project_id, caller);
+        }
+    }
+
+    /// Return the forced status change queued for `project_id`, if any.
+    pub fn get_pending_force_status(env: Env, project_id: u64) -> Option<PendingStatusChange> {
+        storage::get_pending_force(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Escrow migration
+    // ─────────────────────────────────────────────────────────
+
+    /// Move `project_id` and its escrow to the successor contract.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    /// - `successor` must equal `ProtocolConfig::successor`
+    ///   (`NotAuthorized` otherwise).
+    /// - The project must not be `Completed` or `Expired`, nor have a
+    ///   verified release pending.
+    ///
+    /// Follows the same two-step timelock as `force_status`, using
+    /// `ProtocolConfig::force_status_delay`: the first call queues the
+    /// migration and emits `migr_req`; calling again with the same
+    /// successor once the delay has elapsed transfers every escrowed
+    /// balance, hands the successor a [`MigrationRecord`] through
+    /// [`migration::EscrowIntakeInterface`] and emits `migrated`. The
+    /// project is then `Expired` here and flagged as migrated; refunds are
+    /// the successor's responsibility. Returns `true` once applied.
+    pub fn migrate_escrow(env: Env, caller: Address, project_id: u64, successor: Address) -> bool {
+        let _guard = ReentrancyGuard::acquire(&env);
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        let protocol = storage::get_protocol_config(&env);
+        if protocol.successor.as_ref() != Some(&successor) {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        let (config, mut state) = load_project_pair(&env, project_id);
+        if let Err(error) = fsm::transition(&state.status, StatusEvent::Cancel) {
+            panic_with_error!(&env, error);
+        }
+        require_no_pending_release(&env, &state);
+
+        let now = env.ledger().timestamp();
+        match storage::get_pending_migration(&env, project_id) {
+            Some(pending) if pending.successor == successor => {
+                if now < pending.eta {
+                    panic_with_error!(&env, Error::TimelockNotElapsed);
+                }
+            }
+            _ => {
+                let eta = now.saturating_add(protocol.force_status_delay);
+                let pending = PendingMigration {
+                    successor: successor.clone(),
+                    eta,
+                };
+                storage::set_pending_migration(&env, project_id, &pending);
+                log_admin(
+                    &env,
+                    &caller,
+                    symbol_short!("migr_req"),
+                    Some(successor.clone()),
+                    Some(project_id),
+                );
+                events::emit_migration_queued(&env, project_id, successor, eta);
+                return false;
+            }
+        }
+
+        let project = load_project(&env, project_id);
+        let contract = env.current_contract_address();
+        let mut balances = Vec::new(&env);
+        for token in config.accepted_tokens.iter() {
+            let amount = storage::get_token_balance(&env, project_id, &token);
+            if amount > 0 {
+                record_outflow(&env, project_id, &token, amount);
+                storage::drain_token_balance(&env, project_id, &token);
+                token::Client::new(&env, &token).transfer(&contract, &successor, &amount);
+                events::emit_escrow_transfer(
+                    &env,
+                    token.clone(),
+                    contract.clone(),
+                    successor.clone(),
+                    amount,
+                );
+            }
+            balances.push_back(TokenBalance {
+                token,
+                balance: amount,
+            });
+        }
+
+        let record = MigrationRecord {
+            project,
+            released_bps: state.released_bps,
+            milestones: storage::get_milestones(&env, project_id),
+            balances: balances.clone(),
+        };
+        fsm::advance(&env, &mut state, StatusEvent::Cancel);
+        state.set_flag(ProjectState::FLAG_MIGRATED, true);
+        storage::update_creator_record(&env, &config.creator, |r| r.projects_cancelled += 1);
+        save_project_state(&env, project_id, &state);
+        storage::set_settled_at(&env, project_id, now);
+        storage::remove_pending_migration(&env, project_id);
+        migration::EscrowIntakeClient::new(&env, &successor).intake_project(&record);
+
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("migrated"),
+            Some(successor.clone()),
+            Some(project_id),
+        );
+        events::emit_escrow_migrated(&env, project_id, successor, balances);
+        true
+    }
+
+    /// Withdraw a queued escrow migration.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    pub fn cancel_migration(env: Env, caller: Address, project_id: u64) {
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        if storage::get_pending_migration(&env, project_id).is_some() {
+            storage::remove_pending_migration(&env, project_id);
+            log_admin(
+                &env,
+                &caller,
+                symbol_short!("migr_del"),
+                None,
+                Some(project_id),
+            );
+            events::emit_migration_cancelled(&env, project_id, caller);
+        }
+    }
+
+    /// Return the escrow migration queued for `project_id`, if any.
+    pub fn get_pending_migration(env: Env, project_id: u64) -> Option<PendingMigration> {
+        storage::get_pending_migration(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Existing entry points — updated to use RBAC
+    // ─────────────────────────────────────────────────────────
+
+    /// Register a new funding project.
+    ///
+    /// `creator` must hold the `ProjectManager`, `Admin`, or `SuperAdmin` role.
+    ///
+    /// `quorum` is the number of distinct oracle attestations required before
+    /// the project completes; it must lie in `1..=ProtocolConfig::max_quorum`.
+    ///
+    /// `milestones` may split the goal into up to
+    /// `DisbursementTemplate::MAX_TRANCHES` parts whose amounts must sum to
+    /// `goal`. Each quorum then releases the next milestone's share of the
+    /// escrow against that milestone's proof, and the last one completes
+    /// the project. Empty releases everything on one verification.
+    ///
+    /// While `ProtocolConfig::token_allowlist` is set, every accepted token
+    /// must be on the allowlist or registration fails with `InvalidToken`.
+    pub fn register_project(
+        env: Env,
+        creator: Address,
+        accepted_tokens: Vec<Address>,
+        goal: i128,
+        proof_hash: BytesN<32>,
+        deadline: u64,
+        quorum: u32,
+        milestones: Vec<Milestone>,
+    ) -> Project {
+        creator.require_auth();
+        validate_registration(
+            &env,
+            &creator,
+            &accepted_tokens,
+            goal,
+            deadline,
+            quorum,
+            &milestones,
+        );
+
+        let id = get_and_increment_project_id(&env);
+        store_new_project(
+            &env,
+            Project {
+                id,
+                creator,
+                accepted_tokens,
+                goal,
+                proof_hash,
+                deadline,
+                quorum,
+                goal_unit: GoalUnit::FirstToken,
+                status: ProjectStatus::Funding,
+                donation_count: 0,
+            },
+            &milestones,
+        )
+    }
+
+    /// Register a project under a content-addressed ID instead of the next
+    /// sequential one.
+    ///
+    /// The ID is `derive_project_id(creator, proof_hash, salt)`, so it can be
+    /// referenced (e.g. in a grant agreement) before registration. Derived
+    /// IDs have the high bit set and never collide with sequential ones.
+    /// Fails with `Error::AlreadyInitialized` if the ID is already taken;
+    /// otherwise behaves exactly like `register_project`.
+    pub fn register_project_at(
+        env: Env,
+        creator: Address,
+        accepted_tokens: Vec<Address>,
+        goal: i128,
+        proof_hash: BytesN<32>,
+        deadline: u64,
+        quorum: u32,
+        salt: BytesN<32>,
+        milestones: Vec<Milestone>,
+    ) -> Project {
+        creator.require_auth();
+        validate_registration(
+            &env,
+            &creator,
+            &accepted_tokens,
+            goal,
+            deadline,
+            quorum,
+            &milestones,
+        );
+
+        let id = storage::derive_project_id(&env, &creator, &proof_hash, &salt);
+        if storage::maybe_load_project_config(&env, id).is_some() {
+            panic_with_error!(&env, Error::AlreadyInitialized);
+        }
+        store_new_project(
+            &env,
+            Project {
+                id,
+                creator,
+                accepted_tokens,
+                goal,
+                proof_hash,
+                deadline,
+                quorum,
+                goal_unit: GoalUnit::FirstToken,
+                status: ProjectStatus::Funding,
+                donation_count: 0,
+            },
+            &milestones,
+        )
+    }
+
+    /// Return the milestones `project_id` registered with; empty if none.
+    pub fn get_milestones(env: Env, project_id: u64) -> Vec<Milestone> {
+        storage::get_milestones(&env, project_id)
+    }
+
+    /// Return the ID `register_project_at` would assign for these inputs.
+    pub fn derive_project_id(
+        env: Env,
+        creator: Address,
+        proof_hash: BytesN<32>,
+        salt: BytesN<32>,
+    ) -> u64 {
+        storage::derive_project_id(&env, &creator, &proof_hash, &salt)
+    }
+
+    /// Denominate a project's goal in the price feed's reference unit, so
+    /// every accepted token counts towards it.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - A price feed must be configured in `ProtocolConfig`.
+    /// - Only allowed before the project has received any deposits or
+    ///   attestations.
+    pub fn set_reference_goal(env: Env, signers: Vec<Address>, project_id: u64) {
+        let mut config = require_unfunded_control(&env, &signers, project_id);
+        if storage::get_protocol_config(&env).price_feed.is_none() {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        config.goal_unit = GoalUnit::Reference;
+        storage::save_project_config(&env, &config);
+    }
+
+    /// Lock a project to whichever accepted token its first deposit uses.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - Only allowed before the project has received any deposits or
+    ///   attestations.
+    ///
+    /// On the first deposit the project's accepted tokens shrink to that one
+    /// token, so a first-token goal is then denominated in it.
+    pub fn set_currency_lock(env: Env, signers: Vec<Address>, project_id: u64) {
+        require_unfunded_control(&env, &signers, project_id);
+        let mut state = storage::load_project_state(&env, project_id);
+        state.set_flag(ProjectState::FLAG_CURRENCY_LOCK, true);
+        save_project_state(&env, project_id, &state);
+    }
+
+    /// Declare projects that must be `Completed` before this one may be
+    /// verified, e.g. so phase 2 of a program only releases after phase 1.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - Only allowed before the project has received any deposits or
+    ///   attestations, so donors always fund under known conditions.
+    /// - Every dependency must exist, be listed once, and not (directly or
+    ///   transitively) depend on this project; at most
+    ///   `ProjectConfig::MAX_DEPENDENCIES` may be given. An empty list
+    ///   removes the dependencies.
+    pub fn set_dependencies(env: Env, signers: Vec<Address>, project_id: u64, deps: Vec<u64>) {
+        require_unfunded_control(&env, &signers, project_id);
+        if deps.len() > ProjectConfig::MAX_DEPENDENCIES {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        for (i, dep) in deps.iter().enumerate() {
+            if !storage::project_exists(&env, dep) {
+                panic_with_error!(&env, Error::ProjectNotFound);
+            }
+            if deps.first_index_of(dep) != Some(i as u32) {
+                panic_with_error!(&env, Error::InvalidConfig);
+            }
+        }
+        if depends_on(&env, &deps, project_id) {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        storage::set_dependencies(&env, project_id, &deps);
+        events::emit_dependencies_set(&env, project_id, deps);
+    }
+
+    /// Return the projects that must complete before `project_id` may be
+    /// verified.
+    pub fn get_dependencies(env: Env, project_id: u64) -> Vec<u64> {
+        storage::get_dependencies(&env, project_id)
+    }
+
+    /// Give a project a human-meaningful handle, unique among its creator's
+    /// projects, for links and integrations.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - A project gets at most one handle, and it can never be changed or
+    ///   reused, so links built on it stay stable.
+    pub fn set_project_handle(env: Env, signers: Vec<Address>, project_id: u64, handle: Symbol) {
+        let config = storage::load_project_config(&env, project_id);
+        require_project_control(&env, &config, &signers);
+        let creator = config.creator;
+        if storage::get_project_handle(&env, project_id).is_some()
+            || storage::get_project_by_handle(&env, &creator, &handle).is_some()
+        {
+            panic_with_error!(&env, Error::AlreadyInitialized);
+        }
+        storage::set_project_handle(&env, &creator, &handle, project_id);
+        events::emit_handle_set(&env, project_id, creator, handle);
+    }
+
+    /// Retrieve the project `creator` registered under `handle`.
+    pub fn get_project_by_handle(env: Env, creator: Address, handle: Symbol) -> Project {
+        match storage::get_project_by_handle(&env, &creator, &handle) {
+            Some(id) => load_project(&env, id),
+            None => panic_with_error!(&env, Error::ProjectNotFound),
+        }
+    }
+
+    /// Return the handle registered for a project, if any.
+    pub fn get_project_handle(env: Env, project_id: u64) -> Option<Symbol> {
+        storage::get_project_handle(&env, project_id)
+    }
+
+    /// Set the display `name` and `metadata_hash` explorers render a
+    /// project page from, replacing any earlier ones.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - Only allowed while the project is `Funding` or `Active`.
+    /// - `name` must be non-empty and at most
+    ///   `ProjectMetadata::MAX_NAME_LEN` bytes (`InvalidConfig`).
+    ///
+    /// Emits `metadata`.
+    pub fn update_metadata(
+        env: Env,
+        signers: Vec<Address>,
+        project_id: u64,
+        name: String,
+        metadata_hash: BytesN<32>,
+    ) {
+        let (config, state) = load_project_pair(&env, project_id);
+        require_project_control(&env, &config, &signers);
+        match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active => {}
+            _ => panic_with_error!(&env, Error::InvalidStatusTransition),
+        }
+        if name.is_empty() || name.len() > ProjectMetadata::MAX_NAME_LEN {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        let metadata = ProjectMetadata {
+            name,
+            metadata_hash,
+        };
+        storage::set_project_metadata(&env, project_id, &metadata);
+        events::emit_metadata_updated(&env, project_id, metadata);
+    }
+
+    /// Return the display metadata of a project, if its creator set any.
+    pub fn get_project_metadata(env: Env, project_id: u64) -> Option<ProjectMetadata> {
+        storage::get_project_metadata(&env, project_id)
+    }
+
+    /// Return the number of sequentially numbered projects, i.e. one past
+    /// the highest ID `register_project` has assigned.
+    pub fn get_project_count(env: Env) -> u64 {
+        storage::get_project_count(&env)
+    }
+
+    /// Return the projects with IDs `start..start + limit`, in ID order.
+    ///
+    /// `limit` is capped at `PROJECT_PAGE` (25); continue from
+    /// `start + limit` until it reaches `get_project_count`. Projects
+    /// registered with `register_project_at` are not listed.
+    pub fn list_projects(env: Env, start: u64, limit: u32) -> Vec<Project> {
+        let mut projects = Vec::new(&env);
+        for id in project_page(&env, start, limit) {
+            if storage::project_exists(&env, id) {
+                projects.push_back(load_project(&env, id));
+            }
+        }
+        projects
+    }
+
+    /// Return the IDs in `start..start + limit` of projects in `status`.
+    ///
+    /// Scans the same window as `list_projects`, so a page may hold fewer
+    /// than `limit` IDs; continue from `start + limit`.
+    pub fn list_project_ids_by_status(
+        env: Env,
+        status: ProjectStatus,
+        start: u64,
+        limit: u32,
+    ) -> Vec<u64> {
+        let mut ids = Vec::new(&env);
+        for id in project_page(&env, start, limit) {
+            match storage::maybe_load_project_state(&env, id) {
+                Some(state) if state.status == status => ids.push_back(id),
+                _ => {}
+            }
+        }
+        ids
+    }
+
+    /// Retrieve a project by its ID.
+    pub fn get_project(env: Env, id: u64) -> Project {
+        load_project(&env, id)
+    }
+
+    /// Retrieve only the immutable configuration of a project.
+    ///
+    /// A single storage read; cheaper than `get_project` for callers that do
+    /// not need the current status.
+    pub fn get_project_config(env: Env, id: u64) -> ProjectConfig {
+        storage::load_project_config(&env, id)
+    }
+
+    /// Retrieve only the mutable state of a project.
+    ///
+    /// A single storage read; cheaper than `get_project` for callers polling
+    /// status or donation counts.
+    pub fn get_project_state(env: Env, id: u64) -> ProjectState {
+        storage::load_project_state(&env, id)
+    }
+
+    /// Return `true` if `project_id` currently accepts deposits in `token`.
+    ///
+    /// Lets wallets and composing contracts validate a deposit before
+    /// building it. Panics if the project does not exist.
+    pub fn accepts_token(env: Env, project_id: u64, token: Address) -> bool {
+        storage::load_project_config(&env, project_id)
+            .accepted_tokens
+            .contains(&token)
+    }
+
+    /// Return the escrowed balance of every accepted token of a project.
+    ///
+    /// Reads one entry per accepted token; projects with many tokens should
+    /// use `get_balances_page` to stay within the read budget.
+    pub fn get_balances(env: Env, project_id: u64) -> ProjectBalances {
+        let project = load_project(&env, project_id);
+        storage::get_all_balances(&env, &project)
+    }
+
+    /// Return the escrowed balances of at most `limit` accepted tokens,
+    /// starting at index `offset` of the project's accepted-token list.
+    ///
+    /// An `offset` past the end yields an empty page.
+    pub fn get_balances_page(
+        env: Env,
+        project_id: u64,
+        offset: u32,
+        limit: u32,
+    ) -> ProjectBalances {
+        let (config, _) = load_project_pair(&env, project_id);
+        storage::get_balances_page(&env, &config, offset, limit)
+    }
+
+    /// Return how much of `token` has entered and left a project's escrow.
+    pub fn get_token_flows(env: Env, project_id: u64, token: Address) -> TokenFlows {
+        storage::load_project_config(&env, project_id);
+        storage::get_token_flows(&env, project_id, &token)
+    }
+
+    /// Return the recorded balance of `token` in a project's escrow together
+    /// with when it last changed and its lifetime inflow and outflow.
+    ///
+    /// Meant for lending, insurance and other contracts composing with PIFP:
+    /// `balance == inflow - outflow` holds for a healthy escrow.
+    pub fn get_escrow_proof(env: Env, project_id: u64, token: Address) -> EscrowProof {
+        storage::load_project_config(&env, project_id);
+        let flows = storage::get_token_flows(&env, project_id, &token);
+        EscrowProof {
+            project_id,
+            balance: storage::get_token_balance(&env, project_id, &token),
+            token,
+            updated_ledger: flows.updated_ledger,
+            inflow: flows.inflow,
+            outflow: flows.outflow,
+        }
+    }
+
+    /// Return raised, released, refunded and outstanding amounts for every
+    /// accepted token of a project, plus reference-unit totals.
+    ///
+    /// `raised` is summed over the project's contributions, so the read cost
+    /// grows with the number of (donor, token) pairs. `released` is what has
+    /// been paid to the creator, `refunded` what donors reclaimed from escrow
+    /// and `outstanding` what is still in escrow.
+    pub fn get_project_report(env: Env, project_id: u64) -> ProjectReport {
+        let (config, state) = load_project_pair(&env, project_id);
+        let donors = storage::get_project_donors(&env, project_id);
+        let feed = storage::get_protocol_config(&env).price_feed;
+
+        let mut tokens = Vec::new(&env);
+        let mut raised_value = 0i128;
+        let mut outstanding_value = 0i128;
+        for token in config.accepted_tokens.iter() {
+            let mut raised = 0i128;
+            for (donor, donated) in donors.iter() {
+                if donated == token {
+                    raised += storage::get_contribution(&env, project_id, &donor, &token);
+                    raised_value +=
+                        storage::get_contribution_value(&env, project_id, &donor, &token);
+                }
+            }
+            let flows = storage::get_token_flows(&env, project_id, &token);
+            let outstanding = storage::get_token_balance(&env, project_id, &token);
+            if let Some(feed) = &feed {
+                if outstanding > 0 {
+                    outstanding_value +=
+                        price::reference_value(&env, feed, &token, outstanding).unwrap_or(0);
+                }
+            }
+            tokens.push_back(TokenReport {
+                token,
+                raised,
+                released: flows.released,
+                refunded: flows.refunded,
+                outstanding,
+            });
+        }
+        ProjectReport {
+            project_id,
+            status: state.status,
+            tokens,
+            raised_value,
+            outstanding_value,
+        }
+    }
+
+    /// Return the IDs of projects in the `kind` queue, scanning project IDs
+    /// `offset..offset + limit`.
+    ///
+    /// `limit` is capped at `PROJECT_PAGE` (25). Projects outside the
+    /// queue are skipped, so a page may hold fewer than `limit` IDs;
+    /// continue from `offset + limit` until it passes the project count.
+    /// Only sequentially numbered projects are scanned; projects registered
+    /// with `register_project_at` are not listed.
+    pub fn get_actionable_projects(
+        env: Env,
+        kind: ActionableKind,
+        offset: u64,
+        limit: u32,
+    ) -> Vec<u64> {
+        let mut ids = Vec::new(&env);
+        for id in project_page(&env, offset, limit) {
+            let (config, state) = match (
+                storage::maybe_load_project_config(&env, id),
+                storage::maybe_load_project_state(&env, id),
+            ) {
+                (Some(config), Some(state)) => (config, state),
+                _ => continue,
+            };
+            if needs_action(&env, &kind, &config, &state) {
+                ids.push_back(id);
+            }
+        }
+        ids
+    }
+
+    /// Return a snapshot of protocol health for monitoring: pause flags,
+    /// oracle count, open disputes, projects past their deadline, treasury
+    /// holdings of `tokens`, and the stored schema version.
+    ///
+    /// The per-project counts cover project IDs `offset..offset + limit`
+    /// only, with `limit` capped at `PROJECT_PAGE` (25); sum them over
+    /// pages, continuing from `offset + limit` until it passes
+    /// `project_count`. Projects registered with `register_project_at` are
+    /// not counted.
+    pub fn health_check(env: Env, tokens: Vec<Address>, offset: u64, limit: u32) -> ProtocolHealth {
+        let project_count = storage::get_project_count(&env);
+        let (mut paused_projects, mut open_disputes, mut past_deadline) = (0, 0, 0);
+        for id in project_page(&env, offset, limit) {
+            let (config, state) = match (
+                storage::maybe_load_project_config(&env, id),
+                storage::maybe_load_project_state(&env, id),
+            ) {
+                (Some(config), Some(state)) => (config, state),
+                _ => continue,
+            };
+            if is_settled(&state) {
+                continue;
+            }
+            if state.has_flag(ProjectState::FLAG_PAUSED) {
+                paused_projects += 1;
+            }
+            if needs_action(&env, &ActionableKind::DisputeOpen, &config, &state) {
+                open_disputes += 1;
+            }
+            if needs_action(&env, &ActionableKind::PastDeadline, &config, &state) {
+                past_deadline += 1;
+            }
+        }
+
+        let mut paused_tokens = Vec::new(&env);
+        let mut treasury_balances = Vec::new(&env);
+        let treasury = storage::get_protocol_config(&env).treasury;
+        for token in tokens.iter() {
+            if storage::is_token_paused(&env, &token) {
+                paused_tokens.push_back(token.clone());
+            }
+            if let Some(treasury) = &treasury {
+                let balance = token::Client::new(&env, &token).balance(treasury);
+                treasury_balances.push_back(TokenBalance { token, balance });
+            }
+        }
+
+        ProtocolHealth {
+            paused: protocol_paused(&env),
+            sunset: storage::is_sunset(&env),
+            paused_tokens,
+            paused_projects,
+            oracle_count: rbac::oracle_count(&env),
+            open_disputes,
+            past_deadline,
+            project_count,
+            treasury_balances,
+            version: Self::get_version(env.clone()),
+        }
+    }
+
+    /// Deposit funds into a project. Returns the amount transferred and the
+    /// part of it tagged eligible for matching.
+    ///
+    /// The `token` must be one of the project's accepted tokens.
+    ///
+    /// With `cap_to_goal`, a deposit in the token the goal is denominated in
+    /// is reduced to whatever the project still needs to reach its goal
+    /// (grossed up for any insurance premium and protocol fee), and nothing
+    /// is transferred
+    /// once the goal is met. Deposits in other tokens, and into projects with
+    /// reference-unit goals, are never capped.
+    pub fn deposit(
+        env: Env,
+        project_id: u64,
+        donator: Address,
+        token: Address,
+        amount: i128,
+        cap_to_goal: bool,
+    ) -> DepositReceipt {
+        let _guard = ReentrancyGuard::acquire(&env);
+        donator.require_auth();
+
+        // Read both config and state with a single helper that bumps TTLs
+        // atomically. This is the optimized retrieval pattern; it also returns
+        // the state needed for the subsequent checks.
+        let (mut config, mut state) = load_project_pair(&env, project_id);
+        require_not_paused(&env, &state);
+        require_not_sunset(&env);
+        require_not_frozen(&env, &donator);
+        if !donor_allowed(&env, project_id, &state, &donator) {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        if storage::is_token_paused(&env, &token) {
+            panic_with_error!(&env, Error::TokenPaused);
+        }
+
+        // Basic status check: must be Funding or Active.
+        match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active => {}
+            _ => panic!("project not accepting deposits"),
+        }
+
+        // Verify token is accepted.
+        let mut found = false;
+        for t in config.accepted_tokens.iter() {
+            if t == token {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            panic!("token not accepted by this project");
+        }
+        if state.has_flag(ProjectState::FLAG_CURRENCY_LOCK) && config.accepted_tokens.len() > 1 {
+            lock_currency(&env, &mut config, &token);
+        }
+
+        let premium_bps = if state.has_flag(ProjectState::FLAG_INSURED) {
+            storage::get_insurance(&env, project_id).unwrap_or(0)
+        } else {
+            0
+        };
+        let protocol = storage::get_protocol_config(&env);
+        let treasury = protocol.treasury.clone();
+        let fee_bps = match treasury {
+            Some(_) => storage::get_fee_override(&env, project_id).unwrap_or(protocol.fee_bps),
+            None => 0,
+        };
+        let amount = if cap_to_goal {
+            amount.min(goal_shortfall(&env, &config, &token, premium_bps + fee_bps))
+        } else {
+            amount
+        };
+        if amount <= 0 && cap_to_goal {
+            return DepositReceipt::default();
+        }
+
+        // Transfer tokens from donator to contract.
+        let token_client = token::Client::new(&env, &token);
+        token_client.transfer(&donator, &env.current_contract_address(), &amount);
+        events::emit_escrow_transfer(
+            &env,
+            token.clone(),
+            donator.clone(),
+            env.current_contract_address(),
+            amount,
+        );
+
+        // Insured projects route their premium to the pool; the donor's
+        // contribution is still recorded in full.
+        let premium = amount * premium_bps as i128 / 10_000;
+        if premium > 0 {
+            storage::add_to_insurance_pool(&env, &token, premium);
+            events::emit_premium_paid(&env, project_id, token.clone(), premium);
+        }
+
+        // The protocol fee goes straight to the treasury, unless it is
+        // held for conversion into the reserve token.
+        let fee = amount * fee_bps as i128 / 10_000;
+        if let (Some(treasury), true) = (treasury, fee > 0) {
+            if protocol.reserve_token.as_ref().is_some_and(|r| *r != token) {
+                storage::add_to_fee_vault(&env, &token, fee);
+            } else {
+                token_client.transfer(&env.current_contract_address(), &treasury, &fee);
+                events::emit_escrow_transfer(
+                    &env,
+                    token.clone(),
+                    env.current_contract_address(),
+                    treasury,
+                    fee,
+                );
+            }
+            events::emit_fee_paid(&env, project_id, token.clone(), fee);
+        }
+
+        // Update the per-token balance and the donor's contribution ledger.
+        storage::add_to_token_balance(&env, project_id, &token, amount - premium - fee);
+        record_inflow(&env, project_id, &token, amount - premium - fee);
+        storage::add_contribution(&env, project_id, &donator, &token, amount);
+        state.donation_count = state.donation_count.saturating_add(1);
+        save_project_state(&env, project_id, &state);
+        let remaining = config.deadline.saturating_sub(env.ledger().timestamp());
+        storage::add_contribution_weight(
+            &env,
+            project_id,
+            &donator,
+            &token,
+            amount * remaining as i128,
+        );
+        let matched = tag_match_eligible(&env, &config, &donator, &token, amount);
+        record_ledger(
+            &env,
+            &donator,
+            project_id,
+            &token,
+            LedgerKind::Deposit,
+            amount,
+        );
+        record_ledger(
+            &env,
+            &donator,
+            project_id,
+            &token,
+            LedgerKind::Match,
+            matched,
+        );
+        // Under a match cap, pools match only the part tagged eligible.
+        let matchable = if storage::get_match_cap(&env, project_id) > 0 {
+            matched
+        } else {
+            amount
+        };
+        match_from_pools(&env, &config, &donator, &token, matchable);
+        let mut valued = 0;
+        if let Some(feed) = protocol.price_feed {
+            if let Some(value) = price::reference_value(&env, &feed, &token, amount) {
+                valued = value;
+                storage::add_contribution_value(&env, project_id, &donator, &token, value);
+                events::emit_deposit_valued(
+                    &env,
+                    project_id,
+                    donator.clone(),
+                    token.clone(),
+                    amount,
+                    value,
+                );
+            }
+        }
+
+        let mut stats = storage::get_donor_stats(&env, &donator);
+        stats.record(env.ledger().timestamp() / protocol.streak_period, valued);
+        storage::set_donor_stats(&env, &donator, &stats);
+
+        // Standardized event emission
+        events::emit_project_funded(&env, project_id, donator.clone(), amount);
+        hooks::notify_deposit(&env, &donator, project_id, &token, amount);
+        DepositReceipt {
+            transferred: amount,
+            matched,
+        }
+    }
+
+    /// Deposit like [`PifpProtocol::deposit`], flagging the contribution as
+    /// non-refundable. Returns the same receipt.
+    ///
+    /// If the project expires, the donor's share of the escrow for this
+    /// contribution is not refunded but paid to the creator or added to the
+    /// commons pool, per `route`. The latest route applies to all of a
+    /// donor's grants to the project in `token`. Emits `granted`.
+    pub fn deposit_grant(
+        env: Env,
+        project_id: u64,
+        donator: Address,
+        token: Address,
+        amount: i128,
+        route: GrantRoute,
+    ) -> DepositReceipt {
+        let receipt = Self::deposit(
+            env.clone(),
+            project_id,
+            donator.clone(),
+            token.clone(),
+            amount,
+            false,
+        );
+        let amount = receipt.transferred;
+        storage::add_grant(&env, project_id, &donator, &token, amount, route.clone());
+        events::emit_grant_pledged(&env, project_id, donator, token, amount, route);
+        receipt
+    }
+
+    /// Return the non-refundable part of `donor`'s contribution to
+    /// `project_id` in `token`, if any.
+    pub fn get_grant(env: Env, project_id: u64, donor: Address, token: Address) -> Option<Grant> {
+        storage::get_grant(&env, project_id, &donor, &token)
+    }
+
+    /// Grant the Oracle role to `oracle`.
+    ///
+    /// Replaces the original `set_oracle(admin, oracle)`.
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    ///
+    /// If an address already holds the Oracle role, calling this with a new
+    /// address will grant Oracle to the new one; the old one retains its role
+    /// unless explicitly revoked. If you want a single oracle, revoke the old
+    /// one first, then call `set_oracle`.
+    pub fn set_oracle(env: Env, caller: Address, oracle: Address) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        rbac::grant_role(&env, &caller, &oracle, Role::Oracle, None, None);
+        log_admin(&env, &caller, symbol_short!("role_set"), Some(oracle), None);
+    }
+
+    /// Swap out a compromised oracle in one step.
+    ///
+    /// - `super_admin` must authorize and hold `SuperAdmin`; Admins cannot
+    ///   use this path.
+    /// - `old` must hold the Oracle role; it is revoked and `new` is granted
+    ///   it, effective immediately.
+    /// - Attestations `old` made towards projects that have not yet met
+    ///   quorum are discarded, and `new` takes its place in every project
+    ///   it was assigned to.
+    ///
+    /// Emits a high-severity `ora_emerg` event. Returns the number of
+    /// attestations discarded.
+    pub fn replace_oracle_emergency(
+        env: Env,
+        super_admin: Address,
+        old: Address,
+        new: Address,
+    ) -> u32 {
+        super_admin.require_auth();
+        rbac::require_role(&env, &super_admin, &Role::SuperAdmin);
+        rbac::require_oracle(&env, &old);
+        if old == new {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        rbac::revoke_role(&env, &super_admin, &old, None);
+        rbac::grant_role(&env, &super_admin, &new, Role::Oracle, None, None);
+
+        let mut cancelled = 0u32;
+        for project_id in storage::get_oracle_projects(&env, &old).iter() {
+            let state = storage::load_project_state(&env, project_id);
+            let settling = state.has_flag(ProjectState::FLAG_RELEASE_PENDING)
+                || !matches!(state.status, ProjectStatus::Funding | ProjectStatus::Active);
+            let mut attestations = storage::get_attestations(&env, project_id);
+            if let (false, Some(i)) = (settling, attestations.first_index_of(&old)) {
+                attestations.remove(i);
+                storage::set_attestations(&env, project_id, &attestations);
+                cancelled += 1;
+            }
+
+            let mut assigned = storage::get_project_oracles(&env, project_id);
+            if let Some(i) = assigned.first_index_of(&old) {
+                if assigned.contains(&new) {
+                    assigned.remove(i);
+                } else {
+                    assigned.set(i, new.clone());
+                    storage::index_oracle_project(&env, &new, project_id);
+                }
+                storage::set_project_oracles(&env, project_id, &assigned);
+            }
+        }
+        storage::remove_oracle_projects(&env, &old);
+
+        log_admin(
+            &env,
+            &super_admin,
+            symbol_short!("ora_emerg"),
+            Some(new.clone()),
+            None,
+        );
+        events::emit_oracle_replaced(&env, old, new, cancelled);
+        cancelled
+    }
+
+    /// Verify proof of impact and release funds to the creator.
+    ///
+    /// The registered oracle submits a proof hash. If it matches the project's
+    /// stored `proof_hash`, the oracle's attestation is recorded. Once
+    /// `quorum` distinct oracles have attested, the project status
+    /// transitions to `Completed` and every accepted token's escrow is
+    /// transferred to the creator. Each oracle may attest a project only once.
+    ///
+    /// By default this is a mocked verification (hash equality). Projects that
+    /// registered an external verifier via `set_project_verifier` have the
+    /// submission checked by that contract instead. Projects with oracles
+    /// assigned via `set_project_oracles` only accept attestations from them.
+    ///
+    /// Reads the immutable config (for proof_hash) and mutable state (for status),
+    /// then writes back only the small state entry.
+    pub fn verify_and_release(
+        env: Env,
+        oracle: Address,
+        project_id: u64,
+        submitted_proof_hash: BytesN<32>,
+    ) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        oracle.require_auth();
+        attest(&env, oracle, project_id, submitted_proof_hash);
+    }
+
+    /// Submit `proof_hash` as evidence of impact for the oracles to approve
+    /// or reject, replacing any submission still under review.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - The project must be in a verifiable state.
+    ///
+    /// The submission is kept in temporary storage and lapses after
+    /// `PendingProof::REVIEW_LEDGERS` unless approved first. Emits
+    /// `proof_sub`.
+    pub fn submit_proof(env: Env, signers: Vec<Address>, project_id: u64, proof_hash: BytesN<32>) {
+        let (config, state) = load_project_pair(&env, project_id);
+        require_project_control(&env, &config, &signers);
+        if let Err(error) = fsm::transition(&state.status, StatusEvent::Verify) {
+            panic_with_error!(&env, error);
+        }
+        storage::set_pending_proof(
+            &env,
+            project_id,
+            &PendingProof {
+                proof_hash: proof_hash.clone(),
+                submitted_at: env.ledger().timestamp(),
+            },
+        );
+        events::emit_proof_submitted(&env, project_id, config.creator, proof_hash);
+    }
+
+    /// Attest the proof a creator submitted, exactly as `verify_and_release`
+    /// would with its hash. The submission is cleared once quorum is met.
+    ///
+    /// - `oracle` must hold the `Oracle` role and be assigned to the
+    ///   project, if it has assigned oracles.
+    /// - Fails with `InvalidStatusTransition` if no proof is pending.
+    pub fn approve_proof(env: Env, oracle: Address, project_id: u64) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        oracle.require_auth();
+        let pending = match storage::get_pending_proof(&env, project_id) {
+            Some(pending) => pending,
+            None => panic_with_error!(&env, Error::InvalidStatusTransition),
+        };
+        if attest(&env, oracle, project_id, pending.proof_hash) {
+            storage::remove_pending_proof(&env, project_id);
+        }
+    }
+
+    /// Turn down the proof a creator submitted, so they can submit another.
+    ///
+    /// - `oracle` must hold the `Oracle` role and be assigned to the
+    ///   project, if it has assigned oracles.
+    /// - `reason_hash` is the hash of the off-chain explanation.
+    /// - Fails with `InvalidStatusTransition` if no proof is pending.
+    ///
+    /// Emits `proof_rej`.
+    pub fn reject_proof(env: Env, oracle: Address, project_id: u64, reason_hash: BytesN<32>) {
+        oracle.require_auth();
+        rbac::require_oracle(&env, &oracle);
+        require_assigned_oracle(&env, project_id, &oracle);
+        let pending = match storage::get_pending_proof(&env, project_id) {
+            Some(pending) => pending,
+            None => panic_with_error!(&env, Error::InvalidStatusTransition),
+        };
+        storage::remove_pending_proof(&env, project_id);
+        events::emit_proof_rejected(&env, project_id, oracle, pending.proof_hash, reason_hash);
+    }
+
+    /// Return the proof awaiting oracle review for a project, if any.
+    pub fn get_pending_proof(env: Env, project_id: u64) -> Option<PendingProof> {
+        storage::get_pending_proof(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Creator withdrawals
+    // ─────────────────────────────────────────────────────────
+
+    /// Transfer the creator everything released to them in `token` while
+    /// `ProtocolConfig::pull_payments` was set. Returns the amount paid.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - Fails with `AddressFrozen` while the creator is under a compliance
+    ///   freeze, and with `InsufficientBalance` if nothing is claimable.
+    ///
+    /// Emits `withdrawn`.
+    pub fn withdraw(env: Env, signers: Vec<Address>, project_id: u64, token: Address) -> i128 {
+        let _guard = ReentrancyGuard::acquire(&env);
+        let config = storage::load_project_config(&env, project_id);
+        require_project_control(&env, &config, &signers);
+        let creator = config.creator;
+        require_not_frozen(&env, &creator);
+        let amount = storage::get_claimable(&env, project_id, &token);
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+
+        storage::set_claimable(&env, project_id, &token, 0);
+        let contract = env.current_contract_address();
+        token::Client::new(&env, &token).transfer(&contract, &creator, &amount);
+        events::emit_escrow_transfer(&env, token.clone(), contract, creator.clone(), amount);
+        events::emit_funds_withdrawn(&env, project_id, creator, token, amount);
+        amount
+    }
+
+    /// Return the released funds of `project_id` in `token` awaiting the
+    /// creator's `withdraw`.
+    pub fn get_claimable(env: Env, project_id: u64, token: Address) -> i128 {
+        storage::get_claimable(&env, project_id, &token)
+    }
+
+    /// Register the BLS12-381 public key `oracle` signs aggregate
+    /// attestations with.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - `oracle` must hold the `Oracle` role.
+    ///
+    /// The caller is responsible for checking the key's proof-of-possession
+    /// off-chain; see [`bls`] for why this matters.
+    pub fn set_oracle_bls_key(env: Env, caller: Address, oracle: Address, public_key: BytesN<96>) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        rbac::require_oracle(&env, &oracle);
+        storage::set_oracle_bls_key(&env, &oracle, &public_key);
+    }
+
+    /// Return the BLS public key registered for `oracle`, if any.
+    pub fn get_oracle_bls_key(env: Env, oracle: Address) -> Option<BytesN<96>> {
+        storage::get_oracle_bls_key(&env, &oracle)
+    }
+
+    /// Return the message oracles must sign to attest `proof_hash` for
+    /// `project_id` on this deployment; see [`bls::attestation_payload`].
+    pub fn get_attestation_payload(env: Env, project_id: u64, proof_hash: BytesN<32>) -> Bytes {
+        bls::attestation_payload(
+            &env,
+            &env.current_contract_address(),
+            project_id,
+            &proof_hash,
+        )
+    }
+
+    /// Verify a project with one aggregated BLS signature from `signers`.
+    ///
+    /// - `submitter` must authorize and hold the `Oracle` role.
+    /// - Every signer must hold the `Oracle` role, be assigned to the project
+    ///   if it has assigned oracles, have a registered BLS key, and appear at
+    ///   most once; `signature` must be their aggregate over
+    ///   [`bls::attestation_payload`] for `proof_hash`.
+    ///
+    /// Signers count towards the project's quorum alongside any individual
+    /// attestations already submitted through `verify_and_release`. Once the
+    /// quorum is met the project completes exactly as with individual calls.
+    pub fn verify_aggregate(
+        env: Env,
+        submitter: Address,
+        project_id: u64,
+        proof_hash: BytesN<32>,
+        signers: Vec<Address>,
+        signature: BytesN<192>,
+    ) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        submitter.require_auth();
+        rbac::require_oracle(&env, &submitter);
+
+        let (config, mut state) = load_project_pair(&env, project_id);
+        require_not_paused(&env, &state);
+        require_no_pending_release(&env, &state);
+        require_dependencies_completed(&env, project_id);
+        if let Err(error) = fsm::transition(&state.status, StatusEvent::Verify) {
+            panic_with_error!(&env, error);
+        }
+        let expected = expected_proof(&env, &config, &state);
+        if !verifier::verify_proof(&env, &config, &expected, &proof_hash) {
+            panic!("proof verification failed: hash mismatch");
+        }
+
+        let mut attestations = storage::get_attestations(&env, project_id);
+        let mut public_keys: Vec<BytesN<96>> = Vec::new(&env);
+        for (i, signer) in signers.iter().enumerate() {
+            if signers.first_index_of(&signer) != Some(i as u32) {
+                panic_with_error!(&env, Error::AlreadyAttested);
+            }
+            rbac::require_oracle(&env, &signer);
+            require_assigned_oracle(&env, project_id, &signer);
+            match storage::get_oracle_bls_key(&env, &signer) {
+                Some(key) => public_keys.push_back(key),
+                None => panic_with_error!(&env, Error::NotAuthorized),
+            }
+        }
+
+        let payload = bls::attestation_payload(
+            &env,
+            &env.current_contract_address(),
+            project_id,
+            &proof_hash,
+        );
+        if !bls::verify_aggregate(&env, &public_keys, &payload, &signature) {
+            panic_with_error!(&env, Error::InvalidAggregateSignature);
+        }
+
+        for signer in signers.iter() {
+            if !attestations.contains(&signer) {
+                attestations.push_back(signer.clone());
+                storage::index_oracle_project(&env, &signer, project_id);
+                events::emit_project_attested(
+                    &env,
+                    project_id,
+                    signer,
+                    attestations.len(),
+                    config.quorum,
+                );
+            }
+        }
+        storage::set_attestations(&env, project_id, &attestations);
+        if attestations.len() < config.quorum {
+            return;
+        }
+
+        settle_quorum(&env, &config, &mut state, submitter, proof_hash);
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Donor queries
+    // ─────────────────────────────────────────────────────────
+
+    /// Return at most `limit` of `donor`'s contributions as
+    /// `(project_id, token, amount)` tuples, starting at index `offset`.
+    ///
+    /// Entries are ordered by the donor's first deposit into each
+    /// (project, token) pair; `amount` is the cumulative total deposited.
+    pub fn get_donor_history(
+        env: Env,
+        donor: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(u64, Address, i128)> {
+        let index = storage::get_donor_projects(&env, &donor);
+        let mut history = Vec::new(&env);
+        let end = offset.saturating_add(limit).min(index.len());
+        for i in offset..end {
+            let (project_id, token) = index.get_unchecked(i);
+            let amount = storage::get_contribution(&env, project_id, &donor, &token);
+            history.push_back((project_id, token, amount));
+        }
+        history
+    }
+
+    /// Return the IDs of every project `donor` has deposited into, in order
+    /// of their first deposit, each listed once however many tokens they
+    /// gave in.
+    ///
+    /// Reads the donor's whole history; dashboards for donors with many
+    /// contributions should page through `get_donor_history` instead.
+    pub fn get_donor_projects(env: Env, donor: Address) -> Vec<u64> {
+        let mut ids = Vec::new(&env);
+        for (project_id, _) in storage::get_donor_projects(&env, &donor).iter() {
+            if !ids.contains(project_id) {
+                ids.push_back(project_id);
+            }
+        }
+        ids
+    }
+
+    /// Return the cumulative amount `donor` deposited into `project_id` in
+    /// `token`, or `0` if they never did.
+    pub fn get_donation(env: Env, project_id: u64, donor: Address, token: Address) -> i128 {
+        storage::get_contribution(&env, project_id, &donor, &token)
+    }
+
+    /// Return the number of deposits made into `project_id`, counting every
+    /// deposit of every donor.
+    pub fn get_donation_count(env: Env, project_id: u64) -> u32 {
+        storage::load_project_state(&env, project_id).donation_count
+    }
+
+    /// Return the reference-unit value of `donor`'s deposits into
+    /// `project_id` in `token`, each priced when it was made.
+    ///
+    /// Deposits made while no price feed was configured, or that the feed
+    /// could not price, contribute `0`.
+    pub fn get_donation_value(env: Env, project_id: u64, donor: Address, token: Address) -> i128 {
+        storage::get_contribution_value(&env, project_id, &donor, &token)
+    }
+
+    /// Return the time-weighted total of `donor`'s deposits into
+    /// `project_id` in `token`: the sum over deposits of amount × seconds
+    /// left before the project's deadline when it was made.
+    ///
+    /// Earlier support weighs more, so matching formulas rewarding early
+    /// supporters can be computed from chain state. Deposits made after the
+    /// deadline weigh `0`.
+    pub fn get_donation_weight(env: Env, project_id: u64, donor: Address, token: Address) -> i128 {
+        storage::get_contribution_weight(&env, project_id, &donor, &token)
+    }
+
+    /// Return `donor`'s deposit count, giving streak and total deposit-time
+    /// value across every project.
+    ///
+    /// `current_streak` reflects the period of the last deposit; callers
+    /// should treat it as broken once a full `streak_period` has passed
+    /// without a deposit.
+    pub fn get_donor_stats(env: Env, donor: Address) -> DonorStats {
+        storage::get_donor_stats(&env, &donor)
+    }
+
+    /// Return `donor`'s deposits, refunds and match-tagged amounts made
+    /// between `from_ts` and `to_ts` inclusive, totalled per token in order
+    /// of first appearance.
+    ///
+    /// Amounts in different tokens are never summed; convert them off-chain
+    /// if a single figure is needed. Deposits are reported gross of fees
+    /// and premiums. The ledger survives project cleanup, so statements
+    /// stay reproducible after a project's escrow records are gone.
+    pub fn get_donor_statement(
+        env: Env,
+        donor: Address,
+        from_ts: u64,
+        to_ts: u64,
+    ) -> DonorStatement {
+        let mut lines: Vec<StatementLine> = Vec::new(&env);
+        for entry in storage::get_donor_ledger(&env, &donor).iter() {
+            if entry.at > to_ts {
+                break;
+            }
+            if entry.at < from_ts {
+                continue;
+            }
+            let at = lines.iter().position(|l| l.token == entry.token);
+            let mut line = match at {
+                Some(i) => lines.get_unchecked(i as u32),
+                None => StatementLine {
+                    token: entry.token.clone(),
+                    deposited: 0,
+                    refunded: 0,
+                    matched: 0,
+                },
+            };
+            match entry.kind {
+                LedgerKind::Deposit => line.deposited += entry.amount,
+                LedgerKind::Refund => line.refunded += entry.amount,
+                LedgerKind::Match => line.matched += entry.amount,
+            }
+            match at {
+                Some(i) => lines.set(i as u32, line),
+                None => lines.push_back(line),
+            }
+        }
+        DonorStatement {
+            donor,
+            from_ts,
+            to_ts,
+            lines,
+        }
+    }
+
+    /// Return at most `limit` of `project_id`'s `(donor, token)` pairs,
+    /// scanning the project's donor index from `offset`.
+    ///
+    /// Donors who set `hide_from_listings` are skipped, so a page may hold
+    /// fewer than `limit` entries; continue from `offset + limit`.
+    pub fn get_project_donors(
+        env: Env,
+        project_id: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<(Address, Address)> {
+        let index = storage::get_project_donors(&env, project_id);
+        let mut donors = Vec::new(&env);
+        let end = offset.saturating_add(limit).min(index.len());
+        for i in offset..end {
+            let (donor, token) = index.get_unchecked(i);
+            if !storage::get_donor_preferences(&env, &donor).hide_from_listings {
+                donors.push_back((donor, token));
+            }
+        }
+        donors
+    }
+
+    /// Set `donor`'s privacy preferences. `donor` must authorize.
+    ///
+    /// Takes effect for every listing, ranking and certificate feature from
+    /// the next call on; contributions themselves stay on the ledger.
+    pub fn set_donor_preferences(env: Env, donor: Address, prefs: DonorPreferences) {
+        donor.require_auth();
+        storage::set_donor_preferences(&env, &donor, &prefs);
+        events::emit_donor_preferences_set(&env, donor, prefs);
+    }
+
+    /// Return `donor`'s privacy preferences; all visible if never set.
+    pub fn get_donor_preferences(env: Env, donor: Address) -> DonorPreferences {
+        storage::get_donor_preferences(&env, &donor)
+    }
+
+    /// Opt a donor contract in to (or out of) deposit receipts.
+    ///
+    /// While enabled, every deposit by `donor` ends with a call to its
+    /// `on_deposit_receipt`; see [`hooks`]. A failing hook fails the
+    /// deposit. `donor` must authorize.
+    pub fn set_deposit_hook(env: Env, donor: Address, enabled: bool) {
+        donor.require_auth();
+        storage::set_deposit_hook(&env, &donor, enabled);
+        events::emit_deposit_hook_set(&env, donor, enabled);
+    }
+
+    /// Return `true` if `donor` receives deposit receipts.
+    pub fn has_deposit_hook(env: Env, donor: Address) -> bool {
+        storage::has_deposit_hook(&env, &donor)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Creator reputation
+    // ─────────────────────────────────────────────────────────
+
+    /// Return the reputation record for `creator`.
+    ///
+    /// Addresses that have never registered a project get an all-zero record.
+    pub fn get_creator_record(env: Env, creator: Address) -> CreatorRecord {
+        storage::get_creator_record(&env, &creator)
+    }
+
+    /// Return the amount of `token` ever released to `creator`, across
+    /// every project they registered and net of release fees.
+    ///
+    /// Amounts are kept per token rather than summed, since tokens are not
+    /// interchangeable.
+    pub fn get_creator_released(env: Env, creator: Address, token: Address) -> i128 {
+        storage::get_creator_released(&env, &creator, &token)
+    }
+
+    /// Return the IDs of every project `creator` registered, oldest first,
+    /// including content-addressed ones from `register_project_at`.
+    pub fn get_projects_by_creator(env: Env, creator: Address) -> Vec<u64> {
+        storage::get_creator_projects(&env, &creator)
+    }
+
+    /// Record a fraud flag against `creator`.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    ///
+    /// Flags are cumulative and cannot be removed; they are surfaced through
+    /// `get_creator_record` so approvers and donors can weigh them.
+    pub fn flag_creator(env: Env, caller: Address, creator: Address) -> CreatorRecord {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+
+        let record = storage::update_creator_record(&env, &creator, |r| r.fraud_flags += 1);
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("flagged"),
+            Some(creator.clone()),
+            None,
+        );
+        events::emit_creator_flagged(&env, creator, caller, record.fraud_flags);
+        record
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // External verifiers
+    // ─────────────────────────────────────────────────────────
+
+    /// Add `verifier` to the allowlist of contracts projects may register.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    pub fn approve_verifier(env: Env, caller: Address, verifier: Address) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        storage::set_verifier_approved(&env, &verifier, true);
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("verif_ok"),
+            Some(verifier.clone()),
+            None,
+        );
+        events::emit_verifier_approval(&env, verifier, true, caller);
+    }
+
+    /// Remove `verifier` from the allowlist.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    ///
+    /// Projects already using `verifier` cannot be verified until it is
+    /// approved again.
+    pub fn revoke_verifier(env: Env, caller: Address, verifier: Address) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        storage::set_verifier_approved(&env, &verifier, false);
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("verif_del"),
+            Some(verifier.clone()),
+            None,
+        );
+        events::emit_verifier_approval(&env, verifier, false, caller);
+    }
+
+    /// Return `true` if `verifier` is on the allowlist.
+    pub fn is_verifier_approved(env: Env, verifier: Address) -> bool {
+        storage::is_verifier_approved(&env, &verifier)
+    }
+
+    /// Register an external verifier contract and verifying-key hash for a
+    /// project, replacing the default hash-equality check.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - `verifier` must be on the allowlist.
+    /// - Only allowed before the project has received any deposits or
+    ///   attestations, so donors always fund under a fixed verification path.
+    pub fn set_project_verifier(
+        env: Env,
+        signers: Vec<Address>,
+        project_id: u64,
+        verifier: Address,
+        vk_hash: BytesN<32>,
+    ) {
+        require_unfunded_control(&env, &signers, project_id);
+        if !storage::is_verifier_approved(&env, &verifier) {
+            panic_with_error!(&env, Error::VerifierNotApproved);
+        }
+
+        storage::set_project_verifier(
+            &env,
+            project_id,
+            &ProjectVerifier {
+                verifier: verifier.clone(),
+                vk_hash: vk_hash.clone(),
+            },
+        );
+        events::emit_verifier_set(&env, project_id, verifier, vk_hash);
+    }
+
+    /// Return the external verifier registered for a project, if any.
+    pub fn get_project_verifier(env: Env, project_id: u64) -> Option<ProjectVerifier> {
+        storage::get_project_verifier(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Oracle assignment
+    // ─────────────────────────────────────────────────────────
+
+    /// Restrict who may attest a project to `oracles`.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - Every entry must hold the `Oracle` role and appear once, and there
+    ///   must be at least the project's quorum of them.
+    /// - An empty list lifts the restriction so any oracle may attest.
+    ///
+    /// Attestations already recorded keep counting towards the quorum.
+    pub fn set_project_oracles(env: Env, caller: Address, project_id: u64, oracles: Vec<Address>) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        let config = storage::load_project_config(&env, project_id);
+        if !oracles.is_empty() && oracles.len() < config.quorum {
+            panic_with_error!(&env, Error::InvalidQuorum);
+        }
+        for (i, oracle) in oracles.iter().enumerate() {
+            if oracles.first_index_of(&oracle) != Some(i as u32) {
+                panic_with_error!(&env, Error::InvalidQuorum);
+            }
+            rbac::require_oracle(&env, &oracle);
+        }
+
+        storage::set_project_oracles(&env, project_id, &oracles);
+        for oracle in oracles.iter() {
+            storage::index_oracle_project(&env, &oracle, project_id);
+        }
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("oracles"),
+            None,
+            Some(project_id),
+        );
+        events::emit_project_oracles_set(&env, project_id, oracles);
+    }
+
+    /// Return the oracles assigned to a project; empty if any oracle may
+    /// attest it.
+    pub fn get_project_oracles(env: Env, project_id: u64) -> Vec<Address> {
+        storage::get_project_oracles(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Project ownership
+    // ─────────────────────────────────────────────────────────
+
+    /// Hand control of a project to an M-of-N owner set.
+    ///
+    /// - `signers` must satisfy the project's current controlling identity:
+    ///   the creator, or `threshold` of the existing owners.
+    /// - `owners` must be distinct, at most `ProjectOwners::MAX_OWNERS`, with
+    ///   `threshold` in `1..=owners.len()`.
+    ///
+    /// Once set, every creator-gated entry point (metadata, handle, proof
+    /// submission, withdrawals, deadline extensions, donor allowlists and
+    /// the pre-funding settings) requires `threshold` owner signatures in
+    /// its `signers`; the creator alone no longer suffices. Released funds
+    /// are still paid to the creator address.
+    pub fn set_project_owners(
+        env: Env,
+        signers: Vec<Address>,
+        project_id: u64,
+        owners: Vec<Address>,
+        threshold: u32,
+    ) {
+        let config = storage::load_project_config(&env, project_id);
+        require_project_control(&env, &config, &signers);
+        let set = ProjectOwners { owners, threshold };
+        if !set.is_valid() {
+            panic_with_error!(&env, Error::InvalidQuorum);
+        }
+        storage::set_project_owners(&env, project_id, &set);
+        events::emit_owners_set(&env, project_id, set.owners, set.threshold);
+    }
+
+    /// Return the multi-signature owner set of a project, if configured.
+    pub fn get_project_owners(env: Env, project_id: u64) -> Option<ProjectOwners> {
+        storage::get_project_owners(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Maintenance
+    // ─────────────────────────────────────────────────────────
+
+    /// Extend the TTL of every persistent entry belonging to a project.
+    ///
+    /// Permissionless: any `keeper` may call it. If rent self-funding is
+    /// enabled (`ProtocolConfig::rent_slice_bps > 0`) and at least
+    /// `rent_bump_interval` seconds have passed since the project's last paid
+    /// bump, the keeper is reimbursed `rent_slice_bps` of the project's
+    /// escrowed `token` balance. Returns the amount paid (possibly `0`).
+    ///
+    /// The reimbursement comes out of the project's rent fund (see
+    /// `sponsor_rent`) first and only the rest out of the escrow. Bumps of
+    /// a `Completed` or `Expired` project are never paid: its escrow is
+    /// owed to its donors' refunds and its rent fund to its sponsors.
+    pub fn bump_project(env: Env, keeper: Address, project_id: u64, token: Address) -> i128 {
+        let _guard = ReentrancyGuard::acquire(&env);
+        keeper.require_auth();
+        let config = storage::load_project_config(&env, project_id);
+        storage::extend_project_ttl(&env, &config);
+
+        let protocol_config = storage::get_protocol_config(&env);
+        if protocol_config.rent_slice_bps == 0 || !config.accepted_tokens.contains(&token) {
+            return 0;
+        }
+        if is_settled(&storage::load_project_state(&env, project_id)) {
+            return 0;
+        }
+        let now = env.ledger().timestamp();
+        if let Some(last) = storage::get_last_rent_bump(&env, project_id) {
+            if now < last.saturating_add(protocol_config.rent_bump_interval) {
+                return 0;
+            }
+        }
+
+        let balance = storage::get_token_balance(&env, project_id, &token);
+        let amount = balance * protocol_config.rent_slice_bps as i128 / 10_000;
+        if amount <= 0 {
+            return 0;
+        }
+
+        let (contributed, remaining) = storage::get_rent_fund(&env, project_id, &token);
+        let sponsored = amount.min(remaining);
+        if sponsored > 0 {
+            storage::set_rent_fund(&env, project_id, &token, contributed, remaining - sponsored);
+        }
+        if amount > sponsored {
+            record_outflow(&env, project_id, &token, amount - sponsored);
+            storage::set_token_balance(&env, project_id, &token, balance - (amount - sponsored));
+        }
+        storage::set_last_rent_bump(&env, project_id, now);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &keeper,
+            &amount,
+        );
+        events::emit_escrow_transfer(
+            &env,
+            token.clone(),
+            env.current_contract_address(),
+            keeper.clone(),
+            amount,
+        );
+        events::emit_rent_paid(&env, project_id, keeper, token, amount);
+        amount
+    }
+
+    /// Set aside `amount` of `token` to pay a project's future TTL bumps.
+    ///
+    /// The rent fund is kept apart from the escrow and does not count
+    /// towards the goal; `bump_project` draws on it before touching donor
+    /// funds. `token` must be one the project accepts and `sponsor` must
+    /// authorize the transfer. Whatever is unspent when the project settles
+    /// can be taken back with `reclaim_rent_sponsorship`.
+    pub fn sponsor_rent(env: Env, sponsor: Address, project_id: u64, token: Address, amount: i128) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        sponsor.require_auth();
+        let (config, state) = load_project_pair(&env, project_id);
+        if amount <= 0 || !config.accepted_tokens.contains(&token) || is_settled(&state) {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        token::Client::new(&env, &token).transfer(
+            &sponsor,
+            &env.current_contract_address(),
+            &amount,
+        );
+        events::emit_escrow_transfer(
+            &env,
+            token.clone(),
+            sponsor.clone(),
+            env.current_contract_address(),
+            amount,
+        );
+        let (contributed, remaining) = storage::get_rent_fund(&env, project_id, &token);
+        storage::set_rent_fund(
+            &env,
+            project_id,
+            &token,
+            contributed + amount,
+            remaining + amount,
+        );
+        let own = storage::get_rent_sponsorship(&env, project_id, &sponsor, &token);
+        storage::set_rent_sponsorship(&env, project_id, &sponsor, &token, own + amount);
+        events::emit_rent_sponsored(&env, project_id, sponsor, token, amount);
+    }
+
+    /// Return `sponsor`'s share of a settled project's unspent rent fund.
+    ///
+    /// Each sponsor gets back the unspent part of the fund in proportion to
+    /// what it put in. The project must be `Completed` or `Expired`.
+    /// Returns the amount paid (possibly `0`).
+    pub fn reclaim_rent_sponsorship(
+        env: Env,
+        sponsor: Address,
+        project_id: u64,
+        token: Address,
+    ) -> i128 {
+        let _guard = ReentrancyGuard::acquire(&env);
+        sponsor.require_auth();
+        let state = storage::load_project_state(&env, project_id);
+        if !is_settled(&state) {
+            panic_with_error!(&env, Error::ProjectNotSettled);
+        }
+        let own = storage::get_rent_sponsorship(&env, project_id, &sponsor, &token);
+        if own == 0 {
+            return 0;
+        }
+        let (contributed, remaining) = storage::get_rent_fund(&env, project_id, &token);
+        let refund = remaining * own / contributed;
+        storage::set_rent_sponsorship(&env, project_id, &sponsor, &token, 0);
+        storage::set_rent_fund(
+            &env,
+            project_id,
+            &token,
+            contributed - own,
+            remaining - refund,
+        );
+        if refund > 0 {
+            token::Client::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &sponsor,
+                &refund,
+            );
+            events::emit_escrow_transfer(
+                &env,
+                token.clone(),
+                env.current_contract_address(),
+                sponsor.clone(),
+                refund,
+            );
+        }
+        events::emit_rent_reclaimed(&env, project_id, sponsor, token, refund);
+        refund
+    }
+
+    /// Return the unspent rent fund of a project in `token`.
+    pub fn get_rent_fund(env: Env, project_id: u64, token: Address) -> i128 {
+        storage::get_rent_fund(&env, project_id, &token).1
+    }
+
+    /// Delete the per-token balance, contribution and attestation entries of
+    /// a settled project so it stops accruing rent on dead data.
+    ///
+    /// Permissionless. The project must be `Completed` or `Expired` and every
+    /// accepted-token balance must be at most `ProtocolConfig::dust_threshold`
+    /// — nothing worth releasing or refunding is left. Any such remainder is
+    /// moved to the dust ledger for `sweep_dust`. The project's config and
+    /// state are kept, so `get_project` keeps working. Returns the number of
+    /// entries removed.
+    ///
+    /// Rejected with `TimelockNotElapsed` until
+    /// `ProtocolConfig::retention_period` has passed since the project
+    /// settled, so auditors can rely on the data staying available. A
+    /// project that settled before settlement times were recorded has its
+    /// retention period start at the first `cleanup`, which removes nothing
+    /// and returns `0`.
+    pub fn cleanup(env: Env, keeper: Address, project_id: u64) -> u32 {
+        let _guard = ReentrancyGuard::acquire(&env);
+        keeper.require_auth();
+        let (config, state) = load_project_pair(&env, project_id);
+        match state.status {
+            ProjectStatus::Completed | ProjectStatus::Expired => {}
+            _ => panic_with_error!(&env, Error::ProjectNotSettled),
+        }
+        let protocol = storage::get_protocol_config(&env);
+        let now = env.ledger().timestamp();
+        let settled_at = match storage::get_settled_at(&env, project_id) {
+            Some(settled_at) => settled_at,
+            None if protocol.retention_period == 0 => now,
+            None => {
+                // Returning rather than panicking keeps the stamp.
+                storage::set_settled_at(&env, project_id, now);
+                return 0;
+            }
+        };
+        if now < settled_at.saturating_add(protocol.retention_period) {
+            panic_with_error!(&env, Error::TimelockNotElapsed);
+        }
+        let dust_threshold = protocol.dust_threshold;
+        for token in config.accepted_tokens.iter() {
+            if storage::get_token_balance(&env, project_id, &token) > dust_threshold {
+                panic_with_error!(&env, Error::ProjectNotSettled);
+            }
+        }
+        for token in config.accepted_tokens.iter() {
+            let dust = storage::get_token_balance(&env, project_id, &token);
+            if dust > 0 {
+                record_outflow(&env, project_id, &token, dust);
+                storage::set_token_balance(&env, project_id, &token, 0);
+                storage::set_dust(&env, &token, storage::get_dust(&env, &token) + dust);
+                events::emit_dust_recorded(&env, project_id, token, dust);
+            }
+        }
+
+        let removed = storage::remove_settled_project_data(&env, &config);
+        events::emit_project_cleaned(&env, project_id, removed);
+        if removed > 0 {
+            pay_keeper(&env, &keeper, project_id);
+        }
+        removed
+    }
+
+    /// Add `amount` of `token` to the pool keeper rewards are paid from.
+    /// Anyone may fund it; `funder` must authorize the transfer.
+    pub fn fund_keeper_pool(env: Env, funder: Address, token: Address, amount: i128) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        funder.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        token::Client::new(&env, &token).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        events::emit_escrow_transfer(
+            &env,
+            token.clone(),
+            funder.clone(),
+            env.current_contract_address(),
+            amount,
+        );
+        storage::set_keeper_pool(
+            &env,
+            &token,
+            storage::get_keeper_pool(&env, &token) + amount,
+        );
+        events::emit_keeper_pool_funded(&env, funder, token, amount);
+    }
+
+    /// Return the funds reserved for keeper rewards in `token`.
+    pub fn get_keeper_pool(env: Env, token: Address) -> i128 {
+        storage::get_keeper_pool(&env, &token)
+    }
+
+    /// Transfer the dust ledger of `token` to `ProtocolConfig::treasury`.
+    ///
+    /// - `admin` must hold `SuperAdmin` or `Admin`.
+    /// - A treasury must be configured.
+    ///
+    /// Returns the amount swept, `0` if there was no dust.
+    pub fn sweep_dust(env: Env, admin: Address, token: Address) -> i128 {
+        let _guard = ReentrancyGuard::acquire(&env);
+        admin.require_auth();
+        rbac::require_admin_or_above(&env, &admin);
+        let treasury = match storage::get_protocol_config(&env).treasury {
+            Some(treasury) => treasury,
+            None => panic_with_error!(&env, Error::InvalidConfig),
+        };
+
+        let dust = storage::get_dust(&env, &token);
+        if dust == 0 {
+            return 0;
+        }
+        storage::set_dust(&env, &token, 0);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &treasury,
+            &dust,
+        );
+        events::emit_escrow_transfer(
+            &env,
+            token.clone(),
+            env.current_contract_address(),
+            treasury.clone(),
+            dust,
+        );
+        log_admin(
+            &env,
+            &admin,
+            symbol_short!("dust_swp"),
+            Some(treasury.clone()),
+            None,
+        );
+        events::emit_dust_swept(&env, token, treasury, dust);
+        dust
+    }
+
+    /// Return the rounding residue held in `token` awaiting `sweep_dust`.
+    pub fn get_dust(env: Env, token: Address) -> i128 {
+        storage::get_dust(&env, &token)
+    }
+
+    /// Report which of a project's storage entries exist and how large they
+    /// are, so operators can track rent exposure and archival risk.
+    ///
+    /// - `caller` must hold `SuperAdmin`, `Admin` or `Auditor`.
+    pub fn storage_report(env: Env, caller: Address, project_id: u64) -> StorageReport {
+        caller.require_auth();
+        rbac::require_admin_or_auditor(&env, &caller);
+        let config = storage::load_project_config(&env, project_id);
+        storage::storage_report(&env, &config)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Insurance
+    // ─────────────────────────────────────────────────────────
+
+    /// Opt a project into donor protection.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - Only allowed before the project has received any deposits or
+    ///   attestations.
+    ///
+    /// The current `ProtocolConfig::insurance_premium_bps` is locked for the
+    /// project; from then on that share of every deposit goes to the
+    /// insurance pool rather than the project's escrow. The project's state
+    /// carries `ProjectState::FLAG_INSURED` so donors can see it is covered.
+    pub fn set_insured(env: Env, signers: Vec<Address>, project_id: u64) {
+        require_unfunded_control(&env, &signers, project_id);
+        let premium_bps = storage::get_protocol_config(&env).insurance_premium_bps;
+        if premium_bps == 0 {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        storage::set_insurance(&env, project_id, premium_bps);
+
+        let mut state = storage::load_project_state(&env, project_id);
+        state.set_flag(ProjectState::FLAG_INSURED, true);
+        save_project_state(&env, project_id, &state);
+        events::emit_project_insured(&env, project_id, premium_bps);
+    }
+
+    /// Return the premium rate of an insured project, or `None` if it is not
+    /// covered.
+    pub fn get_insurance(env: Env, project_id: u64) -> Option<u32> {
+        storage::get_insurance(&env, project_id)
+    }
+
+    /// Return the premiums collected in `token`.
+    pub fn get_insurance_pool(env: Env, token: Address) -> i128 {
+        storage::get_insurance_pool(&env, &token)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Protocol fees
+    // ─────────────────────────────────────────────────────────
+
+    /// Override the protocol fee for one project, e.g. to exempt a flagship
+    /// or emergency-relief campaign.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - `fee_bps` may be zero but not exceed `ProtocolConfig::MAX_FEE_BPS`;
+    ///   `None` removes the override so the global fee applies again.
+    ///
+    /// Applies to deposits made after the call.
+    pub fn set_fee_override(env: Env, caller: Address, project_id: u64, fee_bps: Option<u32>) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        if !storage::project_exists(&env, project_id) {
+            panic_with_error!(&env, Error::ProjectNotFound);
+        }
+        if fee_bps.is_some_and(|bps| bps > ProtocolConfig::MAX_FEE_BPS) {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        storage::set_fee_override(&env, project_id, fee_bps);
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("fee_ovr"),
+            None,
+            Some(project_id),
+        );
+        events::emit_fee_override_set(&env, project_id, fee_bps);
+    }
+
+    /// Return the fee bps deposits into `project_id` currently pay: its
+    /// override if one is set, otherwise `ProtocolConfig::fee_bps`. No fee
+    /// is charged while no treasury is configured.
+    pub fn get_fee_bps(env: Env, project_id: u64) -> u32 {
+        let protocol = storage::get_protocol_config(&env);
+        if protocol.treasury.is_none() {
+            return 0;
+        }
+        storage::get_fee_override(&env, project_id).unwrap_or(protocol.fee_bps)
+    }
+
+    /// Set the fee skimmed from every release, in basis points.
+    ///
+    /// - `caller` must be the SuperAdmin.
+    /// - `fee_bps` may not exceed `ProtocolConfig::MAX_FEE_BPS`.
+    ///
+    /// - Fails with `TimelockNotElapsed` while `ProtocolConfig::action_delay`
+    ///   is set; queue a `TimelockedAction::SetFees` instead.
+    ///
+    /// The fee is held by the contract for `withdraw_treasury` and only
+    /// charged while a treasury is configured. Applies to releases made
+    /// after the call.
+    pub fn set_release_fee_bps(env: Env, caller: Address, fee_bps: u32) {
+        caller.require_auth();
+        rbac::require_role(&env, &caller, &Role::SuperAdmin);
+        require_no_action_delay(&env);
+        if fee_bps > ProtocolConfig::MAX_FEE_BPS {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        let mut config = storage::get_protocol_config(&env);
+        config.release_fee_bps = fee_bps;
+        storage::set_protocol_config(&env, &config);
+        log_admin(&env, &caller, symbol_short!("rel_fee"), None, None);
+    }
+
+    /// Return the treasury and the deposit and release fee rates.
+    pub fn get_fee_config(env: Env) -> FeeConfig {
+        let config = storage::get_protocol_config(&env);
+        FeeConfig {
+            treasury: config.treasury,
+            deposit_fee_bps: config.fee_bps,
+            release_fee_bps: config.release_fee_bps,
+        }
+    }
+
+    /// Pay `amount` of the fees held in `token` to the treasury.
+    ///
+    /// - `admin` must hold `SuperAdmin` or `Admin`.
+    /// - A treasury must be configured.
+    /// - Fails with `InsufficientBalance` unless `amount` is positive and
+    ///   no more than `get_fee_vault(token)`.
+    ///
+    /// Withdraws fees as held, so deposit fees awaiting conversion are paid
+    /// out unconverted.
+    pub fn withdraw_treasury(env: Env, admin: Address, token: Address, amount: i128) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        admin.require_auth();
+        rbac::require_admin_or_above(&env, &admin);
+        let treasury = match storage::get_protocol_config(&env).treasury {
+            Some(treasury) => treasury,
+            None => panic_with_error!(&env, Error::InvalidConfig),
+        };
+        if amount <= 0 || amount > storage::get_fee_vault(&env, &token) {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+
+        storage::add_to_fee_vault(&env, &token, -amount);
+        let contract = env.current_contract_address();
+        token::Client::new(&env, &token).transfer(&contract, &treasury, &amount);
+        events::emit_escrow_transfer(&env, token.clone(), contract, treasury.clone(), amount);
+        log_admin(
+            &env,
+            &admin,
+            symbol_short!("treas_wd"),
+            Some(treasury.clone()),
+            None,
+        );
+        events::emit_treasury_withdrawn(&env, token, treasury, amount);
+    }
+
+    /// Convert the fees held in `token` into `ProtocolConfig::reserve_token`
+    /// through `ProtocolConfig::dex_router` and pay the proceeds to the
+    /// treasury. Anyone may call it. Returns the amount paid.
+    ///
+    /// - Fails with `InsufficientBalance` when no fees are held, or fewer
+    ///   than `ProtocolConfig::conversion_threshold`.
+    /// - Fails with `SlippageExceeded` if the treasury receives less than
+    ///   the price feed's quote minus `ProtocolConfig::max_slippage_bps`.
+    /// - Fails with `InvalidConfig` without a treasury, or if the feed
+    ///   cannot quote the pair.
+    ///
+    /// If conversion has been switched off, or `token` has since become
+    /// the reserve token, the held fees are paid out unconverted.
+    pub fn convert_fees(env: Env, token: Address) -> i128 {
+        let _guard = ReentrancyGuard::acquire(&env);
+        let config = storage::get_protocol_config(&env);
+        let treasury = match config.treasury {
+            Some(treasury) => treasury,
+            None => panic_with_error!(&env, Error::InvalidConfig),
+        };
+        let held = storage::get_fee_vault(&env, &token);
+        if held <= 0 {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+        let contract = env.current_contract_address();
+
+        let (reserve, router, feed) =
+            match (config.reserve_token, config.dex_router, config.price_feed) {
+                (Some(reserve), Some(router), Some(feed)) if reserve != token => {
+                    (reserve, router, feed)
+                }
+                _ => {
+                    storage::add_to_fee_vault(&env, &token, -held);
+                    token::Client::new(&env, &token).transfer(&contract, &treasury, &held);
+                    events::emit_escrow_transfer(&env, token.clone(), contract, treasury, held);
+                    events::emit_fees_converted(&env, token.clone(), token, held, held);
+                    return held;
+                }
+            };
+        if held < config.conversion_threshold {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+        let quote = match price::convert(&env, &feed, &token, &reserve, held) {
+            Some(quote) => quote,
+            None => panic_with_error!(&env, Error::InvalidConfig),
+        };
+        let min_out = quote * (10_000 - config.max_slippage_bps) as i128 / 10_000;
+
+        storage::add_to_fee_vault(&env, &token, -held);
+        token::Client::new(&env, &token).transfer(&contract, &router, &held);
+        events::emit_escrow_transfer(&env, token.clone(), contract, router.clone(), held);
+        let received = dex::swap(&env, &router, &token, &reserve, held, min_out, &treasury);
+        if received < min_out {
+            panic_with_error!(&env, Error::SlippageExceeded);
+        }
+        events::emit_fees_converted(&env, token, reserve, held, received);
+        received
+    }
+
+    /// Return the fees of `token` held for conversion.
+    pub fn get_fee_vault(env: Env, token: Address) -> i128 {
+        storage::get_fee_vault(&env, &token)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Donation matching
+    // ─────────────────────────────────────────────────────────
+
+    /// Cap how much of each donor's giving a project's matching round
+    /// covers, in units of the project's first accepted token.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - `cap` must not be negative; `0` turns matching off.
+    ///
+    /// The cap is enforced at deposit time: each deposit in the first token
+    /// tags the part still under the donor's cap as eligible and emits it
+    /// in a `matchable` event, so the donor knows what will be matched
+    /// before the round finalizes. Deposits made earlier are not re-tagged.
+    /// While a cap is set, matching pools match only the tagged part.
+    pub fn set_match_cap(env: Env, caller: Address, project_id: u64, cap: i128) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        if !storage::project_exists(&env, project_id) {
+            panic_with_error!(&env, Error::ProjectNotFound);
+        }
+        if cap < 0 {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        storage::set_match_cap(&env, project_id, cap);
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("match_cap"),
+            None,
+            Some(project_id),
+        );
+        events::emit_match_cap_set(&env, project_id, cap);
+    }
+
+    /// Return the per-donor match cap of a project; `0` if matching is off.
+    pub fn get_match_cap(env: Env, project_id: u64) -> i128 {
+        storage::get_match_cap(&env, project_id)
+    }
+
+    /// Return how much of `donor`'s giving to a project is tagged for
+    /// matching.
+    pub fn get_match_eligible(env: Env, project_id: u64, donor: Address) -> i128 {
+        storage::get_match_eligible(&env, project_id, &donor)
+    }
+
+    /// Open a pool that matches deposits to a project in `token` and return
+    /// its ID. The pool matches nothing until funded with `fund_match_pool`.
+    ///
+    /// - `sponsor` must authorize and must not be frozen.
+    /// - The project must be `Funding` or `Active` and accept `token`, and
+    ///   may have at most `MatchPool::MAX_PER_PROJECT` pools.
+    /// - `ratio_bps` must be in `1..=MatchPool::MAX_RATIO_BPS` and `cap`
+    ///   positive, else `InvalidConfig`.
+    ///
+    /// Each later deposit in `token` is matched at `ratio_bps` of its amount
+    /// until the pool has matched `cap` in total or runs dry; if the project
+    /// has a match cap (`set_match_cap`), only the part of the deposit
+    /// tagged eligible is matched. Matched funds are escrowed as the
+    /// sponsor's contribution, so they are refunded to the sponsor if the
+    /// project expires.
+    ///
+    /// Pools are bound to a single project. Projects carry no category, so
+    /// pools spanning a category of projects are not supported.
+    ///
+    /// Emits `mpool_new`.
+    pub fn create_match_pool(
+        env: Env,
+        sponsor: Address,
+        project_id: u64,
+        token: Address,
+        ratio_bps: u32,
+        cap: i128,
+    ) -> u64 {
+        sponsor.require_auth();
+        require_not_frozen(&env, &sponsor);
+        require_not_sunset(&env);
+        let (config, state) = load_project_pair(&env, project_id);
+        if !matches!(state.status, ProjectStatus::Funding | ProjectStatus::Active) {
+            panic_with_error!(&env, Error::InvalidStatusTransition);
+        }
+        if !config.accepted_tokens.contains(&token) {
+            panic_with_error!(&env, Error::InvalidToken);
+        }
+        if ratio_bps == 0 || ratio_bps > MatchPool::MAX_RATIO_BPS || cap <= 0 {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        let mut pools = storage::get_match_pools(&env, project_id);
+        if pools.len() >= MatchPool::MAX_PER_PROJECT {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+
+        let pool_id = storage::next_match_pool_id(&env);
+        storage::set_match_pool(
+            &env,
+            pool_id,
+            &MatchPool {
+                sponsor: sponsor.clone(),
+                project_id,
+                token: token.clone(),
+                ratio_bps,
+                cap,
+                balance: 0,
+                matched: 0,
+            },
+        );
+        pools.push_back(pool_id);
+        storage::set_match_pools(&env, project_id, &pools);
+        events::emit_match_pool_created(&env, pool_id, project_id, sponsor, token, ratio_bps, cap);
+        pool_id
+    }
+
+    /// Add `amount` of the pool's token to a matching pool.
+    ///
+    /// - `sponsor` must be the pool's sponsor and must authorize.
+    /// - `amount` must be positive, else `InvalidConfig`, and the project
+    ///   must still be `Funding` or `Active`.
+    ///
+    /// Emits `mpool_fnd`.
+    pub fn fund_match_pool(env: Env, sponsor: Address, pool_id: u64, amount: i128) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        sponsor.require_auth();
+        require_not_frozen(&env, &sponsor);
+        require_not_sunset(&env);
+        let mut pool = match storage::get_match_pool(&env, pool_id) {
+            Some(pool) => pool,
+            None => panic_with_error!(&env, Error::ProjectNotFound),
+        };
+        if pool.sponsor != sponsor {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        let state = storage::load_project_state(&env, pool.project_id);
+        if !matches!(state.status, ProjectStatus::Funding | ProjectStatus::Active) {
+            panic_with_error!(&env, Error::InvalidStatusTransition);
+        }
+
+        let contract = env.current_contract_address();
+        token::Client::new(&env, &pool.token).transfer(&sponsor, &contract, &amount);
+        events::emit_escrow_transfer(&env, pool.token.clone(), sponsor.clone(), contract, amount);
+        pool.balance += amount;
+        storage::set_match_pool(&env, pool_id, &pool);
+        events::emit_match_pool_funded(&env, pool_id, sponsor, amount);
+    }
+
+    /// Return a matching pool's unspent funds to its sponsor once the
+    /// project is `Completed` or `Expired`. Returns the amount paid.
+    ///
+    /// - `sponsor` must be the pool's sponsor and must authorize.
+    /// - Fails with `ProjectNotSettled` before then, and with
+    ///   `InsufficientBalance` if nothing is left.
+    ///
+    /// Emits `mpool_end`.
+    pub fn close_match_pool(env: Env, sponsor: Address, pool_id: u64) -> i128 {
+        let _guard = ReentrancyGuard::acquire(&env);
+        sponsor.require_auth();
+        require_not_frozen(&env, &sponsor);
+        let mut pool = match storage::get_match_pool(&env, pool_id) {
+            Some(pool) => pool,
+            None => panic_with_error!(&env, Error::ProjectNotFound),
+        };
+        if pool.sponsor != sponsor {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+        if !is_settled(&storage::load_project_state(&env, pool.project_id)) {
+            panic_with_error!(&env, Error::ProjectNotSettled);
+        }
+        let amount = pool.balance;
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+
+        pool.balance = 0;
+        storage::set_match_pool(&env, pool_id, &pool);
+        let contract = env.current_contract_address();
+        token::Client::new(&env, &pool.token).transfer(&contract, &sponsor, &amount);
+        events::emit_escrow_transfer(&env, pool.token.clone(), contract, sponsor.clone(), amount);
+        events::emit_match_pool_closed(&env, pool_id, sponsor, amount);
+        amount
+    }
+
+    /// Return matching pool `pool_id`, if it exists.
+    pub fn get_match_pool(env: Env, pool_id: u64) -> Option<MatchPool> {
+        storage::get_match_pool(&env, pool_id)
+    }
+
+    /// Return the IDs of the pools matching a project, oldest first.
+    pub fn get_match_pools(env: Env, project_id: u64) -> Vec<u64> {
+        storage::get_match_pools(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Expiry and refunds
+    // ─────────────────────────────────────────────────────────
+
+    /// Move a project that missed its deadline to `Expired`. Permissionless.
+    ///
+    /// - Fails with `TimelockNotElapsed` until the deadline has passed.
+    /// - Fails with `InvalidStatusTransition` if the project is already
+    ///   `Completed` or `Expired`, and with `ReleasePending` while a
+    ///   verified release is held by a donor vote or the release throttle.
+    ///
+    /// Emits `expired`, the canonical trigger for indexers and refunds.
+    pub fn expire_project(env: Env, project_id: u64) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        let (config, mut state) = load_project_pair(&env, project_id);
+        if let Err(error) = fsm::transition(&state.status, StatusEvent::Expire) {
+            panic_with_error!(&env, error);
+        }
+        if env.ledger().timestamp() <= config.deadline {
+            panic_with_error!(&env, Error::TimelockNotElapsed);
+        }
+        require_no_pending_release(&env, &state);
+        mark_expired(&env, &config, &mut state);
+    }
+
+    /// Reclaim `donator`'s contribution in `token` from a project that
+    /// missed its deadline. Returns the amount refunded.
+    ///
+    /// - `donator` must authorize and must not be frozen.
+    /// - The project must be `Expired`, or past its deadline without having
+    ///   completed, in which case this call expires it first. Fails with
+    ///   `ProjectNotSettled` before the deadline and
+    ///   `InvalidStatusTransition` for completed or migrated projects.
+    /// - Each donor may claim each token once; a second claim fails with
+    ///   `AlreadyAttested`.
+    /// - Fails with `InsufficientBalance` if the donor has nothing to claim,
+    ///   including when their whole contribution was made as a grant.
+    ///
+    /// The escrow left when refunds opened is shared out in proportion to
+    /// refundable contributions, so donors get back exactly what they deposited unless
+    /// fees, rent or earlier tranche releases were taken from it. Insured
+    /// projects also return the donor's premium from the insurance pool.
+    /// Small claims are reimbursed from the sponsorship pool.
+    pub fn claim_refund(env: Env, project_id: u64, donator: Address, token: Address) -> i128 {
+        let _guard = ReentrancyGuard::acquire(&env);
+        donator.require_auth();
+        require_not_frozen(&env, &donator);
+        let (config, mut state) = load_project_pair(&env, project_id);
+        if state.has_flag(ProjectState::FLAG_MIGRATED) {
+            panic_with_error!(&env, Error::InvalidStatusTransition);
+        }
+        match state.status {
+            ProjectStatus::Expired => {}
+            ProjectStatus::Funding | ProjectStatus::Active
+                if env.ledger().timestamp() > config.deadline =>
+            {
+                require_no_pending_release(&env, &state);
+                mark_expired(&env, &config, &mut state);
+            }
+            ProjectStatus::Completed => {
+                panic_with_error!(&env, Error::InvalidStatusTransition)
+            }
+            _ => panic_with_error!(&env, Error::ProjectNotSettled),
+        }
+
+        let contribution = storage::get_contribution(&env, project_id, &donator, &token)
+            - storage::get_grant(&env, project_id, &donator, &token).map_or(0, |g| g.amount);
+        if contribution <= 0 {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+        if storage::get_refunded(&env, project_id, &donator, &token) > 0 {
+            panic_with_error!(&env, Error::AlreadyAttested);
+        }
+        let (escrow, raised) = refund_basis(&env, project_id, &token);
+        let share = escrow * contribution / raised;
+        let cover = if state.has_flag(ProjectState::FLAG_INSURED) {
+            let premium_bps = storage::get_insurance(&env, project_id).unwrap_or(0);
+            (contribution * premium_bps as i128 / 10_000)
+                .min(storage::get_insurance_pool(&env, &token))
+        } else {
+            0
+        };
+        let amount = share + cover;
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+
+        if share > 0 {
+            record_outflow(&env, project_id, &token, share);
+            let mut flows = storage::get_token_flows(&env, project_id, &token);
+            flows.refunded += share;
+            storage::set_token_flows(&env, project_id, &token, &flows);
+            let balance = storage::get_token_balance(&env, project_id, &token);
+            storage::set_token_balance(&env, project_id, &token, balance - share);
+        }
+        if cover > 0 {
+            storage::add_to_insurance_pool(&env, &token, -cover);
+        }
+        storage::set_refunded(&env, project_id, &donator, &token, amount);
+        record_ledger(
+            &env,
+            &donator,
+            project_id,
+            &token,
+            LedgerKind::Refund,
+            amount,
+        );
+        let contract = env.current_contract_address();
+        token::Client::new(&env, &token).transfer(&contract, &donator, &amount);
+        events::emit_escrow_transfer(&env, token.clone(), contract, donator.clone(), amount);
+        events::emit_refunded(&env, project_id, donator.clone(), token.clone(), amount);
+        sponsor_refund(&env, project_id, &donator, &token, amount);
+        amount
+    }
+
+    /// Return the amount refunded to `donator` from `project_id` in `token`.
+    pub fn get_refunded(env: Env, project_id: u64, donator: Address, token: Address) -> i128 {
+        storage::get_refunded(&env, project_id, &donator, &token)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Commons
+    // ─────────────────────────────────────────────────────────
+
+    /// Grant `amount` of `token` from the commons pool to `recipient`.
+    ///
+    /// - `admin` must hold `SuperAdmin` or `Admin`.
+    /// - Fails with `InsufficientBalance` if the pool holds less than
+    ///   `amount`, which must be positive.
+    ///
+    /// The pool collects grants that donors to expired projects routed to
+    /// the commons rather than the creator.
+    pub fn disburse_commons(
+        env: Env,
+        admin: Address,
+        token: Address,
+        recipient: Address,
+        amount: i128,
+    ) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        admin.require_auth();
+        rbac::require_admin_or_above(&env, &admin);
+        if amount <= 0 || amount > storage::get_commons_pool(&env, &token) {
+            panic_with_error!(&env, Error::InsufficientBalance);
+        }
+
+        storage::add_to_commons_pool(&env, &token, -amount);
+        let contract = env.current_contract_address();
+        token::Client::new(&env, &token).transfer(&contract, &recipient, &amount);
+        events::emit_escrow_transfer(&env, token.clone(), contract, recipient.clone(), amount);
+        log_admin(
+            &env,
+            &admin,
+            symbol_short!("commons"),
+            Some(recipient.clone()),
+            None,
+        );
+        events::emit_commons_disbursed(&env, token, recipient, amount);
+    }
+
+    /// Return the grants of `token` held in the commons pool.
+    pub fn get_commons_pool(env: Env, token: Address) -> i128 {
+        storage::get_commons_pool(&env, &token)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Refund sponsorship
+    // ─────────────────────────────────────────────────────────
+
+    /// Add `amount` of `token` to the pool that reimburses small donors for
+    /// claiming refunds. Typically funded by the treasury; anyone may add to
+    /// it and `funder` must authorize the transfer.
+    ///
+    /// Claims of at most `ProtocolConfig::sponsor_threshold` are paid
+    /// `sponsor_amount` each, up to `sponsor_budget` per `sponsor_period`.
+    pub fn fund_sponsor_pool(env: Env, funder: Address, token: Address, amount: i128) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        funder.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        token::Client::new(&env, &token).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        events::emit_escrow_transfer(
+            &env,
+            token.clone(),
+            funder.clone(),
+            env.current_contract_address(),
+            amount,
+        );
+        storage::set_sponsor_pool(
+            &env,
+            &token,
+            storage::get_sponsor_pool(&env, &token) + amount,
+        );
+        events::emit_sponsor_pool_funded(&env, funder, token, amount);
+    }
+
+    /// Return the funds reserved for refund sponsorship in `token`.
+    pub fn get_sponsor_pool(env: Env, token: Address) -> i128 {
+        storage::get_sponsor_pool(&env, &token)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Disbursement schedules
+    // ─────────────────────────────────────────────────────────
+
+    /// Define or replace reusable disbursement template `template_id`.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - `template` must pass [`DisbursementTemplate::is_valid`].
+    ///
+    /// Projects copy a template when they adopt it, so replacing one does not
+    /// affect projects already using it.
+    pub fn set_disbursement_template(
+        env: Env,
+        caller: Address,
+        template_id: u32,
+        template: DisbursementTemplate,
+    ) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        if !template.is_valid() {
+            panic_with_error!(&env, Error::InvalidMilestones);
+        }
+        storage::set_template(&env, template_id, &template);
+        log_admin(&env, &caller, symbol_short!("template"), None, None);
+    }
+
+    /// Return disbursement template `template_id`, if defined.
+    pub fn get_disbursement_template(env: Env, template_id: u32) -> Option<DisbursementTemplate> {
+        storage::get_template(&env, template_id)
+    }
+
+    /// Release a project's escrow in tranches following template
+    /// `template_id` instead of all at once on verification.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - Only allowed before the project has received any deposits or
+    ///   attestations, and not for ongoing projects or projects registered
+    ///   with milestones.
+    ///
+    /// Leading ungated tranches are released immediately. Each attestation
+    /// quorum then releases the next gated tranche, plus any ungated ones
+    /// after it, and clears the attestations for the following gate. The
+    /// last tranche completes the project as usual.
+    pub fn use_disbursement_template(
+        env: Env,
+        signers: Vec<Address>,
+        project_id: u64,
+        template_id: u32,
+    ) {
+        require_unfunded_control(&env, &signers, project_id);
+        if storage::get_cadence(&env, project_id).is_some()
+            || !storage::get_milestones(&env, project_id).is_empty()
+        {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        let template = match storage::get_template(&env, template_id) {
+            Some(template) => template,
+            None => panic_with_error!(&env, Error::MilestoneNotFound),
+        };
+        storage::set_schedule(&env, project_id, &template);
+
+        let mut state = storage::load_project_state(&env, project_id);
+        state.released_bps = template.release_until_gate(0);
+        save_project_state(&env, project_id, &state);
+        if state.released_bps > 0 {
+            events::emit_tranche_released(&env, project_id, state.released_bps);
+        }
+    }
+
+    /// Return the disbursement schedule a project adopted, if any.
+    pub fn get_disbursement_schedule(env: Env, project_id: u64) -> Option<DisbursementTemplate> {
+        storage::get_schedule(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Donor votes
+    // ─────────────────────────────────────────────────────────
+
+    /// Opt a project into a donor vote after each verification.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - `window` must be non-zero; `objection_bps` must lie in `1..=10_000`.
+    /// - Only allowed before the project has received any deposits or
+    ///   attestations.
+    ///
+    /// Each time quorum is met — for every gated tranche of a scheduled
+    /// project, and for completion — the release is held for `window`
+    /// seconds while donors may object, weighted by their contribution in the first
+    /// accepted token. If objections reach `objection_bps` of that weight
+    /// the release is contested and an admin decides via `resolve_contest`.
+    pub fn set_donor_vote(
+        env: Env,
+        signers: Vec<Address>,
+        project_id: u64,
+        window: u64,
+        objection_bps: u32,
+    ) {
+        require_unfunded_control(&env, &signers, project_id);
+        if window == 0 || objection_bps == 0 || objection_bps > 10_000 {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        storage::set_vote_config(
+            &env,
+            project_id,
+            &DonorVoteConfig {
+                window,
+                objection_bps,
+            },
+        );
+    }
+
+    /// Return a project's donor vote settings, if it opted in.
+    pub fn get_donor_vote_config(env: Env, project_id: u64) -> Option<DonorVoteConfig> {
+        storage::get_vote_config(&env, project_id)
+    }
+
+    /// Return the open or contested donor vote of a project, if any.
+    pub fn get_donor_vote(env: Env, project_id: u64) -> Option<DonorVote> {
+        storage::get_donor_vote(&env, project_id)
+    }
+
+    /// Object to a verified project's pending release. Returns the weight
+    /// counted.
+    ///
+    /// - `donor` must authorize and have contributed in the first accepted
+    ///   token.
+    /// - The vote must still be open; each donor may object once.
+    pub fn object_to_release(env: Env, donor: Address, project_id: u64) -> i128 {
+        donor.require_auth();
+        let config = storage::load_project_config(&env, project_id);
+        let mut vote = match storage::get_donor_vote(&env, project_id) {
+            Some(vote) if env.ledger().timestamp() < vote.ends_at => vote,
+            _ => panic_with_error!(&env, Error::VoteNotOpen),
+        };
+        if storage::has_voted(&env, project_id, &donor, vote.ends_at) {
+            panic_with_error!(&env, Error::AlreadyAttested);
+        }
+        let weight = match config.accepted_tokens.get(0) {
+            Some(token) => storage::get_contribution(&env, project_id, &donor, &token),
+            None => 0,
+        };
+        if weight <= 0 {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+
+        vote.objections += weight;
+        storage::set_donor_vote(&env, project_id, &vote);
+        storage::set_voted(&env, project_id, &donor, vote.ends_at);
+        events::emit_release_objected(&env, project_id, donor, weight);
+        weight
+    }
+
+    /// Close a donor vote whose window has ended. Permissionless; `keeper`
+    /// receives the keeper reward, if one is configured.
+    ///
+    /// Applies the release the vote held — the next tranche, or completion
+    /// — unless objections reached the threshold, in which case it is
+    /// marked contested for `resolve_contest`. An approved release still
+    /// goes through the challenge window, if one is configured. Returns
+    /// `true` if the release was contested.
+    ///
+    /// - Fails with `ProtocolPaused` or `ProjectPaused` while paused, so a
+    ///   held release waits out the pause.
+    pub fn close_donor_vote(env: Env, keeper: Address, project_id: u64) -> bool {
+        let _guard = ReentrancyGuard::acquire(&env);
+        keeper.require_auth();
+        let (config, mut state) = load_project_pair(&env, project_id);
+        require_not_paused(&env, &state);
+        let vote = match storage::get_donor_vote(&env, project_id) {
+            Some(vote) if !state.has_flag(ProjectState::FLAG_CONTESTED) => vote,
+            _ => panic_with_error!(&env, Error::VoteNotOpen),
+        };
+        if env.ledger().timestamp() < vote.ends_at {
+            panic_with_error!(&env, Error::VoteNotOpen);
+        }
+
+        let vote_config = storage::get_vote_config(&env, project_id).unwrap();
+        let contested = vote.total_weight > 0
+            && vote.objections * 10_000 >= vote.total_weight * vote_config.objection_bps as i128;
+        events::emit_vote_closed(&env, project_id, contested, vote.objections);
+        if contested {
+            state.set_flag(ProjectState::FLAG_CONTESTED, true);
+            save_project_state(&env, project_id, &state);
+        } else {
+            storage::remove_donor_vote(&env, project_id);
+            state.set_flag(ProjectState::FLAG_RELEASE_PENDING, false);
+            challenge_or_release(
+                &env,
+                &config,
+                &mut state,
+                vote.released_bps,
+                vote.oracle,
+                vote.proof_hash,
+            );
+        }
+        pay_keeper(&env, &keeper, project_id);
+        contested
+    }
+
+    /// Settle a contested release.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - `release = true` applies the held release as verified; `false`
+    ///   discards the attestations so the project must be verified again.
+    pub fn resolve_contest(env: Env, caller: Address, project_id: u64, release: bool) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        let (config, mut state) = load_project_pair(&env, project_id);
+        let vote = match storage::get_donor_vote(&env, project_id) {
+            Some(vote) if state.has_flag(ProjectState::FLAG_CONTESTED) => vote,
+            _ => panic_with_error!(&env, Error::VoteNotOpen),
+        };
+
+        storage::remove_donor_vote(&env, project_id);
+        state.set_flag(ProjectState::FLAG_CONTESTED, false);
+        state.set_flag(ProjectState::FLAG_RELEASE_PENDING, false);
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("resolved"),
+            None,
+            Some(project_id),
+        );
+        events::emit_contest_resolved(&env, project_id, release, caller);
+        if release {
+            release_or_queue(
+                &env,
+                &config,
+                &mut state,
+                vote.released_bps,
+                vote.oracle,
+                vote.proof_hash,
+            );
+        } else {
+            fsm::advance(&env, &mut state, StatusEvent::Reject);
+            storage::set_attestations(&env, project_id, &Vec::new(&env));
+            save_project_state(&env, project_id, &state);
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Challenge window
+    // ─────────────────────────────────────────────────────────
+
+    /// Dispute a verified release during its challenge window, holding it
+    /// until an admin calls `resolve_dispute`.
+    ///
+    /// - `auditor` must hold the `Auditor` role and must authorize.
+    /// - Fails with `ChallengeNotOpen` unless the release is still inside
+    ///   an undisputed challenge window.
+    ///
+    /// Emits `disputed`.
+    pub fn raise_dispute(env: Env, auditor: Address, project_id: u64, evidence_hash: BytesN<32>) {
+        auditor.require_auth();
+        rbac::require_role(&env, &auditor, &Role::Auditor);
+        let mut state = storage::load_project_state(&env, project_id);
+        let mut challenge = match storage::get_challenge(&env, project_id) {
+            Some(challenge)
+                if challenge.disputed_by.is_none()
+                    && env.ledger().timestamp() < challenge.ends_at =>
+            {
+                challenge
+            }
+            _ => panic_with_error!(&env, Error::ChallengeNotOpen),
+        };
+        challenge.disputed_by = Some(auditor.clone());
+        challenge.evidence_hash = Some(evidence_hash.clone());
+        storage::set_challenge(&env, project_id, &challenge);
+        state.set_flag(ProjectState::FLAG_CONTESTED, true);
+        save_project_state(&env, project_id, &state);
+        events::emit_dispute_raised(&env, project_id, auditor, evidence_hash);
+    }
+
+    /// Apply a release whose challenge window ended without a dispute.
+    /// Permissionless; `keeper` receives the keeper reward, if one is
+    /// configured.
+    ///
+    /// - Fails with `ChallengeNotOpen` while the window is still open, if
+    ///   the release was disputed, or if none is held.
+    /// - Fails with `ProtocolPaused` or `ProjectPaused` while paused.
+    pub fn close_challenge(env: Env, keeper: Address, project_id: u64) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        keeper.require_auth();
+        let (config, mut state) = load_project_pair(&env, project_id);
+        require_not_paused(&env, &state);
+        let challenge = match storage::get_challenge(&env, project_id) {
+            Some(challenge)
+                if challenge.disputed_by.is_none()
+                    && env.ledger().timestamp() >= challenge.ends_at =>
+            {
+                challenge
+            }
+            _ => panic_with_error!(&env, Error::ChallengeNotOpen),
+        };
+        storage::remove_challenge(&env, project_id);
+        state.set_flag(ProjectState::FLAG_RELEASE_PENDING, false);
+        release_or_queue(
+            &env,
+            &config,
+            &mut state,
+            challenge.released_bps,
+            challenge.oracle,
+            challenge.proof_hash,
+        );
+        pay_keeper(&env, &keeper, project_id);
+    }
+
+    /// Settle a disputed release.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - `release = true` applies the release as verified; `false` discards
+    ///   the attestations so the project must be verified again.
+    /// - Fails with `ChallengeNotOpen` unless the release was disputed.
+    ///
+    /// Emits `disp_res`.
+    pub fn resolve_dispute(env: Env, caller: Address, project_id: u64, release: bool) {
+        let _guard = ReentrancyGuard::acquire(&env);
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        let (config, mut state) = load_project_pair(&env, project_id);
+        let challenge = match storage::get_challenge(&env, project_id) {
+            Some(challenge) if challenge.disputed_by.is_some() => challenge,
+            _ => panic_with_error!(&env, Error::ChallengeNotOpen),
+        };
+
+        storage::remove_challenge(&env, project_id);
+        state.set_flag(ProjectState::FLAG_CONTESTED, false);
+        state.set_flag(ProjectState::FLAG_RELEASE_PENDING, false);
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("resolved"),
+            None,
+            Some(project_id),
+        );
+        events::emit_dispute_resolved(&env, project_id, release, caller);
+        if release {
+            release_or_queue(
+                &env,
+                &config,
+                &mut state,
+                challenge.released_bps,
+                challenge.oracle,
+                challenge.proof_hash,
+            );
+        } else {
+            fsm::advance(&env, &mut state, StatusEvent::Reject);
+            storage::set_attestations(&env, project_id, &Vec::new(&env));
+            save_project_state(&env, project_id, &state);
+        }
+    }
+
+    /// Return the release of a project held in its challenge window or
+    /// disputed, if any.
+    pub fn get_challenge(env: Env, project_id: u64) -> Option<Challenge> {
+        storage::get_challenge(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Release throttling
+    // ─────────────────────────────────────────────────────────
+
+    /// Apply queued releases, oldest first, while the current window of
+    /// `ProtocolConfig::release_cap` has room. Permissionless.
+    ///
+    /// Releases of paused projects stay queued without holding up the rest.
+    /// Returns the number of releases applied.
+    pub fn process_release_queue(env: Env) -> u32 {
+        let _guard = ReentrancyGuard::acquire(&env);
+        let queue = storage::get_release_queue(&env);
+        let mut remaining = Vec::new(&env);
+        let mut applied = 0;
+        let mut full = false;
+        for next in queue.iter() {
+            if !full {
+                let (config, mut state) = load_project_pair(&env, next.project_id);
+                if state.has_flag(ProjectState::FLAG_PAUSED) {
+                    remaining.push_back(next);
+                    continue;
+                }
+                if reserve_outflow(&env, &config, next.released_bps) {
+                    state.set_flag(ProjectState::FLAG_RELEASE_PENDING, false);
+                    apply_release(
+                        &env,
+                        &config,
+                        &mut state,
+                        next.released_bps,
+                        next.oracle,
+                        next.proof_hash,
+                    );
+                    applied += 1;
+                    continue;
+                }
+                full = true;
+            }
+            remaining.push_back(next);
+        }
+        storage::set_release_queue(&env, &remaining);
+        applied
+    }
+
+    /// Return the releases waiting for throttle room, oldest first.
+    pub fn get_release_queue(env: Env) -> Vec<QueuedRelease> {
+        storage::get_release_queue(&env)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Deadline extensions
+    // ─────────────────────────────────────────────────────────
+
+    /// Move the deadline of a `Funding` or `Active` project out to
+    /// `new_deadline`. Returns how many times it has now been extended.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners), extending by at most
+    ///   `ProtocolConfig::max_deadline_extension`, or be a single
+    ///   `SuperAdmin` or `Admin`, which approves a longer extension.
+    /// - `new_deadline` must be later than the current deadline and at most
+    ///   `ProtocolConfig::MAX_DEADLINE_HORIZON` from now (`InvalidConfig`).
+    ///
+    /// Emits `extended`.
+    pub fn extend_deadline(
+        env: Env,
+        signers: Vec<Address>,
+        project_id: u64,
+        new_deadline: u64,
+    ) -> u32 {
+        let (mut config, state) = load_project_pair(&env, project_id);
+        match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active => {}
+            _ => panic_with_error!(&env, Error::InvalidStatusTransition),
+        }
+        let now = env.ledger().timestamp();
+        if new_deadline <= config.deadline
+            || new_deadline > now.saturating_add(ProtocolConfig::MAX_DEADLINE_HORIZON)
+        {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        let within_limit = new_deadline - config.deadline
+            <= storage::get_protocol_config(&env).max_deadline_extension;
+        if !has_project_control(&env, &config, &signers) || !within_limit {
+            require_admin_signer(&env, &config, &signers, symbol_short!("extended"));
+        }
+
+        let old_deadline = config.deadline;
+        config.deadline = new_deadline;
+        storage::save_project_config(&env, &config);
+        // A donor vote on the old deadline no longer applies.
+        storage::remove_extension_vote(&env, project_id);
+        events::emit_deadline_extended(&env, project_id, old_deadline, new_deadline);
+        storage::increment_extension_count(&env, project_id)
+    }
+
+    /// Return how many times the deadline of `project_id` was extended,
+    /// by `extend_deadline` or a donor vote.
+    pub fn get_extension_count(env: Env, project_id: u64) -> u32 {
+        storage::get_extension_count(&env, project_id)
+    }
+
+    /// Back extending a project's missed deadline to `new_deadline` instead
+    /// of unwinding its escrow. Returns `true` if this vote carried it.
+    ///
+    /// - `donor` must authorize and have contributed in the first accepted
+    ///   token; weight is that contribution.
+    /// - The deadline must have passed without the project completing or
+    ///   expiring, and `new_deadline` must be in the future.
+    /// - The first backer fixes `new_deadline`; later backers must match it,
+    ///   and it may be at most `ProtocolConfig::MAX_DEADLINE_HORIZON` away.
+    ///
+    /// The deadline moves once backers hold a strict majority of the
+    /// first-token contributions.
+    pub fn back_extension(env: Env, donor: Address, project_id: u64, new_deadline: u64) -> bool {
+        donor.require_auth();
+        let (mut config, state) = load_project_pair(&env, project_id);
+        let now = env.ledger().timestamp();
+        match state.status {
+            ProjectStatus::Funding | ProjectStatus::Active if now > config.deadline => {}
+            _ => panic_with_error!(&env, Error::VoteNotOpen),
+        }
+        if new_deadline <= now
+            || new_deadline > now.saturating_add(ProtocolConfig::MAX_DEADLINE_HORIZON)
+        {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        let first_token = config.accepted_tokens.get(0).unwrap();
+
+        let mut vote = match storage::get_extension_vote(&env, project_id) {
+            Some(vote) if vote.old_deadline == config.deadline => vote,
+            _ => ExtensionVote {
+                old_deadline: config.deadline,
+                new_deadline,
+                support: 0,
+                total_weight: donor_vote_weight(&env, project_id, &first_token),
+            },
+        };
+        if vote.new_deadline != new_deadline {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        if storage::has_backed_extension(&env, project_id, &donor, vote.old_deadline) {
+            panic_with_error!(&env, Error::AlreadyAttested);
+        }
+        let weight = storage::get_contribution(&env, project_id, &donor, &first_token);
+        if weight <= 0 {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+
+        vote.support += weight;
+        storage::set_backed_extension(&env, project_id, &donor, vote.old_deadline);
+        events::emit_extension_backed(&env, project_id, donor, new_deadline, weight);
+        if vote.support * 2 <= vote.total_weight {
+            storage::set_extension_vote(&env, project_id, &vote);
+            return false;
+        }
+
+        storage::remove_extension_vote(&env, project_id);
+        config.deadline = new_deadline;
+        storage::save_project_config(&env, &config);
+        storage::increment_extension_count(&env, project_id);
+        events::emit_deadline_extended(&env, project_id, vote.old_deadline, new_deadline);
+        true
+    }
+
+    /// Return the pending deadline extension proposal of a project, if any.
+    pub fn get_extension_vote(env: Env, project_id: u64) -> Option<ExtensionVote> {
+        storage::get_extension_vote(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Milestone amendments
+    // ─────────────────────────────────────────────────────────
+
+    /// Propose replacing the milestones of a project, to take effect once
+    /// donors back it or an admin approves it.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners).
+    /// - The project must be `Funding` or `Active`, have registered with
+    ///   milestones (`MilestoneNotFound`) and have no release pending.
+    /// - `milestones` follows the registration rules and must keep every
+    ///   released milestone unchanged (`MilestoneAlreadyReleased`).
+    /// - Fails with `AmendmentPending` while an earlier proposal is open.
+    ///
+    /// Donors have `MilestoneAmendment::VOTE_WINDOW` to back the proposal.
+    /// Emits `amend_req`.
+    pub fn propose_amendment(
+        env: Env,
+        signers: Vec<Address>,
+        project_id: u64,
+        milestones: Vec<Milestone>,
+    ) -> MilestoneAmendment {
+        let config = storage::load_project_config(&env, project_id);
+        require_project_control(&env, &config, &signers);
+        require_amendable(&env, project_id, &milestones);
+        let now = env.ledger().timestamp();
+        if let Some(open) = storage::get_amendment(&env, project_id) {
+            if now < open.ends_at {
+                panic_with_error!(&env, Error::AmendmentPending);
+            }
+        }
+
+        let first_token = config.accepted_tokens.get(0).unwrap();
+        let amendment = MilestoneAmendment {
+            milestones,
+            ends_at: now.saturating_add(MilestoneAmendment::VOTE_WINDOW),
+            support: 0,
+            total_weight: donor_vote_weight(&env, project_id, &first_token),
+        };
+        storage::set_amendment(&env, project_id, &amendment);
+        events::emit_amendment_proposed(
+            &env,
+            project_id,
+            amendment.milestones.clone(),
+            amendment.ends_at,
+        );
+        amendment
+    }
+
+    /// Back the open milestone amendment of a project. Returns `true` if
+    /// this vote carried it.
+    ///
+    /// - `donor` must authorize and have contributed in the first accepted
+    ///   token; weight is that contribution.
+    /// - Fails with `VoteNotOpen` unless a proposal is open.
+    ///
+    /// The milestones are replaced once backers hold a strict majority of
+    /// the first-token contributions at the time of the proposal. Emits
+    /// `amend_vot`, and `amended` when the proposal carries.
+    pub fn back_amendment(env: Env, donor: Address, project_id: u64) -> bool {
+        donor.require_auth();
+        let config = storage::load_project_config(&env, project_id);
+        let mut amendment = match storage::get_amendment(&env, project_id) {
+            Some(amendment) if env.ledger().timestamp() < amendment.ends_at => amendment,
+            _ => panic_with_error!(&env, Error::VoteNotOpen),
+        };
+        if storage::has_backed_amendment(&env, project_id, &donor, amendment.ends_at) {
+            panic_with_error!(&env, Error::AlreadyAttested);
+        }
+        let first_token = config.accepted_tokens.get(0).unwrap();
+        let weight = storage::get_contribution(&env, project_id, &donor, &first_token);
+        if weight <= 0 {
+            panic_with_error!(&env, Error::NotAuthorized);
+        }
+
+        amendment.support += weight;
+        storage::set_backed_amendment(&env, project_id, &donor, amendment.ends_at);
+        events::emit_amendment_backed(&env, project_id, donor, weight);
+        if amendment.support * 2 <= amendment.total_weight {
+            storage::set_amendment(&env, project_id, &amendment);
+            return false;
+        }
+
+        apply_amendment(&env, project_id, &amendment);
+        events::emit_amendment_resolved(&env, project_id, true, None);
+        true
+    }
+
+    /// Approve or reject the open milestone amendment of a project without
+    /// waiting for donors.
+    ///
+    /// - `caller` must hold `SuperAdmin` or `Admin`.
+    /// - Fails with `VoteNotOpen` unless a proposal is open.
+    ///
+    /// Emits `amended`.
+    pub fn resolve_amendment(env: Env, caller: Address, project_id: u64, approve: bool) {
+        caller.require_auth();
+        rbac::require_admin_or_above(&env, &caller);
+        let amendment = match storage::get_amendment(&env, project_id) {
+            Some(amendment) if env.ledger().timestamp() < amendment.ends_at => amendment,
+            _ => panic_with_error!(&env, Error::VoteNotOpen),
+        };
+
+        if approve {
+            apply_amendment(&env, project_id, &amendment);
+        } else {
+            storage::remove_amendment(&env, project_id);
+        }
+        log_admin(
+            &env,
+            &caller,
+            symbol_short!("amended"),
+            None,
+            Some(project_id),
+        );
+        events::emit_amendment_resolved(&env, project_id, approve, Some(caller));
+    }
+
+    /// Return the milestone amendment proposal of a project, if any. A
+    /// proposal past its `ends_at` has lapsed.
+    pub fn get_amendment(env: Env, project_id: u64) -> Option<MilestoneAmendment> {
+        storage::get_amendment(&env, project_id)
+    }
+
+    // ─────────────────────────────────────────────────────────
+    // Ongoing projects
+    // ─────────────────────────────────────────────────────────
+
+    /// Mark a project as ongoing, requiring a fresh oracle quorum at least
+    /// every `period` seconds.
+    ///
+    /// - `signers` must control the project (its creator, or `threshold`
+    ///   of its owners) and must authorize.
+    /// - `period` must be non-zero.
+    /// - Only allowed before the project has received any deposits or
+    ///   attestations.
+    ///
+    /// Ongoing projects never complete: each attestation round that meets
+    /// quorum moves the project to `Active` and renews its stream. A round
+    /// met within `period` of the last (or the first round) releases the
+    /// whole escrow to the creator. A missed round pauses the stream: the
+    /// late round that follows releases nothing, so funds given while it
+    /// lapsed wait for the next on-time round.
+    pub fn set_ongoing(env: Env, signers: Vec<Address>, project_id: u64, period: u64) {
+        require_unfunded_control(&env, &signers, project_id);
+        if period == 0 {
+            panic_with_error!(&env, Error::InvalidMilestones);
+        }
+        if storage::get_schedule(&env, project_id).is_some() {
+            panic_with_error!(&env, Error::InvalidConfig);
+        }
+        storage::set_cadence(
+            &env,
+            project_id,
+            &OngoingCadence {
+                period,
+                last_attested: 0,
+            },
+        );
+    }
+
+    /// Return the re-attestation cadence of an ongoing project, if any.
+    pub fn get_cadence(env: Env, project_id: u64) -> Option<OngoingCadence> {
+        storage::get_cadence(&env, project_id)
+    }
+
+    /// Return `true` if an ongoing project's last attestation is still
+    /// within its period. Always `false` for one-shot projects.
+    pub fn is_stream_active(env: Env, project_id: u64) -> bool {
+        match storage::get_cadence(&env, project_id) {
+            Some(cadence) => cadence.is_active(env.ledger().timestamp()),
+            None => false,
+        }
+    }
+}
+
+/// Set or clear a project's pause flag and emit `prj_pause`.
+fn set_project_paused(env: &Env, caller: Address, project_id: u64, paused: bool) {
+    caller.require_auth();
+    rbac::require_admin_or_above(env, &caller);
+    let mut state = storage::load_project_state(env, project_id);
+    state.set_flag(ProjectState::FLAG_PAUSED, paused);
+    save_project_state(env, project_id, &state);
+    let action = if paused {
+        symbol_short!("prj_pause")
+    } else {
+        symbol_short!("prj_open")
+    };
+    log_admin(env, &caller, action, None, Some(project_id));
+    events::emit_project_paused(env, project_id, paused, caller);
+}
+
+/// Shared body of `pause_token` and `unpause_token`.
+fn set_token_paused(env: &Env, caller: Address, token: Address, paused: bool) {
+    caller.require_auth();
+    rbac::require_admin_or_above(env, &caller);
+    storage::set_token_paused(env, &token, paused);
+    let action = if paused {
+        symbol_short!("tok_pause")
+    } else {
+        symbol_short!("tok_open")
+    };
+    log_admin(env, &caller, action, None, None);
+    events::emit_token_paused(env, token, paused, caller);
+}
+
+/// Append an entry to the administrative audit log. Actions by the
+/// SuperAdmin also count as a dead-man switch heartbeat.
+fn log_admin(
+    env: &Env,
+    actor: &Address,
+    action: Symbol,
+    target: Option<Address>,
+    project_id: Option<u64>,
+) {
+    if rbac::has_role(env, actor.clone(), Role::SuperAdmin) {
+        record_heartbeat(env);
+    }
+    storage::append_admin_log(
+        env,
+        AdminAction {
+            seq: 0,
+            timestamp: env.ledger().timestamp(),
+            actor: actor.clone(),
+            action,
+            target,
+            project_id,
+        },
+    );
+}
+
+/// Shared checks of `register_project` and `register_project_at`.
+fn validate_registration(
+    env: &Env,
+    creator: &Address,
+    accepted_tokens: &Vec<Address>,
+    goal: i128,
+    deadline: u64,
+    quorum: u32,
+    milestones: &Vec<Milestone>,
+) {
+    // RBAC gate: only authorised roles may create projects.
+    rbac::require_can_register(env, creator);
+    require_protocol_active(env);
+    require_not_sunset(env);
+
+    let protocol_config = storage::get_protocol_config(env);
+    if accepted_tokens.is_empty() {
+        panic_with_error!(env, Error::InvalidMilestones);
+    }
+    if accepted_tokens.len() > protocol_config.max_accepted_tokens {
+        panic_with_error!(env, Error::TooManyTokens);
+    }
+    if protocol_config.strict_tokens {
+        for token in accepted_tokens.iter() {
+            require_token_contract(env, &token);
+        }
+    }
+    if protocol_config.token_allowlist {
+        let allowed = storage::get_allowed_tokens(env);
+        for token in accepted_tokens.iter() {
+            if !allowed.contains(&token) {
+                panic_with_error!(env, Error::InvalidToken);
+            }
+        }
+    }
+    if goal <= 0 {
+        panic_with_error!(env, Error::InvalidMilestones);
+    }
+    let now = env.ledger().timestamp();
+    if deadline <= now || deadline > now.saturating_add(ProtocolConfig::MAX_DEADLINE_HORIZON) {
+        panic_with_error!(env, Error::InvalidMilestones);
+    }
+    if quorum == 0 || quorum > protocol_config.max_quorum {
+        panic_with_error!(env, Error::InvalidQuorum);
+    }
+    if !milestones.is_empty() {
+        validate_milestones(env, goal, milestones);
+    }
+}
+
+/// Panic unless `milestones` holds `1..=DisbursementTemplate::MAX_TRANCHES`
+/// positive amounts summing to `goal`, each worth a non-zero share of it.
+fn validate_milestones(env: &Env, goal: i128, milestones: &Vec<Milestone>) {
+    if milestones.is_empty() || milestones.len() > DisbursementTemplate::MAX_TRANCHES {
+        panic_with_error!(env, Error::InvalidMilestones);
+    }
+    let mut total = 0i128;
+    for milestone in milestones.iter() {
+        if milestone.amount <= 0 {
+            panic_with_error!(env, Error::InvalidMilestones);
+        }
+        total = total.saturating_add(milestone.amount);
+    }
+    if total != goal {
+        panic_with_error!(env, Error::GoalMismatch);
+    }
+    let mut previous = 0u32;
+    for cumulative in milestone_bps(goal, milestones).iter() {
+        if cumulative == previous {
+            panic_with_error!(env, Error::InvalidMilestones);
+        }
+        previous = cumulative;
+    }
+}
+
+/// Panic with `Error::InvalidToken` unless `token` answers `decimals()` and
+/// `name()` like a token contract.
+fn require_token_contract(env: &Env, token: &Address) {
+    let client = token::Client::new(env, token);
+    if !matches!(client.try_decimals(), Ok(Ok(_))) || !matches!(client.try_name(), Ok(Ok(_))) {
+        panic_with_error!(env, Error::InvalidToken);
+    }
+}
+
+/// Persist a freshly registered project and emit `created`.
+///
+/// Milestones are stored alongside a disbursement schedule with one gated
+/// tranche per milestone, so they release through the schedule machinery.
+fn store_new_project(env: &Env, project: Project, milestones: &Vec<Milestone>) -> Project {
+    save_project(env, &project);
+    if !milestones.is_empty() {
+        storage::set_milestones(env, project.id, milestones);
+        storage::set_schedule(
+            env,
+            project.id,
+            &milestone_schedule(project.goal, milestones),
+        );
+    }
+    storage::update_creator_record(env, &project.creator, |r| r.projects_created += 1);
+    storage::index_creator_project(env, &project.creator, project.id);
+
+    // Standardized event emission (using the first token as a reference for the created event)
+    if let Some(token) = project.accepted_tokens.get(0) {
+        events::emit_project_created(
+            env,
+            project.id,
+            project.creator.clone(),
+            token,
+            project.goal,
+        );
+    }
+
+    project
+}
+
+/// Pay `keeper` the configured reward for a crank call on `project_id` that
+/// did work. Rewards come only from the keeper pool, never from escrow, and
+/// are capped by what the pool holds. Each crank can only do work once per
+/// project, which bounds how often a single project can be farmed.
+fn pay_keeper(env: &Env, keeper: &Address, project_id: u64) -> i128 {
+    let config = storage::get_protocol_config(env);
+    let token = match config.keeper_reward_token {
+        Some(token) if config.keeper_reward > 0 => token,
+        _ => return 0,
+    };
+    let pool = storage::get_keeper_pool(env, &token);
+    let amount = config.keeper_reward.min(pool);
+    if amount <= 0 {
+        return 0;
+    }
+
+    storage::set_keeper_pool(env, &token, pool - amount);
+    token::Client::new(env, &token).transfer(&env.current_contract_address(), keeper, &amount);
+    events::emit_escrow_transfer(
+        env,
+        token.clone(),
+        env.current_contract_address(),
+        keeper.clone(),
+        amount,
+    );
+    events::emit_keeper_rewarded(env, project_id, keeper.clone(), token, amount);
+    amount
+}
+
+/// Reimburse `donor` for claiming a refund of `refunded` in `token` from
+/// `project_id`, if the claim is small enough to be sponsored. Paid from the
+/// sponsorship pool only, and never more than the period's remaining budget.
+/// Returns the amount paid.
+fn sponsor_refund(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    token: &Address,
+    refunded: i128,
+) -> i128 {
+    let config = storage::get_protocol_config(env);
+    if config.sponsor_threshold == 0 || refunded > config.sponsor_threshold {
+        return 0;
+    }
+    let period = env.ledger().timestamp() / config.sponsor_period;
+    let spent = storage::get_sponsor_spent(env, token, period);
+    let pool = storage::get_sponsor_pool(env, token);
+    let amount = config
+        .sponsor_amount
+        .min(config.sponsor_budget - spent)
+        .min(pool);
+    if amount <= 0 {
+        return 0;
+    }
+
+    storage::set_sponsor_pool(env, token, pool - amount);
+    storage::set_sponsor_spent(env, token, period, spent + amount);
+    token::Client::new(env, token).transfer(&env.current_contract_address(), donor, &amount);
+    events::emit_escrow_transfer(
+        env,
+        token.clone(),
+        env.current_contract_address(),
+        donor.clone(),
+        amount,
+    );
+    events::emit_refund_sponsored(env, project_id, donor.clone(), token.clone(), amount);
+    amount
+}
+
+/// Refresh the dead-man switch heartbeat, voiding any announced claim.
+/// Callers must have checked that the SuperAdmin signed.
+fn record_heartbeat(env: &Env) {
+    if let Some(mut switch) = storage::get_dead_man(env) {
+        switch.last_heartbeat = env.ledger().timestamp();
+        switch.claim_eta = None;
+        storage::set_dead_man(env, Some(&switch));
+    }
+}
+
+/// Return the dead-man switch if `successor` is its successor; otherwise
+/// panic with `Error::NotAuthorized`.
+fn require_successor(env: &Env, successor: &Address) -> DeadManSwitch {
+    match storage::get_dead_man(env) {
+        Some(switch) if switch.successor == *successor => switch,
+        _ => panic_with_error!(env, Error::NotAuthorized),
+    }
+}
+
+/// Panic with `Error::AddressFrozen` if `address` is under a compliance freeze.
+fn require_not_frozen(env: &Env, address: &Address) {
+    if storage::get_freeze(env, address).is_some() {
+        panic_with_error!(env, Error::AddressFrozen);
+    }
+}
+
+/// Panic with `Error::ProtocolSunset` once wind-down mode has begun.
+fn require_not_sunset(env: &Env) {
+    if storage::is_sunset(env) {
+        panic_with_error!(env, Error::ProtocolSunset);
+    }
+}
+
+/// Panic with `Error::ProtocolPaused` while the global pause is on or a
+/// maintenance window is running.
+fn require_protocol_active(env: &Env) {
+    if protocol_paused(env) {
+        panic_with_error!(env, Error::ProtocolPaused);
+    }
+}
+
+/// Return `true` while the protocol is paused by an admin or by an active
+/// maintenance window.
+fn protocol_paused(env: &Env) -> bool {
+    if storage::is_paused(env) {
+        return true;
+    }
+    match storage::get_maintenance(env) {
+        Some(window) => window.is_active(env.ledger().timestamp()),
+        None => false,
+    }
+}
+
+/// Panic if either the protocol or this project is paused.
+fn require_not_paused(env: &Env, state: &ProjectState) {
+    require_protocol_active(env);
+    if state.has_flag(ProjectState::FLAG_PAUSED) {
+        panic_with_error!(env, Error::ProjectPaused);
+    }
+}
+
+#[contractimpl]
+impl CrowdfundingInterface for PifpProtocol {
+    /// Alias for `deposit`. The interface returns nothing so that any
+    /// crowdfunding contract can implement it, so the `DepositReceipt` is
+    /// dropped; call `deposit` directly to get the fee and premium split.
+    fn fund(env: Env, funder: Address, project_id: u64, token: Address, amount: i128) {
+        Self::deposit(env, project_id, funder, token, amount, false);
+    }
+
+    fn status(env: Env, project_id: u64) -> ProjectStatus {
+        storage::load_project_state(&env, project_id).status
+    }
+
+    /// `raised` follows the project's [`GoalUnit`]: everything credited to
+    /// the first token's escrow, or every token's credits valued at
+    /// current feed prices. Releases do not reduce it.
+    fn goal_progress(env: Env, project_id: u64) -> GoalProgress {
+        let config = storage::load_project_config(&env, project_id);
+        GoalProgress {
+            raised: goal_raised(&env, &config),
+            goal: config.goal,
+        }
+    }
+}
+
+/// Amount raised towards `config.goal`, in the goal's unit: the escrow
+/// inflow, net of premiums and fees, so that releases and rent do not
+/// count against it.
+///
+/// For reference-unit goals, tokens the feed cannot price count as zero.
+fn goal_raised(env: &Env, config: &ProjectConfig) -> i128 {
+    match config.goal_unit {
+        GoalUnit::FirstToken => match config.accepted_tokens.get(0) {
+            Some(token) => storage::get_token_flows(env, config.id, &token).inflow,
+            None => 0,
+        },
+        GoalUnit::Reference => {
+            let feed = match storage::get_protocol_config(env).price_feed {
+                Some(feed) => feed,
+                None => return 0,
+            };
+            let mut raised = 0i128;
+            for token in config.accepted_tokens.iter() {
+                let inflow = storage::get_token_flows(env, config.id, &token).inflow;
+                if inflow > 0 {
+                    raised += price::reference_value(env, &feed, &token, inflow).unwrap_or(0);
+                }
+            }
+            raised
+        }
+    }
+}
+
+/// Largest deposit of `token` that does not take the project past its goal,
+/// given that `deducted_bps` of it goes to the insurance pool and treasury.
+///
+/// Unbounded for tokens the goal is not denominated in.
+fn goal_shortfall(env: &Env, config: &ProjectConfig, token: &Address, deducted_bps: u32) -> i128 {
+    if config.goal_unit != GoalUnit::FirstToken
+        || config.accepted_tokens.get(0).as_ref() != Some(token)
+    {
+        return i128::MAX;
+    }
+    let missing = config.goal - storage::get_token_balance(env, config.id, token);
+    if missing <= 0 {
+        return 0;
+    }
+    // Round up so that the escrowed remainder still covers the goal.
+    let net_bps = (10_000 - deducted_bps) as i128;
+    (missing * 10_000 + net_bps - 1) / net_bps
+}
+
+/// Assert that `signers` control the project and have all authorized.
+///
+/// Without a configured owner set the creator alone controls the project;
+/// otherwise at least `threshold` distinct owners must be among `signers`.
+/// Repeated signers are counted once.
+fn require_project_control(env: &Env, config: &ProjectConfig, signers: &Vec<Address>) {
+    if !has_project_control(env, config, signers) {
+        panic_with_error!(env, Error::NotAuthorized);
+    }
+}
+
+/// Require every distinct signer's authorization and return `true` if
+/// together they control the project; see [`require_project_control`].
+fn has_project_control(env: &Env, config: &ProjectConfig, signers: &Vec<Address>) -> bool {
+    let owners = storage::get_project_owners(env, config.id);
+    let mut approvals = 0u32;
+    for (i, signer) in signers.iter().enumerate() {
+        if signers.first_index_of(&signer) != Some(i as u32) {
+            continue;
+        }
+        signer.require_auth();
+        let counts = match &owners {
+            None => signer == config.creator,
+            Some(set) => set.owners.contains(&signer),
+        };
+        if counts {
+            approvals += 1;
+        }
+    }
+    approvals >= owners.map(|set| set.threshold).unwrap_or(1)
+}
+
+/// Tag the part of a deposit that still fits under the donor's match cap
+/// as eligible for matching, returning the amount tagged. Only deposits in
+/// the project's first accepted token count towards the cap.
+fn tag_match_eligible(
+    env: &Env,
+    config: &ProjectConfig,
+    donor: &Address,
+    token: &Address,
+    amount: i128,
+) -> i128 {
+    let cap = storage::get_match_cap(env, config.id);
+    if cap == 0 || config.accepted_tokens.get(0).as_ref() != Some(token) {
+        return 0;
+    }
+    let tagged = storage::get_match_eligible(env, config.id, donor);
+    let eligible = amount.min(cap - tagged);
+    if eligible <= 0 {
+        return 0;
+    }
+    storage::set_match_eligible(env, config.id, donor, tagged + eligible);
+    events::emit_match_eligible(env, config.id, donor.clone(), eligible);
+    eligible
+}
+
+/// Fail with `NotAuthorized` unless `signers` control the project (see
+/// [`require_project_control`]) or are a single `SuperAdmin` or `Admin`.
+/// Returns the address acting: the creator, or the admin, whose call is
+/// recorded in the admin log under `action`.
+fn require_control_or_admin(
+    env: &Env,
+    config: &ProjectConfig,
+    signers: &Vec<Address>,
+    action: Symbol,
+) -> Address {
+    if has_project_control(env, config, signers) {
+        return config.creator.clone();
+    }
+    require_admin_signer(env, config, signers, action)
+}
+
+/// Fail with `NotAuthorized` unless `signers`, already authorized by
+/// [`has_project_control`], are a single `SuperAdmin` or `Admin`, and record
+/// their action on the project in the admin log. Returns the admin.
+fn require_admin_signer(
+    env: &Env,
+    config: &ProjectConfig,
+    signers: &Vec<Address>,
+    action: Symbol,
+) -> Address {
+    if signers.len() != 1 {
+        panic_with_error!(env, Error::NotAuthorized);
+    }
+    let admin = signers.get_unchecked(0);
+    rbac::require_admin_or_above(env, &admin);
+    log_admin(env, &admin, action, None, Some(config.id));
+    admin
+}
+
+/// Return `true` unless the project's donor allowlist is on and `donor` is
+/// not on it.
+fn donor_allowed(env: &Env, project_id: u64, state: &ProjectState, donor: &Address) -> bool {
+    !state.has_flag(ProjectState::FLAG_DONOR_ALLOWLIST)
+        || storage::is_allowed_donor(env, project_id, donor)
+}
+
+/// Match a deposit of `amount` in `token` from every pool of the project
+/// holding that token, oldest first. Each match is escrowed as the pool
+/// sponsor's contribution and emitted in a `matched` event.
+fn match_from_pools(
+    env: &Env,
+    config: &ProjectConfig,
+    donor: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    for pool_id in storage::get_match_pools(env, config.id).iter() {
+        let mut pool = match storage::get_match_pool(env, pool_id) {
+            Some(pool) if pool.token == *token => pool,
+            _ => continue,
+        };
+        let matched = (amount * pool.ratio_bps as i128 / 10_000)
+            .min(pool.cap - pool.matched)
+            .min(pool.balance);
+        if matched <= 0 {
+            continue;
+        }
+        pool.balance -= matched;
+        pool.matched += matched;
+        storage::set_match_pool(env, pool_id, &pool);
+        storage::add_to_token_balance(env, config.id, token, matched);
+        record_inflow(env, config.id, token, matched);
+        storage::add_contribution(env, config.id, &pool.sponsor, token, matched);
+        events::emit_donation_matched(env, config.id, pool_id, donor.clone(), matched);
+    }
+}
+
+/// Append a movement of `amount` to `donor`'s contribution ledger, stamped
+/// with the current ledger time. Zero amounts are not recorded.
+fn record_ledger(
+    env: &Env,
+    donor: &Address,
+    project_id: u64,
+    token: &Address,
+    kind: LedgerKind,
+    amount: i128,
+) {
+    if amount == 0 {
+        return;
+    }
+    storage::add_ledger_entry(
+        env,
+        donor,
+        LedgerEntry {
+            at: env.ledger().timestamp(),
+            project_id,
+            token: token.clone(),
+            kind,
+            amount,
+        },
+    );
+}
+
+/// Credit `amount` of `token` to the inflow ledger of `project_id`.
+fn record_inflow(env: &Env, project_id: u64, token: &Address, amount: i128) {
+    let mut flows = storage::get_token_flows(env, project_id, token);
+    flows.inflow += amount;
+    flows.updated_ledger = env.ledger().sequence();
+    storage::set_token_flows(env, project_id, token, &flows);
+}
+
+/// Debit `amount` of `token` from the escrow of `project_id`.
+///
+/// Every path moving project funds out goes through here before touching
+/// the balance, so a bug elsewhere can never pay out more than the project
+/// took in. Panics with `OutflowExceedsInflow` if it would.
+fn record_outflow(env: &Env, project_id: u64, token: &Address, amount: i128) {
+    let mut flows = storage::get_token_flows(env, project_id, token);
+    flows.outflow += amount;
+    if flows.outflow > flows.inflow {
+        panic_with_error!(env, Error::OutflowExceedsInflow);
+    }
+    flows.updated_ledger = env.ledger().sequence();
+    storage::set_token_flows(env, project_id, token, &flows);
+}
+
+/// Narrow a currency-locked project's accepted tokens to `token`, dropping
+/// the (still empty) balance entries of the others.
+fn lock_currency(env: &Env, config: &mut ProjectConfig, token: &Address) {
+    for other in config.accepted_tokens.iter() {
+        if other != *token {
+            storage::remove_token_balance(env, config.id, &other);
+        }
+    }
+    config.accepted_tokens = Vec::from_array(env, [token.clone()]);
+    storage::save_project_config(env, config);
+    events::emit_currency_locked(env, config.id, token.clone());
+}
+
+/// Assert that `signers` control `project_id` (see
+/// [`require_project_control`]) and that the project has not yet received
+/// any deposits or attestations. Returns the project config.
+///
+/// Guards per-project settings that donors rely on being fixed once funding
+/// starts.
+fn require_unfunded_control(env: &Env, signers: &Vec<Address>, project_id: u64) -> ProjectConfig {
+    let (config, _) = load_project_pair(env, project_id);
+    require_project_control(env, &config, signers);
+    for token in config.accepted_tokens.iter() {
+        if storage::get_token_balance(env, project_id, &token) != 0 {
+            panic_with_error!(env, Error::ProjectAlreadyFunded);
+        }
+    }
+    if !storage::get_attestations(env, project_id).is_empty() {
+        panic_with_error!(env, Error::ProjectAlreadyFunded);
+    }
+    config
+}
+
+/// Act on a project whose attestation quorum has just been met.
+///
+/// One-shot projects complete and scheduled projects release their next
+/// gated tranche, completing with the last; either way the release first
+/// goes to a donor vote if the project opted in. Ongoing projects start a
+/// new cadence period instead.
+fn settle_quorum(
+    env: &Env,
+    config: &ProjectConfig,
+    state: &mut ProjectState,
+    oracle: Address,
+    proof_hash: BytesN<32>,
+) {
+    if let Some(cadence) = storage::get_cadence(env, config.id) {
+        return renew_cadence(env, config, state, cadence, oracle);
+    }
+    let released_bps = match storage::get_schedule(env, config.id) {
+        Some(schedule) => schedule.release_next_gate(state.released_bps),
+        None => 10_000,
+    };
+    match storage::get_vote_config(env, config.id) {
+        Some(vote_config) => open_donor_vote(
+            env,
+            config,
+            state,
+            vote_config,
+            released_bps,
+            oracle,
+            proof_hash,
+        ),
+        None => challenge_or_release(env, config, state, released_bps, oracle, proof_hash),
+    }
+}
+
+/// Hold a release up to `released_bps` for `ProtocolConfig::challenge_period`
+/// so auditors can dispute it, or release it straight away without one.
+fn challenge_or_release(
+    env: &Env,
+    config: &ProjectConfig,
+    state: &mut ProjectState,
+    released_bps: u32,
+    oracle: Address,
+    proof_hash: BytesN<32>,
+) {
+    let period = storage::get_protocol_config(env).challenge_period;
+    if period == 0 {
+        return release_or_queue(env, config, state, released_bps, oracle, proof_hash);
+    }
+    let ends_at = env.ledger().timestamp().saturating_add(period);
+    storage::set_challenge(
+        env,
+        config.id,
+        &Challenge {
+            oracle,
+            proof_hash,
+            released_bps,
+            ends_at,
+            disputed_by: None,
+            evidence_hash: None,
+        },
+    );
+    state.set_flag(ProjectState::FLAG_RELEASE_PENDING, true);
+    save_project_state(env, config.id, state);
+    events::emit_challenge_opened(env, config.id, released_bps, ends_at);
+}
+
+/// Release a verified project's escrow up to `released_bps` — completing it
+/// at `10_000` — or, if the release throttle has no room, queue the release
+/// for `process_release_queue` and hold the project as release-pending.
+///
+/// Releases queue behind any already waiting, so they apply in order.
+fn release_or_queue(
+    env: &Env,
+    config: &ProjectConfig,
+    state: &mut ProjectState,
+    released_bps: u32,
+    oracle: Address,
+    proof_hash: BytesN<32>,
+) {
+    let mut queue = storage::get_release_queue(env);
+    if queue.is_empty() && reserve_outflow(env, config, released_bps) {
+        return apply_release(env, config, state, released_bps, oracle, proof_hash);
+    }
+    queue.push_back(QueuedRelease {
+        project_id: config.id,
+        released_bps,
+        oracle,
+        proof_hash,
+    });
+    storage::set_release_queue(env, &queue);
+    state.set_flag(ProjectState::FLAG_RELEASE_PENDING, true);
+    save_project_state(env, config.id, state);
+    events::emit_release_queued(env, config.id, released_bps);
+}
+
+/// Release a tranche, or complete the project at `10_000`.
+fn apply_release(
+    env: &Env,
+    config: &ProjectConfig,
+    state: &mut ProjectState,
+    released_bps: u32,
+    oracle: Address,
+    proof_hash: BytesN<32>,
+) {
+    let milestones = storage::get_milestones(env, config.id);
+    if let Some(index) = current_milestone(config.goal, &milestones, state.released_bps) {
+        events::emit_milestone_released(env, config.id, index);
+    }
+    if released_bps < 10_000 {
+        release_tranche(env, config, state, released_bps);
+    } else {
+        complete_project(env, config, state, oracle, proof_hash);
+    }
+}
+
+/// Count a release up to `released_bps` against the current throttle window
+/// of every accepted token. Returns `false`, recording nothing, if any token
+/// would go past `ProtocolConfig::release_cap`.
+///
+/// A release larger than the cap on its own is still admitted into an
+/// otherwise unused window, so it cannot be starved forever.
+fn reserve_outflow(env: &Env, config: &ProjectConfig, released_bps: u32) -> bool {
+    let protocol = storage::get_protocol_config(env);
+    if protocol.release_cap == 0 {
+        return true;
+    }
+    let window = env.ledger().timestamp() / protocol.release_window;
+    let mut outflows = Vec::new(env);
+    for token in config.accepted_tokens.iter() {
+        let balance = storage::get_token_balance(env, config.id, &token);
+        let paid = storage::get_token_flows(env, config.id, &token).released;
+        let amount = release_amount(balance, paid, released_bps);
+        let window_total = storage::get_release_outflow(env, &token, window);
+        if window_total > 0 && window_total + amount > protocol.release_cap {
+            return false;
+        }
+        if amount > 0 {
+            outflows.push_back((token, window_total + amount));
+        }
+    }
+    for (token, total) in outflows.iter() {
+        storage::set_release_outflow(env, &token, window, total);
+    }
+    true
+}
+
+/// Hold a verified release up to `released_bps` for a donor vote.
+///
+/// Voting weight is denominated in the first accepted token, like the goal,
+/// and totals every donor's gross contribution (see `donor_vote_weight`).
+fn open_donor_vote(
+    env: &Env,
+    config: &ProjectConfig,
+    state: &mut ProjectState,
+    vote_config: DonorVoteConfig,
+    released_bps: u32,
+    oracle: Address,
+    proof_hash: BytesN<32>,
+) {
+    let ends_at = env.ledger().timestamp().saturating_add(vote_config.window);
+    let total_weight = match config.accepted_tokens.get(0) {
+        Some(token) => donor_vote_weight(env, config.id, &token),
+        None => 0,
+    };
+    storage::set_donor_vote(
+        env,
+        config.id,
+        &DonorVote {
+            oracle,
+            proof_hash,
+            released_bps,
+            ends_at,
+            objections: 0,
+            total_weight,
+        },
+    );
+    state.set_flag(ProjectState::FLAG_RELEASE_PENDING, true);
+    save_project_state(env, config.id, state);
+    events::emit_vote_opened(env, config.id, ends_at, total_weight);
+}
+
+/// Total voting weight of `project_id`'s donors in `token`: the sum of
+/// their gross contributions, the same unit each donor votes with. Fees,
+/// releases and rent do not shrink it.
+fn donor_vote_weight(env: &Env, project_id: u64, token: &Address) -> i128 {
+    let mut total = 0i128;
+    for (donor, donated) in storage::get_project_donors(env, project_id).iter() {
+        if donated == *token {
+            total += storage::get_contribution(env, project_id, &donor, token);
+        }
+    }
+    total
+}
+
+/// Panic with `Error::NotAuthorized` unless `oracle` may attest `project_id`:
+/// either no oracles are assigned to it, or `oracle` is one of them.
+fn require_assigned_oracle(env: &Env, project_id: u64, oracle: &Address) {
+    let assigned = storage::get_project_oracles(env, project_id);
+    if !assigned.is_empty() && !assigned.contains(oracle) {
+        panic_with_error!(env, Error::NotAuthorized);
+    }
+}
+
+/// Panic with `DependencyNotMet` unless every project `project_id` depends
+/// on is `Completed`.
+fn require_dependencies_completed(env: &Env, project_id: u64) {
+    for dep in storage::get_dependencies(env, project_id).iter() {
+        if storage::load_project_state(env, dep).status != ProjectStatus::Completed {
+            panic_with_error!(env, Error::DependencyNotMet);
+        }
+    }
+}
+
+/// Return `true` if any of `deps`, or anything they transitively depend on,
+/// is `project_id`.
+fn depends_on(env: &Env, deps: &Vec<u64>, project_id: u64) -> bool {
+    let mut pending = deps.clone();
+    let mut seen: Vec<u64> = Vec::new(env);
+    while let Some(dep) = pending.pop_back() {
+        if dep == project_id {
+            return true;
+        }
+        if seen.contains(dep) {
+            continue;
+        }
+        seen.push_back(dep);
+        pending.append(&storage::get_dependencies(env, dep));
+    }
+    false
+}
+
+/// Cumulative share of the escrow released once each of `milestones` is
+/// verified, in basis points.
+fn milestone_bps(goal: i128, milestones: &Vec<Milestone>) -> Vec<u32> {
+    let mut bps = Vec::new(milestones.env());
+    let mut cumulative = 0i128;
+    for milestone in milestones.iter() {
+        cumulative += milestone.amount;
+        bps.push_back((cumulative * 10_000 / goal) as u32);
+    }
+    bps
+}
+
+/// Schedule releasing each of `milestones` as one gated tranche.
+fn milestone_schedule(goal: i128, milestones: &Vec<Milestone>) -> DisbursementTemplate {
+    let mut tranches = Vec::new(milestones.env());
+    let mut previous = 0u32;
+    for cumulative in milestone_bps(goal, milestones).iter() {
+        tranches.push_back(Tranche {
+            bps: cumulative - previous,
+            gated: true,
+        });
+        previous = cumulative;
+    }
+    DisbursementTemplate { tranches }
+}
+
+/// Panic unless `project_id` may replace its milestones with `milestones`
+/// now: it must be `Funding` or `Active`, have milestones, have no release
+/// pending, and keep every released milestone as it was.
+fn require_amendable(env: &Env, project_id: u64, milestones: &Vec<Milestone>) {
+    let (config, state) = load_project_pair(env, project_id);
+    match state.status {
+        ProjectStatus::Funding | ProjectStatus::Active => {}
+        _ => panic_with_error!(env, Error::InvalidStatusTransition),
+    }
+    require_no_pending_release(env, &state);
+    let current = storage::get_milestones(env, project_id);
+    if current.is_empty() {
+        panic_with_error!(env, Error::MilestoneNotFound);
+    }
+    validate_milestones(env, config.goal, milestones);
+    let released =
+        current_milestone(config.goal, &current, state.released_bps).unwrap_or(current.len());
+    for i in 0..released {
+        if milestones.get(i) != current.get(i) {
+            panic_with_error!(env, Error::MilestoneAlreadyReleased);
+        }
+    }
+}
+
+/// Replace the milestones of `project_id` with the amendment's, rebuilding
+/// its schedule and discarding attestations made against the old ones.
+fn apply_amendment(env: &Env, project_id: u64, amendment: &MilestoneAmendment) {
+    require_amendable(env, project_id, &amendment.milestones);
+    let config = storage::load_project_config(env, project_id);
+    storage::remove_amendment(env, project_id);
+    storage::set_milestones(env, project_id, &amendment.milestones);
+    storage::set_schedule(
+        env,
+        project_id,
+        &milestone_schedule(config.goal, &amendment.milestones),
+    );
+    storage::set_attestations(env, project_id, &Vec::new(env));
+}
+
+/// Index of the milestone due for verification once `released_bps` has been
+/// released, or `None` if the project has no milestones left.
+fn current_milestone(goal: i128, milestones: &Vec<Milestone>, released_bps: u32) -> Option<u32> {
+    milestone_bps(goal, milestones)
+        .iter()
+        .position(|bps| bps > released_bps)
+        .map(|i| i as u32)
+}
+
+/// Proof oracles must submit to verify `config` next: its current
+/// milestone's, or the project's own if it has no milestones.
+fn expected_proof(env: &Env, config: &ProjectConfig, state: &ProjectState) -> BytesN<32> {
+    let milestones = storage::get_milestones(env, config.id);
+    match current_milestone(config.goal, &milestones, state.released_bps) {
+        Some(index) => milestones.get_unchecked(index).proof_hash,
+        None => config.proof_hash.clone(),
+    }
+}
+
+/// Sequential project IDs from `start`, at most `limit` (capped at
+/// `PROJECT_PAGE`) and never past the project count.
+fn project_page(env: &Env, start: u64, limit: u32) -> core::ops::Range<u64> {
+    let limit = limit.min(storage::PROJECT_PAGE) as u64;
+    let end = start
+        .saturating_add(limit)
+        .min(storage::get_project_count(env));
+    start..end.max(start)
+}
+
+/// Record `oracle`'s attestation of `proof_hash` for `project_id`, settling
+/// the project once quorum is met. Returns `true` if this attestation met it.
+///
+/// Shared by `verify_and_release` and `approve_proof`; the caller has
+/// checked `oracle`'s authorization and holds the reentrancy guard.
+fn attest(env: &Env, oracle: Address, project_id: u64, proof_hash: BytesN<32>) -> bool {
+    // RBAC gate: caller must hold the Oracle role.
+    rbac::require_oracle(env, &oracle);
+
+    // Optimised dual-read helper
+    let (config, mut state) = load_project_pair(env, project_id);
+    require_not_paused(env, &state);
+    require_no_pending_release(env, &state);
+    require_assigned_oracle(env, project_id, &oracle);
+    require_dependencies_completed(env, project_id);
+
+    // Ensure the project is in a verifiable state.
+    if let Err(error) = fsm::transition(&state.status, StatusEvent::Verify) {
+        panic_with_error!(env, error);
+    }
+
+    // Project verifier if registered, otherwise hash equality.
+    let expected = expected_proof(env, &config, &state);
+    if !verifier::verify_proof(env, &config, &expected, &proof_hash) {
+        panic!("proof verification failed: hash mismatch");
+    }
+
+    // Record this oracle's attestation; release only once quorum is met.
+    let mut attestations = storage::get_attestations(env, project_id);
+    if attestations.contains(&oracle) {
+        panic_with_error!(env, Error::AlreadyAttested);
+    }
+    attestations.push_back(oracle.clone());
+    storage::set_attestations(env, project_id, &attestations);
+    storage::index_oracle_project(env, &oracle, project_id);
+    events::emit_project_attested(
+        env,
+        project_id,
+        oracle.clone(),
+        attestations.len(),
+        config.quorum,
+    );
+    if attestations.len() < config.quorum {
+        return false;
+    }
+
+    settle_quorum(env, &config, &mut state, oracle, proof_hash);
+    true
+}
+
+/// Replace the contract's Wasm on behalf of `by`, once storage is at this
+/// build's schema.
+fn apply_upgrade(env: &Env, by: &Address, new_wasm_hash: BytesN<32>) {
+    if stored_schema(env) != ContractVersion::SCHEMA {
+        panic_with_error!(env, Error::VersionMismatch);
+    }
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+    log_admin(env, by, symbol_short!("upgraded"), None, None);
+    events::emit_upgraded(env, by.clone(), new_wasm_hash);
+}
+
+/// Panic with `TimelockNotElapsed` if changes must go through the action
+/// timelock.
+fn require_no_action_delay(env: &Env) {
+    if storage::get_protocol_config(env).action_delay > 0 {
+        panic_with_error!(env, Error::TimelockNotElapsed);
+    }
+}
+
+/// Panic with `InvalidConfig` if `action` could never be applied.
+fn validate_action(env: &Env, action: &TimelockedAction) {
+    let valid = match action {
+        TimelockedAction::SetFees(deposit_bps, release_bps) => {
+            *deposit_bps <= ProtocolConfig::MAX_FEE_BPS
+                && *release_bps <= ProtocolConfig::MAX_FEE_BPS
+        }
+        TimelockedAction::Upgrade(_)
+        | TimelockedAction::SetDelay(_)
+        | TimelockedAction::RevokeRole(_) => true,
+        TimelockedAction::EmergencyWithdraw(_, _, amount) => *amount > 0,
+        TimelockedAction::GrantRole(_, role) => *role != Role::SuperAdmin,
+        TimelockedAction::SetCouncil(members, threshold) => {
+            rbac::validate_council(
+                env,
+                &Council {
+                    members: members.clone(),
+                    threshold: *threshold,
+                },
+            );
+            true
+        }
+    };
+    if !valid {
+        panic_with_error!(env, Error::InvalidConfig);
+    }
+}
+
+/// Amount of `token` the contract holds beyond every project escrow and
+/// protocol pool, e.g. from direct transfers.
+fn untracked_balance(env: &Env, token: &Address) -> i128 {
+    token::Client::new(env, token).balance(&env.current_contract_address())
+        - storage::get_escrow_total(env, token)
+        - storage::get_insurance_pool(env, token)
+        - storage::get_keeper_pool(env, token)
+        - storage::get_dust(env, token)
+        - storage::get_sponsor_pool(env, token)
+        - storage::get_commons_pool(env, token)
+        - storage::get_fee_vault(env, token)
+        - storage::get_rent_fund_total(env, token)
+        - storage::get_claimable_total(env, token)
+        - storage::get_match_pool_total(env, token)
+}
+
+/// Storage layout version recorded by the last `init` or `migrate`.
+fn stored_schema(env: &Env) -> u32 {
+    storage::get_stored_version(env).map_or(1, |v| v.schema)
+}
+
+/// Return `true` if the project belongs in the `kind` operational queue.
+fn needs_action(
+    env: &Env,
+    kind: &ActionableKind,
+    config: &ProjectConfig,
+    state: &ProjectState,
+) -> bool {
+    if is_settled(state) {
+        return false;
+    }
+    let pending = state.has_flag(ProjectState::FLAG_RELEASE_PENDING);
+    let contested = state.has_flag(ProjectState::FLAG_CONTESTED);
+    match kind {
+        ActionableKind::PendingApproval => pending && !contested,
+        ActionableKind::AwaitingVerification => {
+            !pending
+                && (state.status == ProjectStatus::Active
+                    || goal_raised(env, config) >= config.goal)
+        }
+        ActionableKind::PastDeadline => env.ledger().timestamp() > config.deadline,
+        ActionableKind::DisputeOpen => contested,
+    }
+}
+
+/// Expire a project that missed its deadline and record when it settled.
+fn mark_expired(env: &Env, config: &ProjectConfig, state: &mut ProjectState) {
+    fsm::advance(env, state, StatusEvent::Expire);
+    save_project_state(env, config.id, state);
+    storage::set_settled_at(env, config.id, env.ledger().timestamp());
+    storage::update_creator_record(env, &config.creator, |r| r.projects_expired += 1);
+    events::emit_project_expired(env, config.id);
+    route_grants(env, config);
+}
+
+/// Pay out the grants made to an expired project, each at the refund rate,
+/// to the creator or the commons pool as its donor chose.
+fn route_grants(env: &Env, config: &ProjectConfig) {
+    let contract = env.current_contract_address();
+    for (donor, token) in storage::get_project_donors(env, config.id).iter() {
+        let grant = match storage::get_grant(env, config.id, &donor, &token) {
+            Some(grant) => grant,
+            None => continue,
+        };
+        let (escrow, raised) = refund_basis(env, config.id, &token);
+        let amount = escrow * grant.amount / raised;
+        if amount <= 0 {
+            continue;
+        }
+
+        record_outflow(env, config.id, &token, amount);
+        let balance = storage::get_token_balance(env, config.id, &token);
+        storage::set_token_balance(env, config.id, &token, balance - amount);
+        match grant.route {
+            GrantRoute::Creator => {
+                let mut flows = storage::get_token_flows(env, config.id, &token);
+                flows.released += amount;
+                storage::set_token_flows(env, config.id, &token, &flows);
+                token::Client::new(env, &token).transfer(&contract, &config.creator, &amount);
+                events::emit_escrow_transfer(
+                    env,
+                    token.clone(),
+                    contract.clone(),
+                    config.creator.clone(),
+                    amount,
+                );
+                storage::add_creator_released(env, &config.creator, &token, amount);
+            }
+            GrantRoute::Commons => storage::add_to_commons_pool(env, &token, amount),
+        }
+        events::emit_grant_routed(env, config.id, donor, token, amount, grant.route);
+    }
+}
+
+/// Escrow and total contributions refunds of `token` from `project_id` are
+/// shared out against, fixed by the first claim so that every donor gets
+/// the same rate.
+fn refund_basis(env: &Env, project_id: u64, token: &Address) -> (i128, i128) {
+    if let Some(basis) = storage::get_refund_basis(env, project_id, token) {
+        return basis;
+    }
+    let mut raised = 0i128;
+    for (donor, donated) in storage::get_project_donors(env, project_id).iter() {
+        if donated == *token {
+            raised += storage::get_contribution(env, project_id, &donor, token);
+        }
+    }
+    let basis = (storage::get_token_balance(env, project_id, token), raised);
+    storage::set_refund_basis(env, project_id, token, basis);
+    basis
+}
+
+/// Return `true` once a project is `Completed` or `Expired`.
+fn is_settled(state: &ProjectState) -> bool {
+    matches!(
+        state.status,
+        ProjectStatus::Completed | ProjectStatus::Expired
+    )
+}
+
+/// Panic with `Error::ReleasePending` while a donor vote, challenge window or
+/// the release throttle holds a verified release.
+fn require_no_pending_release(env: &Env, state: &ProjectState) {
+    if state.has_flag(ProjectState::FLAG_RELEASE_PENDING) {
+        panic_with_error!(env, Error::ReleasePending);
+    }
+}
+
+/// Amount of a token to pay the creator so that `released_bps` of everything
+/// escrowed — the current `balance` plus the `paid` already released — has
+/// been released. At `10_000` this is the whole balance, so rounding never
+/// strands funds.
+fn release_amount(balance: i128, paid: i128, released_bps: u32) -> i128 {
+    if released_bps >= 10_000 {
+        return balance;
+    }
+    ((balance + paid) * released_bps as i128 / 10_000 - paid).clamp(0, balance)
+}
+
+/// Transfer the creator their share of every accepted token up to
+/// `released_bps`, emitting `released` per token paid. The release fee, if
+/// charged, is held for the treasury out of each token's share.
+///
+/// Under `ProtocolConfig::pull_payments` the share is credited to the
+/// project's claimable balance for `withdraw` instead of transferred.
+fn pay_creator(env: &Env, config: &ProjectConfig, released_bps: u32) {
+    let contract = env.current_contract_address();
+    let protocol = storage::get_protocol_config(env);
+    let fee_bps = match protocol.treasury {
+        Some(_) => protocol.release_fee_bps,
+        None => 0,
+    };
+    for token in config.accepted_tokens.iter() {
+        let balance = storage::get_token_balance(env, config.id, &token);
+        let mut flows = storage::get_token_flows(env, config.id, &token);
+        let amount = release_amount(balance, flows.released, released_bps);
+        if amount <= 0 {
+            continue;
+        }
+
+        record_outflow(env, config.id, &token, amount);
+        flows = storage::get_token_flows(env, config.id, &token);
+        flows.released += amount;
+        storage::set_token_flows(env, config.id, &token, &flows);
+        if amount == balance {
+            storage::drain_token_balance(env, config.id, &token);
+        } else {
+            storage::set_token_balance(env, config.id, &token, balance - amount);
+        }
+
+        let fee = amount * fee_bps as i128 / 10_000;
+        if fee > 0 {
+            storage::add_to_fee_vault(env, &token, fee);
+            events::emit_release_fee_paid(env, config.id, token.clone(), fee);
+        }
+        let amount = amount - fee;
+        if protocol.pull_payments {
+            let claimable = storage::get_claimable(env, config.id, &token);
+            storage::set_claimable(env, config.id, &token, claimable + amount);
+        } else {
+            token::Client::new(env, &token).transfer(&contract, &config.creator, &amount);
+            events::emit_escrow_transfer(
+                env,
+                token.clone(),
+                contract.clone(),
+                config.creator.clone(),
+                amount,
+            );
+        }
+        storage::add_creator_released(env, &config.creator, &token, amount);
+        events::emit_funds_released(env, config.id, config.creator.clone(), token, amount);
+    }
+}
+
+/// Release a scheduled project's escrow up to `released_bps` and reset
+/// attestations for its next gate.
+fn release_tranche(env: &Env, config: &ProjectConfig, state: &mut ProjectState, released_bps: u32) {
+    pay_creator(env, config, released_bps);
+    storage::set_attestations(env, config.id, &Vec::new(env));
+    state.released_bps = released_bps;
+    fsm::advance(env, state, StatusEvent::Activate);
+    save_project_state(env, config.id, state);
+    events::emit_tranche_released(env, config.id, released_bps);
+}
+
+/// Start a new attestation period for an ongoing project, paying the
+/// creator its escrow if the stream was still active.
+///
+/// The attesting oracle set is cleared so the next period needs a fresh
+/// quorum.
+fn renew_cadence(
+    env: &Env,
+    config: &ProjectConfig,
+    state: &mut ProjectState,
+    mut cadence: OngoingCadence,
+    oracle: Address,
+) {
+    let now = env.ledger().timestamp();
+    let streaming = cadence.last_attested == 0 || cadence.is_active(now);
+    cadence.last_attested = now;
+    storage::set_cadence(env, config.id, &cadence);
+    storage::set_attestations(env, config.id, &Vec::new(env));
+    if streaming {
+        pay_creator(env, config, 10_000);
+    }
+
+    if state.status != ProjectStatus::Active {
+        fsm::advance(env, state, StatusEvent::Activate);
+        save_project_state(env, config.id, state);
+    }
+
+    events::emit_project_reattested(env, config.id, oracle, now.saturating_add(cadence.period));
+}
+
+/// Transition a verified project to `Completed` and emit the `verified` event.
+///
+/// Shared by every verification path once the project's quorum is met.
+fn complete_project(
+    env: &Env,
+    config: &ProjectConfig,
+    state: &mut ProjectState,
+    oracle: Address,
+    proof_hash: BytesN<32>,
+) {
+    pay_creator(env, config, 10_000);
+
+    // Transition to Completed — only write the state entry.
+    fsm::advance(env, state, StatusEvent::Verify);
+    state.released_bps = 10_000;
+    save_project_state(env, config.id, state);
+    storage::set_settled_at(env, config.id, env.ledger().timestamp());
+    storage::update_creator_record(env, &config.creator, |r| r.projects_completed += 1);
+
+    // Standardized event emission
+    events::emit_project_verified(env, config.id, oracle, proof_hash);
 }
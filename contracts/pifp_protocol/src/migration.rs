@@ -0,0 +1,24 @@
+//! # Escrow migration
+//!
+//! Lets the SuperAdmin move a live project to a successor deployment, for
+//! upgrades on deployments that have locked the in-place upgrade path.
+//!
+//! `migrate_escrow` transfers every escrowed token balance of the project
+//! to the successor configured in `ProtocolConfig::successor`, then calls
+//! [`EscrowIntakeInterface::intake_project`] on it with a
+//! [`MigrationRecord`] describing the project. The call is not isolated: if
+//! the successor rejects the intake, the whole migration reverts and the
+//! escrow stays here.
+
+use soroban_sdk::{contractclient, Env};
+
+use crate::types::MigrationRecord;
+
+/// Interface a successor contract implements to take over migrated
+/// projects.
+#[contractclient(name = "EscrowIntakeClient")]
+pub trait EscrowIntakeInterface {
+    /// Called after the balances listed in `record` have been transferred
+    /// to the successor.
+    fn intake_project(env: Env, record: MigrationRecord);
+}
@@ -0,0 +1,74 @@
+//! # Price feed
+//!
+//! Optional reference-unit valuation of deposits.
+//!
+//! When `ProtocolConfig::price_feed` is set, each deposit is valued through a
+//! [SEP-40](https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0040.md)
+//! price oracle at deposit time and the value is stored next to the raw
+//! amount. A feed that fails or has no price for the token never blocks a
+//! deposit; the deposit is simply left unvalued.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+/// SEP-40 asset identifier.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// SEP-40 price record.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Subset of the SEP-40 oracle interface used by PIFP.
+#[contractclient(name = "PriceFeedClient")]
+pub trait PriceFeedInterface {
+    /// Number of decimals in returned prices.
+    fn decimals(env: Env) -> u32;
+
+    /// Most recent price of `asset`, if known.
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+}
+
+/// Convert `amount` of `from` into units of `to` at the feed's latest
+/// prices, or `None` if the feed cannot price either. Assumes both tokens
+/// use the same number of decimals, as Stellar assets do.
+pub fn convert(
+    env: &Env,
+    feed: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Option<i128> {
+    let client = PriceFeedClient::new(env, feed);
+    let price_of = |token: &Address| {
+        client
+            .try_lastprice(&Asset::Stellar(token.clone()))
+            .ok()?
+            .ok()?
+            .map(|p| p.price)
+    };
+    let to_price = price_of(to).filter(|p| *p > 0)?;
+    amount.checked_mul(price_of(from)?)?.checked_div(to_price)
+}
+
+/// Value `amount` of `token` in the feed's reference unit, or `None` if the
+/// feed cannot price it.
+pub fn reference_value(env: &Env, feed: &Address, token: &Address, amount: i128) -> Option<i128> {
+    let client = PriceFeedClient::new(env, feed);
+    let decimals = client.try_decimals().ok()?.ok()?;
+    let price = client
+        .try_lastprice(&Asset::Stellar(token.clone()))
+        .ok()?
+        .ok()??
+        .price;
+    amount
+        .checked_mul(price)?
+        .checked_div(10i128.checked_pow(decimals)?)
+}
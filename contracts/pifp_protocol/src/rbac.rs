@@ -13,28 +13,51 @@
 //! ## Storage layout
 //!
 //! - `RbacKey::SuperAdmin` → `Address`  — the one and only super-admin.
+//!   For a council deployment this is the contract's own address.
+//! - `RbacKey::Council` → `Council`  — members and approval threshold of
+//!   the council holding SuperAdmin, if any.
 //! - `RbacKey::Role(addr)` → `Role`     — the role held by `addr`, if any.
+//! - `RbacKey::AdminCount` → `u32`   — number of addresses holding `Admin`.
+//! - `RbacKey::OracleCount` → `u32`  — number of addresses holding `Oracle`.
+//! - `RbacKey::Members(role)` → `Vec<Address>` — addresses holding `role`,
+//!   in order of their grant.
+//! - `RbacKey::Expiry(addr)` → `u64`  — timestamp from which the role of
+//!   `addr` lapses; absent for grants without an expiry.
+//! - `RbacKey::History(addr)` → `Vec<RoleChange>` — the last
+//!   `MAX_ROLE_HISTORY` grants/revocations affecting `addr`, oldest first.
 //!
 //! ## Event emissions
 //!
 //! Every mutation emits an on-chain event so that off-chain indexers can
-//! reconstruct a complete audit trail without storing membership lists on-chain:
+//! reconstruct a complete audit trail; the current holders of each role can
+//! also be read directly with `list_role_members`:
 //!
 //! | Event topic prefix | Trigger |
 //! |--------------------|---------|
 //! | `role_set`         | Role granted or replaced |
 //! | `role_del`         | Role revoked |
+//! | `sa_xfer`          | SuperAdmin transferred (old, new) |
 //!
 //! ## Threat model notes
 //!
 //! - `Admin` cannot escalate to `SuperAdmin` — only `SuperAdmin` may grant that role.
-//! - `SuperAdmin` cannot be removed via `revoke_role`; use `transfer_super_admin`.
+//! - `SuperAdmin` cannot be removed via `revoke_role`; use `transfer_super_admin`,
+//!   or guardian recovery if the key is lost.
+//! - Under a council no single key holds `SuperAdmin`: the contract itself
+//!   does, and only acts on timelocked actions approved by `threshold`
+//!   members, so one compromised member key cannot move funds or roles.
 //! - An address holds **at most one role** at a time; granting a new role replaces the old one.
+//! - At most `ProtocolConfig::max_admins` addresses hold `Admin`, so a compromised
+//!   Admin cannot mint unbounded peers.
+//! - A role granted with an expiry is treated as absent by every guard and
+//!   query from that timestamp on. It still counts towards `max_admins`
+//!   until it is revoked or replaced.
 
 #![allow(unused)]
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
 
+use crate::types::Council;
 use crate::Error;
 
 // ─────────────────────────────────────────────────────────
@@ -54,12 +77,31 @@ pub enum Role {
     Admin,
     /// Can call `verify_and_release`; replaces the single oracle address.
     Oracle,
-    /// Read-only observer; confirmed by off-chain checks rather than on-chain gates.
+    /// Read-only observer; may call operator views such as `storage_report`.
     Auditor,
     /// Can call `register_project`; restricted to managing their own projects.
     ProjectManager,
 }
 
+/// One grant or revocation in an address's role history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleChange {
+    /// The role granted, or the role removed by a revocation.
+    pub role: Role,
+    /// `true` for a grant, `false` for a revocation.
+    pub granted: bool,
+    /// The address that made the change; `None` for the initial SuperAdmin.
+    pub actor: Option<Address>,
+    /// Ledger timestamp of the change.
+    pub timestamp: u64,
+    /// Hash of the off-chain justification, if the caller supplied one.
+    pub reason_hash: Option<BytesN<32>>,
+}
+
+/// Number of role changes retained per address; older ones are dropped.
+pub const MAX_ROLE_HISTORY: u32 = 16;
+
 // ─────────────────────────────────────────────────────────
 // Storage keys
 // ─────────────────────────────────────────────────────────
@@ -71,28 +113,149 @@ pub enum RbacKey {
     Role(Address),
     /// The one and only SuperAdmin address.
     SuperAdmin,
+    /// Maps an address → its recent role changes.
+    History(Address),
+    /// Number of addresses currently holding `Role::Admin`.
+    AdminCount,
+    /// Number of addresses currently holding `Role::Oracle`.
+    OracleCount,
+    /// Maps an address → the timestamp its role lapses at.
+    Expiry(Address),
+    /// Maps a role → the addresses holding it.
+    Members(Role),
+    /// The council holding SuperAdmin, if the deployment has one.
+    Council,
 }
 
 // ─────────────────────────────────────────────────────────
 // Storage helpers (private)
 // ─────────────────────────────────────────────────────────
 
-/// Persist a role assignment. Overwrites any existing role.
+/// Persist a role assignment without an expiry. Overwrites any existing
+/// role.
 fn store_role(env: &Env, address: &Address, role: &Role) {
+    let previous = stored_role(env, address);
+    if previous.as_ref() != Some(role) {
+        if let Some(previous) = previous {
+            adjust_count(env, &previous, -1);
+            remove_member(env, &previous, address);
+        }
+        adjust_count(env, role, 1);
+        let mut members = stored_members(env, role);
+        members.push_back(address.clone());
+        env.storage()
+            .persistent()
+            .set(&RbacKey::Members(role.clone()), &members);
+    }
     env.storage()
         .persistent()
         .set(&RbacKey::Role(address.clone()), role);
+    env.storage()
+        .persistent()
+        .remove(&RbacKey::Expiry(address.clone()));
 }
 
 /// Remove any role stored for `address`.
 fn clear_role(env: &Env, address: &Address) {
+    if let Some(previous) = stored_role(env, address) {
+        adjust_count(env, &previous, -1);
+        remove_member(env, &previous, address);
+    }
     env.storage()
         .persistent()
         .remove(&RbacKey::Role(address.clone()));
+    env.storage()
+        .persistent()
+        .remove(&RbacKey::Expiry(address.clone()));
+}
+
+/// Addresses stored as holding `role`, including expired grants.
+fn stored_members(env: &Env, role: &Role) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&RbacKey::Members(role.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn remove_member(env: &Env, role: &Role, address: &Address) {
+    let mut members = stored_members(env, role);
+    if let Some(i) = members.first_index_of(address) {
+        members.remove(i);
+        env.storage()
+            .persistent()
+            .set(&RbacKey::Members(role.clone()), &members);
+    }
+}
+
+/// Key of the holder count kept for `role`, if one is kept.
+fn count_key(role: &Role) -> Option<RbacKey> {
+    match role {
+        Role::Admin => Some(RbacKey::AdminCount),
+        Role::Oracle => Some(RbacKey::OracleCount),
+        _ => None,
+    }
+}
+
+fn role_count(env: &Env, key: &RbacKey) -> u32 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+fn adjust_count(env: &Env, role: &Role, delta: i32) {
+    if let Some(key) = count_key(role) {
+        let count = role_count(env, &key).saturating_add_signed(delta);
+        env.storage().persistent().set(&key, &count);
+    }
 }
 
-/// Read the role for `address`, returning `None` if unassigned.
+/// Number of addresses currently holding `Role::Admin`.
+pub fn admin_count(env: &Env) -> u32 {
+    role_count(env, &RbacKey::AdminCount)
+}
+
+/// Number of addresses currently holding `Role::Oracle`.
+pub fn oracle_count(env: &Env) -> u32 {
+    role_count(env, &RbacKey::OracleCount)
+}
+
+/// Append a change to `address`'s role history, dropping the oldest entry
+/// once `MAX_ROLE_HISTORY` is reached.
+fn record_change(
+    env: &Env,
+    address: &Address,
+    role: Role,
+    granted: bool,
+    actor: Option<Address>,
+    reason_hash: Option<BytesN<32>>,
+) {
+    let key = RbacKey::History(address.clone());
+    let mut history: Vec<RoleChange> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    if history.len() >= MAX_ROLE_HISTORY {
+        history.pop_front();
+    }
+    history.push_back(RoleChange {
+        role,
+        granted,
+        actor,
+        timestamp: env.ledger().timestamp(),
+        reason_hash,
+    });
+    env.storage().persistent().set(&key, &history);
+}
+
+/// Read the role for `address`, returning `None` if unassigned or expired.
 pub fn get_role(env: &Env, address: &Address) -> Option<Role> {
+    match role_expiry(env, address) {
+        Some(expiry) if env.ledger().timestamp() >= expiry => None,
+        _ => stored_role(env, address),
+    }
+}
+
+/// Read the role stored for `address`, whether or not it has expired.
+fn stored_role(env: &Env, address: &Address) -> Option<Role> {
     env.storage()
         .persistent()
         .get(&RbacKey::Role(address.clone()))
@@ -117,6 +280,7 @@ pub fn init_super_admin(env: &Env, super_admin: &Address) {
         .persistent()
         .set(&RbacKey::SuperAdmin, super_admin);
     store_role(env, super_admin, &Role::SuperAdmin);
+    record_change(env, super_admin, Role::SuperAdmin, true, None, None);
 
     emit(
         env,
@@ -124,9 +288,78 @@ pub fn init_super_admin(env: &Env, super_admin: &Address) {
         super_admin,
         &Role::SuperAdmin,
         None::<Address>,
+        None,
     );
 }
 
+// ─────────────────────────────────────────────────────────
+// Council
+// ─────────────────────────────────────────────────────────
+
+/// Read the council holding SuperAdmin, or `None` for a single-key deployment.
+pub fn get_council(env: &Env) -> Option<Council> {
+    env.storage().persistent().get(&RbacKey::Council)
+}
+
+/// Store `council` after checking it is usable: 1 ≤ `threshold` ≤ member
+/// count ≤ `Council::MAX_MEMBERS`, with no duplicates and without the
+/// contract itself. Panics with `Error::InvalidConfig` otherwise.
+///
+/// Performs no authorization. Emits `council`.
+pub fn set_council(env: &Env, council: &Council) {
+    validate_council(env, council);
+    env.storage().persistent().set(&RbacKey::Council, council);
+    crate::events::emit_council_set(env, council.clone());
+}
+
+/// Panic with `Error::InvalidConfig` unless `council` is usable.
+pub fn validate_council(env: &Env, council: &Council) {
+    let len = council.members.len();
+    let contract = env.current_contract_address();
+    let mut valid = council.threshold >= 1
+        && council.threshold <= len
+        && len <= Council::MAX_MEMBERS
+        && !council.members.contains(&contract);
+    for (i, member) in council.members.iter().enumerate() {
+        if council.members.first_index_of(&member) != Some(i as u32) {
+            valid = false;
+        }
+    }
+    if !valid {
+        panic_with_error_rbac(env, Error::InvalidConfig);
+    }
+}
+
+/// Whether `address` may queue, approve and cancel timelocked actions:
+/// a council member if there is a council, otherwise the SuperAdmin.
+pub fn can_propose(env: &Env, address: &Address) -> bool {
+    match get_council(env) {
+        Some(council) => council.members.contains(address),
+        None => get_role(env, address) == Some(Role::SuperAdmin),
+    }
+}
+
+/// Assert `can_propose`. Panics with `Error::NotAuthorized` on failure.
+pub fn require_proposer(env: &Env, address: &Address) {
+    if !can_propose(env, address) {
+        panic_with_error_rbac(env, Error::NotAuthorized);
+    }
+}
+
+/// Approvals a timelocked action needs: the council threshold, or 1.
+pub fn approval_threshold(env: &Env) -> u32 {
+    get_council(env).map_or(1, |council| council.threshold)
+}
+
+/// Number of `approvals` given by addresses that may still propose, so
+/// approvals from removed members stop counting.
+pub fn count_approvals(env: &Env, approvals: &Vec<Address>) -> u32 {
+    approvals
+        .iter()
+        .filter(|approver| can_propose(env, approver))
+        .count() as u32
+}
+
 // ─────────────────────────────────────────────────────────
 // Role assignment
 // ─────────────────────────────────────────────────────────
@@ -136,9 +369,21 @@ pub fn init_super_admin(env: &Env, super_admin: &Address) {
 /// - `caller` must hold `SuperAdmin` or `Admin`.
 /// - `Admin` callers cannot grant `SuperAdmin` — only SuperAdmin can elevate.
 /// - Assigning a role to an address that already has one replaces it.
+/// - Granting `Admin` fails with `Error::AdminLimitReached` once
+///   `ProtocolConfig::max_admins` addresses hold it.
+/// - `expires_at`, if given, is the timestamp the role lapses at; it must
+///   be in the future, and `SuperAdmin` cannot be granted with one.
+/// - `reason_hash` optionally ties the change to an off-chain approval.
 ///
-/// Emits a `role_set` event.
-pub fn grant_role(env: &Env, caller: &Address, target: &Address, role: Role) {
+/// Emits a `role_set` event, followed by `role_exp` for time-bound grants.
+pub fn grant_role(
+    env: &Env,
+    caller: &Address,
+    target: &Address,
+    role: Role,
+    expires_at: Option<u64>,
+    reason_hash: Option<BytesN<32>>,
+) {
     let caller_role = get_role(env, caller);
 
     match &role {
@@ -152,31 +397,65 @@ pub fn grant_role(env: &Env, caller: &Address, target: &Address, role: Role) {
         }
     }
 
+    if let Some(expiry) = expires_at {
+        if role == Role::SuperAdmin || expiry <= env.ledger().timestamp() {
+            panic_with_error_rbac(env, Error::InvalidConfig);
+        }
+    }
+
     // Prevent demotion of the SuperAdmin via grant_role
-    if let Some(Role::SuperAdmin) = get_role(env, target) {
+    let current = stored_role(env, target);
+    if let Some(Role::SuperAdmin) = current {
         if role != Role::SuperAdmin {
             panic_with_error_rbac(env, Error::NotAuthorized);
         }
     }
 
+    // Bound the number of Admins so one compromised Admin cannot entrench
+    if role == Role::Admin
+        && current != Some(Role::Admin)
+        && admin_count(env) >= crate::storage::get_protocol_config(env).max_admins
+    {
+        panic_with_error_rbac(env, Error::AdminLimitReached);
+    }
+
     store_role(env, target, &role);
+    if let Some(expiry) = expires_at {
+        env.storage()
+            .persistent()
+            .set(&RbacKey::Expiry(target.clone()), &expiry);
+    }
+    record_change(
+        env,
+        target,
+        role.clone(),
+        true,
+        Some(caller.clone()),
+        reason_hash.clone(),
+    );
     emit(
         env,
         symbol_short!("role_set"),
         target,
         &role,
         Some(caller.clone()),
+        reason_hash,
     );
+    if let Some(expiry) = expires_at {
+        env.events()
+            .publish((symbol_short!("role_exp"), target.clone()), expiry);
+    }
 }
 
 /// Revoke any role from `target`.
 ///
 /// - `caller` must hold `SuperAdmin` or `Admin`.
 /// - The SuperAdmin address itself cannot be revoked; use `transfer_super_admin`.
-/// - Revoking a role from an address with no role is a no-op.
+/// - Revoking a role from an address with no role is a no-op. An expired
+///   role is still revoked, freeing its `max_admins` slot.
 ///
 /// Emits a `role_del` event if a role existed.
-pub fn revoke_role(env: &Env, caller: &Address, target: &Address) {
+pub fn revoke_role(env: &Env, caller: &Address, target: &Address, reason_hash: Option<BytesN<32>>) {
     require_any_of(env, caller, &[Role::SuperAdmin, Role::Admin]);
 
     // Protect the SuperAdmin address from revocation via this path
@@ -185,9 +464,17 @@ pub fn revoke_role(env: &Env, caller: &Address, target: &Address) {
         panic_with_error_rbac(env, Error::NotAuthorized);
     }
 
-    if get_role(env, target).is_some() {
+    if let Some(role) = stored_role(env, target) {
         clear_role(env, target);
-        emit_revoke(env, target, Some(caller.clone()));
+        record_change(
+            env,
+            target,
+            role,
+            false,
+            Some(caller.clone()),
+            reason_hash.clone(),
+        );
+        emit_revoke(env, target, Some(caller.clone()), reason_hash);
     }
 }
 
@@ -197,24 +484,50 @@ pub fn revoke_role(env: &Env, caller: &Address, target: &Address) {
 /// - `new_super_admin` is granted the `SuperAdmin` role.
 /// - The old SuperAdmin loses the `SuperAdmin` role automatically.
 ///
-/// This is the only way to remove a SuperAdmin.
+/// This is the only way to remove a SuperAdmin. Emits `role_del` and
+/// `role_set` for the two addresses, followed by a dedicated `sa_xfer`.
 pub fn transfer_super_admin(env: &Env, current: &Address, new: &Address) {
     require_role(env, current, &Role::SuperAdmin);
+    replace_super_admin(env, current, new, current);
+}
+
+/// Rotate the SuperAdmin role to `new` on behalf of the recovery guardians.
+///
+/// Performs no authorization: the caller must already have checked the
+/// guardian approvals and delay. `actor` is recorded as the address that
+/// made the change.
+pub fn recover_super_admin(env: &Env, new: &Address, actor: &Address) {
+    let current = get_super_admin(env).expect("SuperAdmin is set at init");
+    replace_super_admin(env, &current, new, actor);
+}
 
+/// Move the SuperAdmin role from `current` to `new`, recording `actor`.
+fn replace_super_admin(env: &Env, current: &Address, new: &Address, actor: &Address) {
     // Clear old SuperAdmin
     clear_role(env, current);
-    emit_revoke(env, current, Some(current.clone()));
+    record_change(
+        env,
+        current,
+        Role::SuperAdmin,
+        false,
+        Some(actor.clone()),
+        None,
+    );
+    emit_revoke(env, current, Some(actor.clone()), None);
 
     // Set new SuperAdmin
     env.storage().persistent().set(&RbacKey::SuperAdmin, new);
     store_role(env, new, &Role::SuperAdmin);
+    record_change(env, new, Role::SuperAdmin, true, Some(actor.clone()), None);
     emit(
         env,
         symbol_short!("role_set"),
         new,
         &Role::SuperAdmin,
-        Some(current.clone()),
+        Some(actor.clone()),
+        None,
     );
+    crate::events::emit_super_admin_transferred(env, current.clone(), new.clone());
 }
 
 // ─────────────────────────────────────────────────────────
@@ -248,6 +561,17 @@ pub fn require_admin_or_above(env: &Env, address: &Address) {
     require_any_of(env, address, &[Role::SuperAdmin, Role::Admin]);
 }
 
+/// Assert that `address` is the SuperAdmin, an Admin, or an Auditor.
+/// Used to gate operator-facing views.
+#[inline]
+pub fn require_admin_or_auditor(env: &Env, address: &Address) {
+    require_any_of(
+        env,
+        address,
+        &[Role::SuperAdmin, Role::Admin, Role::Auditor],
+    );
+}
+
 /// Assert that `address` holds the Oracle role.
 /// Used to gate `verify_and_release`.
 #[inline]
@@ -275,31 +599,118 @@ pub fn role_of(env: &Env, address: Address) -> Option<Role> {
     get_role(env, &address)
 }
 
+/// Returns the timestamp the role of `address` lapses at, if it was
+/// granted with an expiry.
+pub fn role_expiry(env: &Env, address: &Address) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&RbacKey::Expiry(address.clone()))
+}
+
+/// Returns the addresses currently holding `role`, in order of their
+/// grant, leaving out expired grants.
+pub fn list_members(env: &Env, role: &Role) -> Vec<Address> {
+    let now = env.ledger().timestamp();
+    let mut members = Vec::new(env);
+    for address in stored_members(env, role).iter() {
+        match role_expiry(env, &address) {
+            Some(expiry) if now >= expiry => {}
+            _ => members.push_back(address),
+        }
+    }
+    members
+}
+
+/// Returns the recorded role changes for `address`, oldest first.
+pub fn role_history(env: &Env, address: Address) -> Vec<RoleChange> {
+    env.storage()
+        .persistent()
+        .get(&RbacKey::History(address))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
 /// Returns `true` if `address` holds `role`.
 pub fn has_role(env: &Env, address: Address, role: Role) -> bool {
     get_role(env, &address).map(|r| r == role).unwrap_or(false)
 }
 
+/// Actions `role` is permitted, as the names `capabilities_of` reports.
+///
+/// Mirrors the `require_*` gates on the entry points:
+///
+/// | Capability  | Entry points                                          |
+/// |-------------|-------------------------------------------------------|
+/// | `register`  | `register_project`, `register_project_at`             |
+/// | `verify`    | `verify_and_release`, `verify_aggregate`              |
+/// | `grant`     | `grant_role`, `revoke_role`, `set_oracle`             |
+/// | `configure` | `set_protocol_config`, templates, fees, match caps, project oracles |
+/// | `pause`     | `pause`, `unpause`, maintenance, project and token pauses |
+/// | `approve`   | `approve_verifier`, `revoke_verifier`                 |
+/// | `freeze`    | `freeze_address`, `unfreeze_address`, `flag_creator`  |
+/// | `resolve`   | `resolve_contest`, `sweep_dust`                       |
+/// | `report`    | `storage_report`                                      |
+/// | `force`     | `force_status`, `replace_oracle_emergency`            |
+/// | `guardians` | `set_guardians`, dead-man switch, `transfer_super_admin` |
+/// | `sunset`    | `begin_sunset`, `finalize_sunset`                     |
+pub fn capabilities(env: &Env, role: &Role) -> Vec<Symbol> {
+    let admin = [
+        symbol_short!("register"),
+        symbol_short!("grant"),
+        symbol_short!("configure"),
+        symbol_short!("pause"),
+        symbol_short!("approve"),
+        symbol_short!("freeze"),
+        symbol_short!("resolve"),
+        symbol_short!("report"),
+    ];
+    let mut caps = Vec::new(env);
+    match role {
+        Role::SuperAdmin => {
+            caps.extend_from_array(admin);
+            caps.push_back(symbol_short!("force"));
+            caps.push_back(symbol_short!("guardians"));
+            caps.push_back(symbol_short!("sunset"));
+        }
+        Role::Admin => caps.extend_from_array(admin),
+        Role::Oracle => caps.push_back(symbol_short!("verify")),
+        Role::Auditor => caps.push_back(symbol_short!("report")),
+        Role::ProjectManager => caps.push_back(symbol_short!("register")),
+    }
+    caps
+}
+
 // ─────────────────────────────────────────────────────────
 // Internal helpers
 // ─────────────────────────────────────────────────────────
 
 /// Emit a role assignment event.
 /// Topic: `(role_set, target_address, role_name_symbol)`
-/// Data:  `Option<caller_address>`
-fn emit(env: &Env, event: soroban_sdk::Symbol, target: &Address, role: &Role, by: Option<Address>) {
+/// Data:  `(Option<caller_address>, Option<reason_hash>)`
+fn emit(
+    env: &Env,
+    event: soroban_sdk::Symbol,
+    target: &Address,
+    role: &Role,
+    by: Option<Address>,
+    reason_hash: Option<BytesN<32>>,
+) {
     let role_sym = role_to_symbol(env, role);
-    env.events().publish((event, target.clone(), role_sym), by);
+    env.events()
+        .publish((event, target.clone(), role_sym), (by, reason_hash));
 }
 
 /// Emit a role revocation event.
-fn emit_revoke(env: &Env, target: &Address, by: Option<Address>) {
-    env.events()
-        .publish((symbol_short!("role_del"), target.clone()), by);
+/// Topic: `(role_del, target_address)`
+/// Data:  `(Option<caller_address>, Option<reason_hash>)`
+fn emit_revoke(env: &Env, target: &Address, by: Option<Address>, reason_hash: Option<BytesN<32>>) {
+    env.events().publish(
+        (symbol_short!("role_del"), target.clone()),
+        (by, reason_hash),
+    );
 }
 
 /// Convert a Role to a short Symbol for event topics.
-fn role_to_symbol(env: &Env, role: &Role) -> soroban_sdk::Symbol {
+fn role_to_symbol(env: &Env, role: &Role) -> Symbol {
     match role {
         Role::SuperAdmin => symbol_short!("supadmin"),
         Role::Admin => symbol_short!("admin"),
@@ -8,6 +8,20 @@
 //! |------------------|-----------|------------------------------------|
 //! | `ProjectCount`   | `u64`     | Auto-increment project ID counter  |
 //! | `OracleKey`      | `Address` | Active trusted oracle address      |
+//! | `ProtocolConfig` | `ProtocolConfig` | Protocol-wide tunables      |
+//! | `Paused`         | `PauseInfo` | Present while globally paused    |
+//! | `Sunset`         | `bool`    | Wind-down mode switch              |
+//! | `Maintenance`    | `MaintenanceWindow` | Scheduled pause window   |
+//! | `AdminLogCount`  | `u64`     | Admin actions ever recorded        |
+//! | `Guardians`      | `GuardianSet` | SuperAdmin recovery guardians  |
+//! | `Recovery`       | `RecoveryRequest` | Pending SuperAdmin recovery |
+//! | `DeadMan`        | `DeadManSwitch` | SuperAdmin heartbeat and successor |
+//! | `Version`        | `ContractVersion` | Build that last initialised or migrated storage |
+//! | `MigrationCursor` | `u64`    | Next project a paged migration step rewrites |
+//! | `ActionCount`    | `u64`     | Timelocked actions ever queued     |
+//! | `AllowedTokens`  | `Vec<Address>` | Tokens projects may register with |
+//! | `MatchPoolCount` | `u64`     | Matching pools ever created        |
+//! | `Locked`         | `bool`    | Present while a guarded entry point runs |
 //!
 //! Instance TTL is bumped by **7 days** whenever it falls below 1 day remaining.
 //!
@@ -15,21 +29,115 @@
 //!
 //! | Key                | Type            | Description                      |
 //! |--------------------|-----------------|----------------------------------|
-//! | `ProjConfig(id)`   | `ProjectConfig` | Immutable project configuration  |
-//! | `ProjState(id)`    | `ProjectState`  | Mutable project state            |
+//! | `ProjConfig(id)`   | `ProjectConfig` | Project configuration            |
+//! | `ProjState(id)`    | `u128`          | Packed `ProjectState` (v1)       |
+//! | `CreatorRecord(a)` | `CreatorRecord` | Per-creator reputation aggregates |
+//! | `CreatorProjects(a)` | `Vec<u64>`    | Projects a creator registered, oldest first |
+//! | `CreatorReleased(a, t)` | `i128`     | Amount of token `t` ever released to creator `a` |
+//! | `Attestations(id)` | `Vec<Address>`  | Oracles that attested the proof  |
+//! | `ProjVerifier(id)` | `ProjectVerifier` | Project's external verifier    |
+//! | `ProjOracles(id)`  | `Vec<Address>`  | Oracles assigned to the project  |
+//! | `Handle(c, h)`     | `u64`           | Project creator `c` registered under handle `h` |
+//! | `ProjHandle(id)`   | `Symbol`        | Handle registered for a project  |
+//! | `Metadata(id)`     | `ProjectMetadata` | Display name and metadata hash |
+//! | `Depends(id)`      | `Vec<u64>`      | Projects that must complete before this one releases |
+//! | `ApprovedVerifier(a)` | `bool`       | Verifier allowlist membership    |
+//! | `OracleBlsKey(a)`  | `BytesN<96>`    | Oracle's BLS12-381 G1 public key |
+//! | `OracleProjects(a)` | `Vec<u64>`     | Projects an oracle attested or is assigned to |
+//! | `Cadence(id)`      | `OngoingCadence` | Re-attestation schedule         |
+//! | `Contribution(id, donor, token)` | `i128` | Donor's cumulative deposit |
+//! | `ContribValue(id, donor, token)` | `i128` | Reference value of those deposits at deposit time |
+//! | `ContribWeight(id, donor, token)` | `i128` | Time-weighted total of those deposits |
+//! | `Refunded(id, donor, token)` | `i128` | Amount refunded to a donor of an expired project |
+//! | `RefundBasis(id, token)` | `(i128, i128)` | Escrow and total contributions when refunds opened |
+//! | `Granted(id, donor, token)` | `Grant` | Non-refundable part of a donor's contribution |
+//! | `DonorProjects(donor)` | `Vec<(u64, Address)>` | Donor's (project, token) pairs |
+//! | `LastRentBump(id)` | `u64`           | Timestamp of last paid TTL bump  |
+//! | `ProjectDonors(id)` | `Vec<(Address, Address)>` | Project's (donor, token) pairs |
+//! | `Frozen(a)`        | `BytesN<32>`    | Compliance freeze reason hash    |
+//! | `AllowedDonor(id, d)` | `bool`       | Donor may deposit while the project's allowlist is on |
+//! | `TokenPaused(t)`   | `bool`          | Deposits of the token suspended  |
+//! | `PendingForce(id)` | `PendingStatusChange` | Queued forced status change |
+//! | `PendingMigr(id)` | `PendingMigration` | Queued escrow migration |
+//! | `Action(n)`        | `QueuedAction`  | Timelocked action awaiting execution |
+//! | `Flows(id, token)` | `TokenFlows`    | Escrow inflow and outflow of a token |
+//! | `EscrowTotal(token)` | `i128`        | Sum of all project balances in a token |
+//! | `ProjOwners(id)`   | `ProjectOwners` | M-of-N controlling owner set     |
+//! | `VoteConfig(id)`   | `DonorVoteConfig` | Opt-in donor vote settings     |
+//! | `DonorVote(id)`    | `DonorVote`     | Open or contested donor vote     |
+//! | `Challenge(id)`    | `Challenge`     | Release held open for auditor disputes |
+//! | `ExtVote(id)`      | `ExtensionVote` | Pending donor deadline extension |
+//! | `ExtVoted(id, donor)` | `u64`        | `old_deadline` of the extension a donor backed |
+//! | `Extensions(id)`   | `u32`           | Times the project's deadline was extended |
+//! | `Amendment(id)`    | `MilestoneAmendment` | Pending milestone amendment proposal |
+//! | `AmendVoted(id, donor)` | `u64`      | `ends_at` of the amendment a donor backed |
+//! | `Voted(id, donor)` | `u64`           | `ends_at` of the vote a donor objected in |
+//! | `DonorStats(donor)` | `DonorStats`   | Per-donor deposit counters and streak |
+//! | `DonorLedger(donor)` | `Vec<LedgerEntry>` | Donor's timestamped deposits, refunds and match tags |
+//! | `DonorPrefs(donor)` | `DonorPreferences` | Donor's privacy opt-outs     |
+//! | `DepositHook(donor)` | `bool`        | Donor contract receives deposit receipts |
+//! | `Template(n)`      | `DisbursementTemplate` | Admin-defined release schedule |
+//! | `Schedule(id)`     | `DisbursementTemplate` | Project's copy of a template |
+//! | `Milestones(id)`   | `Vec<Milestone>` | Milestones a project registered with |
+//! | `Insurance(id)`    | `u32`           | Premium bps locked when the project opted in |
+//! | `InsurancePool(token)` | `i128`      | Premiums collected in a token    |
+//! | `FeeOverride(id)`  | `u32`           | Fee bps replacing `ProtocolConfig::fee_bps` |
+//! | `MatchCap(id)`     | `i128`          | Per-donor cap on match-eligible giving |
+//! | `MatchEligible(id, d)` | `i128`      | Donor's giving tagged for matching |
+//! | `MatchPool(n)`     | `MatchPool`     | Sponsor funds matching a project's deposits |
+//! | `MatchPools(id)`   | `Vec<u64>`      | Pools matching a project, oldest first |
+//! | `MatchPoolTotal(token)` | `i128`     | Sum of all matching pools' unspent funds |
+//! | `KeeperPool(token)` | `i128`         | Funds reserved for keeper rewards |
+//! | `Dust(token)`      | `i128`          | Rounding residue awaiting `sweep_dust` |
+//! | `RentFund(id, token)` | `(i128, i128)` | Rent sponsorship contributed to and left for a project |
+//! | `RentSponsor(id, sponsor, token)` | `i128` | One sponsor's unclaimed rent sponsorship |
+//! | `RentFundTotal(token)` | `i128`      | Sum of all projects' remaining rent sponsorship |
+//! | `SponsorPool(token)` | `i128`        | Funds reimbursing small refund claims |
+//! | `CommonsPool(token)` | `i128`        | Grants from expired projects awaiting disbursement |
+//! | `FeeVault(token)`  | `i128`          | Release fees, and deposit fees awaiting conversion, held for the treasury |
+//! | `Claimable(id, token)` | `i128`      | Released funds the creator has yet to `withdraw` |
+//! | `ClaimableTotal(token)` | `i128`     | Sum of all projects' claimable releases |
+//! | `SponsorSpent(token)` | `(u64, i128)` | Current sponsorship period and amount paid in it |
+//! | `ReleaseOutflow(token)` | `(u64, i128)` | Current throttle window and amount released in it |
+//! | `ReleaseQueue`     | `Vec<QueuedRelease>` | Releases waiting for throttle room |
+//! | `SettledAt(id)`    | `u64`           | When the project reached a terminal state |
+//! | `TtlHorizon(id)`   | `u32`           | Ledger the project's config and state live until |
+//! | `AdminLog(slot)`   | `AdminAction`   | Ring buffer of recent admin actions |
 //!
 //! Persistent TTL is bumped by **30 days** whenever it falls below 7 days remaining.
 //!
+//! ## Temporary storage (lapses with its TTL)
+//!
+//! | Key                | Type            | Description                      |
+//! |--------------------|-----------------|----------------------------------|
+//! | `PendingProof(id)` | `PendingProof`  | Creator-submitted proof awaiting oracle review |
+//!
+//! Entries live for `PendingProof::REVIEW_LEDGERS` from their last write.
+//!
 //! ## Why split Config and State?
 //!
 //! Deposits are high-frequency writes. Writing the full `Project` struct (~150 bytes)
-//! on every deposit is wasteful. `ProjectState` is ~20 bytes — separating it cuts
+//! on every deposit is wasteful. `ProjectState` is packed into a single `u128`
+//! (20 bytes of XDR, enforced by `test_maintenance`) — separating it cuts
 //! ledger write costs by ~87% per deposit while keeping the public API clean via
 //! the reconstructed [`Project`] return type.
 
-use soroban_sdk::{contracttype, Address, Env, Vec};
+use soroban_sdk::{
+    contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, IntoVal, Symbol, TryFromVal, Val,
+    Vec,
+};
 
-use crate::types::{Project, ProjectBalances, ProjectConfig, ProjectState, TokenBalance};
+use crate::events;
+use crate::types::{
+    AdminAction, Challenge, ContractVersion, CreatorRecord, DeadManSwitch, DisbursementTemplate,
+    DonorPreferences, DonorStats, DonorVote, DonorVoteConfig, ExtensionVote, GoalUnit, Grant,
+    GrantRoute, GuardianSet, LedgerEntry, MaintenanceWindow, MatchPool, Milestone,
+    MilestoneAmendment, OngoingCadence, PauseInfo, PendingMigration, PendingProof,
+    PendingStatusChange, Project, ProjectBalances, ProjectConfig, ProjectMetadata, ProjectOwners,
+    ProjectState, ProjectStatus, ProjectVerifier, ProtocolConfig, QueuedAction, QueuedRelease,
+    RecoveryRequest, StorageEntry, StorageReport, TokenBalance, TokenFlows,
+};
+use crate::upgrade::{ProjectConfigV1, ProjectStateV1};
 
 // ── TTL Constants ────────────────────────────────────────────────────
 
@@ -46,22 +154,247 @@ const PERSISTENT_LIFETIME_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS;
 
 // ── Storage Keys ─────────────────────────────────────────────────────
 
-/// All contract storage keys.
+/// Protocol-wide storage keys: counters, switches, role-adjacent and
+/// per-creator or per-oracle entries.
+///
+/// Keys are split across [`DataKey`], [`ProjectKey`], [`DonorKey`] and
+/// [`PoolKey`] because a `#[contracttype]` enum may have at most 50 cases.
+/// A key's XDR is its variant name and fields, not its enum, so moving a
+/// variant between these enums does not change where its value is stored.
 ///
-/// Instance-tier keys (`ProjectCount`, `OracleKey`) live as long as the
-/// contract and are extended together. Persistent-tier keys (`ProjConfig`,
-/// `ProjState`) hold per-project data with independent TTLs.
+/// Instance-tier keys (`ProjectCount`, `ProtocolConfig`) live as long as
+/// the contract and are extended together.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     /// Global auto-increment counter for project IDs (Instance).
     ProjectCount,
-    /// Immutable project configuration keyed by ID (Persistent).
+    /// Reputation aggregates for a project creator (Persistent).
+    CreatorRecord(Address),
+    /// Projects a creator registered, in registration order (Persistent).
+    CreatorProjects(Address),
+    /// Amount of one token ever released to a creator (Persistent).
+    CreatorReleased(Address, Address),
+    /// Protocol-wide tunables (Instance).
+    ProtocolConfig,
+    /// Project a creator registered under a handle (Persistent).
+    Handle(Address, Symbol),
+    /// Admin-approved verifier contract allowlist (Persistent).
+    ApprovedVerifier(Address),
+    /// BLS12-381 public key registered for an oracle (Persistent).
+    OracleBlsKey(Address),
+    /// Projects an oracle has attested or been assigned to (Persistent).
+    OracleProjects(Address),
+    /// Global pause switch (Instance).
+    Paused,
+    /// Reason hash of an active compliance freeze on an address (Persistent).
+    Frozen(Address),
+    /// Deposits of a token suspended protocol-wide (Persistent).
+    TokenPaused(Address),
+    /// Wind-down mode switch (Instance).
+    Sunset,
+    /// Reentrancy lock held by a running entry point (Instance).
+    Locked,
+    /// Scheduled maintenance window (Instance).
+    Maintenance,
+    /// Number of admin actions ever recorded (Instance).
+    AdminLogCount,
+    /// Admin audit log slot `seq % ADMIN_LOG_CAPACITY` (Persistent).
+    AdminLog(u64),
+    /// Reusable disbursement schedule, keyed by admin-chosen id (Persistent).
+    Template(u32),
+    /// Sponsorship period index and amount of one token paid in it
+    /// (Persistent).
+    SponsorSpent(Address),
+    /// Throttle window index and amount of one token released in it
+    /// (Persistent).
+    ReleaseOutflow(Address),
+    /// Releases held back by the throttle, oldest first (Persistent).
+    ReleaseQueue,
+    /// SuperAdmin recovery guardians (Instance).
+    Guardians,
+    /// Pending guardian-initiated SuperAdmin rotation (Instance).
+    Recovery,
+    /// SuperAdmin heartbeat requirement and successor (Instance).
+    DeadMan,
+    /// Build that last initialised or migrated storage (Instance).
+    Version,
+    /// Next project ID a paged migration step rewrites (Instance).
+    MigrationCursor,
+    /// Number of timelocked actions ever queued (Instance).
+    ActionCount,
+    /// Timelocked action awaiting execution, by ID (Persistent).
+    Action(u64),
+    /// Tokens projects may register with under `token_allowlist` (Instance).
+    AllowedTokens,
+}
+
+/// Storage keys of data belonging to one project, each with an
+/// independent TTL.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProjectKey {
+    /// Project configuration keyed by ID (Persistent).
     ProjConfig(u64),
     /// Mutable project state keyed by ID (Persistent).
     ProjState(u64),
     /// Token balance for a specific project and token (Persistent).
     TokenBalance(u64, Address),
+    /// Escrow inflow and outflow for a specific project and token
+    /// (Persistent).
+    Flows(u64, Address),
+    /// Oracles that have attested a project's proof so far (Persistent).
+    Attestations(u64),
+    /// External verifier registered for a project (Persistent).
+    ProjVerifier(u64),
+    /// Oracles allowed to attest a project; unset means any oracle
+    /// (Persistent).
+    ProjOracles(u64),
+    /// Handle registered for a project (Persistent).
+    ProjHandle(u64),
+    /// Display name and metadata hash of a project (Persistent).
+    Metadata(u64),
+    /// Creator-submitted proof awaiting oracle review (Temporary).
+    PendingProof(u64),
+    /// Projects that must complete before a project may release
+    /// (Persistent).
+    Depends(u64),
+    /// Re-attestation cadence for an ongoing project (Persistent).
+    Cadence(u64),
+    /// Escrow and total contributions of one token, fixed by the first refund
+    /// claim on an expired project (Persistent).
+    RefundBasis(u64, Address),
+    /// Ledger timestamp of the last escrow-funded TTL bump (Persistent).
+    LastRentBump(u64),
+    /// Every (donor, token) pair that has contributed to a project, in
+    /// first-deposit order (Persistent).
+    ProjectDonors(u64),
+    /// Donor admitted by a project's donor allowlist (Persistent).
+    AllowedDonor(u64, Address),
+    /// SuperAdmin status override awaiting its timelock (Persistent).
+    PendingForce(u64),
+    /// SuperAdmin escrow migration awaiting its timelock (Persistent).
+    PendingMigr(u64),
+    /// Multi-signature owner set controlling a project (Persistent).
+    ProjOwners(u64),
+    /// Opt-in donor vote settings for a project (Persistent).
+    VoteConfig(u64),
+    /// Donor vote currently open or contested for a project (Persistent).
+    DonorVote(u64),
+    /// Release held in its challenge window or disputed (Persistent).
+    Challenge(u64),
+    /// Pending donor proposal to extend a missed deadline (Persistent).
+    ExtVote(u64),
+    /// Number of times a project's deadline was extended (Persistent).
+    Extensions(u64),
+    /// Pending creator proposal to replace a project's milestones
+    /// (Persistent).
+    Amendment(u64),
+    /// Disbursement schedule a project adopted, copied from its template
+    /// (Persistent).
+    Schedule(u64),
+    /// Milestones a project registered with (Persistent).
+    Milestones(u64),
+    /// Premium rate an insured project pays on deposits (Persistent).
+    Insurance(u64),
+    /// Per-project replacement for the protocol fee (Persistent).
+    FeeOverride(u64),
+    /// Per-donor cap on the giving a project's matching round covers
+    /// (Persistent).
+    MatchCap(u64),
+    /// Released funds of a project in one token awaiting the creator's
+    /// `withdraw` (Persistent).
+    Claimable(u64, Address),
+    /// Rent sponsorship contributed to a project in one token and the part
+    /// still unspent (Persistent).
+    RentFund(u64, Address),
+    /// One sponsor's unclaimed contribution to a project's rent fund
+    /// (Persistent).
+    RentSponsor(u64, Address, Address),
+    /// Timestamp a project reached `Completed` or `Expired` (Persistent).
+    SettledAt(u64),
+    /// Last known `live_until` ledger of a project's config and state
+    /// (Persistent).
+    TtlHorizon(u64),
+    /// IDs of the pools matching a project, oldest first (Persistent).
+    MatchPools(u64),
+}
+
+/// Storage keys of data belonging to one donor, alone or within a
+/// project.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DonorKey {
+    /// Cumulative amount a donor deposited into a project in one token (Persistent).
+    Contribution(u64, Address, Address),
+    /// Amount refunded to a donor of an expired project in one token
+    /// (Persistent).
+    Refunded(u64, Address, Address),
+    /// Part of a donor's contribution in one token flagged non-refundable,
+    /// and where it goes on expiry (Persistent).
+    Granted(u64, Address, Address),
+    /// Every (project, token) pair a donor has contributed to, in first-deposit
+    /// order (Persistent).
+    DonorProjects(Address),
+    /// `ends_at` of the last vote a donor objected in (Persistent).
+    Voted(u64, Address),
+    /// `old_deadline` of the last extension a donor supported (Persistent).
+    ExtVoted(u64, Address),
+    /// `ends_at` of the last milestone amendment a donor backed
+    /// (Persistent).
+    AmendVoted(u64, Address),
+    /// Cumulative deposit-time reference value of a donor's deposits into a
+    /// project in one token (Persistent).
+    ContribValue(u64, Address, Address),
+    /// Cumulative time weight (amount × seconds before the deadline) of a
+    /// donor's deposits into a project in one token (Persistent).
+    ContribWeight(u64, Address, Address),
+    /// Per-donor deposit counters and giving streak (Persistent).
+    DonorStats(Address),
+    /// Every deposit, refund and match tag of a donor, oldest first; never
+    /// pruned by cleanup (Persistent).
+    DonorLedger(Address),
+    /// Privacy opt-outs of one donor (Persistent).
+    DonorPrefs(Address),
+    /// Donor contract opted in to deposit receipts (Persistent).
+    DepositHook(Address),
+    /// Portion of a donor's giving to a project tagged for matching
+    /// (Persistent).
+    MatchEligible(u64, Address),
+}
+
+/// Storage keys of protocol-held funds: per-token pools and totals, and
+/// matching pools.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PoolKey {
+    /// Sum of every project's `TokenBalance` in one token (Persistent).
+    EscrowTotal(Address),
+    /// Premiums collected from insured projects in one token (Persistent).
+    InsurancePool(Address),
+    /// Funds reserved for keeper rewards in one token (Persistent).
+    KeeperPool(Address),
+    /// Rounding residue of settled projects in one token (Persistent).
+    Dust(Address),
+    /// Funds reimbursing small refund claims in one token (Persistent).
+    SponsorPool(Address),
+    /// Grants routed to the commons by expired projects in one token
+    /// (Persistent).
+    CommonsPool(Address),
+    /// Protocol fees of one token held for the treasury: release fees, and
+    /// deposit fees awaiting conversion into the reserve token (Persistent).
+    FeeVault(Address),
+    /// Sum of every project's claimable releases in one token (Persistent).
+    ClaimableTotal(Address),
+    /// Sum of every project's unspent rent sponsorship in one token
+    /// (Persistent).
+    RentFundTotal(Address),
+    /// Number of matching pools ever created (Instance).
+    MatchPoolCount,
+    /// Matching pool by ID (Persistent).
+    MatchPool(u64),
+    /// Sum of every matching pool's unspent funds in one token (Persistent).
+    MatchPoolTotal(Address),
 }
 
 // ── Instance Storage Helpers ─────────────────────────────────────────
@@ -77,6 +410,19 @@ fn bump_instance(env: &Env) {
 // Project counter
 // ─────────────────────────────────────────────────────────
 
+/// Maximum number of project IDs scanned by one enumeration call
+/// (`list_projects`, `list_project_ids_by_status`,
+/// `get_actionable_projects`).
+pub const PROJECT_PAGE: u32 = 25;
+
+/// Number of sequentially numbered projects registered so far.
+pub fn get_project_count(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ProjectCount)
+        .unwrap_or(0)
+}
+
 /// Atomically read and increment the project counter.
 /// Returns the ID that should be used for the next project.
 pub fn get_and_increment_project_id(env: &Env) -> u64 {
@@ -92,10 +438,303 @@ pub fn get_and_increment_project_id(env: &Env) -> u64 {
     current
 }
 
+/// High bit set on every content-addressed project ID, keeping them
+/// disjoint from the sequential counter.
+pub const DERIVED_ID_FLAG: u64 = 1 << 63;
+
+/// Project ID for `register_project_at`: the first eight bytes of
+/// `sha256(xdr(creator) ‖ proof_hash ‖ salt)` with [`DERIVED_ID_FLAG`] set.
+pub fn derive_project_id(
+    env: &Env,
+    creator: &Address,
+    proof_hash: &BytesN<32>,
+    salt: &BytesN<32>,
+) -> u64 {
+    let mut preimage = creator.clone().to_xdr(env);
+    preimage.append(&proof_hash.clone().into());
+    preimage.append(&salt.clone().into());
+    let digest = env.crypto().sha256(&preimage).to_array();
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(prefix) | DERIVED_ID_FLAG
+}
+
+// ─────────────────────────────────────────────────────────
+// Pause switch
+// ─────────────────────────────────────────────────────────
+
+/// Return `true` if the protocol is globally paused.
+pub fn is_paused(env: &Env) -> bool {
+    bump_instance(env);
+    env.storage().instance().has(&DataKey::Paused)
+}
+
+/// Details of the current global pause, if any.
+pub fn get_pause_info(env: &Env) -> Option<PauseInfo> {
+    bump_instance(env);
+    env.storage().instance().get(&DataKey::Paused)
+}
+
+/// Pause the protocol with `info`, or lift the pause with `None`.
+pub fn set_pause_info(env: &Env, info: Option<&PauseInfo>) {
+    bump_instance(env);
+    match info {
+        Some(info) => env.storage().instance().set(&DataKey::Paused, info),
+        None => env.storage().instance().remove(&DataKey::Paused),
+    }
+}
+
+/// The scheduled maintenance window, if any.
+pub fn get_maintenance(env: &Env) -> Option<MaintenanceWindow> {
+    bump_instance(env);
+    env.storage().instance().get(&DataKey::Maintenance)
+}
+
+/// Schedule `window`, or cancel the scheduled one with `None`.
+pub fn set_maintenance(env: &Env, window: Option<&MaintenanceWindow>) {
+    bump_instance(env);
+    match window {
+        Some(window) => env.storage().instance().set(&DataKey::Maintenance, window),
+        None => env.storage().instance().remove(&DataKey::Maintenance),
+    }
+}
+
+/// The SuperAdmin recovery guardians, if configured.
+pub fn get_guardians(env: &Env) -> Option<GuardianSet> {
+    bump_instance(env);
+    env.storage().instance().get(&DataKey::Guardians)
+}
+
+/// Replace the SuperAdmin recovery guardians.
+pub fn set_guardians(env: &Env, set: &GuardianSet) {
+    bump_instance(env);
+    env.storage().instance().set(&DataKey::Guardians, set);
+}
+
+/// The pending SuperAdmin recovery, if any.
+pub fn get_recovery(env: &Env) -> Option<RecoveryRequest> {
+    bump_instance(env);
+    env.storage().instance().get(&DataKey::Recovery)
+}
+
+/// Store `request` as the pending recovery, or clear it with `None`.
+pub fn set_recovery(env: &Env, request: Option<&RecoveryRequest>) {
+    bump_instance(env);
+    match request {
+        Some(request) => env.storage().instance().set(&DataKey::Recovery, request),
+        None => env.storage().instance().remove(&DataKey::Recovery),
+    }
+}
+
+/// The build that last initialised or migrated storage; `None` on
+/// deployments that predate the record.
+pub fn get_stored_version(env: &Env) -> Option<ContractVersion> {
+    bump_instance(env);
+    env.storage().instance().get(&DataKey::Version)
+}
+
+/// Record `version` as the build storage is laid out for.
+pub fn set_stored_version(env: &Env, version: &ContractVersion) {
+    bump_instance(env);
+    env.storage().instance().set(&DataKey::Version, version);
+}
+
+/// Next project ID the running migration step rewrites; `0` when none
+/// is under way.
+pub fn get_migration_cursor(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::MigrationCursor)
+        .unwrap_or(0)
+}
+
+/// Record where the running migration step resumes, or clear it with `None`.
+pub fn set_migration_cursor(env: &Env, next: Option<u64>) {
+    match next {
+        Some(next) => env
+            .storage()
+            .instance()
+            .set(&DataKey::MigrationCursor, &next),
+        None => env.storage().instance().remove(&DataKey::MigrationCursor),
+    }
+    bump_instance(env);
+}
+
+/// Rewrite project `id` from the schema 1 layout; see [`crate::upgrade`].
+///
+/// Entries already in the current layout are left alone, so the step is
+/// safe to repeat. Completed projects are recorded as fully released.
+pub fn migrate_project_v1(env: &Env, id: u64) {
+    let state_key = ProjectKey::ProjState(id);
+    let Some(raw) = env.storage().persistent().get::<_, Val>(&state_key) else {
+        return;
+    };
+    if u128::try_from_val(env, &raw).is_ok() {
+        return;
+    }
+    let old_state = ProjectStateV1::try_from_val(env, &raw).expect("unknown project state layout");
+    let config_key = ProjectKey::ProjConfig(id);
+    let old_config: ProjectConfigV1 = env
+        .storage()
+        .persistent()
+        .get(&config_key)
+        .expect("project config missing");
+
+    let config = ProjectConfig {
+        id: old_config.id,
+        creator: old_config.creator,
+        accepted_tokens: old_config.accepted_tokens,
+        goal: old_config.goal,
+        proof_hash: old_config.proof_hash,
+        deadline: old_config.deadline,
+        quorum: 1,
+        goal_unit: GoalUnit::FirstToken,
+    };
+    save_project_config(env, &config);
+    let released_bps = if old_state.status == ProjectStatus::Completed {
+        10_000
+    } else {
+        0
+    };
+    let state = ProjectState {
+        status: old_state.status,
+        donation_count: old_state.donation_count,
+        flags: 0,
+        released_bps,
+    };
+    save_project_state(env, id, &state);
+
+    // Schema 1 kept only the balance; seed the flow ledger and escrow total
+    // from it so releases, refunds and sweeps reconcile.
+    for token in config.accepted_tokens.iter() {
+        let balance_key = ProjectKey::TokenBalance(id, token.clone());
+        let balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
+        if balance == 0 {
+            continue;
+        }
+        bump_persistent(env, &balance_key);
+        let flows = TokenFlows {
+            inflow: balance,
+            ..TokenFlows::default()
+        };
+        set_token_flows(env, id, &token, &flows);
+        let total_key = PoolKey::EscrowTotal(token.clone());
+        let total = get_escrow_total(env, &token) + balance;
+        env.storage().persistent().set(&total_key, &total);
+        bump_persistent(env, &total_key);
+    }
+}
+
+/// The SuperAdmin dead-man switch, if configured.
+pub fn get_dead_man(env: &Env) -> Option<DeadManSwitch> {
+    bump_instance(env);
+    env.storage().instance().get(&DataKey::DeadMan)
+}
+
+/// Store `switch`, or remove the dead-man switch with `None`.
+pub fn set_dead_man(env: &Env, switch: Option<&DeadManSwitch>) {
+    bump_instance(env);
+    match switch {
+        Some(switch) => env.storage().instance().set(&DataKey::DeadMan, switch),
+        None => env.storage().instance().remove(&DataKey::DeadMan),
+    }
+}
+
+/// Number of entries the admin audit log retains; older ones are
+/// overwritten.
+pub const ADMIN_LOG_CAPACITY: u64 = 128;
+
+/// Maximum number of audit log entries returned by one read.
+pub const ADMIN_LOG_PAGE: u32 = 50;
+
+/// Number of admin actions ever recorded.
+pub fn get_admin_log_count(env: &Env) -> u64 {
+    bump_instance(env);
+    env.storage()
+        .instance()
+        .get(&DataKey::AdminLogCount)
+        .unwrap_or(0)
+}
+
+/// Append `entry` to the admin audit log, assigning its sequence number.
+pub fn append_admin_log(env: &Env, mut entry: AdminAction) {
+    let seq = get_admin_log_count(env);
+    entry.seq = seq;
+    let key = DataKey::AdminLog(seq % ADMIN_LOG_CAPACITY);
+    env.storage().persistent().set(&key, &entry);
+    bump_persistent(env, &key);
+    env.storage()
+        .instance()
+        .set(&DataKey::AdminLogCount, &(seq + 1));
+}
+
+/// Admin log entry with sequence number `seq`, if still retained.
+pub fn get_admin_log_entry(env: &Env, seq: u64) -> Option<AdminAction> {
+    let key = DataKey::AdminLog(seq % ADMIN_LOG_CAPACITY);
+    let entry: Option<AdminAction> = env.storage().persistent().get(&key);
+    match entry {
+        Some(entry) if entry.seq == seq => {
+            bump_persistent(env, &key);
+            Some(entry)
+        }
+        _ => None,
+    }
+}
+
+/// Return `true` once wind-down mode has begun.
+pub fn is_sunset(env: &Env) -> bool {
+    bump_instance(env);
+    env.storage()
+        .instance()
+        .get(&DataKey::Sunset)
+        .unwrap_or(false)
+}
+
+/// Enter wind-down mode. There is no way back.
+pub fn set_sunset(env: &Env) {
+    bump_instance(env);
+    env.storage().instance().set(&DataKey::Sunset, &true);
+}
+
+/// Return `true` while a reentrancy-guarded entry point is running.
+pub fn is_locked(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Locked)
+}
+
+/// Take or release the reentrancy lock.
+pub fn set_locked(env: &Env, locked: bool) {
+    if locked {
+        env.storage().instance().set(&DataKey::Locked, &true);
+    } else {
+        env.storage().instance().remove(&DataKey::Locked);
+    }
+}
+
+// ─────────────────────────────────────────────────────────
+// Protocol configuration
+// ─────────────────────────────────────────────────────────
+
+/// Read the protocol configuration, falling back to the defaults.
+pub fn get_protocol_config(env: &Env) -> ProtocolConfig {
+    bump_instance(env);
+    env.storage()
+        .instance()
+        .get(&DataKey::ProtocolConfig)
+        .unwrap_or_else(ProtocolConfig::default_config)
+}
+
+/// Overwrite the protocol configuration.
+pub fn set_protocol_config(env: &Env, config: &ProtocolConfig) {
+    bump_instance(env);
+    env.storage()
+        .instance()
+        .set(&DataKey::ProtocolConfig, config);
+}
+
 // ── Persistent Storage Helpers ───────────────────────────────────────
 
 /// Extend the TTL for a persistent storage key.
-fn bump_persistent(env: &Env, key: &DataKey) {
+fn bump_persistent<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
     env.storage().persistent().extend_ttl(
         key,
         PERSISTENT_LIFETIME_THRESHOLD,
@@ -103,10 +742,105 @@ fn bump_persistent(env: &Env, key: &DataKey) {
     );
 }
 
+/// Extend the TTL of a persistent key to the full bump amount, regardless of
+/// how much TTL it currently has left. No-op if the key does not exist.
+fn extend_persistent_fully<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    if env.storage().persistent().has(key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(key, PERSISTENT_BUMP_AMOUNT, PERSISTENT_BUMP_AMOUNT);
+    }
+}
+
+/// Record that a project's config and state were just extended to the full
+/// bump amount and emit `ttl_bump` with the new horizon.
+fn record_project_horizon(env: &Env, id: u64) {
+    let live_until = env.ledger().sequence() + PERSISTENT_BUMP_AMOUNT;
+    let key = ProjectKey::TtlHorizon(id);
+    env.storage().persistent().set(&key, &live_until);
+    extend_persistent_fully(env, &key);
+    events::emit_ttl_extended(env, id, live_until);
+}
+
+/// Bump a project's config and state if they are below the TTL threshold,
+/// recording and announcing the new horizon when the bump takes effect.
+///
+/// Mirrors the host's own rule: an entry is only extended once fewer than
+/// `PERSISTENT_LIFETIME_THRESHOLD` ledgers remain, so a horizon closer than
+/// that means this bump extended it. Projects without a recorded horizon are
+/// treated as bumped.
+fn bump_project_entries(env: &Env, id: u64) {
+    bump_persistent(env, &ProjectKey::ProjConfig(id));
+    bump_persistent(env, &ProjectKey::ProjState(id));
+    let horizon: Option<u32> = env.storage().persistent().get(&ProjectKey::TtlHorizon(id));
+    let threshold = env.ledger().sequence() + PERSISTENT_LIFETIME_THRESHOLD;
+    let bumped = match horizon {
+        Some(live_until) => live_until < threshold,
+        None => true,
+    };
+    if bumped {
+        record_project_horizon(env, id);
+    }
+}
+
+/// Extend every persistent entry belonging to a project to the full bump
+/// amount: config, state, per-token balances, and optional per-project
+/// settings.
+pub fn extend_project_ttl(env: &Env, config: &ProjectConfig) {
+    let id = config.id;
+    record_project_horizon(env, id);
+    extend_persistent_fully(env, &ProjectKey::ProjConfig(id));
+    extend_persistent_fully(env, &ProjectKey::ProjState(id));
+    for token in config.accepted_tokens.iter() {
+        extend_persistent_fully(env, &ProjectKey::TokenBalance(id, token.clone()));
+        extend_persistent_fully(env, &ProjectKey::Flows(id, token));
+    }
+    extend_persistent_fully(env, &ProjectKey::Attestations(id));
+    extend_persistent_fully(env, &ProjectKey::ProjVerifier(id));
+    extend_persistent_fully(env, &ProjectKey::ProjOracles(id));
+    extend_persistent_fully(env, &ProjectKey::Depends(id));
+    extend_persistent_fully(env, &ProjectKey::Cadence(id));
+    extend_persistent_fully(env, &ProjectKey::Milestones(id));
+    extend_persistent_fully(env, &ProjectKey::LastRentBump(id));
+    extend_persistent_fully(env, &ProjectKey::SettledAt(id));
+    extend_persistent_fully(env, &ProjectKey::ProjectDonors(id));
+}
+
+/// Timestamp of the last escrow-funded bump of `project_id`, if any.
+pub fn get_last_rent_bump(env: &Env, project_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&ProjectKey::LastRentBump(project_id))
+}
+
+/// Timestamp `project_id` reached a terminal state, if recorded.
+pub fn get_settled_at(env: &Env, project_id: u64) -> Option<u64> {
+    let key = ProjectKey::SettledAt(project_id);
+    let settled_at: Option<u64> = env.storage().persistent().get(&key);
+    if settled_at.is_some() {
+        bump_persistent(env, &key);
+    }
+    settled_at
+}
+
+/// Record that `project_id` reached a terminal state at `timestamp`.
+pub fn set_settled_at(env: &Env, project_id: u64, timestamp: u64) {
+    let key = ProjectKey::SettledAt(project_id);
+    env.storage().persistent().set(&key, &timestamp);
+    bump_persistent(env, &key);
+}
+
+/// Record an escrow-funded bump of `project_id` at `timestamp`.
+pub fn set_last_rent_bump(env: &Env, project_id: u64, timestamp: u64) {
+    let key = ProjectKey::LastRentBump(project_id);
+    env.storage().persistent().set(&key, &timestamp);
+    bump_persistent(env, &key);
+}
+
 /// Save both the immutable config and initial mutable state for a new project.
 pub fn save_project(env: &Env, project: &Project) {
-    let config_key = DataKey::ProjConfig(project.id);
-    let state_key = DataKey::ProjState(project.id);
+    let config_key = ProjectKey::ProjConfig(project.id);
+    let state_key = ProjectKey::ProjState(project.id);
 
     let config = ProjectConfig {
         id: project.id,
@@ -115,15 +849,19 @@ pub fn save_project(env: &Env, project: &Project) {
         goal: project.goal,
         proof_hash: project.proof_hash.clone(),
         deadline: project.deadline,
+        quorum: project.quorum,
+        goal_unit: project.goal_unit.clone(),
     };
 
     let state = ProjectState {
         status: project.status.clone(),
         donation_count: project.donation_count,
+        flags: 0,
+        released_bps: 0,
     };
 
     env.storage().persistent().set(&config_key, &config);
-    env.storage().persistent().set(&state_key, &state);
+    write_project_state(env, &state_key, &state);
     bump_persistent(env, &config_key);
     bump_persistent(env, &state_key);
 
@@ -150,11 +888,19 @@ pub fn save_project(env: &Env, project: &Project) {
 //     }
 // }
 
-/// Load only the immutable project configuration.
+/// Overwrite the configuration of an existing project.
+///
+/// Only for explicit amendment paths; registration uses [`save_project`].
+pub fn save_project_config(env: &Env, config: &ProjectConfig) {
+    let key = ProjectKey::ProjConfig(config.id);
+    env.storage().persistent().set(&key, config);
+    bump_persistent(env, &key);
+}
+
+/// Load only the project configuration.
 ///
 /// This helper panics with a generic string if the project does not exist. It
 /// is a thin wrapper around [`maybe_load_project_config`].
-#[allow(dead_code)]
 pub fn load_project_config(env: &Env, id: u64) -> ProjectConfig {
     maybe_load_project_config(env, id).expect("project not found")
 }
@@ -163,18 +909,29 @@ pub fn load_project_config(env: &Env, id: u64) -> ProjectConfig {
 ///
 /// Panics with a generic string if the project does not exist; delegates to
 /// [`maybe_load_project_state`].
-#[allow(dead_code)]
 pub fn load_project_state(env: &Env, id: u64) -> ProjectState {
     maybe_load_project_state(env, id).expect("project not found")
 }
 
 /// Save only the mutable project state (optimized for deposits/verification).
 pub fn save_project_state(env: &Env, id: u64, state: &ProjectState) {
-    let key = DataKey::ProjState(id);
-    env.storage().persistent().set(&key, state);
+    let key = ProjectKey::ProjState(id);
+    write_project_state(env, &key, state);
     bump_persistent(env, &key);
 }
 
+/// Write `state` packed under `key`.
+fn write_project_state(env: &Env, key: &ProjectKey, state: &ProjectState) {
+    env.storage().persistent().set(key, &state.pack());
+}
+
+/// Read the state stored under `key`, panicking on an unknown layout
+/// version. Does not bump the TTL.
+fn read_project_state(env: &Env, key: &ProjectKey) -> Option<ProjectState> {
+    let packed: Option<u128> = env.storage().persistent().get(key);
+    packed.map(|packed| ProjectState::unpack(packed).expect("unsupported project state encoding"))
+}
+
 // ── New retrieval helpers ─────────────────────────────────────────
 
 /// Returns `true` if a project with the given `id` exists in persistent storage.
@@ -182,9 +939,8 @@ pub fn save_project_state(env: &Env, id: u64, state: &ProjectState) {
 /// This performs a *single* storage `has` check and does **not** bump the TTL.
 /// It can be useful for quick existence guards without expensive panics or
 /// unwrapping.
-#[allow(dead_code)]
 pub fn project_exists(env: &Env, id: u64) -> bool {
-    let config_key = DataKey::ProjConfig(id);
+    let config_key = ProjectKey::ProjConfig(id);
     env.storage().persistent().has(&config_key)
 }
 
@@ -195,7 +951,7 @@ pub fn project_exists(env: &Env, id: u64) -> bool {
 /// exist **no TTL bump occurs**.
 #[allow(dead_code)]
 pub fn maybe_load_project_config(env: &Env, id: u64) -> Option<ProjectConfig> {
-    let key = DataKey::ProjConfig(id);
+    let key = ProjectKey::ProjConfig(id);
     let opt: Option<ProjectConfig> = env.storage().persistent().get(&key);
     if opt.is_some() {
         bump_persistent(env, &key);
@@ -208,12 +964,12 @@ pub fn maybe_load_project_config(env: &Env, id: u64) -> Option<ProjectConfig> {
 /// Works analogously to [`maybe_load_project_config`].
 #[allow(dead_code)]
 pub fn maybe_load_project_state(env: &Env, id: u64) -> Option<ProjectState> {
-    let key = DataKey::ProjState(id);
-    let opt: Option<ProjectState> = env.storage().persistent().get(&key);
-    if opt.is_some() {
+    let key = ProjectKey::ProjState(id);
+    let state = read_project_state(env, &key);
+    if state.is_some() {
         bump_persistent(env, &key);
     }
-    opt
+    state
 }
 
 /// Fetch both config and state in one call.
@@ -226,22 +982,17 @@ pub fn maybe_load_project_state(env: &Env, id: u64) -> Option<ProjectState> {
 ///
 /// Panics with `project not found` if either component is missing.
 pub fn load_project_pair(env: &Env, id: u64) -> (ProjectConfig, ProjectState) {
-    let config_key = DataKey::ProjConfig(id);
-    let state_key = DataKey::ProjState(id);
+    let config_key = ProjectKey::ProjConfig(id);
+    let state_key = ProjectKey::ProjState(id);
 
     let config: ProjectConfig = env
         .storage()
         .persistent()
         .get(&config_key)
         .expect("project not found");
-    let state: ProjectState = env
-        .storage()
-        .persistent()
-        .get(&state_key)
-        .expect("project not found");
+    let state = read_project_state(env, &state_key).expect("project not found");
 
-    bump_persistent(env, &config_key);
-    bump_persistent(env, &state_key);
+    bump_project_entries(env, id);
 
     (config, state)
 }
@@ -259,6 +1010,8 @@ pub fn load_project(env: &Env, id: u64) -> Project {
         goal: config.goal,
         proof_hash: config.proof_hash,
         deadline: config.deadline,
+        quorum: config.quorum,
+        goal_unit: config.goal_unit,
         status: state.status,
         donation_count: state.donation_count,
     }
@@ -271,7 +1024,7 @@ pub fn load_project(env: &Env, id: u64) -> Project {
 /// TTL of both underlying entries when present.
 #[allow(dead_code)]
 pub fn maybe_load_project(env: &Env, id: u64) -> Option<Project> {
-    let config_key = DataKey::ProjConfig(id);
+    let config_key = ProjectKey::ProjConfig(id);
     // We test existence on one key only; if a project is corrupt (config
     // without state) the subsequent `get` will still panic, which is acceptable
     // since such a situation should never occur in normal operation.
@@ -286,6 +1039,8 @@ pub fn maybe_load_project(env: &Env, id: u64) -> Option<Project> {
         goal: config.goal,
         proof_hash: config.proof_hash,
         deadline: config.deadline,
+        quorum: config.quorum,
+        goal_unit: config.goal_unit,
         status: state.status,
         donation_count: state.donation_count,
     })
@@ -293,49 +1048,1868 @@ pub fn maybe_load_project(env: &Env, id: u64) -> Option<Project> {
 
 /// Retrieve the balance of `token` for `project_id`.
 pub fn get_token_balance(env: &Env, project_id: u64, token: &Address) -> i128 {
-    let key = DataKey::TokenBalance(project_id, token.clone());
-    let balance = env.storage().persistent().get(&key).unwrap_or(0);
-    bump_persistent(env, &key);
-    balance
+    let key = ProjectKey::TokenBalance(project_id, token.clone());
+    let balance: Option<i128> = env.storage().persistent().get(&key);
+    match balance {
+        Some(balance) => {
+            bump_persistent(env, &key);
+            balance
+        }
+        None => 0,
+    }
 }
 
 /// Set the balance of `token` for `project_id`.
+///
+/// Keeps the token's `EscrowTotal` in step with the change.
 pub fn set_token_balance(env: &Env, project_id: u64, token: &Address, balance: i128) {
-    let key = DataKey::TokenBalance(project_id, token.clone());
+    let key = ProjectKey::TokenBalance(project_id, token.clone());
+    let previous: i128 = env.storage().persistent().get(&key).unwrap_or(0);
     env.storage().persistent().set(&key, &balance);
     bump_persistent(env, &key);
+    if balance != previous {
+        let total_key = PoolKey::EscrowTotal(token.clone());
+        let total = get_escrow_total(env, token) + balance - previous;
+        env.storage().persistent().set(&total_key, &total);
+        bump_persistent(env, &total_key);
+    }
 }
 
-/// Add `amount` to the existing balance of `token` for `project_id`.
-/// Returns the new balance.
-pub fn add_to_token_balance(env: &Env, project_id: u64, token: &Address, amount: i128) -> i128 {
-    let current = get_token_balance(env, project_id, token);
-    let new_balance = current + amount;
-    set_token_balance(env, project_id, token, new_balance);
-    new_balance
+/// Escrow inflow and outflow of `token` for `project_id`.
+pub fn get_token_flows(env: &Env, project_id: u64, token: &Address) -> TokenFlows {
+    let key = ProjectKey::Flows(project_id, token.clone());
+    let flows: Option<TokenFlows> = env.storage().persistent().get(&key);
+    match flows {
+        Some(flows) => {
+            bump_persistent(env, &key);
+            flows
+        }
+        None => TokenFlows::default(),
+    }
 }
 
-/// Zero out the balance of `token` for `project_id` and return what it was.
-/// Called during `verify_and_release` after transferring funds to the creator.
-#[allow(dead_code)]
-pub fn drain_token_balance(env: &Env, project_id: u64, token: &Address) -> i128 {
-    let balance = get_token_balance(env, project_id, token);
-    if balance > 0 {
-        set_token_balance(env, project_id, token, 0);
+/// Store the escrow inflow and outflow of `token` for `project_id`.
+pub fn set_token_flows(env: &Env, project_id: u64, token: &Address, flows: &TokenFlows) {
+    let key = ProjectKey::Flows(project_id, token.clone());
+    env.storage().persistent().set(&key, flows);
+    bump_persistent(env, &key);
+}
+
+/// Sum of every project's escrowed balance in `token`.
+pub fn get_escrow_total(env: &Env, token: &Address) -> i128 {
+    let key = PoolKey::EscrowTotal(token.clone());
+    let total: Option<i128> = env.storage().persistent().get(&key);
+    match total {
+        Some(total) => {
+            bump_persistent(env, &key);
+            total
+        }
+        None => 0,
     }
-    balance
 }
 
-/// Build a `ProjectBalances` snapshot by reading each accepted token's balance.
-#[allow(dead_code)]
-pub fn get_all_balances(env: &Env, project: &Project) -> ProjectBalances {
-    let mut balances: Vec<TokenBalance> = Vec::new(env);
-    for token in project.accepted_tokens.iter() {
-        let balance = get_token_balance(env, project.id, &token);
-        balances.push_back(TokenBalance { token, balance });
+/// Premiums collected in `token` and held for donor protection.
+pub fn get_insurance_pool(env: &Env, token: &Address) -> i128 {
+    let key = PoolKey::InsurancePool(token.clone());
+    let pool: Option<i128> = env.storage().persistent().get(&key);
+    match pool {
+        Some(pool) => {
+            bump_persistent(env, &key);
+            pool
+        }
+        None => 0,
     }
-    ProjectBalances {
-        project_id: project.id,
-        balances,
+}
+
+/// Add `amount` to the insurance pool of `token`.
+pub fn add_to_insurance_pool(env: &Env, token: &Address, amount: i128) {
+    let key = PoolKey::InsurancePool(token.clone());
+    let pool = get_insurance_pool(env, token) + amount;
+    env.storage().persistent().set(&key, &pool);
+    bump_persistent(env, &key);
+}
+
+/// Fee bps overriding the protocol fee for `project_id`, if any.
+pub fn get_fee_override(env: &Env, project_id: u64) -> Option<u32> {
+    let key = ProjectKey::FeeOverride(project_id);
+    let fee_bps: Option<u32> = env.storage().persistent().get(&key);
+    if fee_bps.is_some() {
+        bump_persistent(env, &key);
+    }
+    fee_bps
+}
+
+/// Set or clear the fee override of `project_id`.
+pub fn set_fee_override(env: &Env, project_id: u64, fee_bps: Option<u32>) {
+    let key = ProjectKey::FeeOverride(project_id);
+    match fee_bps {
+        Some(fee_bps) => {
+            env.storage().persistent().set(&key, &fee_bps);
+            bump_persistent(env, &key);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Per-donor match cap of `project_id`; `0` when matching is off.
+pub fn get_match_cap(env: &Env, project_id: u64) -> i128 {
+    let key = ProjectKey::MatchCap(project_id);
+    let cap: Option<i128> = env.storage().persistent().get(&key);
+    match cap {
+        Some(cap) => {
+            bump_persistent(env, &key);
+            cap
+        }
+        None => 0,
+    }
+}
+
+/// Set the per-donor match cap of `project_id`; `0` removes it.
+pub fn set_match_cap(env: &Env, project_id: u64, cap: i128) {
+    let key = ProjectKey::MatchCap(project_id);
+    if cap == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &cap);
+        bump_persistent(env, &key);
+    }
+}
+
+/// Portion of `donor`'s giving to `project_id` tagged for matching.
+pub fn get_match_eligible(env: &Env, project_id: u64, donor: &Address) -> i128 {
+    let key = DonorKey::MatchEligible(project_id, donor.clone());
+    let eligible: Option<i128> = env.storage().persistent().get(&key);
+    match eligible {
+        Some(eligible) => {
+            bump_persistent(env, &key);
+            eligible
+        }
+        None => 0,
+    }
+}
+
+/// Store the portion of `donor`'s giving to `project_id` tagged for matching.
+pub fn set_match_eligible(env: &Env, project_id: u64, donor: &Address, eligible: i128) {
+    let key = DonorKey::MatchEligible(project_id, donor.clone());
+    env.storage().persistent().set(&key, &eligible);
+    bump_persistent(env, &key);
+}
+
+/// Rounding residue held in `token` awaiting `sweep_dust`.
+pub fn get_dust(env: &Env, token: &Address) -> i128 {
+    let key = PoolKey::Dust(token.clone());
+    let dust: Option<i128> = env.storage().persistent().get(&key);
+    match dust {
+        Some(dust) => {
+            bump_persistent(env, &key);
+            dust
+        }
+        None => 0,
+    }
+}
+
+/// Set the dust ledger of `token` to `amount`, removing it once empty.
+pub fn set_dust(env: &Env, token: &Address, amount: i128) {
+    let key = PoolKey::Dust(token.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+        bump_persistent(env, &key);
+    }
+}
+
+/// Amount of `token` released during throttle window `window`.
+pub fn get_release_outflow(env: &Env, token: &Address, window: u64) -> i128 {
+    let key = DataKey::ReleaseOutflow(token.clone());
+    let outflow: Option<(u64, i128)> = env.storage().persistent().get(&key);
+    match outflow {
+        Some((stored, released)) if stored == window => released,
+        _ => 0,
     }
 }
+
+/// Record that `released` of `token` has gone out during window `window`.
+pub fn set_release_outflow(env: &Env, token: &Address, window: u64, released: i128) {
+    let key = DataKey::ReleaseOutflow(token.clone());
+    env.storage().persistent().set(&key, &(window, released));
+    bump_persistent(env, &key);
+}
+
+/// Releases waiting for throttle room, oldest first.
+pub fn get_release_queue(env: &Env) -> Vec<QueuedRelease> {
+    let key = DataKey::ReleaseQueue;
+    let queue: Option<Vec<QueuedRelease>> = env.storage().persistent().get(&key);
+    match queue {
+        Some(queue) => {
+            bump_persistent(env, &key);
+            queue
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Persist the release queue, removing the entry once it is empty.
+pub fn set_release_queue(env: &Env, queue: &Vec<QueuedRelease>) {
+    let key = DataKey::ReleaseQueue;
+    if queue.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, queue);
+        bump_persistent(env, &key);
+    }
+}
+
+/// Funds reserved for refund sponsorship in `token`.
+pub fn get_sponsor_pool(env: &Env, token: &Address) -> i128 {
+    let key = PoolKey::SponsorPool(token.clone());
+    let pool: Option<i128> = env.storage().persistent().get(&key);
+    match pool {
+        Some(pool) => {
+            bump_persistent(env, &key);
+            pool
+        }
+        None => 0,
+    }
+}
+
+/// Set the sponsorship pool of `token` to `amount`.
+pub fn set_sponsor_pool(env: &Env, token: &Address, amount: i128) {
+    let key = PoolKey::SponsorPool(token.clone());
+    env.storage().persistent().set(&key, &amount);
+    bump_persistent(env, &key);
+}
+
+/// Grants of `token` in the commons pool.
+pub fn get_commons_pool(env: &Env, token: &Address) -> i128 {
+    let key = PoolKey::CommonsPool(token.clone());
+    let pool: Option<i128> = env.storage().persistent().get(&key);
+    match pool {
+        Some(pool) => {
+            bump_persistent(env, &key);
+            pool
+        }
+        None => 0,
+    }
+}
+
+/// Add `amount` to the commons pool of `token`.
+pub fn add_to_commons_pool(env: &Env, token: &Address, amount: i128) {
+    let key = PoolKey::CommonsPool(token.clone());
+    let pool = get_commons_pool(env, token) + amount;
+    env.storage().persistent().set(&key, &pool);
+    bump_persistent(env, &key);
+}
+
+/// Fees of `token` held for the treasury.
+pub fn get_fee_vault(env: &Env, token: &Address) -> i128 {
+    let key = PoolKey::FeeVault(token.clone());
+    let held: Option<i128> = env.storage().persistent().get(&key);
+    match held {
+        Some(held) => {
+            bump_persistent(env, &key);
+            held
+        }
+        None => 0,
+    }
+}
+
+/// Add `amount` to the fees of `token` held for the treasury.
+pub fn add_to_fee_vault(env: &Env, token: &Address, amount: i128) {
+    let key = PoolKey::FeeVault(token.clone());
+    let held = get_fee_vault(env, token) + amount;
+    env.storage().persistent().set(&key, &held);
+    bump_persistent(env, &key);
+}
+
+/// Released funds of `project_id` in `token` awaiting the creator.
+pub fn get_claimable(env: &Env, project_id: u64, token: &Address) -> i128 {
+    let key = ProjectKey::Claimable(project_id, token.clone());
+    let claimable: Option<i128> = env.storage().persistent().get(&key);
+    match claimable {
+        Some(claimable) => {
+            bump_persistent(env, &key);
+            claimable
+        }
+        None => 0,
+    }
+}
+
+/// Store the claimable releases of `project_id` in `token`, keeping its
+/// `ClaimableTotal` in step. Removed once withdrawn.
+pub fn set_claimable(env: &Env, project_id: u64, token: &Address, amount: i128) {
+    let key = ProjectKey::Claimable(project_id, token.clone());
+    let previous = get_claimable(env, project_id, token);
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+        bump_persistent(env, &key);
+    }
+    let total_key = PoolKey::ClaimableTotal(token.clone());
+    let total = get_claimable_total(env, token) + amount - previous;
+    env.storage().persistent().set(&total_key, &total);
+    bump_persistent(env, &total_key);
+}
+
+/// Sum of every project's claimable releases in `token`.
+pub fn get_claimable_total(env: &Env, token: &Address) -> i128 {
+    let key = PoolKey::ClaimableTotal(token.clone());
+    let total: Option<i128> = env.storage().persistent().get(&key);
+    match total {
+        Some(total) => {
+            bump_persistent(env, &key);
+            total
+        }
+        None => 0,
+    }
+}
+
+/// Amount of `token` paid as sponsorship during period `period`.
+pub fn get_sponsor_spent(env: &Env, token: &Address, period: u64) -> i128 {
+    let key = DataKey::SponsorSpent(token.clone());
+    let spent: Option<(u64, i128)> = env.storage().persistent().get(&key);
+    match spent {
+        Some((stored, spent)) if stored == period => spent,
+        _ => 0,
+    }
+}
+
+/// Record that `spent` of `token` has been paid during period `period`.
+pub fn set_sponsor_spent(env: &Env, token: &Address, period: u64, spent: i128) {
+    let key = DataKey::SponsorSpent(token.clone());
+    env.storage().persistent().set(&key, &(period, spent));
+    bump_persistent(env, &key);
+}
+
+/// Rent sponsorship of `project_id` in `token` as `(contributed,
+/// remaining)`; zero if never sponsored.
+pub fn get_rent_fund(env: &Env, project_id: u64, token: &Address) -> (i128, i128) {
+    let key = ProjectKey::RentFund(project_id, token.clone());
+    let fund: Option<(i128, i128)> = env.storage().persistent().get(&key);
+    match fund {
+        Some(fund) => {
+            bump_persistent(env, &key);
+            fund
+        }
+        None => (0, 0),
+    }
+}
+
+/// Store the rent fund of `project_id` in `token`, keeping its
+/// `RentFundTotal` in step. Removed once fully claimed.
+pub fn set_rent_fund(
+    env: &Env,
+    project_id: u64,
+    token: &Address,
+    contributed: i128,
+    remaining: i128,
+) {
+    let key = ProjectKey::RentFund(project_id, token.clone());
+    let (_, previous) = get_rent_fund(env, project_id, token);
+    if contributed == 0 && remaining == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage()
+            .persistent()
+            .set(&key, &(contributed, remaining));
+        bump_persistent(env, &key);
+    }
+    if remaining != previous {
+        let total_key = PoolKey::RentFundTotal(token.clone());
+        let total = get_rent_fund_total(env, token) + remaining - previous;
+        env.storage().persistent().set(&total_key, &total);
+        bump_persistent(env, &total_key);
+    }
+}
+
+/// Sum of every project's unspent rent sponsorship in `token`.
+pub fn get_rent_fund_total(env: &Env, token: &Address) -> i128 {
+    let key = PoolKey::RentFundTotal(token.clone());
+    let total: Option<i128> = env.storage().persistent().get(&key);
+    match total {
+        Some(total) => {
+            bump_persistent(env, &key);
+            total
+        }
+        None => 0,
+    }
+}
+
+/// `sponsor`'s unclaimed contribution to the rent fund of `project_id`.
+pub fn get_rent_sponsorship(
+    env: &Env,
+    project_id: u64,
+    sponsor: &Address,
+    token: &Address,
+) -> i128 {
+    let key = ProjectKey::RentSponsor(project_id, sponsor.clone(), token.clone());
+    let amount: Option<i128> = env.storage().persistent().get(&key);
+    match amount {
+        Some(amount) => {
+            bump_persistent(env, &key);
+            amount
+        }
+        None => 0,
+    }
+}
+
+/// Store `sponsor`'s unclaimed contribution; `0` removes the entry.
+pub fn set_rent_sponsorship(
+    env: &Env,
+    project_id: u64,
+    sponsor: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    let key = ProjectKey::RentSponsor(project_id, sponsor.clone(), token.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+        bump_persistent(env, &key);
+    }
+}
+
+/// Funds reserved for keeper rewards in `token`.
+pub fn get_keeper_pool(env: &Env, token: &Address) -> i128 {
+    let key = PoolKey::KeeperPool(token.clone());
+    let pool: Option<i128> = env.storage().persistent().get(&key);
+    match pool {
+        Some(pool) => {
+            bump_persistent(env, &key);
+            pool
+        }
+        None => 0,
+    }
+}
+
+/// Set the keeper pool of `token` to `amount`.
+pub fn set_keeper_pool(env: &Env, token: &Address, amount: i128) {
+    let key = PoolKey::KeeperPool(token.clone());
+    env.storage().persistent().set(&key, &amount);
+    bump_persistent(env, &key);
+}
+
+/// Premium rate locked for an insured project, if it opted in.
+pub fn get_insurance(env: &Env, project_id: u64) -> Option<u32> {
+    let key = ProjectKey::Insurance(project_id);
+    let premium_bps: Option<u32> = env.storage().persistent().get(&key);
+    if premium_bps.is_some() {
+        bump_persistent(env, &key);
+    }
+    premium_bps
+}
+
+/// Lock `premium_bps` as the premium rate of `project_id`.
+pub fn set_insurance(env: &Env, project_id: u64, premium_bps: u32) {
+    let key = ProjectKey::Insurance(project_id);
+    env.storage().persistent().set(&key, &premium_bps);
+    bump_persistent(env, &key);
+}
+
+/// Add `amount` to the existing balance of `token` for `project_id`.
+/// Returns the new balance.
+pub fn add_to_token_balance(env: &Env, project_id: u64, token: &Address, amount: i128) -> i128 {
+    let current = get_token_balance(env, project_id, token);
+    let new_balance = current + amount;
+    set_token_balance(env, project_id, token, new_balance);
+    new_balance
+}
+
+/// Delete the balance entry of `token` for `project_id`. Only for tokens
+/// the project no longer accepts and whose balance is zero.
+pub fn remove_token_balance(env: &Env, project_id: u64, token: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&ProjectKey::TokenBalance(project_id, token.clone()));
+}
+
+/// Zero out the balance of `token` for `project_id` and return what it was.
+/// Called when a verified project completes, to pay the creator the rest.
+pub fn drain_token_balance(env: &Env, project_id: u64, token: &Address) -> i128 {
+    let balance = get_token_balance(env, project_id, token);
+    if balance > 0 {
+        set_token_balance(env, project_id, token, 0);
+    }
+    balance
+}
+
+/// Build a `ProjectBalances` snapshot by reading each accepted token's balance.
+pub fn get_all_balances(env: &Env, project: &Project) -> ProjectBalances {
+    let mut balances: Vec<TokenBalance> = Vec::new(env);
+    for token in project.accepted_tokens.iter() {
+        let balance = get_token_balance(env, project.id, &token);
+        balances.push_back(TokenBalance { token, balance });
+    }
+    ProjectBalances {
+        project_id: project.id,
+        balances,
+    }
+}
+
+/// Read the balances of at most `limit` accepted tokens, starting at index
+/// `offset` of the project's accepted-token list.
+///
+/// Only the requested window is read, so the cost is bounded by `limit`
+/// regardless of how many tokens the project accepts.
+pub fn get_balances_page(
+    env: &Env,
+    config: &ProjectConfig,
+    offset: u32,
+    limit: u32,
+) -> ProjectBalances {
+    let mut balances: Vec<TokenBalance> = Vec::new(env);
+    let end = offset
+        .saturating_add(limit)
+        .min(config.accepted_tokens.len());
+    for i in offset..end {
+        let token = config.accepted_tokens.get_unchecked(i);
+        let balance = get_token_balance(env, config.id, &token);
+        balances.push_back(TokenBalance { token, balance });
+    }
+    ProjectBalances {
+        project_id: config.id,
+        balances,
+    }
+}
+
+// ── Creator reputation ───────────────────────────────────────────────
+
+/// Load the reputation record for `creator`, or an all-zero record if the
+/// address has never registered a project.
+pub fn get_creator_record(env: &Env, creator: &Address) -> CreatorRecord {
+    let key = DataKey::CreatorRecord(creator.clone());
+    let record: Option<CreatorRecord> = env.storage().persistent().get(&key);
+    match record {
+        Some(record) => {
+            bump_persistent(env, &key);
+            record
+        }
+        None => CreatorRecord::default(),
+    }
+}
+
+/// Apply `f` to the reputation record for `creator` and persist the result.
+pub fn update_creator_record<F>(env: &Env, creator: &Address, f: F) -> CreatorRecord
+where
+    F: FnOnce(&mut CreatorRecord),
+{
+    let mut record = get_creator_record(env, creator);
+    f(&mut record);
+    let key = DataKey::CreatorRecord(creator.clone());
+    env.storage().persistent().set(&key, &record);
+    bump_persistent(env, &key);
+    record
+}
+
+/// Amount of `token` ever released to `creator`, across their projects.
+pub fn get_creator_released(env: &Env, creator: &Address, token: &Address) -> i128 {
+    let key = DataKey::CreatorReleased(creator.clone(), token.clone());
+    let released: Option<i128> = env.storage().persistent().get(&key);
+    match released {
+        Some(released) => {
+            bump_persistent(env, &key);
+            released
+        }
+        None => 0,
+    }
+}
+
+/// Add `amount` of `token` to the total released to `creator`.
+pub fn add_creator_released(env: &Env, creator: &Address, token: &Address, amount: i128) {
+    let key = DataKey::CreatorReleased(creator.clone(), token.clone());
+    let released = get_creator_released(env, creator, token);
+    env.storage().persistent().set(&key, &(released + amount));
+    bump_persistent(env, &key);
+}
+
+/// Projects `creator` has registered, oldest first.
+pub fn get_creator_projects(env: &Env, creator: &Address) -> Vec<u64> {
+    let key = DataKey::CreatorProjects(creator.clone());
+    let projects: Option<Vec<u64>> = env.storage().persistent().get(&key);
+    match projects {
+        Some(projects) => {
+            bump_persistent(env, &key);
+            projects
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Append `project_id` to `creator`'s project index.
+pub fn index_creator_project(env: &Env, creator: &Address, project_id: u64) {
+    let mut projects = get_creator_projects(env, creator);
+    projects.push_back(project_id);
+    let key = DataKey::CreatorProjects(creator.clone());
+    env.storage().persistent().set(&key, &projects);
+    bump_persistent(env, &key);
+}
+
+// ── Oracle attestations ──────────────────────────────────────────────
+
+/// Oracles that have attested `project_id`'s proof so far.
+pub fn get_attestations(env: &Env, project_id: u64) -> Vec<Address> {
+    let key = ProjectKey::Attestations(project_id);
+    let attestations: Option<Vec<Address>> = env.storage().persistent().get(&key);
+    match attestations {
+        Some(attestations) => {
+            bump_persistent(env, &key);
+            attestations
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Persist the attesting oracle set for `project_id`.
+pub fn set_attestations(env: &Env, project_id: u64, attestations: &Vec<Address>) {
+    let key = ProjectKey::Attestations(project_id);
+    env.storage().persistent().set(&key, attestations);
+    bump_persistent(env, &key);
+}
+
+// ── External verifiers ───────────────────────────────────────────────
+
+/// Returns `true` if `verifier` is on the admin-approved allowlist.
+pub fn is_verifier_approved(env: &Env, verifier: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ApprovedVerifier(verifier.clone()))
+        .unwrap_or(false)
+}
+
+/// Add `verifier` to, or remove it from, the allowlist.
+pub fn set_verifier_approved(env: &Env, verifier: &Address, approved: bool) {
+    let key = DataKey::ApprovedVerifier(verifier.clone());
+    if approved {
+        env.storage().persistent().set(&key, &true);
+        bump_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// The external verifier registered for `project_id`, if any.
+pub fn get_project_verifier(env: &Env, project_id: u64) -> Option<ProjectVerifier> {
+    let key = ProjectKey::ProjVerifier(project_id);
+    let verifier: Option<ProjectVerifier> = env.storage().persistent().get(&key);
+    if verifier.is_some() {
+        bump_persistent(env, &key);
+    }
+    verifier
+}
+
+/// Register the external verifier for `project_id`.
+pub fn set_project_verifier(env: &Env, project_id: u64, verifier: &ProjectVerifier) {
+    let key = ProjectKey::ProjVerifier(project_id);
+    env.storage().persistent().set(&key, verifier);
+    bump_persistent(env, &key);
+}
+
+/// Oracles assigned to `project_id`; empty if any oracle may attest.
+pub fn get_project_oracles(env: &Env, project_id: u64) -> Vec<Address> {
+    let key = ProjectKey::ProjOracles(project_id);
+    let oracles: Option<Vec<Address>> = env.storage().persistent().get(&key);
+    match oracles {
+        Some(oracles) => {
+            bump_persistent(env, &key);
+            oracles
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Assign `oracles` to `project_id`; an empty list removes the assignment.
+pub fn set_project_oracles(env: &Env, project_id: u64, oracles: &Vec<Address>) {
+    let key = ProjectKey::ProjOracles(project_id);
+    if oracles.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, oracles);
+        bump_persistent(env, &key);
+    }
+}
+
+// ── Project handles ──────────────────────────────────────────────────
+
+/// Project `creator` registered under `handle`, if any.
+pub fn get_project_by_handle(env: &Env, creator: &Address, handle: &Symbol) -> Option<u64> {
+    let key = DataKey::Handle(creator.clone(), handle.clone());
+    let id: Option<u64> = env.storage().persistent().get(&key);
+    if id.is_some() {
+        bump_persistent(env, &key);
+    }
+    id
+}
+
+/// Handle registered for `project_id`, if any.
+pub fn get_project_handle(env: &Env, project_id: u64) -> Option<Symbol> {
+    let key = ProjectKey::ProjHandle(project_id);
+    let handle: Option<Symbol> = env.storage().persistent().get(&key);
+    if handle.is_some() {
+        bump_persistent(env, &key);
+    }
+    handle
+}
+
+/// Register `handle` for `project_id` under `creator`, in both directions.
+pub fn set_project_handle(env: &Env, creator: &Address, handle: &Symbol, project_id: u64) {
+    let key = DataKey::Handle(creator.clone(), handle.clone());
+    env.storage().persistent().set(&key, &project_id);
+    bump_persistent(env, &key);
+    let key = ProjectKey::ProjHandle(project_id);
+    env.storage().persistent().set(&key, handle);
+    bump_persistent(env, &key);
+}
+
+/// Return the display metadata of `project_id`, if its creator set any.
+pub fn get_project_metadata(env: &Env, project_id: u64) -> Option<ProjectMetadata> {
+    let key = ProjectKey::Metadata(project_id);
+    let metadata: Option<ProjectMetadata> = env.storage().persistent().get(&key);
+    if metadata.is_some() {
+        bump_persistent(env, &key);
+    }
+    metadata
+}
+
+/// Replace the display metadata of `project_id`.
+pub fn set_project_metadata(env: &Env, project_id: u64, metadata: &ProjectMetadata) {
+    let key = ProjectKey::Metadata(project_id);
+    env.storage().persistent().set(&key, metadata);
+    bump_persistent(env, &key);
+}
+
+// ── Proof submissions ────────────────────────────────────────────────
+
+/// Return the proof awaiting review for `project_id`, if it has not lapsed.
+pub fn get_pending_proof(env: &Env, project_id: u64) -> Option<PendingProof> {
+    env.storage()
+        .temporary()
+        .get(&ProjectKey::PendingProof(project_id))
+}
+
+/// Store `proof` for review, replacing any earlier submission.
+pub fn set_pending_proof(env: &Env, project_id: u64, proof: &PendingProof) {
+    let key = ProjectKey::PendingProof(project_id);
+    env.storage().temporary().set(&key, proof);
+    env.storage().temporary().extend_ttl(
+        &key,
+        PendingProof::REVIEW_LEDGERS,
+        PendingProof::REVIEW_LEDGERS,
+    );
+}
+
+/// Drop the proof awaiting review for `project_id`.
+pub fn remove_pending_proof(env: &Env, project_id: u64) {
+    env.storage()
+        .temporary()
+        .remove(&ProjectKey::PendingProof(project_id));
+}
+
+// ── Project dependencies ─────────────────────────────────────────────
+
+/// Projects `project_id` depends on; empty if none.
+pub fn get_dependencies(env: &Env, project_id: u64) -> Vec<u64> {
+    let key = ProjectKey::Depends(project_id);
+    let deps: Option<Vec<u64>> = env.storage().persistent().get(&key);
+    match deps {
+        Some(deps) => {
+            bump_persistent(env, &key);
+            deps
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Replace the dependencies of `project_id`; an empty list removes them.
+pub fn set_dependencies(env: &Env, project_id: u64, deps: &Vec<u64>) {
+    let key = ProjectKey::Depends(project_id);
+    if deps.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, deps);
+        bump_persistent(env, &key);
+    }
+}
+
+// ── Oracle BLS keys ──────────────────────────────────────────────────
+
+/// Projects `oracle` has attested or been assigned to.
+pub fn get_oracle_projects(env: &Env, oracle: &Address) -> Vec<u64> {
+    let key = DataKey::OracleProjects(oracle.clone());
+    let projects: Option<Vec<u64>> = env.storage().persistent().get(&key);
+    match projects {
+        Some(projects) => {
+            bump_persistent(env, &key);
+            projects
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Record that `oracle` has attested or been assigned to `project_id`.
+pub fn index_oracle_project(env: &Env, oracle: &Address, project_id: u64) {
+    let mut projects = get_oracle_projects(env, oracle);
+    if projects.contains(project_id) {
+        return;
+    }
+    projects.push_back(project_id);
+    let key = DataKey::OracleProjects(oracle.clone());
+    env.storage().persistent().set(&key, &projects);
+    bump_persistent(env, &key);
+}
+
+/// Drop the project index of `oracle`.
+pub fn remove_oracle_projects(env: &Env, oracle: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::OracleProjects(oracle.clone()));
+}
+
+/// The BLS public key registered for `oracle`, if any.
+pub fn get_oracle_bls_key(env: &Env, oracle: &Address) -> Option<BytesN<96>> {
+    let key = DataKey::OracleBlsKey(oracle.clone());
+    let public_key: Option<BytesN<96>> = env.storage().persistent().get(&key);
+    if public_key.is_some() {
+        bump_persistent(env, &key);
+    }
+    public_key
+}
+
+/// Register or replace the BLS public key for `oracle`.
+pub fn set_oracle_bls_key(env: &Env, oracle: &Address, public_key: &BytesN<96>) {
+    let key = DataKey::OracleBlsKey(oracle.clone());
+    env.storage().persistent().set(&key, public_key);
+    bump_persistent(env, &key);
+}
+
+// ── Ongoing projects ─────────────────────────────────────────────────
+
+/// The re-attestation cadence for `project_id`, or `None` for one-shot projects.
+pub fn get_cadence(env: &Env, project_id: u64) -> Option<OngoingCadence> {
+    let key = ProjectKey::Cadence(project_id);
+    let cadence: Option<OngoingCadence> = env.storage().persistent().get(&key);
+    if cadence.is_some() {
+        bump_persistent(env, &key);
+    }
+    cadence
+}
+
+/// Persist the re-attestation cadence for `project_id`.
+pub fn set_cadence(env: &Env, project_id: u64, cadence: &OngoingCadence) {
+    let key = ProjectKey::Cadence(project_id);
+    env.storage().persistent().set(&key, cadence);
+    bump_persistent(env, &key);
+}
+
+// ── Contribution ledger ──────────────────────────────────────────────
+
+/// Cumulative amount `donor` has deposited into `project_id` in `token`.
+pub fn get_contribution(env: &Env, project_id: u64, donor: &Address, token: &Address) -> i128 {
+    let key = DonorKey::Contribution(project_id, donor.clone(), token.clone());
+    let amount: Option<i128> = env.storage().persistent().get(&key);
+    match amount {
+        Some(amount) => {
+            bump_persistent(env, &key);
+            amount
+        }
+        None => 0,
+    }
+}
+
+/// Record a deposit of `amount` by `donor` and return the new cumulative total.
+///
+/// The first deposit for a given (project, token) pair also appends that pair
+/// to the donor's `DonorProjects` index and the project's `ProjectDonors` index.
+pub fn add_contribution(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    token: &Address,
+    amount: i128,
+) -> i128 {
+    let key = DonorKey::Contribution(project_id, donor.clone(), token.clone());
+    let existing: Option<i128> = env.storage().persistent().get(&key);
+    if existing.is_none() {
+        let mut index = get_donor_projects(env, donor);
+        index.push_back((project_id, token.clone()));
+        let index_key = DonorKey::DonorProjects(donor.clone());
+        env.storage().persistent().set(&index_key, &index);
+        bump_persistent(env, &index_key);
+
+        let mut donors = get_project_donors(env, project_id);
+        donors.push_back((donor.clone(), token.clone()));
+        let donors_key = ProjectKey::ProjectDonors(project_id);
+        env.storage().persistent().set(&donors_key, &donors);
+        bump_persistent(env, &donors_key);
+    }
+    let total = existing.unwrap_or(0) + amount;
+    env.storage().persistent().set(&key, &total);
+    bump_persistent(env, &key);
+    total
+}
+
+/// Amount refunded to `donor` from `project_id` in `token`; `0` if none.
+pub fn get_refunded(env: &Env, project_id: u64, donor: &Address, token: &Address) -> i128 {
+    let key = DonorKey::Refunded(project_id, donor.clone(), token.clone());
+    match env.storage().persistent().get(&key) {
+        Some(amount) => {
+            bump_persistent(env, &key);
+            amount
+        }
+        None => 0,
+    }
+}
+
+/// Record that `donor` was refunded `amount` from `project_id` in `token`.
+pub fn set_refunded(env: &Env, project_id: u64, donor: &Address, token: &Address, amount: i128) {
+    let key = DonorKey::Refunded(project_id, donor.clone(), token.clone());
+    env.storage().persistent().set(&key, &amount);
+    bump_persistent(env, &key);
+}
+
+/// Escrow and total contributions of `token` when refunds of `project_id`
+/// opened, if a refund has been claimed.
+pub fn get_refund_basis(env: &Env, project_id: u64, token: &Address) -> Option<(i128, i128)> {
+    let key = ProjectKey::RefundBasis(project_id, token.clone());
+    let basis: Option<(i128, i128)> = env.storage().persistent().get(&key);
+    if basis.is_some() {
+        bump_persistent(env, &key);
+    }
+    basis
+}
+
+/// Fix the refund basis of `token` for `project_id`.
+pub fn set_refund_basis(env: &Env, project_id: u64, token: &Address, basis: (i128, i128)) {
+    let key = ProjectKey::RefundBasis(project_id, token.clone());
+    env.storage().persistent().set(&key, &basis);
+    bump_persistent(env, &key);
+}
+
+/// Non-refundable part of `donor`'s contribution to `project_id` in
+/// `token`, if they flagged any.
+pub fn get_grant(env: &Env, project_id: u64, donor: &Address, token: &Address) -> Option<Grant> {
+    let key = DonorKey::Granted(project_id, donor.clone(), token.clone());
+    let grant: Option<Grant> = env.storage().persistent().get(&key);
+    if grant.is_some() {
+        bump_persistent(env, &key);
+    }
+    grant
+}
+
+/// Flag `amount` more of `donor`'s contribution as non-refundable, routed
+/// per `route`. The latest route applies to the donor's whole grant.
+pub fn add_grant(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    token: &Address,
+    amount: i128,
+    route: GrantRoute,
+) {
+    let key = DonorKey::Granted(project_id, donor.clone(), token.clone());
+    let existing = get_grant(env, project_id, donor, token).map_or(0, |g| g.amount);
+    let grant = Grant {
+        amount: existing + amount,
+        route,
+    };
+    env.storage().persistent().set(&key, &grant);
+    bump_persistent(env, &key);
+}
+
+/// Cumulative deposit-time reference value of `donor`'s deposits into
+/// `project_id` in `token`; `0` if none were valued.
+pub fn get_contribution_value(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    token: &Address,
+) -> i128 {
+    let key = DonorKey::ContribValue(project_id, donor.clone(), token.clone());
+    let value: Option<i128> = env.storage().persistent().get(&key);
+    match value {
+        Some(value) => {
+            bump_persistent(env, &key);
+            value
+        }
+        None => 0,
+    }
+}
+
+/// Add `value` to the recorded reference value of a contribution.
+pub fn add_contribution_value(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    token: &Address,
+    value: i128,
+) {
+    let total = get_contribution_value(env, project_id, donor, token) + value;
+    let key = DonorKey::ContribValue(project_id, donor.clone(), token.clone());
+    env.storage().persistent().set(&key, &total);
+    bump_persistent(env, &key);
+}
+
+/// Time-weighted total of `donor`'s deposits into `project_id` in `token`.
+pub fn get_contribution_weight(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    token: &Address,
+) -> i128 {
+    let key = DonorKey::ContribWeight(project_id, donor.clone(), token.clone());
+    let weight: Option<i128> = env.storage().persistent().get(&key);
+    match weight {
+        Some(weight) => {
+            bump_persistent(env, &key);
+            weight
+        }
+        None => 0,
+    }
+}
+
+/// Add `weight` to the time-weighted total of a contribution.
+pub fn add_contribution_weight(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    token: &Address,
+    weight: i128,
+) {
+    let total = get_contribution_weight(env, project_id, donor, token) + weight;
+    let key = DonorKey::ContribWeight(project_id, donor.clone(), token.clone());
+    env.storage().persistent().set(&key, &total);
+    bump_persistent(env, &key);
+}
+
+/// Giving counters for `donor`; all zero if they never deposited.
+pub fn get_donor_stats(env: &Env, donor: &Address) -> DonorStats {
+    let key = DonorKey::DonorStats(donor.clone());
+    let stats: Option<DonorStats> = env.storage().persistent().get(&key);
+    match stats {
+        Some(stats) => {
+            bump_persistent(env, &key);
+            stats
+        }
+        None => DonorStats::default(),
+    }
+}
+
+/// Persist `donor`'s giving counters.
+pub fn set_donor_stats(env: &Env, donor: &Address, stats: &DonorStats) {
+    let key = DonorKey::DonorStats(donor.clone());
+    env.storage().persistent().set(&key, stats);
+    bump_persistent(env, &key);
+}
+
+/// `donor`'s contribution ledger, oldest entry first.
+pub fn get_donor_ledger(env: &Env, donor: &Address) -> Vec<LedgerEntry> {
+    let key = DonorKey::DonorLedger(donor.clone());
+    let ledger: Option<Vec<LedgerEntry>> = env.storage().persistent().get(&key);
+    match ledger {
+        Some(ledger) => {
+            bump_persistent(env, &key);
+            ledger
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Append `entry` to `donor`'s contribution ledger.
+pub fn add_ledger_entry(env: &Env, donor: &Address, entry: LedgerEntry) {
+    let mut ledger = get_donor_ledger(env, donor);
+    ledger.push_back(entry);
+    let key = DonorKey::DonorLedger(donor.clone());
+    env.storage().persistent().set(&key, &ledger);
+    bump_persistent(env, &key);
+}
+
+/// Return `true` if `donor` registered a deposit hook.
+pub fn has_deposit_hook(env: &Env, donor: &Address) -> bool {
+    let key = DonorKey::DepositHook(donor.clone());
+    let registered = env.storage().persistent().has(&key);
+    if registered {
+        bump_persistent(env, &key);
+    }
+    registered
+}
+
+/// Register or remove `donor`'s deposit hook.
+pub fn set_deposit_hook(env: &Env, donor: &Address, enabled: bool) {
+    let key = DonorKey::DepositHook(donor.clone());
+    if enabled {
+        env.storage().persistent().set(&key, &true);
+        bump_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Privacy preferences of `donor`; all visible if never set.
+pub fn get_donor_preferences(env: &Env, donor: &Address) -> DonorPreferences {
+    let key = DonorKey::DonorPrefs(donor.clone());
+    let prefs: Option<DonorPreferences> = env.storage().persistent().get(&key);
+    match prefs {
+        Some(prefs) => {
+            bump_persistent(env, &key);
+            prefs
+        }
+        None => DonorPreferences::default(),
+    }
+}
+
+/// Persist `donor`'s privacy preferences, removing the entry once every
+/// flag is back to its default.
+pub fn set_donor_preferences(env: &Env, donor: &Address, prefs: &DonorPreferences) {
+    let key = DonorKey::DonorPrefs(donor.clone());
+    if *prefs == DonorPreferences::default() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, prefs);
+        bump_persistent(env, &key);
+    }
+}
+
+/// Every (project, token) pair `donor` has contributed to.
+pub fn get_donor_projects(env: &Env, donor: &Address) -> Vec<(u64, Address)> {
+    let key = DonorKey::DonorProjects(donor.clone());
+    let index: Option<Vec<(u64, Address)>> = env.storage().persistent().get(&key);
+    match index {
+        Some(index) => {
+            bump_persistent(env, &key);
+            index
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Every (donor, token) pair that has contributed to `project_id`.
+pub fn get_project_donors(env: &Env, project_id: u64) -> Vec<(Address, Address)> {
+    let key = ProjectKey::ProjectDonors(project_id);
+    let donors: Option<Vec<(Address, Address)>> = env.storage().persistent().get(&key);
+    match donors {
+        Some(donors) => {
+            bump_persistent(env, &key);
+            donors
+        }
+        None => Vec::new(env),
+    }
+}
+
+// ── Cleanup ──────────────────────────────────────────────────────────
+
+/// Call `f` with every persistent entry keyed by project `config.id` alone
+/// or with one of its accepted tokens: the short name `storage_report`
+/// lists it under, its token, its key, and whether `cleanup` removes it
+/// once the project settles.
+///
+/// Entries also keyed by a donor, sponsor or allowlisted address cannot be
+/// enumerated from the project and are not visited; contributions are
+/// reached through `ProjectDonors` instead.
+fn for_each_project_key<F>(config: &ProjectConfig, mut f: F)
+where
+    F: FnMut(Symbol, Option<Address>, ProjectKey, bool),
+{
+    let id = config.id;
+    f(
+        symbol_short!("config"),
+        None,
+        ProjectKey::ProjConfig(id),
+        false,
+    );
+    f(
+        symbol_short!("state"),
+        None,
+        ProjectKey::ProjState(id),
+        false,
+    );
+    for token in config.accepted_tokens.iter() {
+        let per_token = [
+            (
+                symbol_short!("balance"),
+                ProjectKey::TokenBalance(id, token.clone()),
+                true,
+            ),
+            (
+                symbol_short!("flows"),
+                ProjectKey::Flows(id, token.clone()),
+                false,
+            ),
+            (
+                symbol_short!("refund"),
+                ProjectKey::RefundBasis(id, token.clone()),
+                true,
+            ),
+            (
+                symbol_short!("claimable"),
+                ProjectKey::Claimable(id, token.clone()),
+                false,
+            ),
+            (
+                symbol_short!("rent_fund"),
+                ProjectKey::RentFund(id, token.clone()),
+                false,
+            ),
+        ];
+        for (kind, key, pruned) in per_token {
+            f(kind, Some(token.clone()), key, pruned);
+        }
+    }
+    let single = [
+        (symbol_short!("attest"), ProjectKey::Attestations(id), true),
+        (
+            symbol_short!("verifier"),
+            ProjectKey::ProjVerifier(id),
+            false,
+        ),
+        (symbol_short!("oracles"), ProjectKey::ProjOracles(id), false),
+        (symbol_short!("handle"), ProjectKey::ProjHandle(id), false),
+        (symbol_short!("metadata"), ProjectKey::Metadata(id), false),
+        (symbol_short!("owners"), ProjectKey::ProjOwners(id), false),
+        (symbol_short!("depends"), ProjectKey::Depends(id), false),
+        (symbol_short!("cadence"), ProjectKey::Cadence(id), false),
+        (
+            symbol_short!("milestone"),
+            ProjectKey::Milestones(id),
+            false,
+        ),
+        (symbol_short!("schedule"), ProjectKey::Schedule(id), false),
+        (symbol_short!("vote_cfg"), ProjectKey::VoteConfig(id), false),
+        (symbol_short!("vote"), ProjectKey::DonorVote(id), false),
+        (symbol_short!("challenge"), ProjectKey::Challenge(id), false),
+        (symbol_short!("ext_vote"), ProjectKey::ExtVote(id), false),
+        (
+            symbol_short!("ext_count"),
+            ProjectKey::Extensions(id),
+            false,
+        ),
+        (symbol_short!("amendment"), ProjectKey::Amendment(id), false),
+        (symbol_short!("insurance"), ProjectKey::Insurance(id), false),
+        (symbol_short!("fee"), ProjectKey::FeeOverride(id), false),
+        (symbol_short!("match_cap"), ProjectKey::MatchCap(id), false),
+        (symbol_short!("pools"), ProjectKey::MatchPools(id), false),
+        (symbol_short!("force"), ProjectKey::PendingForce(id), false),
+        (
+            symbol_short!("migration"),
+            ProjectKey::PendingMigr(id),
+            false,
+        ),
+        (
+            symbol_short!("rent_bump"),
+            ProjectKey::LastRentBump(id),
+            true,
+        ),
+        (symbol_short!("ttl"), ProjectKey::TtlHorizon(id), false),
+        (symbol_short!("settled"), ProjectKey::SettledAt(id), false),
+        (symbol_short!("donors"), ProjectKey::ProjectDonors(id), true),
+    ];
+    for (kind, key, pruned) in single {
+        f(kind, None, key, pruned);
+    }
+}
+
+/// Remove a settled project's per-token balances, contribution ledger and
+/// attestation bookkeeping. Returns the number of entries removed.
+///
+/// Entries [`for_each_project_key`] does not mark for removal, such as
+/// `ProjConfig`, `ProjState` and `ProjVerifier`, are kept so the project
+/// remains queryable. Each donor's `DonorProjects` index is pruned of the
+/// project so `get_donor_history` never lists removed contributions.
+pub fn remove_settled_project_data(env: &Env, config: &ProjectConfig) -> u32 {
+    let storage = env.storage().persistent();
+    let mut removed = 0u32;
+    let mut remove = |key: Val| {
+        if storage.has(&key) {
+            storage.remove(&key);
+            removed += 1;
+        }
+    };
+
+    // Donor entries are found through `ProjectDonors`, so they go before it.
+    for (donor, token) in get_project_donors(env, config.id).iter() {
+        remove(DonorKey::Contribution(config.id, donor.clone(), token.clone()).into_val(env));
+        remove(DonorKey::ContribValue(config.id, donor.clone(), token.clone()).into_val(env));
+        remove(DonorKey::ContribWeight(config.id, donor.clone(), token.clone()).into_val(env));
+        remove(DonorKey::Refunded(config.id, donor.clone(), token.clone()).into_val(env));
+        remove(DonorKey::Granted(config.id, donor.clone(), token).into_val(env));
+        remove(DonorKey::MatchEligible(config.id, donor.clone()).into_val(env));
+
+        let index_key = DonorKey::DonorProjects(donor.clone());
+        let index = get_donor_projects(env, &donor);
+        let mut pruned = Vec::new(env);
+        for entry in index.iter() {
+            if entry.0 != config.id {
+                pruned.push_back(entry);
+            }
+        }
+        if pruned.is_empty() {
+            storage.remove(&index_key);
+        } else if pruned.len() != index.len() {
+            storage.set(&index_key, &pruned);
+        }
+    }
+    for_each_project_key(config, |_, _, key, pruned| {
+        if pruned {
+            remove(key.into_val(env));
+        }
+    });
+    removed
+}
+
+// ── Introspection ────────────────────────────────────────────────────
+
+/// Describe every persistent entry [`for_each_project_key`] lists for a
+/// project.
+///
+/// Reads values without bumping their TTLs so that monitoring does not
+/// itself keep dead data alive.
+pub fn storage_report(env: &Env, config: &ProjectConfig) -> StorageReport {
+    let id = config.id;
+    let mut entries = Vec::new(env);
+    let mut total_size = 0u32;
+    for_each_project_key(config, |kind, token, key, _| {
+        let value: Option<Val> = env.storage().persistent().get(&key);
+        let size = match value {
+            Some(value) => key.to_xdr(env).len() + value.to_xdr(env).len(),
+            None => 0,
+        };
+        total_size += size;
+        entries.push_back(StorageEntry {
+            kind,
+            token,
+            present: value.is_some(),
+            size,
+        });
+    });
+
+    let donors: Option<Vec<(Address, Address)>> = env
+        .storage()
+        .persistent()
+        .get(&ProjectKey::ProjectDonors(id));
+    StorageReport {
+        project_id: id,
+        entries,
+        contributions: donors.map(|d| d.len()).unwrap_or(0),
+        total_size,
+        last_rent_bump: env
+            .storage()
+            .persistent()
+            .get(&ProjectKey::LastRentBump(id)),
+    }
+}
+
+// ── Address freezes ──────────────────────────────────────────────────
+
+/// Reason hash of the active freeze on `address`, if any.
+pub fn get_freeze(env: &Env, address: &Address) -> Option<BytesN<32>> {
+    let key = DataKey::Frozen(address.clone());
+    let reason: Option<BytesN<32>> = env.storage().persistent().get(&key);
+    if reason.is_some() {
+        bump_persistent(env, &key);
+    }
+    reason
+}
+
+/// Freeze `address` with `reason_hash`, replacing any existing reason.
+pub fn set_freeze(env: &Env, address: &Address, reason_hash: &BytesN<32>) {
+    let key = DataKey::Frozen(address.clone());
+    env.storage().persistent().set(&key, reason_hash);
+    bump_persistent(env, &key);
+}
+
+/// Lift the freeze on `address`. Returns `false` if it was not frozen.
+pub fn remove_freeze(env: &Env, address: &Address) -> bool {
+    let key = DataKey::Frozen(address.clone());
+    let existed = env.storage().persistent().has(&key);
+    env.storage().persistent().remove(&key);
+    existed
+}
+
+/// Return `true` if `donor` is on the donor allowlist of `project_id`.
+pub fn is_allowed_donor(env: &Env, project_id: u64, donor: &Address) -> bool {
+    let key = ProjectKey::AllowedDonor(project_id, donor.clone());
+    let allowed = env.storage().persistent().has(&key);
+    if allowed {
+        bump_persistent(env, &key);
+    }
+    allowed
+}
+
+/// Add `donor` to, or remove them from, the donor allowlist of `project_id`.
+pub fn set_allowed_donor(env: &Env, project_id: u64, donor: &Address, allowed: bool) {
+    let key = ProjectKey::AllowedDonor(project_id, donor.clone());
+    if allowed {
+        env.storage().persistent().set(&key, &true);
+        bump_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+// ── Token pauses ─────────────────────────────────────────────────────
+
+/// Return `true` if deposits of `token` are suspended.
+pub fn is_token_paused(env: &Env, token: &Address) -> bool {
+    let key = DataKey::TokenPaused(token.clone());
+    let paused = env.storage().persistent().has(&key);
+    if paused {
+        bump_persistent(env, &key);
+    }
+    paused
+}
+
+/// Suspend or resume deposits of `token`.
+pub fn set_token_paused(env: &Env, token: &Address, paused: bool) {
+    let key = DataKey::TokenPaused(token.clone());
+    if paused {
+        env.storage().persistent().set(&key, &true);
+        bump_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Return the registration token allowlist, in the order tokens were added.
+pub fn get_allowed_tokens(env: &Env) -> Vec<Address> {
+    bump_instance(env);
+    env.storage()
+        .instance()
+        .get(&DataKey::AllowedTokens)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Replace the registration token allowlist.
+pub fn set_allowed_tokens(env: &Env, tokens: &Vec<Address>) {
+    env.storage()
+        .instance()
+        .set(&DataKey::AllowedTokens, tokens);
+    bump_instance(env);
+}
+
+// ── Timelocked actions ───────────────────────────────────────────────
+
+/// Reserve the next timelocked action ID.
+pub fn next_action_id(env: &Env) -> u64 {
+    bump_instance(env);
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ActionCount)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::ActionCount, &(id + 1));
+    id
+}
+
+/// Timelocked action `id`, if still queued.
+pub fn get_action(env: &Env, id: u64) -> Option<QueuedAction> {
+    let key = DataKey::Action(id);
+    let action: Option<QueuedAction> = env.storage().persistent().get(&key);
+    if action.is_some() {
+        bump_persistent(env, &key);
+    }
+    action
+}
+
+/// Queue `action` under `id`.
+pub fn set_action(env: &Env, id: u64, action: &QueuedAction) {
+    let key = DataKey::Action(id);
+    env.storage().persistent().set(&key, action);
+    bump_persistent(env, &key);
+}
+
+/// Drop timelocked action `id`.
+pub fn remove_action(env: &Env, id: u64) {
+    env.storage().persistent().remove(&DataKey::Action(id));
+}
+
+// ── Forced status changes ────────────────────────────────────────────
+
+/// Forced status change queued for `project_id`, if any.
+pub fn get_pending_force(env: &Env, project_id: u64) -> Option<PendingStatusChange> {
+    let key = ProjectKey::PendingForce(project_id);
+    let pending: Option<PendingStatusChange> = env.storage().persistent().get(&key);
+    if pending.is_some() {
+        bump_persistent(env, &key);
+    }
+    pending
+}
+
+/// Queue `pending` for `project_id`, replacing any earlier request.
+pub fn set_pending_force(env: &Env, project_id: u64, pending: &PendingStatusChange) {
+    let key = ProjectKey::PendingForce(project_id);
+    env.storage().persistent().set(&key, pending);
+    bump_persistent(env, &key);
+}
+
+/// Drop the queued forced status change for `project_id`.
+pub fn remove_pending_force(env: &Env, project_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&ProjectKey::PendingForce(project_id));
+}
+
+/// Escrow migration queued for `project_id`, if any.
+pub fn get_pending_migration(env: &Env, project_id: u64) -> Option<PendingMigration> {
+    let key = ProjectKey::PendingMigr(project_id);
+    let pending: Option<PendingMigration> = env.storage().persistent().get(&key);
+    if pending.is_some() {
+        bump_persistent(env, &key);
+    }
+    pending
+}
+
+/// Queue an escrow migration for `project_id`, replacing any queued one.
+pub fn set_pending_migration(env: &Env, project_id: u64, pending: &PendingMigration) {
+    let key = ProjectKey::PendingMigr(project_id);
+    env.storage().persistent().set(&key, pending);
+    bump_persistent(env, &key);
+}
+
+/// Drop the queued escrow migration for `project_id`.
+pub fn remove_pending_migration(env: &Env, project_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&ProjectKey::PendingMigr(project_id));
+}
+
+// ── Project owners ───────────────────────────────────────────────────
+
+/// Multi-signature owner set of `project_id`, if one was configured.
+pub fn get_project_owners(env: &Env, project_id: u64) -> Option<ProjectOwners> {
+    let key = ProjectKey::ProjOwners(project_id);
+    let owners: Option<ProjectOwners> = env.storage().persistent().get(&key);
+    if owners.is_some() {
+        bump_persistent(env, &key);
+    }
+    owners
+}
+
+/// Replace the owner set of `project_id`.
+pub fn set_project_owners(env: &Env, project_id: u64, owners: &ProjectOwners) {
+    let key = ProjectKey::ProjOwners(project_id);
+    env.storage().persistent().set(&key, owners);
+    bump_persistent(env, &key);
+}
+
+// ── Disbursement schedules ───────────────────────────────────────────
+
+/// Disbursement template `template_id`, if defined.
+pub fn get_template(env: &Env, template_id: u32) -> Option<DisbursementTemplate> {
+    let key = DataKey::Template(template_id);
+    let template: Option<DisbursementTemplate> = env.storage().persistent().get(&key);
+    if template.is_some() {
+        bump_persistent(env, &key);
+    }
+    template
+}
+
+/// Define or replace disbursement template `template_id`.
+pub fn set_template(env: &Env, template_id: u32, template: &DisbursementTemplate) {
+    let key = DataKey::Template(template_id);
+    env.storage().persistent().set(&key, template);
+    bump_persistent(env, &key);
+}
+
+/// Disbursement schedule adopted by `project_id`, if any.
+pub fn get_schedule(env: &Env, project_id: u64) -> Option<DisbursementTemplate> {
+    let key = ProjectKey::Schedule(project_id);
+    let schedule: Option<DisbursementTemplate> = env.storage().persistent().get(&key);
+    if schedule.is_some() {
+        bump_persistent(env, &key);
+    }
+    schedule
+}
+
+/// Store the disbursement schedule of `project_id`.
+pub fn set_schedule(env: &Env, project_id: u64, schedule: &DisbursementTemplate) {
+    let key = ProjectKey::Schedule(project_id);
+    env.storage().persistent().set(&key, schedule);
+    bump_persistent(env, &key);
+}
+
+/// Milestones `project_id` registered with; empty if none.
+pub fn get_milestones(env: &Env, project_id: u64) -> Vec<Milestone> {
+    let key = ProjectKey::Milestones(project_id);
+    match env.storage().persistent().get(&key) {
+        Some(milestones) => {
+            bump_persistent(env, &key);
+            milestones
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Store the milestones of `project_id`.
+pub fn set_milestones(env: &Env, project_id: u64, milestones: &Vec<Milestone>) {
+    let key = ProjectKey::Milestones(project_id);
+    env.storage().persistent().set(&key, milestones);
+    bump_persistent(env, &key);
+}
+
+// ── Donor votes ──────────────────────────────────────────────────────
+
+/// Donor vote settings of `project_id`, if the project opted in.
+pub fn get_vote_config(env: &Env, project_id: u64) -> Option<DonorVoteConfig> {
+    let key = ProjectKey::VoteConfig(project_id);
+    let config: Option<DonorVoteConfig> = env.storage().persistent().get(&key);
+    if config.is_some() {
+        bump_persistent(env, &key);
+    }
+    config
+}
+
+/// Store donor vote settings for `project_id`.
+pub fn set_vote_config(env: &Env, project_id: u64, config: &DonorVoteConfig) {
+    let key = ProjectKey::VoteConfig(project_id);
+    env.storage().persistent().set(&key, config);
+    bump_persistent(env, &key);
+}
+
+/// The open or contested donor vote of `project_id`, if any.
+pub fn get_donor_vote(env: &Env, project_id: u64) -> Option<DonorVote> {
+    let key = ProjectKey::DonorVote(project_id);
+    let vote: Option<DonorVote> = env.storage().persistent().get(&key);
+    if vote.is_some() {
+        bump_persistent(env, &key);
+    }
+    vote
+}
+
+/// Store the donor vote of `project_id`.
+pub fn set_donor_vote(env: &Env, project_id: u64, vote: &DonorVote) {
+    let key = ProjectKey::DonorVote(project_id);
+    env.storage().persistent().set(&key, vote);
+    bump_persistent(env, &key);
+}
+
+/// Drop the donor vote of `project_id` once it is settled.
+///
+/// Per-donor `Voted` markers are left to expire with their TTL; they are
+/// keyed to the vote's `ends_at`, so a later vote is unaffected.
+pub fn remove_donor_vote(env: &Env, project_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&ProjectKey::DonorVote(project_id));
+}
+
+/// Return the release of `project_id` held for disputes, if any.
+pub fn get_challenge(env: &Env, project_id: u64) -> Option<Challenge> {
+    let key = ProjectKey::Challenge(project_id);
+    let challenge: Option<Challenge> = env.storage().persistent().get(&key);
+    if challenge.is_some() {
+        bump_persistent(env, &key);
+    }
+    challenge
+}
+
+/// Store the release of `project_id` held for disputes.
+pub fn set_challenge(env: &Env, project_id: u64, challenge: &Challenge) {
+    let key = ProjectKey::Challenge(project_id);
+    env.storage().persistent().set(&key, challenge);
+    bump_persistent(env, &key);
+}
+
+/// Drop the held release of `project_id`.
+pub fn remove_challenge(env: &Env, project_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&ProjectKey::Challenge(project_id));
+}
+
+/// Return `true` if `donor` has objected in the vote ending at `ends_at`.
+pub fn has_voted(env: &Env, project_id: u64, donor: &Address, ends_at: u64) -> bool {
+    let marker: Option<u64> = env
+        .storage()
+        .persistent()
+        .get(&DonorKey::Voted(project_id, donor.clone()));
+    marker == Some(ends_at)
+}
+
+/// Record that `donor` has objected in the vote ending at `ends_at`.
+pub fn set_voted(env: &Env, project_id: u64, donor: &Address, ends_at: u64) {
+    let key = DonorKey::Voted(project_id, donor.clone());
+    env.storage().persistent().set(&key, &ends_at);
+    bump_persistent(env, &key);
+}
+
+// ── Deadline extension votes ─────────────────────────────────────────
+
+/// Pending deadline extension proposal of `project_id`, if any.
+pub fn get_extension_vote(env: &Env, project_id: u64) -> Option<ExtensionVote> {
+    let key = ProjectKey::ExtVote(project_id);
+    let vote: Option<ExtensionVote> = env.storage().persistent().get(&key);
+    if vote.is_some() {
+        bump_persistent(env, &key);
+    }
+    vote
+}
+
+/// Store the deadline extension proposal of `project_id`.
+pub fn set_extension_vote(env: &Env, project_id: u64, vote: &ExtensionVote) {
+    let key = ProjectKey::ExtVote(project_id);
+    env.storage().persistent().set(&key, vote);
+    bump_persistent(env, &key);
+}
+
+/// Drop the deadline extension proposal of `project_id`.
+pub fn remove_extension_vote(env: &Env, project_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&ProjectKey::ExtVote(project_id));
+}
+
+/// Return `true` if `donor` backed the extension of the deadline `old_deadline`.
+pub fn has_backed_extension(
+    env: &Env,
+    project_id: u64,
+    donor: &Address,
+    old_deadline: u64,
+) -> bool {
+    let marker: Option<u64> = env
+        .storage()
+        .persistent()
+        .get(&DonorKey::ExtVoted(project_id, donor.clone()));
+    marker == Some(old_deadline)
+}
+
+/// Record that `donor` backed the extension of the deadline `old_deadline`.
+pub fn set_backed_extension(env: &Env, project_id: u64, donor: &Address, old_deadline: u64) {
+    let key = DonorKey::ExtVoted(project_id, donor.clone());
+    env.storage().persistent().set(&key, &old_deadline);
+    bump_persistent(env, &key);
+}
+
+/// Return how many times the deadline of `project_id` was extended.
+pub fn get_extension_count(env: &Env, project_id: u64) -> u32 {
+    let key = ProjectKey::Extensions(project_id);
+    let count: Option<u32> = env.storage().persistent().get(&key);
+    if count.is_some() {
+        bump_persistent(env, &key);
+    }
+    count.unwrap_or(0)
+}
+
+/// Count one more deadline extension of `project_id`, returning the new total.
+pub fn increment_extension_count(env: &Env, project_id: u64) -> u32 {
+    let count = get_extension_count(env, project_id) + 1;
+    let key = ProjectKey::Extensions(project_id);
+    env.storage().persistent().set(&key, &count);
+    bump_persistent(env, &key);
+    count
+}
+
+// ── Milestone amendments ─────────────────────────────────────────────
+
+/// Pending milestone amendment proposal of `project_id`, if any.
+pub fn get_amendment(env: &Env, project_id: u64) -> Option<MilestoneAmendment> {
+    let key = ProjectKey::Amendment(project_id);
+    let amendment: Option<MilestoneAmendment> = env.storage().persistent().get(&key);
+    if amendment.is_some() {
+        bump_persistent(env, &key);
+    }
+    amendment
+}
+
+/// Store the milestone amendment proposal of `project_id`.
+pub fn set_amendment(env: &Env, project_id: u64, amendment: &MilestoneAmendment) {
+    let key = ProjectKey::Amendment(project_id);
+    env.storage().persistent().set(&key, amendment);
+    bump_persistent(env, &key);
+}
+
+/// Drop the milestone amendment proposal of `project_id`.
+pub fn remove_amendment(env: &Env, project_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&ProjectKey::Amendment(project_id));
+}
+
+/// Return `true` if `donor` backed the amendment closing at `ends_at`.
+pub fn has_backed_amendment(env: &Env, project_id: u64, donor: &Address, ends_at: u64) -> bool {
+    let marker: Option<u64> = env
+        .storage()
+        .persistent()
+        .get(&DonorKey::AmendVoted(project_id, donor.clone()));
+    marker == Some(ends_at)
+}
+
+/// Record that `donor` backed the amendment closing at `ends_at`.
+pub fn set_backed_amendment(env: &Env, project_id: u64, donor: &Address, ends_at: u64) {
+    let key = DonorKey::AmendVoted(project_id, donor.clone());
+    env.storage().persistent().set(&key, &ends_at);
+    bump_persistent(env, &key);
+}
+
+// ── Matching pools ───────────────────────────────────────────────────
+
+/// Allocate the next matching pool ID.
+pub fn next_match_pool_id(env: &Env) -> u64 {
+    bump_instance(env);
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&PoolKey::MatchPoolCount)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&PoolKey::MatchPoolCount, &(id + 1));
+    id
+}
+
+/// Matching pool `id`, if it exists.
+pub fn get_match_pool(env: &Env, id: u64) -> Option<MatchPool> {
+    let key = PoolKey::MatchPool(id);
+    let pool: Option<MatchPool> = env.storage().persistent().get(&key);
+    if pool.is_some() {
+        bump_persistent(env, &key);
+    }
+    pool
+}
+
+/// Store matching pool `id`, keeping its token's `MatchPoolTotal` in step
+/// with the change in its balance.
+pub fn set_match_pool(env: &Env, id: u64, pool: &MatchPool) {
+    let key = PoolKey::MatchPool(id);
+    let previous = get_match_pool(env, id).map_or(0, |p| p.balance);
+    env.storage().persistent().set(&key, pool);
+    bump_persistent(env, &key);
+    let total_key = PoolKey::MatchPoolTotal(pool.token.clone());
+    let total = get_match_pool_total(env, &pool.token) + pool.balance - previous;
+    env.storage().persistent().set(&total_key, &total);
+    bump_persistent(env, &total_key);
+}
+
+/// Sum of every matching pool's unspent funds in `token`.
+pub fn get_match_pool_total(env: &Env, token: &Address) -> i128 {
+    let key = PoolKey::MatchPoolTotal(token.clone());
+    let total: Option<i128> = env.storage().persistent().get(&key);
+    match total {
+        Some(total) => {
+            bump_persistent(env, &key);
+            total
+        }
+        None => 0,
+    }
+}
+
+/// IDs of the pools matching `project_id`, oldest first.
+pub fn get_match_pools(env: &Env, project_id: u64) -> Vec<u64> {
+    let key = ProjectKey::MatchPools(project_id);
+    let pools: Option<Vec<u64>> = env.storage().persistent().get(&key);
+    match pools {
+        Some(pools) => {
+            bump_persistent(env, &key);
+            pools
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Store the IDs of the pools matching `project_id`.
+pub fn set_match_pools(env: &Env, project_id: u64, pools: &Vec<u64>) {
+    let key = ProjectKey::MatchPools(project_id);
+    env.storage().persistent().set(&key, pools);
+    bump_persistent(env, &key);
+}
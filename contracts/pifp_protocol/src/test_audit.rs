@@ -0,0 +1,267 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, BytesN, Env, TryIntoVal,
+};
+
+use crate::{PifpProtocol, PifpProtocolClient, Role};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+#[test]
+fn test_admin_actions_are_logged_in_order() {
+    let (env, client, super_admin) = setup_with_init();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let admin = Address::generate(&env);
+
+    client.grant_role(&super_admin, &admin, &Role::Admin, &None);
+    client.set_protocol_config(&admin, &client.get_protocol_config());
+    client.pause(&admin, &BytesN::from_array(&env, &[1u8; 32]), &None);
+    client.unpause(&admin);
+    client.revoke_role(&super_admin, &admin, &None);
+
+    let log = client.get_admin_log(&0, &10);
+    assert_eq!(log.len(), 5);
+    let actions: std::vec::Vec<_> = log.iter().map(|e| e.action).collect();
+    assert_eq!(
+        actions,
+        [
+            symbol_short!("role_set"),
+            symbol_short!("config"),
+            symbol_short!("pause"),
+            symbol_short!("unpause"),
+            symbol_short!("role_del"),
+        ]
+    );
+    let first = log.get(0).unwrap();
+    assert_eq!(first.seq, 0);
+    assert_eq!(first.actor, super_admin);
+    assert_eq!(first.target, Some(admin.clone()));
+    assert_eq!(first.timestamp, 1_000);
+
+    let page = client.get_admin_log(&3, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().seq, 3);
+}
+
+#[test]
+fn test_admin_log_keeps_most_recent_entries() {
+    let (_env, client, super_admin) = setup_with_init();
+    let config = client.get_protocol_config();
+    for _ in 0..130 {
+        client.set_protocol_config(&super_admin, &config);
+    }
+
+    // The two oldest entries were overwritten; pages are capped at 50.
+    let log = client.get_admin_log(&0, &200);
+    assert_eq!(log.len(), 50);
+    assert_eq!(log.get(0).unwrap().seq, 2);
+    assert_eq!(client.get_admin_log(&100, &50).last().unwrap().seq, 129);
+    assert_eq!(client.get_admin_log(&130, &10).len(), 0);
+}
+
+#[test]
+fn test_role_history_records_grants_and_revocations() {
+    let (env, client, super_admin) = setup_with_init();
+    let admin = Address::generate(&env);
+    let target = Address::generate(&env);
+
+    client.grant_role(&super_admin, &admin, &Role::Admin, &None);
+    env.ledger().with_mut(|l| l.timestamp = 500);
+    client.grant_role(&admin, &target, &Role::Auditor, &None);
+    client.grant_role(&super_admin, &target, &Role::Oracle, &None);
+    env.ledger().with_mut(|l| l.timestamp = 900);
+    client.revoke_role(&admin, &target, &None);
+
+    let history = client.get_role_history(&target);
+    assert_eq!(history.len(), 3);
+    let first = history.get(0).unwrap();
+    assert_eq!(first.role, Role::Auditor);
+    assert!(first.granted);
+    assert_eq!(first.actor, Some(admin.clone()));
+    assert_eq!(first.timestamp, 500);
+    assert_eq!(history.get(1).unwrap().actor, Some(super_admin.clone()));
+    let last = history.get(2).unwrap();
+    assert_eq!(last.role, Role::Oracle);
+    assert!(!last.granted);
+    assert_eq!(last.actor, Some(admin));
+    assert_eq!(last.timestamp, 900);
+
+    // The initial SuperAdmin grant has no actor.
+    let root = client.get_role_history(&super_admin).get(0).unwrap();
+    assert_eq!(root.role, Role::SuperAdmin);
+    assert_eq!(root.actor, None);
+    assert_eq!(client.get_role_history(&Address::generate(&env)).len(), 0);
+}
+
+#[test]
+fn test_role_history_is_bounded() {
+    let (env, client, super_admin) = setup_with_init();
+    let target = Address::generate(&env);
+    for i in 0..20u64 {
+        env.ledger().with_mut(|l| l.timestamp = i);
+        client.grant_role(&super_admin, &target, &Role::Auditor, &None);
+    }
+
+    let history = client.get_role_history(&target);
+    assert_eq!(history.len(), crate::rbac::MAX_ROLE_HISTORY);
+    assert_eq!(history.get(0).unwrap().timestamp, 4);
+    assert_eq!(history.last().unwrap().timestamp, 19);
+}
+
+#[test]
+fn test_role_change_records_reason_hash() {
+    let (env, client, super_admin) = setup_with_init();
+    let target = Address::generate(&env);
+    let approval = BytesN::from_array(&env, &[0x42u8; 32]);
+
+    client.grant_role(
+        &super_admin,
+        &target,
+        &Role::Oracle,
+        &Some(approval.clone()),
+    );
+    let event = env.events().all().last().unwrap();
+    let (by, reason): (Option<Address>, Option<BytesN<32>>) = event.2.try_into_val(&env).unwrap();
+    assert_eq!(by, Some(super_admin.clone()));
+    assert_eq!(reason, Some(approval.clone()));
+
+    client.revoke_role(&super_admin, &target, &None);
+    let history = client.get_role_history(&target);
+    assert_eq!(history.get(0).unwrap().reason_hash, Some(approval));
+    assert_eq!(history.get(1).unwrap().reason_hash, None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #26)")]
+fn test_admin_count_is_capped() {
+    let (env, client, super_admin) = setup_with_init();
+    let mut config = client.get_protocol_config();
+    config.max_admins = 2;
+    client.set_protocol_config(&super_admin, &config);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    client.grant_role(&super_admin, &first, &Role::Admin, &None);
+    client.grant_role(&first, &second, &Role::Admin, &None);
+    // Re-granting an existing Admin does not count twice.
+    client.grant_role(&super_admin, &second, &Role::Admin, &None);
+
+    // Revoking frees a slot; demoting via grant does too.
+    client.revoke_role(&super_admin, &second, &None);
+    client.grant_role(&super_admin, &second, &Role::Admin, &None);
+    client.grant_role(&super_admin, &first, &Role::Auditor, &None);
+    client.grant_role(&second, &Address::generate(&env), &Role::Admin, &None);
+
+    client.grant_role(&second, &Address::generate(&env), &Role::Admin, &None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_admin_cannot_raise_admin_cap() {
+    let (env, client, super_admin) = setup_with_init();
+    let admin = Address::generate(&env);
+    client.grant_role(&super_admin, &admin, &Role::Admin, &None);
+
+    let mut config = client.get_protocol_config();
+    config.max_admins -= 1;
+    client.set_protocol_config(&admin, &config);
+    config.max_admins += 1;
+    client.set_protocol_config(&admin, &config);
+}
+
+#[test]
+fn test_time_bound_role_lapses() {
+    let (env, client, super_admin) = setup_with_init();
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let oracle = Address::generate(&env);
+    client.grant_role_until(&super_admin, &oracle, &Role::Oracle, &2_000, &None);
+    assert_eq!(client.role_expiry(&oracle), Some(2_000));
+    assert!(client.has_role(&oracle, &Role::Oracle));
+
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    assert!(!client.has_role(&oracle, &Role::Oracle));
+    assert_eq!(client.role_of(&oracle), None);
+
+    // A permanent grant clears the expiry.
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    assert_eq!(client.role_expiry(&oracle), None);
+    assert!(client.has_role(&oracle, &Role::Oracle));
+}
+
+#[test]
+fn test_expired_admin_cannot_act() {
+    let (env, client, super_admin) = setup_with_init();
+    let admin = Address::generate(&env);
+    let until = env.ledger().timestamp() + 60;
+    client.grant_role_until(&super_admin, &admin, &Role::Admin, &until, &None);
+    client.grant_role(&admin, &Address::generate(&env), &Role::Auditor, &None);
+
+    env.ledger().with_mut(|l| l.timestamp = until);
+    let result = client.try_grant_role(&admin, &Address::generate(&env), &Role::Auditor, &None);
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_super_admin_cannot_be_time_bound() {
+    let (env, client, super_admin) = setup_with_init();
+    let until = env.ledger().timestamp() + 60;
+    client.grant_role_until(
+        &super_admin,
+        &Address::generate(&env),
+        &Role::SuperAdmin,
+        &until,
+        &None,
+    );
+}
+
+#[test]
+fn test_role_members_follow_grants_and_revocations() {
+    let (env, client, super_admin) = setup_with_init();
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    client.grant_role(&super_admin, &first, &Role::Oracle, &None);
+    client.grant_role(&super_admin, &second, &Role::Oracle, &None);
+    assert_eq!(
+        client.list_role_members(&Role::Oracle),
+        soroban_sdk::vec![&env, first.clone(), second.clone()]
+    );
+
+    // Moving to another role leaves the old list.
+    client.grant_role(&super_admin, &first, &Role::Auditor, &None);
+    client.revoke_role(&super_admin, &second, &None);
+    assert!(client.list_role_members(&Role::Oracle).is_empty());
+    assert_eq!(
+        client.list_role_members(&Role::Auditor),
+        soroban_sdk::vec![&env, first.clone()]
+    );
+
+    let successor = Address::generate(&env);
+    client.transfer_super_admin(&super_admin, &successor);
+    assert_eq!(
+        client.list_role_members(&Role::SuperAdmin),
+        soroban_sdk::vec![&env, successor.clone()]
+    );
+
+    // Expired grants drop out of the listing.
+    let until = env.ledger().timestamp() + 60;
+    client.grant_role_until(&successor, &first, &Role::Auditor, &until, &None);
+    env.ledger().with_mut(|l| l.timestamp = until);
+    assert!(client.list_role_members(&Role::Auditor).is_empty());
+}
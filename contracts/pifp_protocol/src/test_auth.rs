@@ -0,0 +1,296 @@
+// Authorization tests that run without `mock_all_auths` on the calls under
+// test: each call is given exactly the authorizations a real wallet would
+// sign, and the recorded auth tree is compared against the expected one.
+
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, MockAuth, MockAuthInvoke},
+    token, vec, Address, BytesN, Env, IntoVal, Symbol,
+};
+
+use crate::{PifpProtocol, PifpProtocolClient, Role};
+
+struct Fixture {
+    env: Env,
+    client: PifpProtocolClient<'static>,
+    super_admin: Address,
+    creator: Address,
+    oracle: Address,
+    token: Address,
+}
+
+/// Deploy, initialise and register one project. Setup calls mock every
+/// auth individually so no blanket mocking leaks into the tests.
+fn setup() -> (Fixture, u64) {
+    let env = Env::default();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    client.mock_all_auths().init(&super_admin);
+    client
+        .mock_all_auths()
+        .grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.mock_all_auths().set_oracle(&super_admin, &oracle);
+    let project = client.mock_all_auths().register_project(
+        &creator,
+        &vec![&env, token.clone()],
+        &10_000,
+        &proof(&env),
+        &(env.ledger().timestamp() + 86_400),
+        &1,
+        &vec![&env],
+    );
+
+    let fixture = Fixture {
+        env,
+        client,
+        super_admin,
+        creator,
+        oracle,
+        token,
+    };
+    (fixture, project.id)
+}
+
+fn proof(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0xabu8; 32])
+}
+
+fn mint(f: &Fixture, to: &Address, amount: i128) {
+    token::StellarAssetClient::new(&f.env, &f.token)
+        .mock_all_auths()
+        .mint(to, &amount);
+}
+
+#[test]
+fn test_deposit_requires_donor_auth_for_transfer() {
+    let (f, id) = setup();
+    let donor = Address::generate(&f.env);
+    mint(&f, &donor, 500);
+
+    let deposit_args = (id, donor.clone(), f.token.clone(), 500i128, false);
+    let transfer_args = (donor.clone(), f.client.address.clone(), 500i128);
+    f.client
+        .mock_auths(&[MockAuth {
+            address: &donor,
+            invoke: &MockAuthInvoke {
+                contract: &f.client.address,
+                fn_name: "deposit",
+                args: deposit_args.clone().into_val(&f.env),
+                sub_invokes: &[MockAuthInvoke {
+                    contract: &f.token,
+                    fn_name: "transfer",
+                    args: transfer_args.clone().into_val(&f.env),
+                    sub_invokes: &[],
+                }],
+            },
+        }])
+        .deposit(&id, &donor, &f.token, &500, &false);
+
+    assert_eq!(
+        f.env.auths(),
+        std::vec![(
+            donor.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    f.client.address.clone(),
+                    Symbol::new(&f.env, "deposit"),
+                    deposit_args.into_val(&f.env),
+                )),
+                sub_invocations: std::vec![AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        f.token.clone(),
+                        Symbol::new(&f.env, "transfer"),
+                        transfer_args.into_val(&f.env),
+                    )),
+                    sub_invocations: std::vec![],
+                }],
+            },
+        )]
+    );
+    assert_eq!(
+        f.client.get_balances(&id).balances.get(0).unwrap().balance,
+        500
+    );
+}
+
+#[test]
+fn test_deposit_without_transfer_sub_invocation_fails() {
+    let (f, id) = setup();
+    let donor = Address::generate(&f.env);
+    mint(&f, &donor, 500);
+
+    // Authorizing `deposit` alone does not cover the token transfer.
+    let result = f
+        .client
+        .mock_auths(&[MockAuth {
+            address: &donor,
+            invoke: &MockAuthInvoke {
+                contract: &f.client.address,
+                fn_name: "deposit",
+                args: (id, donor.clone(), f.token.clone(), 500i128, false).into_val(&f.env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_deposit(&id, &donor, &f.token, &500, &false);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_deposit_signed_by_someone_else_fails() {
+    let (f, id) = setup();
+    let donor = Address::generate(&f.env);
+    let attacker = Address::generate(&f.env);
+    mint(&f, &donor, 500);
+
+    let result = f
+        .client
+        .mock_auths(&[MockAuth {
+            address: &attacker,
+            invoke: &MockAuthInvoke {
+                contract: &f.client.address,
+                fn_name: "deposit",
+                args: (id, donor.clone(), f.token.clone(), 500i128, false).into_val(&f.env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_deposit(&id, &donor, &f.token, &500, &false);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_verify_requires_oracle_auth() {
+    let (f, id) = setup();
+    let args = (f.oracle.clone(), id, proof(&f.env));
+
+    let forged = f
+        .client
+        .mock_auths(&[MockAuth {
+            address: &f.creator,
+            invoke: &MockAuthInvoke {
+                contract: &f.client.address,
+                fn_name: "verify_and_release",
+                args: args.clone().into_val(&f.env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_verify_and_release(&f.oracle, &id, &proof(&f.env));
+    assert!(forged.is_err());
+
+    f.client
+        .mock_auths(&[MockAuth {
+            address: &f.oracle,
+            invoke: &MockAuthInvoke {
+                contract: &f.client.address,
+                fn_name: "verify_and_release",
+                args: args.into_val(&f.env),
+                sub_invokes: &[],
+            },
+        }])
+        .verify_and_release(&f.oracle, &id, &proof(&f.env));
+    assert_eq!(f.env.auths().len(), 1);
+    assert_eq!(f.env.auths()[0].0, f.oracle);
+}
+
+#[test]
+fn test_role_admin_calls_require_caller_auth() {
+    let (f, _) = setup();
+    let target = Address::generate(&f.env);
+
+    // Without the SuperAdmin's signature, none of these may succeed.
+    assert!(f
+        .client
+        .try_grant_role(&f.super_admin, &target, &Role::Admin, &None)
+        .is_err());
+    assert!(f
+        .client
+        .try_revoke_role(&f.super_admin, &f.creator, &None)
+        .is_err());
+    assert!(f
+        .client
+        .try_transfer_super_admin(&f.super_admin, &target)
+        .is_err());
+
+    f.client
+        .mock_auths(&[MockAuth {
+            address: &f.super_admin,
+            invoke: &MockAuthInvoke {
+                contract: &f.client.address,
+                fn_name: "grant_role",
+                args: (
+                    f.super_admin.clone(),
+                    target.clone(),
+                    Role::Admin,
+                    None::<BytesN<32>>,
+                )
+                    .into_val(&f.env),
+                sub_invokes: &[],
+            },
+        }])
+        .grant_role(&f.super_admin, &target, &Role::Admin, &None);
+    assert_eq!(f.client.role_of(&target), Some(Role::Admin));
+}
+
+#[test]
+fn test_register_requires_creator_auth() {
+    let (f, _) = setup();
+    let result = f.client.try_register_project(
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+        &10_000,
+        &proof(&f.env),
+        &(f.env.ledger().timestamp() + 86_400),
+        &1,
+        &vec![&f.env],
+    );
+    assert!(result.is_err());
+}
+
+fn caps(env: &Env, names: &[&str]) -> soroban_sdk::Vec<Symbol> {
+    let mut caps = soroban_sdk::Vec::new(env);
+    for name in names {
+        caps.push_back(Symbol::new(env, name));
+    }
+    caps
+}
+
+#[test]
+fn test_capabilities_follow_roles() {
+    let (f, _) = setup();
+    let env = &f.env;
+    assert_eq!(
+        f.client.capabilities_of(&f.creator),
+        caps(env, &["deposit", "refund", "manage", "register"])
+    );
+    assert_eq!(
+        f.client.capabilities_of(&f.oracle),
+        caps(env, &["deposit", "refund", "verify"])
+    );
+    assert_eq!(
+        f.client.capabilities_of(&Address::generate(env)),
+        caps(env, &["deposit", "refund"])
+    );
+
+    let super_caps = f.client.capabilities_of(&f.super_admin);
+    for name in ["grant", "configure", "pause", "force", "sunset"] {
+        assert!(super_caps.contains(Symbol::new(env, name)));
+    }
+
+    let auditor = Address::generate(env);
+    f.client
+        .mock_all_auths()
+        .grant_role(&f.super_admin, &auditor, &Role::Auditor, &None);
+    f.client.mock_all_auths().freeze_address(
+        &f.super_admin,
+        &auditor,
+        &BytesN::from_array(env, &[1u8; 32]),
+    );
+    assert_eq!(f.client.capabilities_of(&auditor), caps(env, &["report"]));
+}
@@ -0,0 +1,108 @@
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    token, vec,
+    xdr::{ScAddress, ScVal},
+    Address, BytesN, Env, TryFromVal,
+};
+
+use crate::client::{decode_event, format_amount, parse_amount, PifpEvent};
+use crate::{PifpProtocol, PifpProtocolClient, Role};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+/// The most recent event, converted to XDR as an RPC client would see it.
+fn last_event_xdr(env: &Env) -> (std::vec::Vec<ScVal>, ScVal) {
+    let events = env.events().all();
+    let (_, topics, data) = events.last().unwrap();
+    let topics = topics
+        .iter()
+        .map(|t| ScVal::try_from_val(env, &t).unwrap())
+        .collect();
+    (topics, ScVal::try_from_val(env, &data).unwrap())
+}
+
+#[test]
+fn test_decodes_lifecycle_events() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+
+    let project = client.register_project(
+        &creator,
+        &vec![&env, token.clone()],
+        &5_000,
+        &proof_hash,
+        &(env.ledger().timestamp() + 86_400),
+        &1,
+        &vec![&env],
+    );
+    let (topics, data) = last_event_xdr(&env);
+    let PifpEvent::Created(created) = decode_event(&topics, &data).unwrap() else {
+        panic!("expected created");
+    };
+    assert_eq!(created.project_id, project.id);
+    assert_eq!(created.goal, 5_000);
+    assert_eq!(created.creator, ScAddress::from(&creator));
+
+    token::StellarAssetClient::new(&env, &token).mint(&donor, &1_234);
+    client.deposit(&project.id, &donor, &token, &1_234, &false);
+    let (topics, data) = last_event_xdr(&env);
+    let PifpEvent::Funded(funded) = decode_event(&topics, &data).unwrap() else {
+        panic!("expected funded");
+    };
+    assert_eq!(funded.amount, 1_234);
+    assert_eq!(funded.donator, ScAddress::from(&donor));
+
+    client.verify_and_release(&oracle, &project.id, &proof_hash);
+    let events = env.events().all();
+    let verified = events
+        .iter()
+        .filter_map(|(_, topics, data)| {
+            let topics: std::vec::Vec<ScVal> = topics
+                .iter()
+                .map(|t| ScVal::try_from_val(&env, &t).unwrap())
+                .collect();
+            decode_event(&topics, &ScVal::try_from_val(&env, &data).unwrap())
+        })
+        .find_map(|e| match e {
+            PifpEvent::Verified(v) => Some(v),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(verified.proof_hash, [0xabu8; 32]);
+
+    // Events outside the modelled set are skipped.
+    client.pause(&super_admin, &BytesN::from_array(&env, &[1u8; 32]), &None);
+    let (topics, data) = last_event_xdr(&env);
+    assert_eq!(decode_event(&topics, &data), None);
+}
+
+#[test]
+fn test_amount_helpers_round_trip() {
+    assert_eq!(format_amount(12_345_000, 7), "1.2345");
+    assert_eq!(format_amount(-5, 2), "-0.05");
+    assert_eq!(format_amount(300, 2), "3");
+    assert_eq!(parse_amount("1.2345", 7), Some(12_345_000));
+    assert_eq!(parse_amount("-0.05", 2), Some(-5));
+    assert_eq!(parse_amount("3", 0), Some(3));
+    assert_eq!(parse_amount("1.234", 2), None);
+    assert_eq!(parse_amount("1e3", 2), None);
+    assert_eq!(parse_amount(".5", 2), None);
+}
@@ -0,0 +1,611 @@
+extern crate std;
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env, Vec,
+};
+
+use crate::price::{Asset, PriceData};
+use crate::{
+    DonorPreferences, DonorStats, PifpProtocol, PifpProtocolClient, ProtocolConfig, Role,
+    StatementLine,
+};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+#[test]
+fn test_donor_history_tracks_each_project_and_token() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let token_a = create_token(&env, &Address::generate(&env)).address;
+    let token_b = create_token(&env, &Address::generate(&env)).address;
+    let tokens = soroban_sdk::vec![&env, token_a.clone(), token_b.clone()];
+    let first = register(&env, &client, &creator, &tokens);
+    let second = register(&env, &client, &creator, &tokens);
+
+    fund(&env, &client, first.id, &donor, &token_a, 100);
+    fund(&env, &client, second.id, &donor, &token_b, 40);
+    fund(&env, &client, first.id, &donor, &token_a, 25);
+
+    let history = client.get_donor_history(&donor, &0, &10);
+    assert_eq!(
+        history,
+        soroban_sdk::vec![
+            &env,
+            (first.id, token_a.clone(), 125i128),
+            (second.id, token_b.clone(), 40i128),
+        ]
+    );
+
+    let page = client.get_donor_history(&donor, &1, &1);
+    assert_eq!(
+        page,
+        soroban_sdk::vec![&env, (second.id, token_b.clone(), 40i128)]
+    );
+
+    // Each project once, whichever tokens were given.
+    fund(&env, &client, first.id, &donor, &token_b, 5);
+    assert_eq!(
+        client.get_donor_projects(&donor),
+        soroban_sdk::vec![&env, first.id, second.id]
+    );
+}
+
+#[test]
+fn test_donation_count_counts_every_deposit() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    assert_eq!(client.get_donation_count(&project.id), 0);
+
+    let donor = Address::generate(&env);
+    fund(&env, &client, project.id, &donor, &token, 100);
+    fund(&env, &client, project.id, &donor, &token, 50);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        10,
+    );
+    assert_eq!(client.get_donation_count(&project.id), 3);
+    assert_eq!(client.get_project(&project.id).donation_count, 3);
+}
+
+#[test]
+fn test_donor_history_empty_for_unknown_donor() {
+    let (env, client, _) = setup_with_init();
+    let nobody = Address::generate(&env);
+
+    assert_eq!(client.get_donor_history(&nobody, &0, &10).len(), 0);
+    assert!(client.get_donor_projects(&nobody).is_empty());
+}
+
+#[test]
+fn test_get_donation_point_lookup() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.clone()],
+    );
+
+    fund(&env, &client, project.id, &donor, &token, 70);
+    fund(&env, &client, project.id, &donor, &token, 30);
+
+    assert_eq!(client.get_donation(&project.id, &donor, &token), 100);
+    assert_eq!(client.get_donation(&project.id, &other, &token), 0);
+}
+
+// ─── Privacy preferences ────────────────────────────────
+
+#[test]
+fn test_hidden_donor_left_out_of_project_donors() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let public = Address::generate(&env);
+    let private = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.clone()],
+    );
+
+    fund(&env, &client, project.id, &public, &token, 100);
+    fund(&env, &client, project.id, &private, &token, 100);
+    assert_eq!(client.get_project_donors(&project.id, &0, &10).len(), 2);
+
+    let prefs = DonorPreferences {
+        hide_from_listings: true,
+        ..Default::default()
+    };
+    client.set_donor_preferences(&private, &prefs);
+    assert_eq!(client.get_donor_preferences(&private), prefs);
+    assert_eq!(
+        client.get_project_donors(&project.id, &0, &10),
+        soroban_sdk::vec![&env, (public, token.clone())]
+    );
+
+    // The contribution itself is unaffected, and opting back in restores
+    // the listing.
+    assert_eq!(client.get_donation(&project.id, &private, &token), 100);
+    client.set_donor_preferences(&private, &DonorPreferences::default());
+    assert_eq!(client.get_project_donors(&project.id, &0, &10).len(), 2);
+}
+
+// ─── Deposit-time valuation ─────────────────────────────
+
+/// SEP-40 feed with 7 decimals and admin-set prices.
+#[contract]
+pub struct MockPriceFeed;
+
+#[contractimpl]
+impl MockPriceFeed {
+    pub fn set_price(env: Env, token: Address, price: i128) {
+        env.storage().instance().set(&token, &price);
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        match asset {
+            Asset::Stellar(token) => env.storage().instance().get(&token).map(|price| PriceData {
+                price,
+                timestamp: env.ledger().timestamp(),
+            }),
+            Asset::Other(_) => None,
+        }
+    }
+}
+
+/// Donor contract keeping its own tally of deposits.
+#[contract]
+pub struct MockTreasury;
+
+#[contractimpl]
+impl MockTreasury {
+    pub fn on_deposit_receipt(env: Env, project_id: u64, _token: Address, amount: i128) {
+        let tally: i128 = env.storage().instance().get(&project_id).unwrap_or(0);
+        env.storage().instance().set(&project_id, &(tally + amount));
+    }
+
+    pub fn tally(env: Env, project_id: u64) -> i128 {
+        env.storage().instance().get(&project_id).unwrap_or(0)
+    }
+}
+
+#[test]
+fn test_deposit_hook_notifies_donor_contract() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    let treasury = env.register(MockTreasury, ());
+    let treasury_client = MockTreasuryClient::new(&env, &treasury);
+
+    // Deposits before opting in are not reported.
+    fund(&env, &client, project.id, &treasury, &token.address, 100);
+    assert!(!client.has_deposit_hook(&treasury));
+    assert_eq!(treasury_client.tally(&project.id), 0);
+
+    client.set_deposit_hook(&treasury, &true);
+    fund(&env, &client, project.id, &treasury, &token.address, 250);
+    assert_eq!(treasury_client.tally(&project.id), 250);
+
+    client.set_deposit_hook(&treasury, &false);
+    fund(&env, &client, project.id, &treasury, &token.address, 50);
+    assert_eq!(treasury_client.tally(&project.id), 250);
+}
+
+#[test]
+fn test_deposits_valued_at_deposit_time_price() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let feed = MockPriceFeedClient::new(&env, &env.register(MockPriceFeed, ()));
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            price_feed: Some(feed.address.clone()),
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.clone()],
+    );
+
+    feed.set_price(&token, &25_000_000);
+    fund(&env, &client, project.id, &donor, &token, 100);
+    feed.set_price(&token, &30_000_000);
+    fund(&env, &client, project.id, &donor, &token, 100);
+
+    assert_eq!(client.get_donation(&project.id, &donor, &token), 200);
+    assert_eq!(client.get_donation_value(&project.id, &donor, &token), 550);
+}
+
+#[test]
+fn test_unpriced_deposit_still_accepted() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let feed = env.register(MockPriceFeed, ());
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            price_feed: Some(feed),
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.clone()],
+    );
+
+    fund(&env, &client, project.id, &donor, &token, 100);
+    assert_eq!(client.get_donation(&project.id, &donor, &token), 100);
+    assert_eq!(client.get_donation_value(&project.id, &donor, &token), 0);
+}
+
+// ─── Giving streaks ─────────────────────────────────────
+
+#[test]
+fn test_donor_stats_track_consecutive_periods() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let period = client.get_protocol_config().streak_period;
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let tokens = soroban_sdk::vec![&env, token.clone()];
+
+    // Two deposits in the same period, then one in each of the next two.
+    let start = 10 * period;
+    for (offset, amount) in [(0, 10), (1, 10), (period, 10), (2 * period, 10)] {
+        env.ledger().with_mut(|l| l.timestamp = start + offset);
+        let project = register(&env, &client, &creator, &tokens);
+        fund(&env, &client, project.id, &donor, &token, amount);
+    }
+    let stats = client.get_donor_stats(&donor);
+    assert_eq!(stats.deposit_count, 4);
+    assert_eq!(stats.current_streak, 3);
+    assert_eq!(stats.longest_streak, 3);
+    assert_eq!(stats.last_period, 12);
+
+    // Skipping a period restarts the streak but keeps the record.
+    env.ledger().with_mut(|l| l.timestamp = start + 4 * period);
+    let project = register(&env, &client, &creator, &tokens);
+    fund(&env, &client, project.id, &donor, &token, 10);
+    let stats = client.get_donor_stats(&donor);
+    assert_eq!(stats.deposit_count, 5);
+    assert_eq!(stats.current_streak, 1);
+    assert_eq!(stats.longest_streak, 3);
+
+    assert_eq!(
+        client.get_donor_stats(&Address::generate(&env)),
+        DonorStats::default()
+    );
+}
+
+#[test]
+fn test_donor_stats_sum_priced_value() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let feed = MockPriceFeedClient::new(&env, &env.register(MockPriceFeed, ()));
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            price_feed: Some(feed.address.clone()),
+            ..client.get_protocol_config()
+        },
+    );
+    let priced = create_token(&env, &Address::generate(&env)).address;
+    let unpriced = create_token(&env, &Address::generate(&env)).address;
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, priced.clone(), unpriced.clone()],
+    );
+
+    feed.set_price(&priced, &20_000_000);
+    fund(&env, &client, project.id, &donor, &priced, 100);
+    fund(&env, &client, project.id, &donor, &unpriced, 100);
+
+    let stats = client.get_donor_stats(&donor);
+    assert_eq!(stats.deposit_count, 2);
+    assert_eq!(stats.total_value, 200);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_zero_streak_period_rejected() {
+    let (_env, client, super_admin) = setup_with_init();
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            streak_period: 0,
+            ..client.get_protocol_config()
+        },
+    );
+}
+
+// ─── Goal-capped deposits ───────────────────────────────
+
+#[test]
+fn test_cap_to_goal_transfers_only_shortfall() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    fund(&env, &client, project.id, &donor, &token.address, 900);
+
+    token::StellarAssetClient::new(&env, &token.address).mint(&donor, &500);
+    let deposited = client.deposit(&project.id, &donor, &token.address, &500, &true);
+    assert_eq!(deposited.transferred, 100);
+    assert_eq!(token.balance(&donor), 400);
+    assert_eq!(
+        client.get_donation(&project.id, &donor, &token.address),
+        1000
+    );
+
+    // Goal met: a capped deposit moves nothing.
+    assert_eq!(
+        client
+            .deposit(&project.id, &donor, &token.address, &500, &true)
+            .transferred,
+        0
+    );
+    assert_eq!(token.balance(&donor), 400);
+}
+
+#[test]
+fn test_cap_to_goal_covers_insurance_premium() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.clone()],
+    );
+    client.set_insured(&vec![&env, creator.clone()], &project.id);
+
+    token::StellarAssetClient::new(&env, &token).mint(&donor, &2_000);
+    let deposited = client.deposit(&project.id, &donor, &token, &2_000, &true);
+    assert_eq!(deposited.transferred, 1_011);
+    assert!(client.goal_progress(&project.id).raised >= 1_000);
+}
+
+#[test]
+fn test_cap_to_goal_ignores_other_tokens() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let first = create_token(&env, &Address::generate(&env)).address;
+    let second = create_token(&env, &Address::generate(&env)).address;
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, first.clone(), second.clone()],
+    );
+    fund(&env, &client, project.id, &donor, &first, 1_000);
+
+    token::StellarAssetClient::new(&env, &second).mint(&donor, &300);
+    assert_eq!(
+        client
+            .deposit(&project.id, &donor, &second, &300, &true)
+            .transferred,
+        300
+    );
+}
+
+#[test]
+fn test_donor_statement_totals_period_per_token() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token_a = create_token(&env, &Address::generate(&env)).address;
+    let token_b = create_token(&env, &Address::generate(&env)).address;
+    let tokens = vec![&env, token_a.clone(), token_b.clone()];
+    let project = register(&env, &client, &creator, &tokens);
+    client.set_match_cap(&super_admin, &project.id, &300);
+    let start = env.ledger().timestamp();
+
+    fund(&env, &client, project.id, &donor, &token_a, 200);
+    env.ledger().with_mut(|l| l.timestamp += 1_000);
+    fund(&env, &client, project.id, &donor, &token_a, 250);
+    fund(&env, &client, project.id, &donor, &token_b, 50);
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    client.claim_refund(&project.id, &donor, &token_b);
+    let end = env.ledger().timestamp();
+
+    let whole = client.get_donor_statement(&donor, &start, &end);
+    assert_eq!(
+        whole.lines,
+        vec![
+            &env,
+            StatementLine {
+                token: token_a.clone(),
+                deposited: 450,
+                refunded: 0,
+                matched: 300,
+            },
+            StatementLine {
+                token: token_b.clone(),
+                deposited: 50,
+                refunded: 50,
+                matched: 0,
+            },
+        ]
+    );
+
+    // Only the second round of deposits falls inside this window.
+    let middle = client.get_donor_statement(&donor, &(start + 500), &(start + 1_000));
+    assert_eq!(
+        middle.lines,
+        vec![
+            &env,
+            StatementLine {
+                token: token_a,
+                deposited: 250,
+                refunded: 0,
+                matched: 100,
+            },
+            StatementLine {
+                token: token_b,
+                deposited: 50,
+                refunded: 0,
+                matched: 0,
+            },
+        ]
+    );
+    assert!(client
+        .get_donor_statement(&donor, &(end + 1), &u64::MAX)
+        .lines
+        .is_empty());
+}
+
+#[test]
+fn test_donor_allowlist_gates_deposits() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    let approved = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    client.add_allowed_donor(&vec![&env, creator.clone()], &project.id, &approved);
+    client.set_donor_allowlist(&vec![&env, creator.clone()], &project.id, &true);
+    assert!(client.is_donor_allowed(&project.id, &approved));
+    assert!(!client.is_donor_allowed(&project.id, &outsider));
+
+    fund(&env, &client, project.id, &approved, &token, 100);
+    token::StellarAssetClient::new(&env, &token).mint(&outsider, &100);
+    let result = client.try_deposit(&project.id, &outsider, &token, &100, &false);
+    assert!(result.is_err());
+
+    // Removal blocks further deposits; turning the gate off admits anyone.
+    client.remove_allowed_donor(&vec![&env, super_admin.clone()], &project.id, &approved);
+    assert!(!client.is_donor_allowed(&project.id, &approved));
+    client.set_donor_allowlist(&vec![&env, super_admin.clone()], &project.id, &false);
+    fund(&env, &client, project.id, &outsider, &token, 100);
+    assert_eq!(client.get_donation(&project.id, &outsider, &token), 100);
+}
+
+#[test]
+fn test_donor_allowlist_managed_by_creator_or_admin() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token]);
+    let stranger = Address::generate(&env);
+
+    let result = client.try_set_donor_allowlist(&vec![&env, stranger.clone()], &project.id, &true);
+    assert!(result.is_err());
+    let result =
+        client.try_add_allowed_donor(&vec![&env, stranger.clone()], &project.id, &stranger);
+    assert!(result.is_err());
+    assert!(client.is_donor_allowed(&project.id, &stranger));
+}
@@ -3,7 +3,7 @@ extern crate std;
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Events},
-    token, vec, Address, BytesN, Env, IntoVal, TryIntoVal,
+    token, vec, Address, BytesN, Env, IntoVal, String, TryIntoVal,
 };
 
 use crate::events::{ProjectCreated, ProjectFunded, ProjectVerified};
@@ -39,10 +39,18 @@ fn test_project_created_event() {
     let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
     let deadline = env.ledger().timestamp() + 86400;
 
-    client.grant_role(&super_admin, &creator, &Role::ProjectManager);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
 
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
-    let project = client.register_project(&creator, &tokens, &goal, &proof_hash, &deadline);
+    let project = client.register_project(
+        &creator,
+        &tokens,
+        &goal,
+        &proof_hash,
+        &deadline,
+        &1,
+        &vec![&env],
+    );
 
     let all_events = env.events().all();
     let last_event = all_events.last().expect("No events found");
@@ -78,7 +86,7 @@ fn test_project_funded_event() {
     let donator = Address::generate(&env);
     let amount = 1000i128;
 
-    client.grant_role(&super_admin, &creator, &Role::ProjectManager);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
     let project = client.register_project(
         &creator,
@@ -86,12 +94,14 @@ fn test_project_funded_event() {
         &10000,
         &BytesN::from_array(&env, &[0u8; 32]),
         &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![&env],
     );
 
     let token_sac = token::StellarAssetClient::new(&env, &token.address);
     token_sac.mint(&donator, &amount);
 
-    client.deposit(&project.id, &donator, &token.address, &amount);
+    client.deposit(&project.id, &donator, &token.address, &amount, &false);
 
     let all_events = env.events().all();
     let last_event = all_events.last().expect("No events found");
@@ -126,7 +136,7 @@ fn test_project_verified_event() {
     let token = create_token(&env, &token_admin);
     let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
 
-    client.grant_role(&super_admin, &creator, &Role::ProjectManager);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
     client.set_oracle(&super_admin, &oracle);
 
     let tokens = soroban_sdk::vec![&env, token.address.clone()];
@@ -136,6 +146,8 @@ fn test_project_verified_event() {
         &1000,
         &proof_hash,
         &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![&env],
     );
 
     client.verify_and_release(&oracle, &project.id, &proof_hash);
@@ -163,3 +175,81 @@ fn test_project_verified_event() {
         }
     );
 }
+
+#[test]
+fn test_deposit_emits_transfer_companion_event() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    let donator = Address::generate(&env);
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    let project = client.register_project(
+        &creator,
+        &tokens,
+        &10000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![&env],
+    );
+    token::StellarAssetClient::new(&env, &token.address).mint(&donator, &250);
+    client.deposit(&project.id, &donator, &token.address, &250, &false);
+
+    // The companion is emitted by PIFP itself, just before `funded`.
+    let all_events = env.events().all();
+    let companion = all_events.get(all_events.len() - 2).unwrap();
+    assert_eq!(companion.0, client.address);
+    let expected_topics = vec![
+        &env,
+        symbol_short!("transfer").into_val(&env),
+        donator.into_val(&env),
+        client.address.into_val(&env),
+        token.address.into_val(&env),
+    ];
+    assert_eq!(companion.1, expected_topics);
+    let amount: i128 = companion.2.try_into_val(&env).unwrap();
+    assert_eq!(amount, 250);
+}
+
+#[test]
+fn test_init_event_carries_version() {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+
+    let all_events = env.events().all();
+    let last_event = all_events.last().expect("No events found");
+    assert_eq!(last_event.0, client.address);
+    assert_eq!(
+        last_event.1,
+        vec![&env, symbol_short!("init").into_val(&env)]
+    );
+
+    let (admin, version, schema): (Address, String, u32) = last_event.2.try_into_val(&env).unwrap();
+    assert_eq!(admin, super_admin);
+    assert_eq!(version, client.version().version);
+    assert_eq!(schema, client.version().schema);
+    assert_eq!(
+        client.version().version,
+        String::from_str(&env, env!("CARGO_PKG_VERSION"))
+    );
+}
+
+#[test]
+fn test_transfer_super_admin_emits_sa_xfer() {
+    let (env, client, super_admin) = setup_with_init();
+    let successor = Address::generate(&env);
+    client.transfer_super_admin(&super_admin, &successor);
+
+    let all_events = env.events().all();
+    let xfer = all_events
+        .iter()
+        .find(|e| e.1 == vec![&env, symbol_short!("sa_xfer").into_val(&env)])
+        .expect("sa_xfer not emitted");
+    assert_eq!(xfer.0, client.address);
+    let (old, new): (Address, Address) = xfer.2.try_into_val(&env).unwrap();
+    assert_eq!(old, super_admin);
+    assert_eq!(new, successor);
+}
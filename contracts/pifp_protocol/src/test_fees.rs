@@ -0,0 +1,563 @@
+extern crate std;
+
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Events},
+    token, vec, Address, BytesN, Env, IntoVal, Val, Vec,
+};
+
+use crate::price::{Asset, PriceData};
+use crate::{FeeConfig, PifpProtocol, PifpProtocolClient, ProtocolConfig, Role};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+fn escrowed(client: &PifpProtocolClient<'static>, project_id: u64) -> i128 {
+    client
+        .get_balances(&project_id)
+        .balances
+        .get(0)
+        .unwrap()
+        .balance
+}
+
+/// Charge a 2% protocol fee into `treasury`.
+fn enable_fee(client: &PifpProtocolClient<'static>, super_admin: &Address, treasury: &Address) {
+    client.set_protocol_config(
+        super_admin,
+        &ProtocolConfig {
+            treasury: Some(treasury.clone()),
+            fee_bps: 200,
+            ..client.get_protocol_config()
+        },
+    );
+}
+
+#[test]
+fn test_fee_goes_to_treasury() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    enable_fee(&client, &super_admin, &treasury);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(&env, &client, &creator, &vec![&env, token.address.clone()]);
+
+    assert_eq!(client.get_fee_bps(&project.id), 200);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        1_000,
+    );
+    assert_eq!(token.balance(&treasury), 20);
+    assert_eq!(escrowed(&client, project.id), 980);
+}
+
+#[test]
+fn test_fee_override_supersedes_global_fee() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    enable_fee(&client, &super_admin, &treasury);
+    let token = create_token(&env, &Address::generate(&env));
+    let relief = register(&env, &client, &creator, &vec![&env, token.address.clone()]);
+    let flagship = register(&env, &client, &creator, &vec![&env, token.address.clone()]);
+
+    client.set_fee_override(&super_admin, &relief.id, &Some(0));
+    client.set_fee_override(&super_admin, &flagship.id, &Some(500));
+    assert_eq!(client.get_fee_bps(&relief.id), 0);
+    fund(
+        &env,
+        &client,
+        relief.id,
+        &Address::generate(&env),
+        &token.address,
+        1_000,
+    );
+    fund(
+        &env,
+        &client,
+        flagship.id,
+        &Address::generate(&env),
+        &token.address,
+        1_000,
+    );
+    assert_eq!(escrowed(&client, relief.id), 1_000);
+    assert_eq!(escrowed(&client, flagship.id), 950);
+    assert_eq!(token.balance(&treasury), 50);
+
+    // Clearing the override restores the global fee.
+    client.set_fee_override(&super_admin, &relief.id, &None);
+    assert_eq!(client.get_fee_bps(&relief.id), 200);
+}
+
+#[test]
+fn test_no_fee_without_treasury() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            fee_bps: 200,
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        1_000,
+    );
+    assert_eq!(escrowed(&client, project.id), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_fee_override_above_cap_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token]);
+
+    client.set_fee_override(
+        &super_admin,
+        &project.id,
+        &Some(ProtocolConfig::MAX_FEE_BPS + 1),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_fee_override_requires_admin() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token]);
+
+    client.set_fee_override(&creator, &project.id, &Some(0));
+}
+
+// ─── Refund sponsorship ─────────────────────────────────
+
+#[test]
+fn test_small_refunds_sponsored_within_budget() {
+    let (env, client, super_admin) = setup_with_init();
+    let treasury = Address::generate(&env);
+    let donor = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            sponsor_threshold: 100,
+            sponsor_amount: 5,
+            sponsor_budget: 12,
+            ..client.get_protocol_config()
+        },
+    );
+    token::StellarAssetClient::new(&env, &token.address).mint(&treasury, &50);
+    client.fund_sponsor_pool(&treasury, &token.address, &50);
+
+    let paid = env.as_contract(&client.address, || {
+        let sponsor = |refunded| crate::sponsor_refund(&env, 0, &donor, &token.address, refunded);
+        // Large claims are not sponsored; the budget caps the rest.
+        std::vec![
+            sponsor(101),
+            sponsor(100),
+            sponsor(40),
+            sponsor(40),
+            sponsor(40)
+        ]
+    });
+    assert_eq!(paid, std::vec![0, 5, 5, 2, 0]);
+    assert_eq!(token.balance(&donor), 12);
+    assert_eq!(client.get_sponsor_pool(&token.address), 38);
+}
+
+#[contract]
+pub struct FixedPriceFeed;
+
+#[contractimpl]
+impl FixedPriceFeed {
+    pub fn set_price(env: Env, token: Address, price: i128) {
+        env.storage().instance().set(&token, &price);
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        match asset {
+            Asset::Stellar(token) => env.storage().instance().get(&token).map(|price| PriceData {
+                price,
+                timestamp: env.ledger().timestamp(),
+            }),
+            Asset::Other(_) => None,
+        }
+    }
+}
+
+/// Pays out `fill_bps` of the input amount, ignoring `min_out`, so tests
+/// can check the contract's own slippage bound.
+#[contract]
+pub struct MockRouter;
+
+#[contractimpl]
+impl MockRouter {
+    pub fn set_fill(env: Env, fill_bps: i128) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("fill"), &fill_bps);
+    }
+
+    pub fn swap(
+        env: Env,
+        _token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        _min_out: i128,
+        to: Address,
+    ) -> i128 {
+        let fill: i128 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("fill"))
+            .unwrap();
+        let out = amount_in * fill / 10_000;
+        token::Client::new(&env, &token_out).transfer(&env.current_contract_address(), &to, &out);
+        out
+    }
+}
+
+struct Conversion {
+    env: Env,
+    client: PifpProtocolClient<'static>,
+    super_admin: Address,
+    token: Address,
+    reserve: token::Client<'static>,
+    treasury: Address,
+    project_id: u64,
+}
+
+/// A 2% fee converted into a fresh reserve token through a router filling
+/// at `fill_bps` of the feed's 1:1 quote, for a project accepting `token`.
+fn conversion(fill_bps: i128) -> Conversion {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let reserve = create_token(&env, &Address::generate(&env));
+
+    let feed = env.register(FixedPriceFeed, ());
+    let feed_client = FixedPriceFeedClient::new(&env, &feed);
+    feed_client.set_price(&token, &10_000_000);
+    feed_client.set_price(&reserve.address, &10_000_000);
+    let router = env.register(MockRouter, ());
+    MockRouterClient::new(&env, &router).set_fill(&fill_bps);
+    token::StellarAssetClient::new(&env, &reserve.address).mint(&router, &1_000);
+
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            treasury: Some(treasury.clone()),
+            fee_bps: 200,
+            price_feed: Some(feed),
+            reserve_token: Some(reserve.address.clone()),
+            dex_router: Some(router),
+            conversion_threshold: 30,
+            max_slippage_bps: 100,
+            ..client.get_protocol_config()
+        },
+    );
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    Conversion {
+        env,
+        client,
+        super_admin,
+        token,
+        reserve,
+        treasury,
+        project_id: project.id,
+    }
+}
+
+#[test]
+fn test_fees_held_and_converted_to_reserve() {
+    let Conversion {
+        env,
+        client,
+        token,
+        reserve,
+        treasury,
+        project_id,
+        ..
+    } = conversion(9_950);
+    let donor = Address::generate(&env);
+    fund(&env, &client, project_id, &donor, &token, 1_000);
+    assert_eq!(client.get_fee_vault(&token), 20);
+    assert_eq!(token::Client::new(&env, &token).balance(&treasury), 0);
+
+    // Below the threshold nothing is swapped.
+    assert!(client.try_convert_fees(&token).is_err());
+
+    fund(&env, &client, project_id, &donor, &token, 1_000);
+    assert_eq!(client.convert_fees(&token), 39);
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("converted"), token.clone()).into_val(&env);
+    let (_, _, data) = events.iter().find(|(_, t, _)| *t == topics).unwrap();
+    let (out_token, amount_in, amount_out): (Address, i128, i128) = data.into_val(&env);
+    assert_eq!(
+        (out_token, amount_in, amount_out),
+        (reserve.address.clone(), 40, 39)
+    );
+    assert_eq!(reserve.balance(&treasury), 39);
+    assert_eq!(client.get_fee_vault(&token), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #33)")]
+fn test_conversion_beyond_slippage_rejected() {
+    let Conversion {
+        env,
+        client,
+        token,
+        project_id,
+        ..
+    } = conversion(9_800);
+    fund(
+        &env,
+        &client,
+        project_id,
+        &Address::generate(&env),
+        &token,
+        20_000,
+    );
+
+    client.convert_fees(&token);
+}
+
+#[test]
+fn test_held_fees_paid_out_once_conversion_disabled() {
+    let Conversion {
+        env,
+        client,
+        super_admin,
+        token,
+        treasury,
+        project_id,
+        ..
+    } = conversion(9_950);
+    fund(
+        &env,
+        &client,
+        project_id,
+        &Address::generate(&env),
+        &token,
+        1_000,
+    );
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            reserve_token: None,
+            ..client.get_protocol_config()
+        },
+    );
+
+    assert_eq!(client.convert_fees(&token), 20);
+    assert_eq!(token::Client::new(&env, &token).balance(&treasury), 20);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_reserve_without_router_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            reserve_token: Some(Address::generate(&env)),
+            ..client.get_protocol_config()
+        },
+    );
+}
+
+#[test]
+fn test_release_fee_held_and_withdrawn_to_treasury() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            treasury: Some(treasury.clone()),
+            ..client.get_protocol_config()
+        },
+    );
+    client.set_release_fee_bps(&super_admin, &500);
+    assert_eq!(
+        client.get_fee_config(),
+        FeeConfig {
+            treasury: Some(treasury.clone()),
+            deposit_fee_bps: 0,
+            release_fee_bps: 500,
+        }
+    );
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(&env, &client, &creator, &vec![&env, token.address.clone()]);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        1_000,
+    );
+
+    client.verify_and_release(
+        &oracle,
+        &project.id,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+    );
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("rel_fee"), project.id).into_val(&env);
+    assert!(events.iter().any(|(_, t, _)| t == topics));
+    assert_eq!(token.balance(&creator), 950);
+    assert_eq!(client.get_fee_vault(&token.address), 50);
+    assert_eq!(
+        client.get_token_flows(&project.id, &token.address).released,
+        1_000
+    );
+
+    client.withdraw_treasury(&super_admin, &token.address, &30);
+    assert_eq!(token.balance(&treasury), 30);
+    assert_eq!(client.get_fee_vault(&token.address), 20);
+    let result = client.try_withdraw_treasury(&super_admin, &token.address, &21);
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_release_fee_requires_super_admin() {
+    let (env, client, super_admin) = setup_with_init();
+    let admin = Address::generate(&env);
+    client.grant_role(&super_admin, &admin, &Role::Admin, &None);
+
+    client.set_release_fee_bps(&admin, &100);
+}
+
+#[test]
+fn test_pull_payments_held_until_creator_withdraws() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            pull_payments: true,
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(&env, &client, &creator, &vec![&env, token.address.clone()]);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        1_000,
+    );
+
+    client.verify_and_release(
+        &oracle,
+        &project.id,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+    );
+    assert_eq!(token.balance(&creator), 0);
+    assert_eq!(client.get_claimable(&project.id, &token.address), 1_000);
+
+    let stranger = Address::generate(&env);
+    let result = client.try_withdraw(&vec![&env, stranger.clone()], &project.id, &token.address);
+    assert!(result.is_err());
+
+    assert_eq!(
+        client.withdraw(&vec![&env, creator.clone()], &project.id, &token.address),
+        1_000
+    );
+    let events = env.events().all();
+    let topics: Vec<Val> = (
+        symbol_short!("withdrawn"),
+        project.id,
+        token.address.clone(),
+    )
+        .into_val(&env);
+    assert!(events.iter().any(|(_, t, _)| t == topics));
+    assert_eq!(token.balance(&creator), 1_000);
+    assert_eq!(client.get_claimable(&project.id, &token.address), 0);
+
+    let result = client.try_withdraw(&vec![&env, creator.clone()], &project.id, &token.address);
+    assert!(result.is_err());
+}
@@ -0,0 +1,82 @@
+extern crate std;
+
+use crate::fsm::{transition, StatusEvent};
+use crate::invariants::assert_valid_status_transition;
+use crate::{Error, ProjectStatus};
+
+const STATUSES: [ProjectStatus; 4] = [
+    ProjectStatus::Funding,
+    ProjectStatus::Active,
+    ProjectStatus::Completed,
+    ProjectStatus::Expired,
+];
+
+#[test]
+fn test_every_status_change_is_a_valid_transition() {
+    for from in STATUSES.iter() {
+        for event in StatusEvent::ALL {
+            if let Ok(to) = transition(from, event) {
+                if to != *from {
+                    assert_valid_status_transition(from, &to);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_every_valid_transition_has_an_event() {
+    for from in STATUSES.iter() {
+        for to in STATUSES.iter() {
+            let reachable = StatusEvent::ALL
+                .iter()
+                .any(|event| transition(from, *event).as_ref() == Ok(to) && to != from);
+            assert_eq!(reachable, from.can_advance_to(to), "{:?} -> {:?}", from, to);
+        }
+    }
+}
+
+#[test]
+fn test_terminal_states_reject_every_event() {
+    for from in [ProjectStatus::Completed, ProjectStatus::Expired] {
+        for event in StatusEvent::ALL {
+            assert_eq!(
+                transition(&from, event),
+                Err(Error::InvalidStatusTransition)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_event_outcomes() {
+    use ProjectStatus::*;
+    let cases = [
+        (Funding, StatusEvent::Activate, Active),
+        (Active, StatusEvent::Activate, Active),
+        (Funding, StatusEvent::Verify, Completed),
+        (Active, StatusEvent::Verify, Completed),
+        (Funding, StatusEvent::Expire, Expired),
+        (Active, StatusEvent::Expire, Expired),
+        (Funding, StatusEvent::Cancel, Expired),
+        (Active, StatusEvent::Cancel, Expired),
+        (Funding, StatusEvent::Reject, Funding),
+        (Active, StatusEvent::Reject, Active),
+    ];
+    for (from, event, to) in cases {
+        assert_eq!(transition(&from, event), Ok(to));
+    }
+}
+
+#[test]
+fn test_reaching_maps_targets_to_events() {
+    assert_eq!(StatusEvent::reaching(&ProjectStatus::Funding), None);
+    for to in [
+        ProjectStatus::Active,
+        ProjectStatus::Completed,
+        ProjectStatus::Expired,
+    ] {
+        let event = StatusEvent::reaching(&to).unwrap();
+        assert_eq!(transition(&ProjectStatus::Funding, event), Ok(to));
+    }
+}
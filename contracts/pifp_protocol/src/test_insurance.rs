@@ -0,0 +1,143 @@
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, Vec};
+
+use crate::{PifpProtocol, PifpProtocolClient, ProjectState, ProtocolConfig, Role};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+fn escrowed(client: &PifpProtocolClient<'static>, project_id: u64) -> i128 {
+    client
+        .get_balances(&project_id)
+        .balances
+        .get(0)
+        .unwrap()
+        .balance
+}
+
+#[test]
+fn test_insured_project_pays_premium_into_pool() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+
+    client.set_insured(&vec![&env, creator.clone()], &project.id);
+    assert!(client
+        .get_project_state(&project.id)
+        .has_flag(ProjectState::FLAG_INSURED));
+    assert_eq!(client.get_insurance(&project.id), Some(100));
+
+    fund(&env, &client, project.id, &donor, &token, 1_000);
+    assert_eq!(client.get_insurance_pool(&token), 10);
+    assert_eq!(escrowed(&client, project.id), 990);
+    assert_eq!(client.get_donation(&project.id, &donor, &token), 1_000);
+}
+
+#[test]
+fn test_premium_locked_at_opt_in() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let insured = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    let uninsured = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    client.set_insured(&vec![&env, creator.clone()], &insured.id);
+
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            insurance_premium_bps: 500,
+            ..client.get_protocol_config()
+        },
+    );
+    fund(
+        &env,
+        &client,
+        insured.id,
+        &Address::generate(&env),
+        &token,
+        1_000,
+    );
+    fund(
+        &env,
+        &client,
+        uninsured.id,
+        &Address::generate(&env),
+        &token,
+        1_000,
+    );
+
+    assert_eq!(client.get_insurance_pool(&token), 10);
+    assert_eq!(client.get_insurance(&uninsured.id), None);
+    assert_eq!(escrowed(&client, uninsured.id), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_insure_after_funding_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        100,
+    );
+
+    client.set_insured(&vec![&env, creator.clone()], &project.id);
+}
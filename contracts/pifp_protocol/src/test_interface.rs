@@ -0,0 +1,199 @@
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, Vec};
+
+use crate::test_donors::{MockPriceFeed, MockPriceFeedClient};
+use crate::{
+    CrowdfundingClient, GoalProgress, GoalUnit, PifpProtocol, PifpProtocolClient, ProjectStatus,
+    ProtocolConfig, Role,
+};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+#[test]
+fn test_crowdfunding_client_funds_and_reports() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+
+    let generic = CrowdfundingClient::new(&env, &client.address);
+    let funder = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&funder, &400);
+    generic.fund(&funder, &project.id, &token, &400);
+
+    assert_eq!(generic.status(&project.id), ProjectStatus::Funding);
+    assert_eq!(
+        generic.goal_progress(&project.id),
+        GoalProgress {
+            raised: 400,
+            goal: 1000
+        }
+    );
+    assert_eq!(client.get_donation(&project.id, &funder, &token), 400);
+}
+
+#[test]
+fn test_goal_progress_survives_release() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        1_000,
+    );
+
+    client.verify_and_release(
+        &oracle,
+        &project.id,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+    );
+    let generic = CrowdfundingClient::new(&env, &client.address);
+    assert_eq!(generic.status(&project.id), ProjectStatus::Completed);
+    assert_eq!(generic.goal_progress(&project.id).raised, 1_000);
+}
+
+#[test]
+fn test_goal_progress_ignores_secondary_tokens() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let primary = create_token(&env, &Address::generate(&env)).address;
+    let secondary = create_token(&env, &Address::generate(&env)).address;
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &vec![&env, primary.clone(), secondary.clone()],
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &secondary,
+        900,
+    );
+
+    let generic = CrowdfundingClient::new(&env, &client.address);
+    assert_eq!(generic.goal_progress(&project.id).raised, 0);
+}
+
+#[test]
+fn test_reference_goal_sums_all_tokens_at_feed_prices() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let feed = MockPriceFeedClient::new(&env, &env.register(MockPriceFeed, ()));
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            price_feed: Some(feed.address.clone()),
+            ..client.get_protocol_config()
+        },
+    );
+    let usdc = create_token(&env, &Address::generate(&env)).address;
+    let xlm = create_token(&env, &Address::generate(&env)).address;
+    feed.set_price(&usdc, &10_000_000);
+    feed.set_price(&xlm, &1_000_000);
+
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &vec![&env, usdc.clone(), xlm.clone()],
+    );
+    client.set_reference_goal(&vec![&env, creator.clone()], &project.id);
+    assert_eq!(
+        client.get_project(&project.id).goal_unit,
+        GoalUnit::Reference
+    );
+
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &usdc,
+        300,
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &xlm,
+        2_000,
+    );
+
+    let generic = CrowdfundingClient::new(&env, &client.address);
+    assert_eq!(generic.goal_progress(&project.id).raised, 500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_reference_goal_requires_price_feed() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token]);
+
+    client.set_reference_goal(&vec![&env, creator.clone()], &project.id);
+}
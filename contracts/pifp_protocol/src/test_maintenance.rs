@@ -0,0 +1,923 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec,
+    xdr::ToXdr,
+    Address, BytesN, Env, Symbol, TryFromVal, Vec,
+};
+
+use crate::{PifpProtocol, PifpProtocolClient, ProjectState, ProjectStatus, ProtocolConfig, Role};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+// ─── Rent self-funding ──────────────────────────────────
+
+#[test]
+fn test_bump_project_unpaid_by_default() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        10_000,
+    );
+
+    assert_eq!(client.bump_project(&keeper, &project.id, &token.address), 0);
+    assert_eq!(token.balance(&keeper), 0);
+}
+
+#[test]
+fn test_bump_project_pays_keeper_once_per_interval() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            rent_slice_bps: 50,
+            rent_bump_interval: 86_400,
+            ..client.get_protocol_config()
+        },
+    );
+
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        10_000,
+    );
+
+    assert_eq!(
+        client.bump_project(&keeper, &project.id, &token.address),
+        50
+    );
+    assert_eq!(token.balance(&keeper), 50);
+    assert_eq!(
+        client
+            .get_balances(&project.id)
+            .balances
+            .get(0)
+            .unwrap()
+            .balance,
+        9_950
+    );
+
+    // A second bump inside the interval extends TTLs but pays nothing.
+    assert_eq!(client.bump_project(&keeper, &project.id, &token.address), 0);
+
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    assert_eq!(
+        client.bump_project(&keeper, &project.id, &token.address),
+        49
+    );
+}
+
+#[test]
+fn test_rent_recorded_as_outflow() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            rent_slice_bps: 50,
+            ..client.get_protocol_config()
+        },
+    );
+
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    let donor = Address::generate(&env);
+    fund(&env, &client, project.id, &donor, &token.address, 10_000);
+    client.bump_project(&Address::generate(&env), &project.id, &token.address);
+
+    let flows = client.get_token_flows(&project.id, &token.address);
+    assert_eq!(flows.inflow, 10_000);
+    assert_eq!(flows.outflow, 50);
+}
+
+#[test]
+fn test_escrow_proof_reconciles_with_flows() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            rent_slice_bps: 50,
+            ..client.get_protocol_config()
+        },
+    );
+
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    let untouched = client.get_escrow_proof(&project.id, &token.address);
+    assert_eq!(untouched.balance, 0);
+    assert_eq!(untouched.updated_ledger, 0);
+
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        10_000,
+    );
+    env.ledger().with_mut(|l| l.sequence_number += 10);
+    client.bump_project(&Address::generate(&env), &project.id, &token.address);
+
+    let proof = client.get_escrow_proof(&project.id, &token.address);
+    assert_eq!(proof.balance, 9_950);
+    assert_eq!(proof.balance, proof.inflow - proof.outflow);
+    assert_eq!(proof.updated_ledger, env.ledger().sequence());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #28)")]
+fn test_outflow_beyond_inflow_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        100,
+    );
+
+    env.as_contract(&client.address, || {
+        crate::record_outflow(&env, project.id, &token.address, 101);
+    });
+}
+
+#[test]
+fn test_rent_sponsorship_spent_before_escrow_and_reclaimed() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            rent_slice_bps: 50,
+            rent_bump_interval: 86_400,
+            ..client.get_protocol_config()
+        },
+    );
+
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        10_000,
+    );
+    token::StellarAssetClient::new(&env, &token.address).mint(&sponsor, &80);
+    client.sponsor_rent(&sponsor, &project.id, &token.address, &80);
+
+    // The first bump is paid entirely by the sponsor.
+    assert_eq!(
+        client.bump_project(&keeper, &project.id, &token.address),
+        50
+    );
+    assert_eq!(client.get_rent_fund(&project.id, &token.address), 30);
+    assert_eq!(
+        client
+            .get_balances(&project.id)
+            .balances
+            .get(0)
+            .unwrap()
+            .balance,
+        10_000
+    );
+
+    // The second drains the fund; the escrow covers the rest.
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    client.bump_project(&keeper, &project.id, &token.address);
+    assert_eq!(client.get_rent_fund(&project.id, &token.address), 0);
+    assert_eq!(
+        client
+            .get_balances(&project.id)
+            .balances
+            .get(0)
+            .unwrap()
+            .balance,
+        9_980
+    );
+    assert_eq!(token.balance(&keeper), 100);
+}
+
+#[test]
+fn test_settled_project_bumps_leave_refunds_whole() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            rent_slice_bps: 50,
+            rent_bump_interval: 86_400,
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    let donors = [Address::generate(&env), Address::generate(&env)];
+    for donor in donors.iter() {
+        fund(&env, &client, project.id, donor, &token.address, 500);
+    }
+    env.ledger().with_mut(|l| l.timestamp += 86_401);
+    client.expire_project(&project.id);
+
+    // The first claim fixes the refund basis; later bumps must not eat
+    // into what it promised the remaining donors.
+    for donor in donors.iter() {
+        for _ in 0..3 {
+            assert_eq!(client.bump_project(&keeper, &project.id, &token.address), 0);
+            env.ledger().with_mut(|l| l.timestamp += 86_400);
+        }
+        client.claim_refund(&project.id, donor, &token.address);
+        assert_eq!(token.balance(donor), 500);
+    }
+    assert_eq!(token.balance(&keeper), 0);
+}
+
+#[test]
+fn test_unspent_rent_sponsorship_refunded_pro_rata() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    let minter = token::StellarAssetClient::new(&env, &token.address);
+    minter.mint(&first, &300);
+    minter.mint(&second, &100);
+    client.sponsor_rent(&first, &project.id, &token.address, &300);
+    client.sponsor_rent(&second, &project.id, &token.address, &100);
+    // Simulate half of the fund having been spent on bumps.
+    env.as_contract(&client.address, || {
+        crate::storage::set_rent_fund(&env, project.id, &token.address, 400, 200);
+    });
+
+    assert!(client
+        .try_reclaim_rent_sponsorship(&first, &project.id, &token.address)
+        .is_err());
+    complete(&env, &client, &super_admin, project.id);
+
+    assert_eq!(
+        client.reclaim_rent_sponsorship(&first, &project.id, &token.address),
+        150
+    );
+    assert_eq!(
+        client.reclaim_rent_sponsorship(&second, &project.id, &token.address),
+        50
+    );
+    assert_eq!(
+        client.reclaim_rent_sponsorship(&second, &project.id, &token.address),
+        0
+    );
+    assert_eq!(token.balance(&first), 150);
+    assert_eq!(client.get_rent_fund(&project.id, &token.address), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_rent_slice_above_cap_rejected() {
+    let (_env, client, super_admin) = setup_with_init();
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            rent_slice_bps: ProtocolConfig::MAX_RENT_SLICE_BPS + 1,
+            ..client.get_protocol_config()
+        },
+    );
+}
+
+// ─── Cleanup ────────────────────────────────────────────
+
+fn complete(env: &Env, client: &PifpProtocolClient<'static>, super_admin: &Address, id: u64) {
+    let oracle = Address::generate(env);
+    client.grant_role(super_admin, &oracle, &Role::Oracle, &None);
+    client.verify_and_release(&oracle, &id, &BytesN::from_array(env, &[0xabu8; 32]));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_cleanup_rejects_active_project() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+
+    client.cleanup(&creator, &project.id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_cleanup_rejects_unsettled_balance() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        500,
+    );
+    complete(&env, &client, &super_admin, project.id);
+
+    // Simulate escrow left behind after payout.
+    env.as_contract(&client.address, || {
+        crate::storage::set_token_balance(&env, project.id, &token.address, 500);
+    });
+    client.cleanup(&creator, &project.id);
+}
+
+#[test]
+fn test_cleanup_removes_settled_entries() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    let settled = register(&env, &client, &creator, &tokens);
+    let other = register(&env, &client, &creator, &tokens);
+    fund(&env, &client, settled.id, &donor, &token.address, 500);
+    fund(&env, &client, other.id, &donor, &token.address, 300);
+    complete(&env, &client, &super_admin, settled.id);
+    assert_eq!(token.balance(&creator), 500);
+
+    // TokenBalance, Contribution, ContribWeight, ProjectDonors and
+    // Attestations.
+    assert_eq!(client.cleanup(&creator, &settled.id), 5);
+    assert_eq!(client.get_donation(&settled.id, &donor, &token.address), 0);
+    let history = client.get_donor_history(&donor, &0, &10);
+    assert_eq!(history.len(), 1);
+    assert_eq!(
+        history.get(0).unwrap(),
+        (other.id, token.address.clone(), 300)
+    );
+
+    // The project record survives; a second cleanup finds nothing left.
+    assert_eq!(
+        client.get_project(&settled.id).status,
+        crate::ProjectStatus::Completed
+    );
+    assert_eq!(client.cleanup(&creator, &settled.id), 0);
+}
+
+#[test]
+fn test_cleanup_moves_dust_and_sweeps_to_treasury() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            treasury: Some(treasury.clone()),
+            dust_threshold: 5,
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        500,
+    );
+    complete(&env, &client, &super_admin, project.id);
+
+    // Simulate a payout that left a 3-unit remainder in escrow.
+    token::StellarAssetClient::new(&env, &token.address).mint(&client.address, &3);
+    env.as_contract(&client.address, || {
+        crate::storage::set_token_balance(&env, project.id, &token.address, 3);
+        let mut flows = crate::storage::get_token_flows(&env, project.id, &token.address);
+        flows.outflow -= 3;
+        crate::storage::set_token_flows(&env, project.id, &token.address, &flows);
+    });
+    client.cleanup(&creator, &project.id);
+    assert_eq!(client.get_dust(&token.address), 3);
+
+    assert_eq!(client.sweep_dust(&super_admin, &token.address), 3);
+    assert_eq!(token.balance(&treasury), 3);
+    assert_eq!(client.get_dust(&token.address), 0);
+    assert_eq!(client.sweep_dust(&super_admin, &token.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_sweep_dust_requires_treasury() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    client.sweep_dust(&super_admin, &token.address);
+}
+
+// ─── Keeper rewards ─────────────────────────────────────
+
+/// A completed project whose escrow has been paid out, ready for cleanup.
+fn settled_project(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    super_admin: &Address,
+    token: &Address,
+) -> u64 {
+    let creator = Address::generate(env);
+    client.grant_role(super_admin, &creator, &Role::ProjectManager, &None);
+    let project = register(
+        env,
+        client,
+        &creator,
+        &soroban_sdk::vec![env, token.clone()],
+    );
+    fund(env, client, project.id, &Address::generate(env), token, 500);
+    complete(env, client, super_admin, project.id);
+    env.as_contract(&client.address, || {
+        crate::storage::set_token_balance(env, project.id, token, 0);
+    });
+    project.id
+}
+
+#[test]
+fn test_cleanup_pays_keeper_from_pool() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    let funder = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            keeper_reward_token: Some(token.address.clone()),
+            keeper_reward: 30,
+            ..client.get_protocol_config()
+        },
+    );
+    token::StellarAssetClient::new(&env, &token.address).mint(&funder, &50);
+    client.fund_keeper_pool(&funder, &token.address, &50);
+    assert_eq!(client.get_keeper_pool(&token.address), 50);
+
+    let first = settled_project(&env, &client, &super_admin, &token.address);
+    let second = settled_project(&env, &client, &super_admin, &token.address);
+    client.cleanup(&keeper, &first);
+    assert_eq!(token.balance(&keeper), 30);
+
+    // A no-op crank earns nothing.
+    client.cleanup(&keeper, &first);
+    assert_eq!(token.balance(&keeper), 30);
+
+    // The reward is capped by what is left in the pool.
+    client.cleanup(&keeper, &second);
+    assert_eq!(token.balance(&keeper), 50);
+    assert_eq!(client.get_keeper_pool(&token.address), 0);
+}
+
+#[test]
+fn test_cleanup_waits_for_retention_period() {
+    let (env, client, super_admin) = setup_with_init();
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            retention_period: 90 * 86_400,
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env));
+    let id = settled_project(&env, &client, &super_admin, &token.address);
+    let keeper = Address::generate(&env);
+
+    assert!(client.try_cleanup(&keeper, &id).is_err());
+    env.ledger().with_mut(|l| l.timestamp += 90 * 86_400);
+    assert_eq!(client.cleanup(&keeper, &id), 5);
+}
+
+#[test]
+fn test_cleanup_starts_retention_for_unstamped_project() {
+    let (env, client, super_admin) = setup_with_init();
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            retention_period: 90 * 86_400,
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env));
+    let id = settled_project(&env, &client, &super_admin, &token.address);
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .remove(&crate::storage::ProjectKey::SettledAt(id));
+    });
+    env.ledger().with_mut(|l| l.timestamp += 365 * 86_400);
+    let keeper = Address::generate(&env);
+
+    // The first call only stamps the settlement time.
+    assert_eq!(client.cleanup(&keeper, &id), 0);
+    assert!(client.try_cleanup(&keeper, &id).is_err());
+    env.ledger().with_mut(|l| l.timestamp += 90 * 86_400);
+    assert_eq!(client.cleanup(&keeper, &id), 5);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_admin_cannot_shorten_retention() {
+    let (env, client, super_admin) = setup_with_init();
+    let admin = Address::generate(&env);
+    client.grant_role(&super_admin, &admin, &Role::Admin, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            retention_period: 90 * 86_400,
+            ..client.get_protocol_config()
+        },
+    );
+    client.set_protocol_config(
+        &admin,
+        &ProtocolConfig {
+            retention_period: 0,
+            ..client.get_protocol_config()
+        },
+    );
+}
+
+#[test]
+fn test_cleanup_unpaid_without_reward_config() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    let funder = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token.address).mint(&funder, &50);
+    client.fund_keeper_pool(&funder, &token.address, &50);
+
+    let id = settled_project(&env, &client, &super_admin, &token.address);
+    assert_eq!(client.cleanup(&keeper, &id), 5);
+    assert_eq!(token.balance(&keeper), 0);
+    assert_eq!(client.get_keeper_pool(&token.address), 50);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_negative_keeper_reward_rejected() {
+    let (_env, client, super_admin) = setup_with_init();
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            keeper_reward: -1,
+            ..client.get_protocol_config()
+        },
+    );
+}
+
+// ─── State encoding ─────────────────────────────────────
+
+fn sample_state() -> ProjectState {
+    ProjectState {
+        status: ProjectStatus::Active,
+        donation_count: 1_234,
+        flags: 0b101,
+        released_bps: 2_500,
+    }
+}
+
+#[test]
+fn test_project_state_pack_roundtrip() {
+    let state = sample_state();
+    assert_eq!(ProjectState::unpack(state.pack()), Some(state));
+}
+
+#[test]
+fn test_project_state_unpack_rejects_unknown_version() {
+    let packed = sample_state().pack();
+    let bumped = packed + (1u128 << 120);
+    assert_eq!(ProjectState::unpack(bumped), None);
+}
+
+#[test]
+fn test_packed_project_state_is_20_bytes() {
+    let env = Env::default();
+    let state = sample_state();
+    let packed = state.pack().to_xdr(&env);
+    let unpacked = state.to_xdr(&env);
+
+    assert_eq!(packed.len(), 20);
+    assert!(packed.len() * 4 < unpacked.len());
+}
+
+/// Bytes written by storing `state` under a scratch key of a fresh
+/// contract, packed or, if `map`, as the field map it was stored as
+/// before packing.
+fn state_write_bytes(state: &ProjectState, map: bool) -> u32 {
+    let (env, client) = setup();
+    let key = symbol_short!("scratch");
+    env.as_contract(&client.address, || {
+        if map {
+            env.storage().persistent().set(&key, state);
+        } else {
+            env.storage().persistent().set(&key, &state.pack());
+        }
+    });
+    env.cost_estimate().resources().write_bytes
+}
+
+#[test]
+fn test_packed_state_deposit_writes_less() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    fund(&env, &client, project.id, &donor, &token.address, 100);
+    fund(&env, &client, project.id, &donor, &token.address, 100);
+
+    // Deposits store the state packed.
+    let state = env.as_contract(&client.address, || {
+        let packed: u128 = env
+            .storage()
+            .persistent()
+            .get(&crate::storage::ProjectKey::ProjState(project.id))
+            .unwrap();
+        ProjectState::unpack(packed).unwrap()
+    });
+    assert_eq!(state.donation_count, 2);
+
+    // Every state write saves the difference in the entry's size.
+    let packed = state_write_bytes(&state, false);
+    let map = state_write_bytes(&state, true);
+    let saved = state.clone().to_xdr(&env).len() - state.pack().to_xdr(&env).len();
+    assert_eq!(map - packed, saved);
+    assert!(packed < map);
+}
+
+// ─── Storage report ─────────────────────────────────────
+
+#[test]
+fn test_storage_report_lists_project_entries() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &auditor, &Role::Auditor, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token.address,
+        500,
+    );
+
+    let report = client.storage_report(&auditor, &project.id);
+    assert_eq!(report.project_id, project.id);
+    assert_eq!(report.contributions, 1);
+    assert_eq!(report.last_rent_bump, None);
+
+    let entry = |kind| report.entries.iter().find(|e| e.kind == kind).unwrap();
+    let config = entry(symbol_short!("config"));
+    assert!(config.present && config.size > 0);
+    let balance = entry(symbol_short!("balance"));
+    assert_eq!(balance.token, Some(token.address.clone()));
+    assert!(balance.present);
+    let flows = entry(symbol_short!("flows"));
+    assert_eq!(flows.token, Some(token.address.clone()));
+    assert!(flows.present);
+    let cadence = entry(symbol_short!("cadence"));
+    assert!(!cadence.present);
+    assert_eq!(cadence.size, 0);
+
+    let total: u32 = report.entries.iter().map(|e| e.size).sum();
+    assert_eq!(report.total_size, total);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_storage_report_requires_operator_role() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.address.clone()],
+    );
+
+    client.storage_report(&creator, &project.id);
+}
+
+// ─── TTL extension events ───────────────────────────────
+
+/// `live_until` values of every `ttl_bump` event from the last invocation.
+fn ttl_bumps(env: &Env) -> std::vec::Vec<u32> {
+    let events = env.events().all();
+    events
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(env, &topics.get(0).unwrap()) == Ok(symbol_short!("ttl_bump"))
+        })
+        .map(|(_, _, data)| u32::try_from_val(env, &data).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_ttl_bump_events_report_horizon() {
+    let env = Env::default();
+    env.mock_all_auths();
+    // Keep every entry, the contract instance included, alive across the
+    // simulated month regardless of bumps. Set before registering so the
+    // instance is created with the longer TTL.
+    env.ledger().with_mut(|l| {
+        l.min_persistent_entry_ttl = 90 * 17_280;
+        l.max_entry_ttl = 365 * 17_280;
+    });
+    let client = PifpProtocolClient::new(&env, &env.register(PifpProtocol, ()));
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    let creator = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &soroban_sdk::vec![&env, token.clone()],
+    );
+
+    let month = 30 * 17_280;
+    client.bump_project(&keeper, &project.id, &token);
+    assert_eq!(ttl_bumps(&env), [env.ledger().sequence() + month]);
+
+    // Well within the horizon: automatic bumps are no-ops and stay quiet.
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        10,
+    );
+    assert!(ttl_bumps(&env).is_empty());
+
+    // Under a week left: the next read extends the entries and says so.
+    env.ledger().with_mut(|l| l.sequence_number += 24 * 17_280);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        10,
+    );
+    assert_eq!(ttl_bumps(&env), [env.ledger().sequence() + month]);
+}
@@ -0,0 +1,274 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, BytesN, Env, IntoVal, Val, Vec,
+};
+
+use crate::{PifpProtocol, PifpProtocolClient, Role};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+/// A project accepting two tokens, with a 300 per-donor match cap.
+fn matched_project() -> (Env, PifpProtocolClient<'static>, u64, Address, Address) {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let first = create_token(&env, &Address::generate(&env)).address;
+    let second = create_token(&env, &Address::generate(&env)).address;
+    let project = register(
+        &env,
+        &client,
+        &creator,
+        &vec![&env, first.clone(), second.clone()],
+    );
+    client.set_match_cap(&super_admin, &project.id, &300);
+    (env, client, project.id, first, second)
+}
+
+#[test]
+fn test_deposits_tagged_up_to_cap() {
+    let (env, client, project_id, first, _) = matched_project();
+    let donor = Address::generate(&env);
+    assert_eq!(client.get_match_cap(&project_id), 300);
+
+    fund(&env, &client, project_id, &donor, &first, 200);
+    assert_eq!(client.get_match_eligible(&project_id, &donor), 200);
+
+    // The receipt tells the donor how much of this deposit is matched.
+    token::StellarAssetClient::new(&env, &first).mint(&donor, &250);
+    let receipt = client.deposit(&project_id, &donor, &first, &250, &false);
+    assert_eq!(receipt.transferred, 250);
+    assert_eq!(receipt.matched, 100);
+
+    // So does the event.
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("matchable"), project_id).into_val(&env);
+    let (_, _, data) = events.iter().find(|(_, t, _)| *t == topics).unwrap();
+    let (tagged_donor, eligible): (Address, i128) = data.into_val(&env);
+    assert_eq!(tagged_donor, donor);
+    assert_eq!(eligible, 100);
+    assert_eq!(client.get_match_eligible(&project_id, &donor), 300);
+
+    // Once capped, further giving is not tagged.
+    fund(&env, &client, project_id, &donor, &first, 50);
+    assert_eq!(client.get_match_eligible(&project_id, &donor), 300);
+}
+
+#[test]
+fn test_only_first_token_counts_towards_cap() {
+    let (env, client, project_id, _, second) = matched_project();
+    let donor = Address::generate(&env);
+    fund(&env, &client, project_id, &donor, &second, 200);
+    assert_eq!(client.get_match_eligible(&project_id, &donor), 0);
+}
+
+#[test]
+fn test_no_tagging_without_cap() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    let donor = Address::generate(&env);
+    fund(&env, &client, project.id, &donor, &token, 200);
+    assert_eq!(client.get_match_eligible(&project.id, &donor), 0);
+}
+
+#[test]
+fn test_early_deposits_weigh_more() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+
+    fund(&env, &client, project.id, &early, &token, 100);
+    env.ledger().with_mut(|l| l.timestamp += 43_200);
+    fund(&env, &client, project.id, &late, &token, 100);
+    fund(&env, &client, project.id, &early, &token, 10);
+
+    assert_eq!(
+        client.get_donation_weight(&project.id, &early, &token),
+        100 * 86_400 + 10 * 43_200
+    );
+    assert_eq!(
+        client.get_donation_weight(&project.id, &late, &token),
+        100 * 43_200
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_negative_match_cap_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token]);
+    client.set_match_cap(&super_admin, &project.id, &-1);
+}
+
+/// A project in one token with a one-to-one pool capped at 150 and funded
+/// with 200.
+fn pooled_project() -> (
+    Env,
+    PifpProtocolClient<'static>,
+    u64,
+    u64,
+    token::Client<'static>,
+    Address,
+) {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = register(&env, &client, &creator, &vec![&env, token.address.clone()]);
+    let pool_id = client.create_match_pool(&sponsor, &project.id, &token.address, &10_000, &150);
+    token::StellarAssetClient::new(&env, &token.address).mint(&sponsor, &200);
+    client.fund_match_pool(&sponsor, &pool_id, &200);
+    (env, client, project.id, pool_id, token, sponsor)
+}
+
+#[test]
+fn test_pool_matches_deposits_up_to_cap() {
+    let (env, client, project_id, pool_id, token, sponsor) = pooled_project();
+    let donor = Address::generate(&env);
+    assert_eq!(client.get_match_pools(&project_id), vec![&env, pool_id]);
+
+    fund(&env, &client, project_id, &donor, &token.address, 100);
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("matched"), project_id, pool_id).into_val(&env);
+    let (_, _, data) = events.iter().find(|(_, t, _)| *t == topics).unwrap();
+    let (matched_donor, matched): (Address, i128) = data.into_val(&env);
+    assert_eq!(matched_donor, donor);
+    assert_eq!(matched, 100);
+
+    // The cap, not the pool's balance, limits the second match.
+    fund(&env, &client, project_id, &donor, &token.address, 100);
+    let pool = client.get_match_pool(&pool_id).unwrap();
+    assert_eq!(pool.matched, 150);
+    assert_eq!(pool.balance, 50);
+    assert_eq!(
+        client.get_donation(&project_id, &sponsor, &token.address),
+        150
+    );
+    let balances = client.get_balances(&project_id).balances;
+    assert_eq!(balances.get(0).unwrap().balance, 350);
+}
+
+#[test]
+fn test_pool_refunded_to_sponsor_on_expiry() {
+    let (env, client, project_id, pool_id, token, sponsor) = pooled_project();
+    let donor = Address::generate(&env);
+    fund(&env, &client, project_id, &donor, &token.address, 100);
+
+    // Unspent funds stay locked while the project can still be matched.
+    let result = client.try_close_match_pool(&sponsor, &pool_id);
+    assert!(result.is_err());
+
+    env.ledger().with_mut(|l| l.timestamp += 86_401);
+    client.expire_project(&project_id);
+    assert_eq!(client.close_match_pool(&sponsor, &pool_id), 100);
+    assert_eq!(
+        client.claim_refund(&project_id, &sponsor, &token.address),
+        100
+    );
+    assert_eq!(token.balance(&sponsor), 200);
+    assert_eq!(token.balance(&donor), 0);
+    assert_eq!(
+        client.claim_refund(&project_id, &donor, &token.address),
+        100
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_match_pool_ratio_bounded() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    client.create_match_pool(
+        &Address::generate(&env),
+        &project.id,
+        &token,
+        &(crate::MatchPool::MAX_RATIO_BPS + 1),
+        &100,
+    );
+}
+
+#[test]
+fn test_pool_matches_only_eligible_part_under_cap() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    client.set_match_cap(&super_admin, &project.id, &300);
+    let pool_id = client.create_match_pool(&sponsor, &project.id, &token, &10_000, &1_000);
+    token::StellarAssetClient::new(&env, &token).mint(&sponsor, &1_000);
+    client.fund_match_pool(&sponsor, &pool_id, &1_000);
+
+    let donor = Address::generate(&env);
+    fund(&env, &client, project.id, &donor, &token, 500);
+    assert_eq!(client.get_match_eligible(&project.id, &donor), 300);
+    assert_eq!(client.get_match_pool(&pool_id).unwrap().matched, 300);
+
+    // Giving past the cap draws nothing more from the pool.
+    fund(&env, &client, project.id, &donor, &token, 100);
+    assert_eq!(client.get_match_pool(&pool_id).unwrap().matched, 300);
+}
@@ -0,0 +1,169 @@
+extern crate std;
+
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env,
+};
+
+use crate::{
+    MigrationRecord, PifpProtocol, PifpProtocolClient, ProjectState, ProjectStatus, ProtocolConfig,
+    Role,
+};
+
+/// Successor that keeps the last record it was handed.
+#[contract]
+pub struct MockSuccessor;
+
+#[contractimpl]
+impl MockSuccessor {
+    pub fn intake_project(env: Env, record: MigrationRecord) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("record"), &record);
+    }
+
+    pub fn record(env: Env) -> Option<MigrationRecord> {
+        env.storage().instance().get(&symbol_short!("record"))
+    }
+}
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+struct Fixture {
+    env: Env,
+    client: PifpProtocolClient<'static>,
+    super_admin: Address,
+    successor: MockSuccessorClient<'static>,
+    token: token::Client<'static>,
+    project_id: u64,
+}
+
+/// A project holding 600 of one token, with a successor configured.
+fn fixture() -> Fixture {
+    let (env, client, super_admin) = setup_with_init();
+    let successor = MockSuccessorClient::new(&env, &env.register(MockSuccessor, ()));
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            successor: Some(successor.address.clone()),
+            ..client.get_protocol_config()
+        },
+    );
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = client.register_project(
+        &creator,
+        &vec![&env, token.address.clone()],
+        &1000,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![&env],
+    );
+    let donor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token.address).mint(&donor, &600);
+    client.deposit(&project.id, &donor, &token.address, &600, &false);
+    Fixture {
+        env,
+        client,
+        super_admin,
+        successor,
+        token,
+        project_id: project.id,
+    }
+}
+
+fn migrate(f: &Fixture) -> bool {
+    f.client
+        .migrate_escrow(&f.super_admin, &f.project_id, &f.successor.address)
+}
+
+fn wait_out_delay(f: &Fixture) {
+    let delay = f.client.get_protocol_config().force_status_delay;
+    f.env.ledger().with_mut(|l| l.timestamp += delay);
+}
+
+#[test]
+fn test_migration_moves_escrow_after_delay() {
+    let f = fixture();
+    assert!(!migrate(&f));
+    let pending = f.client.get_pending_migration(&f.project_id).unwrap();
+    assert_eq!(pending.successor, f.successor.address);
+
+    wait_out_delay(&f);
+    assert!(migrate(&f));
+
+    assert_eq!(f.token.balance(&f.successor.address), 600);
+    assert_eq!(f.token.balance(&f.client.address), 0);
+    let record = f.successor.record().unwrap();
+    assert_eq!(record.project.id, f.project_id);
+    assert_eq!(record.project.donation_count, 1);
+    assert_eq!(record.balances.get(0).unwrap().balance, 600);
+
+    let state = f.client.get_project_state(&f.project_id);
+    assert_eq!(state.status, ProjectStatus::Expired);
+    assert!(state.has_flag(ProjectState::FLAG_MIGRATED));
+    assert!(f.client.get_pending_migration(&f.project_id).is_none());
+    let flows = f.client.get_token_flows(&f.project_id, &f.token.address);
+    assert_eq!(flows.outflow, 600);
+    let creator = f.client.get_creator_record(&record.project.creator);
+    assert_eq!(creator.projects_cancelled, 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_migration_before_delay_rejected() {
+    let f = fixture();
+    migrate(&f);
+    migrate(&f);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_migration_to_unapproved_successor_rejected() {
+    let f = fixture();
+    f.client
+        .migrate_escrow(&f.super_admin, &f.project_id, &Address::generate(&f.env));
+}
+
+#[test]
+fn test_cancelled_migration_restarts_delay() {
+    let f = fixture();
+    migrate(&f);
+    f.client.cancel_migration(&f.super_admin, &f.project_id);
+    assert!(f.client.get_pending_migration(&f.project_id).is_none());
+
+    wait_out_delay(&f);
+    assert!(!migrate(&f));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_migrated_project_has_no_local_refunds() {
+    let f = fixture();
+    migrate(&f);
+    wait_out_delay(&f);
+    migrate(&f);
+    f.client
+        .claim_refund(&f.project_id, &Address::generate(&f.env), &f.token.address);
+}
@@ -0,0 +1,153 @@
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, String, Vec};
+
+use crate::{PifpProtocol, PifpProtocolClient, Role};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn setup_project() -> (Env, PifpProtocolClient<'static>, Address, u64) {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token]);
+    (env, client, creator, project.id)
+}
+
+#[test]
+fn test_creator_hands_control_to_owner_set() {
+    let (env, client, creator, id) = setup_project();
+    let owners = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    client.set_project_owners(&vec![&env, creator], &id, &owners, &2);
+
+    let set = client.get_project_owners(&id).unwrap();
+    assert_eq!(set.owners, owners);
+    assert_eq!(set.threshold, 2);
+}
+
+#[test]
+fn test_owner_threshold_can_rotate_owners() {
+    let (env, client, creator, id) = setup_project();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let c = Address::generate(&env);
+    client.set_project_owners(
+        &vec![&env, creator],
+        &id,
+        &vec![&env, a.clone(), b.clone(), c],
+        &2,
+    );
+
+    let replacement = vec![&env, Address::generate(&env)];
+    client.set_project_owners(&vec![&env, a, b], &id, &replacement, &1);
+    assert_eq!(client.get_project_owners(&id).unwrap().owners, replacement);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_creator_loses_control_once_owners_set() {
+    let (env, client, creator, id) = setup_project();
+    let owners = vec![&env, Address::generate(&env), Address::generate(&env)];
+    client.set_project_owners(&vec![&env, creator.clone()], &id, &owners, &2);
+
+    client.set_project_owners(&vec![&env, creator.clone()], &id, &vec![&env, creator], &1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_duplicate_signer_counts_once() {
+    let (env, client, creator, id) = setup_project();
+    let a = Address::generate(&env);
+    let owners = vec![&env, a.clone(), Address::generate(&env)];
+    client.set_project_owners(&vec![&env, creator], &id, &owners, &2);
+
+    client.set_project_owners(&vec![&env, a.clone(), a.clone()], &id, &vec![&env, a], &1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_threshold_above_owner_count_rejected() {
+    let (env, client, creator, id) = setup_project();
+    let owners = vec![&env, Address::generate(&env)];
+    client.set_project_owners(&vec![&env, creator], &id, &owners, &2);
+}
+
+#[test]
+fn test_owner_threshold_runs_creator_gated_entry_points() {
+    let (env, client, creator, id) = setup_project();
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let owners = vec![&env, a.clone(), b.clone(), Address::generate(&env)];
+    client.set_project_owners(&vec![&env, creator.clone()], &id, &owners, &2);
+
+    let signers = vec![&env, a.clone(), b];
+    let name = String::from_str(&env, "Wells");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    client.update_metadata(&signers, &id, &name, &hash);
+    client.set_currency_lock(&signers, &id);
+    client.set_donor_allowlist(&signers, &id, &true);
+    assert_eq!(client.get_project_metadata(&id).unwrap().name, name);
+
+    // One owner, or the creator, no longer suffices.
+    assert!(client
+        .try_update_metadata(&vec![&env, a], &id, &name, &hash)
+        .is_err());
+    assert!(client
+        .try_submit_proof(&vec![&env, creator], &id, &hash)
+        .is_err());
+}
+
+#[test]
+fn test_admin_still_manages_owned_project_allowlist() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let id = register(&env, &client, &creator, &vec![&env, token]).id;
+    let owners = vec![&env, Address::generate(&env), Address::generate(&env)];
+    client.set_project_owners(&vec![&env, creator], &id, &owners, &2);
+
+    client.set_donor_allowlist(&vec![&env, super_admin], &id, &true);
+    assert!(!client.is_donor_allowed(&id, &Address::generate(&env)));
+}
@@ -0,0 +1,746 @@
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env, Vec,
+};
+
+use crate::{PifpProtocol, PifpProtocolClient, ProjectStatus, ProtocolConfig, Role};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+struct Fixture {
+    env: Env,
+    client: PifpProtocolClient<'static>,
+    super_admin: Address,
+    creator: Address,
+    token: Address,
+}
+
+fn fixture() -> Fixture {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    Fixture {
+        env,
+        client,
+        super_admin,
+        creator,
+        token,
+    }
+}
+
+// ─── Global pause ───────────────────────────────────────
+
+fn reason(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0x11u8; 32])
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_global_pause_blocks_deposits() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    f.client.pause(&f.super_admin, &reason(&f.env), &None);
+    assert!(f.client.is_paused());
+
+    fund(
+        &f.env,
+        &f.client,
+        project.id,
+        &Address::generate(&f.env),
+        &f.token,
+        100,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_global_pause_blocks_registration() {
+    let f = fixture();
+    f.client.pause(&f.super_admin, &reason(&f.env), &None);
+
+    register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+}
+
+#[test]
+fn test_unpause_restores_deposits() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    f.client.pause(&f.super_admin, &reason(&f.env), &None);
+    f.client.unpause(&f.super_admin);
+    assert!(!f.client.is_paused());
+
+    fund(
+        &f.env,
+        &f.client,
+        project.id,
+        &Address::generate(&f.env),
+        &f.token,
+        100,
+    );
+    assert_eq!(
+        f.client
+            .get_balances(&project.id)
+            .balances
+            .get(0)
+            .unwrap()
+            .balance,
+        100
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_pause_requires_admin() {
+    let f = fixture();
+    f.client.pause(&f.creator, &reason(&f.env), &None);
+}
+
+#[test]
+fn test_pause_info_records_reason_and_resume() {
+    let f = fixture();
+    assert_eq!(f.client.get_pause_info(), None);
+
+    let resume = f.env.ledger().timestamp() + 3_600;
+    f.client
+        .pause(&f.super_admin, &reason(&f.env), &Some(resume));
+    let info = f.client.get_pause_info().unwrap();
+    assert_eq!(info.by, f.super_admin);
+    assert_eq!(info.reason_hash, reason(&f.env));
+    assert_eq!(info.paused_at, f.env.ledger().timestamp());
+    assert_eq!(info.expected_resume, Some(resume));
+
+    f.client.unpause(&f.super_admin);
+    assert_eq!(f.client.get_pause_info(), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_pause_rejects_past_resume() {
+    let f = fixture();
+    f.client.pause(
+        &f.super_admin,
+        &reason(&f.env),
+        &Some(f.env.ledger().timestamp()),
+    );
+}
+
+// ─── Per-project pause ──────────────────────────────────
+
+#[test]
+fn test_project_pause_leaves_other_projects_open() {
+    let f = fixture();
+    let tokens = vec![&f.env, f.token.clone()];
+    let paused = register(&f.env, &f.client, &f.creator, &tokens);
+    let open = register(&f.env, &f.client, &f.creator, &tokens);
+    f.client.pause_project(&f.super_admin, &paused.id);
+
+    assert!(f.client.is_project_paused(&paused.id));
+    assert!(!f.client.is_project_paused(&open.id));
+    assert!(!f.client.is_paused());
+    fund(
+        &f.env,
+        &f.client,
+        open.id,
+        &Address::generate(&f.env),
+        &f.token,
+        100,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_project_pause_blocks_deposits() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    f.client.pause_project(&f.super_admin, &project.id);
+
+    fund(
+        &f.env,
+        &f.client,
+        project.id,
+        &Address::generate(&f.env),
+        &f.token,
+        100,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_project_pause_blocks_release() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    let oracle = Address::generate(&f.env);
+    f.client
+        .grant_role(&f.super_admin, &oracle, &Role::Oracle, &None);
+    f.client.pause_project(&f.super_admin, &project.id);
+
+    f.client.verify_and_release(
+        &oracle,
+        &project.id,
+        &BytesN::from_array(&f.env, &[0xabu8; 32]),
+    );
+}
+
+#[test]
+fn test_unpause_project_allows_release() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    let oracle = Address::generate(&f.env);
+    f.client
+        .grant_role(&f.super_admin, &oracle, &Role::Oracle, &None);
+    f.client.pause_project(&f.super_admin, &project.id);
+    f.client.unpause_project(&f.super_admin, &project.id);
+
+    f.client.verify_and_release(
+        &oracle,
+        &project.id,
+        &BytesN::from_array(&f.env, &[0xabu8; 32]),
+    );
+    assert_eq!(
+        f.client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+    assert!(!f.client.is_project_paused(&project.id));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_project_pause_holds_challenged_release() {
+    let f = fixture();
+    let period = 86_400;
+    f.client.set_protocol_config(
+        &f.super_admin,
+        &ProtocolConfig {
+            challenge_period: period,
+            ..f.client.get_protocol_config()
+        },
+    );
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    fund(
+        &f.env,
+        &f.client,
+        project.id,
+        &Address::generate(&f.env),
+        &f.token,
+        1000,
+    );
+    let oracle = Address::generate(&f.env);
+    f.client
+        .grant_role(&f.super_admin, &oracle, &Role::Oracle, &None);
+    f.client.verify_and_release(
+        &oracle,
+        &project.id,
+        &BytesN::from_array(&f.env, &[0xabu8; 32]),
+    );
+    f.client.pause_project(&f.super_admin, &project.id);
+
+    f.env.ledger().with_mut(|l| l.timestamp += period);
+    f.client
+        .close_challenge(&Address::generate(&f.env), &project.id);
+}
+
+// ─── Per-token pause ────────────────────────────────────
+
+#[test]
+fn test_token_pause_leaves_other_tokens_open() {
+    let f = fixture();
+    let other = create_token(&f.env, &Address::generate(&f.env)).address;
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone(), other.clone()],
+    );
+    f.client.pause_token(&f.super_admin, &f.token);
+
+    assert!(f.client.is_token_paused(&f.token));
+    assert!(!f.client.is_token_paused(&other));
+    assert!(!f.client.is_paused());
+    fund(
+        &f.env,
+        &f.client,
+        project.id,
+        &Address::generate(&f.env),
+        &other,
+        100,
+    );
+
+    f.client.unpause_token(&f.super_admin, &f.token);
+    fund(
+        &f.env,
+        &f.client,
+        project.id,
+        &Address::generate(&f.env),
+        &f.token,
+        100,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #27)")]
+fn test_token_pause_blocks_deposits() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    f.client.pause_token(&f.super_admin, &f.token);
+
+    fund(
+        &f.env,
+        &f.client,
+        project.id,
+        &Address::generate(&f.env),
+        &f.token,
+        100,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_token_pause_requires_admin() {
+    let f = fixture();
+    f.client.pause_token(&f.creator, &f.token);
+}
+
+// ─── Address freezes ────────────────────────────────────
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_frozen_donor_cannot_deposit() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    let donor = Address::generate(&f.env);
+    let reason = BytesN::from_array(&f.env, &[7u8; 32]);
+    f.client.freeze_address(&f.super_admin, &donor, &reason);
+    assert_eq!(f.client.get_freeze(&donor), Some(reason));
+
+    fund(&f.env, &f.client, project.id, &donor, &f.token, 100);
+}
+
+#[test]
+fn test_unfrozen_donor_can_deposit() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    let donor = Address::generate(&f.env);
+    f.client.freeze_address(
+        &f.super_admin,
+        &donor,
+        &BytesN::from_array(&f.env, &[7u8; 32]),
+    );
+    f.client.unfreeze_address(&f.super_admin, &donor);
+    assert_eq!(f.client.get_freeze(&donor), None);
+
+    fund(&f.env, &f.client, project.id, &donor, &f.token, 100);
+    assert_eq!(f.client.get_donation(&project.id, &donor, &f.token), 100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_freeze_requires_admin() {
+    let f = fixture();
+    f.client.freeze_address(
+        &f.creator,
+        &Address::generate(&f.env),
+        &BytesN::from_array(&f.env, &[7u8; 32]),
+    );
+}
+
+// ─── Forced status transitions ──────────────────────────
+
+#[test]
+fn test_force_status_applies_after_timelock() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    let reason = BytesN::from_array(&f.env, &[9u8; 32]);
+
+    assert!(!f.client.force_status(
+        &f.super_admin,
+        &project.id,
+        &ProjectStatus::Expired,
+        &reason
+    ));
+    let pending = f.client.get_pending_force_status(&project.id).unwrap();
+    assert_eq!(pending.new_status, ProjectStatus::Expired);
+    assert_eq!(pending.eta, ProtocolConfig::DEFAULT_FORCE_STATUS_DELAY);
+    assert_eq!(
+        f.client.get_project(&project.id).status,
+        ProjectStatus::Funding
+    );
+
+    f.env.ledger().with_mut(|l| l.timestamp = pending.eta);
+    assert!(f.client.force_status(
+        &f.super_admin,
+        &project.id,
+        &ProjectStatus::Expired,
+        &reason
+    ));
+    assert_eq!(
+        f.client.get_project(&project.id).status,
+        ProjectStatus::Expired
+    );
+    assert_eq!(f.client.get_pending_force_status(&project.id), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_force_status_rejects_early_apply() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    let reason = BytesN::from_array(&f.env, &[9u8; 32]);
+    f.client.force_status(
+        &f.super_admin,
+        &project.id,
+        &ProjectStatus::Expired,
+        &reason,
+    );
+
+    f.client.force_status(
+        &f.super_admin,
+        &project.id,
+        &ProjectStatus::Expired,
+        &reason,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_force_status_rejects_backward_transition() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+
+    f.client.force_status(
+        &f.super_admin,
+        &project.id,
+        &ProjectStatus::Funding,
+        &BytesN::from_array(&f.env, &[9u8; 32]),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_force_status_requires_super_admin() {
+    let f = fixture();
+    let admin = Address::generate(&f.env);
+    f.client
+        .grant_role(&f.super_admin, &admin, &Role::Admin, &None);
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+
+    f.client.force_status(
+        &admin,
+        &project.id,
+        &ProjectStatus::Expired,
+        &BytesN::from_array(&f.env, &[9u8; 32]),
+    );
+}
+
+#[test]
+fn test_cancel_force_status_clears_queue() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    f.client.force_status(
+        &f.super_admin,
+        &project.id,
+        &ProjectStatus::Expired,
+        &BytesN::from_array(&f.env, &[9u8; 32]),
+    );
+
+    f.client.cancel_force_status(&f.super_admin, &project.id);
+    assert_eq!(f.client.get_pending_force_status(&project.id), None);
+}
+
+// ─── Wind-down ──────────────────────────────────────────
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_sunset_blocks_deposits() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    f.client.begin_sunset(&f.super_admin);
+    assert!(f.client.is_sunset());
+
+    fund(
+        &f.env,
+        &f.client,
+        project.id,
+        &Address::generate(&f.env),
+        &f.token,
+        100,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #23)")]
+fn test_sunset_blocks_registration() {
+    let f = fixture();
+    f.client.begin_sunset(&f.super_admin);
+
+    register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+}
+
+#[test]
+fn test_sunset_keeps_verification_working() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    let oracle = Address::generate(&f.env);
+    f.client
+        .grant_role(&f.super_admin, &oracle, &Role::Oracle, &None);
+    f.client.begin_sunset(&f.super_admin);
+
+    f.client.verify_and_release(
+        &oracle,
+        &project.id,
+        &BytesN::from_array(&f.env, &[0xabu8; 32]),
+    );
+    assert_eq!(
+        f.client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+fn test_finalize_sunset_sweeps_only_residual() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    fund(
+        &f.env,
+        &f.client,
+        project.id,
+        &Address::generate(&f.env),
+        &f.token,
+        700,
+    );
+    // Funds sent straight to the contract are not escrowed for any project.
+    token::StellarAssetClient::new(&f.env, &f.token).mint(&f.client.address, &50);
+
+    f.client.begin_sunset(&f.super_admin);
+    let recipient = Address::generate(&f.env);
+    assert_eq!(
+        f.client
+            .finalize_sunset(&f.super_admin, &f.token, &recipient),
+        50
+    );
+
+    let token_client = token::Client::new(&f.env, &f.token);
+    assert_eq!(token_client.balance(&recipient), 50);
+    assert_eq!(token_client.balance(&f.client.address), 700);
+    assert_eq!(
+        f.client
+            .finalize_sunset(&f.super_admin, &f.token, &recipient),
+        0
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_finalize_sunset_requires_begin() {
+    let f = fixture();
+    f.client
+        .finalize_sunset(&f.super_admin, &f.token, &Address::generate(&f.env));
+}
+
+// ─── Maintenance windows ────────────────────────────────
+
+#[test]
+fn test_maintenance_window_pauses_only_while_active() {
+    let f = fixture();
+    let project = register(
+        &f.env,
+        &f.client,
+        &f.creator,
+        &vec![&f.env, f.token.clone()],
+    );
+    let now = f.env.ledger().timestamp();
+    f.client
+        .schedule_maintenance(&f.super_admin, &(now + 100), &(now + 200), &reason(&f.env));
+    assert!(!f.client.is_paused());
+    assert_eq!(f.client.get_pause_info(), None);
+
+    f.env.ledger().with_mut(|l| l.timestamp = now + 100);
+    assert!(f.client.is_paused());
+    assert_eq!(f.client.get_pause_info(), None);
+    let result = f.client.try_deposit(
+        &project.id,
+        &Address::generate(&f.env),
+        &f.token,
+        &100,
+        &false,
+    );
+    assert!(result.is_err());
+
+    f.env.ledger().with_mut(|l| l.timestamp = now + 200);
+    assert!(!f.client.is_paused());
+    fund(
+        &f.env,
+        &f.client,
+        project.id,
+        &Address::generate(&f.env),
+        &f.token,
+        100,
+    );
+}
+
+#[test]
+fn test_cancel_maintenance_ends_window_early() {
+    let f = fixture();
+    let now = f.env.ledger().timestamp();
+    f.client
+        .schedule_maintenance(&f.super_admin, &(now + 10), &(now + 1_000), &reason(&f.env));
+    f.env.ledger().with_mut(|l| l.timestamp = now + 10);
+    assert!(f.client.is_paused());
+
+    f.client.cancel_maintenance(&f.super_admin);
+    assert!(!f.client.is_paused());
+    assert_eq!(f.client.get_maintenance(), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_maintenance_must_start_in_future() {
+    let f = fixture();
+    let now = f.env.ledger().timestamp();
+    f.client
+        .schedule_maintenance(&f.super_admin, &now, &(now + 100), &reason(&f.env));
+}
@@ -0,0 +1,761 @@
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env, String, Symbol, Vec,
+};
+
+use crate::{
+    ActionableKind, DisbursementTemplate, Error, PifpProtocol, PifpProtocolClient, ProjectStatus,
+    ProtocolConfig, Role, TokenReport, Tranche,
+};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn create_tokens(env: &Env, count: u32) -> Vec<Address> {
+    let mut tokens = Vec::new(env);
+    for _ in 0..count {
+        tokens.push_back(create_token(env, &Address::generate(env)).address);
+    }
+    tokens
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+// ─── Split getters ───────────────────────────────────────
+
+#[test]
+fn test_split_getters_match_full_project() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let project = register(&env, &client, &creator, &create_tokens(&env, 2));
+
+    let config = client.get_project_config(&project.id);
+    assert_eq!(config.id, project.id);
+    assert_eq!(config.creator, project.creator);
+    assert_eq!(config.accepted_tokens, project.accepted_tokens);
+    assert_eq!(config.goal, project.goal);
+    assert_eq!(config.deadline, project.deadline);
+
+    let state = client.get_project_state(&project.id);
+    assert_eq!(state.status, ProjectStatus::Funding);
+    assert_eq!(state.donation_count, project.donation_count);
+}
+
+#[test]
+fn test_accepts_token_matches_accepted_list() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let tokens = create_tokens(&env, 2);
+    let project = register(&env, &client, &creator, &tokens);
+
+    for token in tokens.iter() {
+        assert!(client.accepts_token(&project.id, &token));
+    }
+    let other = create_token(&env, &Address::generate(&env)).address;
+    assert!(!client.accepts_token(&project.id, &other));
+}
+
+#[test]
+#[should_panic]
+fn test_get_project_config_missing_panics() {
+    let (_env, client, _) = setup_with_init();
+    client.get_project_config(&42);
+}
+
+// ─── Balances ────────────────────────────────────────────
+
+#[test]
+fn test_get_balances_page_returns_window() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let donator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let tokens = create_tokens(&env, 3);
+    let project = register(&env, &client, &creator, &tokens);
+
+    let second = tokens.get(1).unwrap();
+    token::StellarAssetClient::new(&env, &second).mint(&donator, &250);
+    client.deposit(&project.id, &donator, &second, &250, &false);
+
+    let all = client.get_balances(&project.id);
+    assert_eq!(all.balances.len(), 3);
+
+    let page = client.get_balances_page(&project.id, &1, &1);
+    assert_eq!(page.balances.len(), 1);
+    assert_eq!(page.balances.get(0).unwrap().token, second);
+    assert_eq!(page.balances.get(0).unwrap().balance, 250);
+
+    // Windows past the end are truncated or empty.
+    assert_eq!(
+        client
+            .get_balances_page(&project.id, &2, &10)
+            .balances
+            .len(),
+        1
+    );
+    assert_eq!(
+        client
+            .get_balances_page(&project.id, &5, &10)
+            .balances
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_currency_lock_narrows_to_first_deposit_token() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let tokens = create_tokens(&env, 3);
+    let project = register(&env, &client, &creator, &tokens);
+    client.set_currency_lock(&vec![&env, creator.clone()], &project.id);
+
+    let second = tokens.get(1).unwrap();
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &second,
+        300,
+    );
+    let config = client.get_project_config(&project.id);
+    assert_eq!(
+        config.accepted_tokens,
+        soroban_sdk::vec![&env, second.clone()]
+    );
+    assert_eq!(client.get_balances(&project.id).balances.len(), 1);
+
+    // The locked token keeps accepting deposits.
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &second,
+        200,
+    );
+    assert_eq!(
+        client
+            .get_balances(&project.id)
+            .balances
+            .get(0)
+            .unwrap()
+            .balance,
+        500
+    );
+}
+
+#[test]
+#[should_panic(expected = "token not accepted")]
+fn test_currency_lock_rejects_other_tokens() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let tokens = create_tokens(&env, 2);
+    let project = register(&env, &client, &creator, &tokens);
+    client.set_currency_lock(&vec![&env, creator.clone()], &project.id);
+
+    let donor = Address::generate(&env);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &donor,
+        &tokens.get(0).unwrap(),
+        300,
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &donor,
+        &tokens.get(1).unwrap(),
+        300,
+    );
+}
+
+#[test]
+fn test_max_accepted_tokens_configurable_upward() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            max_accepted_tokens: 12,
+            ..client.get_protocol_config()
+        },
+    );
+
+    let project = register(&env, &client, &creator, &create_tokens(&env, 12));
+    assert_eq!(project.accepted_tokens.len(), 12);
+}
+
+#[test]
+#[should_panic]
+fn test_too_many_tokens_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    register(
+        &env,
+        &client,
+        &creator,
+        &create_tokens(&env, ProtocolConfig::DEFAULT_MAX_ACCEPTED_TOKENS + 1),
+    );
+}
+
+fn strict_config(client: &PifpProtocolClient<'static>, super_admin: &Address) {
+    client.set_protocol_config(
+        super_admin,
+        &ProtocolConfig {
+            strict_tokens: true,
+            ..client.get_protocol_config()
+        },
+    );
+}
+
+#[test]
+fn test_strict_mode_accepts_token_contracts() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    strict_config(&client, &super_admin);
+
+    register(&env, &client, &creator, &create_tokens(&env, 2));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn test_strict_mode_rejects_non_token_contract() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    strict_config(&client, &super_admin);
+
+    let not_a_token = env.register(PifpProtocol, ());
+    register(&env, &client, &creator, &vec![&env, not_a_token]);
+}
+
+#[test]
+fn test_non_token_allowed_without_strict_mode() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let not_a_token = env.register(PifpProtocol, ());
+    register(&env, &client, &creator, &vec![&env, not_a_token]);
+}
+
+#[test]
+fn test_token_allowlist_gates_registration() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let tokens = create_tokens(&env, 2);
+    let listed = tokens.get(0).unwrap();
+    let unlisted = tokens.get(1).unwrap();
+
+    client.add_allowed_token(&super_admin, &listed);
+    client.add_allowed_token(&super_admin, &listed);
+    assert_eq!(client.list_allowed_tokens(), vec![&env, listed.clone()]);
+    // Not enforced until switched on.
+    assert!(client.is_token_allowed(&unlisted));
+
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            token_allowlist: true,
+            ..client.get_protocol_config()
+        },
+    );
+    assert!(!client.is_token_allowed(&unlisted));
+    register(&env, &client, &creator, &vec![&env, listed.clone()]);
+    assert!(client
+        .try_register_project(
+            &creator,
+            &tokens,
+            &1000,
+            &BytesN::from_array(&env, &[0xabu8; 32]),
+            &(env.ledger().timestamp() + 86400),
+            &1,
+            &vec![&env],
+        )
+        .is_err());
+
+    client.remove_allowed_token(&super_admin, &listed);
+    assert!(client.list_allowed_tokens().is_empty());
+    assert!(!client.is_token_allowed(&listed));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #30)")]
+fn test_allowlist_rejects_non_token_contract() {
+    let (env, client, super_admin) = setup_with_init();
+    let not_a_token = env.register(PifpProtocol, ());
+    client.add_allowed_token(&super_admin, &not_a_token);
+}
+
+#[test]
+#[should_panic]
+fn test_max_accepted_tokens_above_limit_rejected() {
+    let (_env, client, super_admin) = setup_with_init();
+
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            max_accepted_tokens: ProtocolConfig::MAX_ACCEPTED_TOKENS_LIMIT + 1,
+            ..client.get_protocol_config()
+        },
+    );
+}
+
+// ─── Enumeration ─────────────────────────────────────────
+
+#[test]
+fn test_list_projects_pages_by_id() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let tokens = create_tokens(&env, 1);
+    assert_eq!(client.get_project_count(), 0);
+    for _ in 0..3 {
+        register(&env, &client, &creator, &tokens);
+    }
+    assert_eq!(client.get_project_count(), 3);
+
+    let page = client.list_projects(&1, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().id, 1);
+    assert_eq!(page.get(1).unwrap(), client.get_project(&2));
+    assert!(client.list_projects(&3, &10).is_empty());
+    assert!(client.list_projects(&u64::MAX, &10).is_empty());
+
+    fund(
+        &env,
+        &client,
+        1,
+        &Address::generate(&env),
+        &tokens.get(0).unwrap(),
+        10,
+    );
+    env.ledger().with_mut(|l| l.timestamp += 86_401);
+    client.expire_project(&1);
+    assert_eq!(
+        client.list_project_ids_by_status(&ProjectStatus::Funding, &0, &10),
+        vec![&env, 0, 2]
+    );
+    assert_eq!(
+        client.list_project_ids_by_status(&ProjectStatus::Expired, &0, &1),
+        Vec::<u64>::new(&env)
+    );
+    assert_eq!(
+        client.list_project_ids_by_status(&ProjectStatus::Expired, &1, &1),
+        vec![&env, 1]
+    );
+}
+
+// ─── Operational queues ──────────────────────────────────
+
+#[test]
+fn test_actionable_projects_by_kind() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let tokens = vec![&env, token.clone()];
+    let funded = register(&env, &client, &creator, &tokens);
+    let idle = register(&env, &client, &creator, &tokens);
+    fund(
+        &env,
+        &client,
+        funded.id,
+        &Address::generate(&env),
+        &token,
+        1000,
+    );
+
+    let awaiting = client.get_actionable_projects(&ActionableKind::AwaitingVerification, &0, &10);
+    assert_eq!(awaiting, vec![&env, funded.id]);
+    assert!(client
+        .get_actionable_projects(&ActionableKind::PastDeadline, &0, &10)
+        .is_empty());
+
+    env.ledger().with_mut(|l| l.timestamp += 86_401);
+    let past = client.get_actionable_projects(&ActionableKind::PastDeadline, &0, &10);
+    assert_eq!(past, vec![&env, funded.id, idle.id]);
+
+    // Pages scan a window of IDs; expired projects leave every queue.
+    client.expire_project(&idle.id);
+    let page = client.get_actionable_projects(&ActionableKind::PastDeadline, &1, &10);
+    assert!(page.is_empty());
+    let page = client.get_actionable_projects(&ActionableKind::PastDeadline, &0, &1);
+    assert_eq!(page, vec![&env, funded.id]);
+}
+
+// ─── Consolidated report ─────────────────────────────────
+
+#[test]
+fn test_project_report_per_token() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_disbursement_template(
+        &super_admin,
+        &1,
+        &DisbursementTemplate {
+            tranches: soroban_sdk::vec![
+                &env,
+                Tranche {
+                    bps: 2_000,
+                    gated: true,
+                },
+                Tranche {
+                    bps: 8_000,
+                    gated: true,
+                },
+            ],
+        },
+    );
+
+    let tokens = create_tokens(&env, 2);
+    let (token_a, token_b) = (tokens.get(0).unwrap(), tokens.get(1).unwrap());
+    let project = register(&env, &client, &creator, &tokens);
+    client.set_insured(&vec![&env, creator.clone()], &project.id);
+    client.use_disbursement_template(&vec![&env, creator.clone()], &project.id, &1);
+
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token_a,
+        600,
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token_a,
+        400,
+    );
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token_b,
+        200,
+    );
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    client.verify_and_release(
+        &oracle,
+        &project.id,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+    );
+
+    let report = client.get_project_report(&project.id);
+    assert_eq!(report.project_id, project.id);
+    assert_eq!(report.status, ProjectStatus::Active);
+    assert_eq!(
+        report.tokens,
+        soroban_sdk::vec![
+            &env,
+            TokenReport {
+                token: token_a,
+                raised: 1_000,
+                released: 198,
+                refunded: 0,
+                outstanding: 792,
+            },
+            TokenReport {
+                token: token_b,
+                raised: 200,
+                released: 39,
+                refunded: 0,
+                outstanding: 159,
+            },
+        ]
+    );
+    assert_eq!(report.raised_value, 0);
+    assert_eq!(report.outstanding_value, 0);
+}
+
+// ─── Error identifiers ───────────────────────────────────
+
+#[test]
+fn test_error_message_names_each_code() {
+    let (env, client, _) = setup_with_init();
+    assert_eq!(
+        client.error_message(&1),
+        Symbol::new(&env, "project_not_found")
+    );
+    assert_eq!(
+        client.error_message(&(Error::ReleasePending as u32)),
+        Symbol::new(&env, "release_pending")
+    );
+    assert_eq!(client.error_message(&0), Symbol::new(&env, "unknown"));
+    assert_eq!(client.error_message(&999), Symbol::new(&env, "unknown"));
+
+    let mut code = 1;
+    while let Some(error) = Error::from_code(code) {
+        assert_eq!(error as u32, code);
+        assert_ne!(error.name(), "unknown");
+        code += 1;
+    }
+    assert_eq!(code, Error::AmendmentPending as u32 + 1);
+}
+
+// ─── Handles ─────────────────────────────────────────────
+
+#[test]
+fn test_project_handle_lookup() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &other, &Role::ProjectManager, &None);
+    let tokens = create_tokens(&env, 1);
+    let project = register(&env, &client, &creator, &tokens);
+    let twin = register(&env, &client, &other, &tokens);
+    let handle = Symbol::new(&env, "clean_water");
+
+    client.set_project_handle(&vec![&env, creator.clone()], &project.id, &handle);
+    // Handles are scoped per creator.
+    client.set_project_handle(&vec![&env, other.clone()], &twin.id, &handle);
+
+    assert_eq!(
+        client.get_project_by_handle(&creator, &handle).id,
+        project.id
+    );
+    assert_eq!(client.get_project_by_handle(&other, &handle).id, twin.id);
+    assert_eq!(client.get_project_handle(&project.id), Some(handle));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_project_handle_unique_per_creator() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let tokens = create_tokens(&env, 1);
+    let first = register(&env, &client, &creator, &tokens);
+    let second = register(&env, &client, &creator, &tokens);
+    let handle = Symbol::new(&env, "clean_water");
+
+    client.set_project_handle(&vec![&env, creator.clone()], &first.id, &handle);
+    client.set_project_handle(&vec![&env, creator.clone()], &second.id, &handle);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_unknown_handle_not_found() {
+    let (env, client, _) = setup_with_init();
+    client.get_project_by_handle(&Address::generate(&env), &Symbol::new(&env, "nope"));
+}
+
+#[test]
+fn test_creator_updates_metadata() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let project = register(&env, &client, &creator, &create_tokens(&env, 1));
+    assert_eq!(client.get_project_metadata(&project.id), None);
+
+    let name = String::from_str(&env, "Clean Water for Kisumu");
+    let hash = BytesN::from_array(&env, &[1u8; 32]);
+    client.update_metadata(&vec![&env, creator.clone()], &project.id, &name, &hash);
+    client.update_metadata(
+        &vec![&env, creator.clone()],
+        &project.id,
+        &name,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+    let metadata = client.get_project_metadata(&project.id).unwrap();
+    assert_eq!(metadata.name, name);
+    assert_eq!(metadata.metadata_hash, BytesN::from_array(&env, &[2u8; 32]));
+
+    let other = Address::generate(&env);
+    assert!(client
+        .try_update_metadata(&vec![&env, other.clone()], &project.id, &name, &hash)
+        .is_err());
+    let long = String::from_str(&env, &"x".repeat(65));
+    assert!(client
+        .try_update_metadata(&vec![&env, creator.clone()], &project.id, &long, &hash)
+        .is_err());
+}
+
+// ─── Content-addressed registration ──────────────────────
+
+#[test]
+fn test_register_project_at_uses_derived_id() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let tokens = create_tokens(&env, 1);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let salt = BytesN::from_array(&env, &[7u8; 32]);
+    let deadline = env.ledger().timestamp() + 86400;
+
+    let expected = client.derive_project_id(&creator, &proof_hash, &salt);
+    assert!(expected >= 1 << 63);
+    let project = client.register_project_at(
+        &creator,
+        &tokens,
+        &1000,
+        &proof_hash,
+        &deadline,
+        &1,
+        &salt,
+        &vec![&env],
+    );
+    assert_eq!(project.id, expected);
+    assert_eq!(client.get_project(&expected).creator, creator);
+
+    // A different salt gives a different ID; the sequential path is untouched.
+    let other = BytesN::from_array(&env, &[8u8; 32]);
+    assert_ne!(
+        client.derive_project_id(&creator, &proof_hash, &other),
+        expected
+    );
+    assert_eq!(register(&env, &client, &creator, &tokens).id, 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_register_project_at_rejects_reused_id() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let tokens = create_tokens(&env, 1);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let salt = BytesN::from_array(&env, &[7u8; 32]);
+    let deadline = env.ledger().timestamp() + 86400;
+
+    client.register_project_at(
+        &creator,
+        &tokens,
+        &1000,
+        &proof_hash,
+        &deadline,
+        &1,
+        &salt,
+        &vec![&env],
+    );
+    client.register_project_at(
+        &creator,
+        &tokens,
+        &2000,
+        &proof_hash,
+        &deadline,
+        &1,
+        &salt,
+        &vec![&env],
+    );
+}
+
+#[test]
+fn test_health_check_reports_protocol_state() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &Address::generate(&env), &Role::Oracle, &None);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            treasury: Some(treasury.clone()),
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let other = create_token(&env, &Address::generate(&env)).address;
+    token::StellarAssetClient::new(&env, &token).mint(&treasury, &70);
+    client.pause_token(&super_admin, &other);
+
+    let stale = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    env.ledger().with_mut(|l| l.timestamp += 86_401);
+    let paused = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    client.pause_project(&super_admin, &paused.id);
+    client.revoke_role(&super_admin, &oracle, &None);
+
+    let health = client.health_check(&vec![&env, token.clone(), other.clone()], &0, &25);
+    assert!(!health.paused);
+    assert!(!health.sunset);
+    assert_eq!(health.paused_tokens, vec![&env, other.clone()]);
+    assert_eq!(health.paused_projects, 1);
+    assert_eq!(health.oracle_count, 1);
+    assert_eq!(health.open_disputes, 0);
+    assert_eq!(health.past_deadline, 1);
+    assert_eq!(health.project_count, 2);
+    assert_eq!(health.treasury_balances.get(0).unwrap().balance, 70);
+    assert_eq!(health.treasury_balances.get(1).unwrap().balance, 0);
+    assert_eq!(health.version, client.get_version());
+
+    // Counts cover only the requested page.
+    let second = client.health_check(&vec![&env], &1, &25);
+    assert_eq!((second.paused_projects, second.past_deadline), (1, 0));
+    assert_eq!(second.project_count, 2);
+
+    // Settled projects drop out of the counts.
+    client.expire_project(&stale.id);
+    assert_eq!(client.health_check(&vec![&env], &0, &25).past_deadline, 0);
+}
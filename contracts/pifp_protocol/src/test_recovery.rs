@@ -0,0 +1,215 @@
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+
+use crate::{GuardianSet, PifpProtocol, PifpProtocolClient, Role};
+
+const DELAY: u64 = GuardianSet::MIN_DELAY;
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+/// Configure three guardians with a 2-of-3 threshold.
+fn setup_guardians() -> (Env, PifpProtocolClient<'static>, Address, [Address; 3]) {
+    let (env, client, super_admin) = setup_with_init();
+    let guardians = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let list = vec![
+        &env,
+        guardians[0].clone(),
+        guardians[1].clone(),
+        guardians[2].clone(),
+    ];
+    client.set_guardians(&super_admin, &list, &2, &DELAY);
+    (env, client, super_admin, guardians)
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|l| l.timestamp += seconds);
+}
+
+#[test]
+fn test_guardians_rotate_super_admin_after_delay() {
+    let (env, client, super_admin, guardians) = setup_guardians();
+    let successor = Address::generate(&env);
+
+    assert_eq!(client.approve_recovery(&guardians[0], &successor), 1);
+    advance(&env, 60);
+    assert_eq!(client.approve_recovery(&guardians[2], &successor), 2);
+    let request = client.get_recovery().unwrap();
+    assert_eq!(request.new_super_admin, successor);
+    assert_eq!(request.eta, DELAY);
+
+    advance(&env, DELAY);
+    client.execute_recovery(&guardians[1]);
+
+    assert_eq!(client.role_of(&successor), Some(Role::SuperAdmin));
+    assert_eq!(client.role_of(&super_admin), None);
+    assert_eq!(client.get_recovery(), None);
+    let change = client.get_role_history(&successor).last().unwrap();
+    assert_eq!(change.actor, Some(guardians[1].clone()));
+}
+
+#[test]
+fn test_approving_other_address_restarts_request() {
+    let (env, client, _super_admin, guardians) = setup_guardians();
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    client.approve_recovery(&guardians[0], &first);
+    advance(&env, 100);
+    assert_eq!(client.approve_recovery(&guardians[1], &second), 1);
+    let request = client.get_recovery().unwrap();
+    assert_eq!(request.new_super_admin, second);
+    assert_eq!(request.approvals, vec![&env, guardians[1].clone()]);
+    assert_eq!(request.eta, 100 + DELAY);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_super_admin_can_cancel_recovery() {
+    let (env, client, super_admin, guardians) = setup_guardians();
+    let successor = Address::generate(&env);
+    client.approve_recovery(&guardians[0], &successor);
+    client.approve_recovery(&guardians[1], &successor);
+
+    client.cancel_recovery(&super_admin);
+    assert_eq!(client.get_recovery(), None);
+    advance(&env, DELAY);
+    client.execute_recovery(&guardians[0]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_execute_before_delay_fails() {
+    let (env, client, _super_admin, guardians) = setup_guardians();
+    let successor = Address::generate(&env);
+    client.approve_recovery(&guardians[0], &successor);
+    client.approve_recovery(&guardians[1], &successor);
+    advance(&env, DELAY - 1);
+    client.execute_recovery(&guardians[0]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_execute_below_threshold_fails() {
+    let (env, client, _super_admin, guardians) = setup_guardians();
+    client.approve_recovery(&guardians[0], &Address::generate(&env));
+    advance(&env, DELAY);
+    client.execute_recovery(&guardians[0]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_guardian_cannot_approve_twice() {
+    let (env, client, _super_admin, guardians) = setup_guardians();
+    let successor = Address::generate(&env);
+    client.approve_recovery(&guardians[0], &successor);
+    client.approve_recovery(&guardians[0], &successor);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_non_guardian_cannot_approve() {
+    let (env, client, _super_admin, _guardians) = setup_guardians();
+    client.approve_recovery(&Address::generate(&env), &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_guardian_delay_has_a_floor() {
+    let (env, client, super_admin) = setup_with_init();
+    let guardians = vec![&env, Address::generate(&env)];
+    client.set_guardians(&super_admin, &guardians, &1, &(DELAY - 1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_guardian_threshold_must_fit_set() {
+    let (env, client, super_admin) = setup_with_init();
+    let guardians = vec![&env, Address::generate(&env)];
+    client.set_guardians(&super_admin, &guardians, &2, &DELAY);
+}
+
+// ─── Dead-man switch ─────────────────────────────────────
+
+const PERIOD: u64 = 30 * 24 * 60 * 60;
+const ANNOUNCE: u64 = 3 * 24 * 60 * 60;
+
+#[test]
+fn test_successor_claims_after_silence_and_announcement() {
+    let (env, client, super_admin) = setup_with_init();
+    let successor = Address::generate(&env);
+    client.set_dead_man_switch(&super_admin, &successor, &PERIOD, &ANNOUNCE);
+
+    advance(&env, PERIOD + 1);
+    let eta = client.announce_claim(&successor);
+    assert_eq!(eta, PERIOD + 1 + ANNOUNCE);
+    advance(&env, ANNOUNCE);
+    client.claim_super_admin(&successor);
+
+    assert_eq!(client.role_of(&successor), Some(Role::SuperAdmin));
+    assert_eq!(client.role_of(&super_admin), None);
+    assert_eq!(client.get_dead_man_switch(), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_claim_cannot_be_announced_while_active() {
+    let (env, client, super_admin) = setup_with_init();
+    let successor = Address::generate(&env);
+    client.set_dead_man_switch(&super_admin, &successor, &PERIOD, &ANNOUNCE);
+
+    // Any SuperAdmin action counts as a heartbeat.
+    advance(&env, PERIOD);
+    client.grant_role(
+        &super_admin,
+        &Address::generate(&env),
+        &Role::Auditor,
+        &None,
+    );
+    advance(&env, 10);
+    client.announce_claim(&successor);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_heartbeat_voids_announced_claim() {
+    let (env, client, super_admin) = setup_with_init();
+    let successor = Address::generate(&env);
+    client.set_dead_man_switch(&super_admin, &successor, &PERIOD, &ANNOUNCE);
+
+    advance(&env, PERIOD + 1);
+    client.announce_claim(&successor);
+    client.heartbeat(&super_admin);
+    assert_eq!(client.get_dead_man_switch().unwrap().claim_eta, None);
+    advance(&env, ANNOUNCE);
+    client.claim_super_admin(&successor);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_only_successor_can_announce() {
+    let (env, client, super_admin) = setup_with_init();
+    client.set_dead_man_switch(&super_admin, &Address::generate(&env), &PERIOD, &ANNOUNCE);
+    advance(&env, PERIOD + 1);
+    client.announce_claim(&Address::generate(&env));
+}
@@ -0,0 +1,166 @@
+extern crate std;
+
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, Symbol,
+};
+
+use crate::guard::ReentrancyGuard;
+use crate::{PifpProtocol, PifpProtocolClient, ProjectStatus, Role};
+
+/// Token that calls back into the protocol from `transfer`, once, and
+/// records whether the protocol let it in.
+#[contract]
+pub struct ReentrantToken;
+
+#[contractimpl]
+impl ReentrantToken {
+    /// Re-enter `protocol` through `entry` (`deposit` or `release`) on the
+    /// next transfer.
+    pub fn arm(env: Env, protocol: Address, project_id: u64, entry: Symbol) {
+        let target = (protocol, project_id, entry);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("target"), &target);
+    }
+
+    pub fn transfer(env: Env, from: Address, _to: Address, amount: i128) {
+        let key = symbol_short!("target");
+        let target: Option<(Address, u64, Symbol)> = env.storage().instance().get(&key);
+        let (protocol, project_id, entry) = match target {
+            Some(target) => target,
+            None => return,
+        };
+        env.storage().instance().remove(&key);
+
+        let client = PifpProtocolClient::new(&env, &protocol);
+        let entered = if entry == symbol_short!("deposit") {
+            let token = env.current_contract_address();
+            client
+                .try_deposit(&project_id, &from, &token, &amount, &false)
+                .is_ok()
+        } else {
+            client.try_process_release_queue().is_ok()
+        };
+        env.storage()
+            .instance()
+            .set(&symbol_short!("entered"), &entered);
+    }
+
+    /// `Some(true)` if a re-entrant call succeeded; `None` if none was made.
+    pub fn entered(env: Env) -> Option<bool> {
+        env.storage().instance().get(&symbol_short!("entered"))
+    }
+}
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+/// A project accepting only the re-entrant token.
+fn project(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    super_admin: &Address,
+) -> (u64, ReentrantTokenClient<'static>) {
+    let creator = Address::generate(env);
+    client.grant_role(super_admin, &creator, &Role::ProjectManager, &None);
+    let token = ReentrantTokenClient::new(env, &env.register(ReentrantToken, ()));
+    let project = client.register_project(
+        &creator,
+        &vec![env, token.address.clone()],
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    );
+    (project.id, token)
+}
+
+#[test]
+fn test_token_cannot_reenter_deposit() {
+    let (env, client, super_admin) = setup_with_init();
+    let (project_id, token) = project(&env, &client, &super_admin);
+    token.arm(&client.address, &project_id, &symbol_short!("deposit"));
+
+    let donor = Address::generate(&env);
+    client.deposit(&project_id, &donor, &token.address, &400, &false);
+
+    assert_eq!(token.entered(), Some(false));
+    assert_eq!(
+        client.get_donation(&project_id, &donor, &token.address),
+        400
+    );
+    assert_eq!(
+        client.get_token_flows(&project_id, &token.address).inflow,
+        400
+    );
+    assert_eq!(client.get_donation_count(&project_id), 1);
+}
+
+#[test]
+fn test_token_cannot_reenter_release() {
+    let (env, client, super_admin) = setup_with_init();
+    let (project_id, token) = project(&env, &client, &super_admin);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    client.deposit(
+        &project_id,
+        &Address::generate(&env),
+        &token.address,
+        &1000,
+        &false,
+    );
+
+    token.arm(&client.address, &project_id, &symbol_short!("release"));
+    env.ledger().with_mut(|l| l.timestamp += 1);
+    client.verify_and_release(
+        &oracle,
+        &project_id,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+    );
+
+    assert_eq!(token.entered(), Some(false));
+    assert_eq!(
+        client.get_project_state(&project_id).status,
+        ProjectStatus::Completed
+    );
+    let flows = client.get_token_flows(&project_id, &token.address);
+    assert_eq!((flows.outflow, flows.released), (1000, 1000));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn test_held_lock_rejects_entry() {
+    let (env, client) = setup();
+    env.as_contract(&client.address, || {
+        let _guard = ReentrancyGuard::acquire(&env);
+        ReentrancyGuard::acquire(&env);
+    });
+}
+
+#[test]
+fn test_lock_released_after_call() {
+    let (env, client, super_admin) = setup_with_init();
+    let (project_id, token) = project(&env, &client, &super_admin);
+    let donor = Address::generate(&env);
+    client.deposit(&project_id, &donor, &token.address, &100, &false);
+    client.deposit(&project_id, &donor, &token.address, &100, &false);
+
+    env.as_contract(&client.address, || {
+        assert!(!crate::storage::is_locked(&env));
+    });
+}
@@ -0,0 +1,335 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, BytesN, Env, IntoVal, Val, Vec,
+};
+
+use crate::{
+    Grant, GrantRoute, PifpProtocol, PifpProtocolClient, ProjectStatus, ProtocolConfig, Role,
+};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+/// A project with a one-day deadline; returns its creator too.
+fn project(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    super_admin: &Address,
+    token: &Address,
+) -> (u64, Address) {
+    let creator = Address::generate(env);
+    client.grant_role(super_admin, &creator, &Role::ProjectManager, &None);
+    let project = client.register_project(
+        &creator,
+        &vec![env, token.clone()],
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    );
+    (project.id, creator)
+}
+
+fn pass_deadline(env: &Env) {
+    env.ledger().with_mut(|l| l.timestamp += 86_401);
+}
+
+#[test]
+fn test_donors_reclaim_deposits_after_deadline() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    let (project_id, creator) = project(&env, &client, &super_admin, &token.address);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    fund(&env, &client, project_id, &alice, &token.address, 300);
+    fund(&env, &client, project_id, &bob, &token.address, 200);
+    pass_deadline(&env);
+
+    assert_eq!(
+        client.claim_refund(&project_id, &alice, &token.address),
+        300
+    );
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("refunded"), project_id).into_val(&env);
+    let (_, _, data) = events.iter().find(|(_, t, _)| *t == topics).unwrap();
+    let (donor, _, amount): (Address, Address, i128) = data.into_val(&env);
+    assert_eq!((donor, amount), (alice.clone(), 300));
+
+    assert_eq!(client.claim_refund(&project_id, &bob, &token.address), 200);
+    assert_eq!(token.balance(&alice), 300);
+    assert_eq!(token.balance(&bob), 200);
+    assert_eq!(
+        client.get_refunded(&project_id, &alice, &token.address),
+        300
+    );
+    assert_eq!(
+        client.get_project_state(&project_id).status,
+        ProjectStatus::Expired
+    );
+    assert_eq!(client.get_creator_record(&creator).projects_expired, 1);
+
+    let report = client
+        .get_project_report(&project_id)
+        .tokens
+        .get(0)
+        .unwrap();
+    assert_eq!(
+        (report.raised, report.refunded, report.outstanding),
+        (500, 500, 0)
+    );
+}
+
+#[test]
+fn test_insured_refund_returns_premium() {
+    let (env, client, super_admin) = setup_with_init();
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            insurance_premium_bps: 500,
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env));
+    let (project_id, creator) = project(&env, &client, &super_admin, &token.address);
+    client.set_insured(&vec![&env, creator.clone()], &project_id);
+    let donor = Address::generate(&env);
+    fund(&env, &client, project_id, &donor, &token.address, 400);
+    pass_deadline(&env);
+
+    assert_eq!(
+        client.claim_refund(&project_id, &donor, &token.address),
+        400
+    );
+    assert_eq!(client.get_insurance_pool(&token.address), 0);
+    assert_eq!(
+        client.get_token_flows(&project_id, &token.address).refunded,
+        380
+    );
+}
+
+#[test]
+fn test_small_refund_claim_is_sponsored() {
+    let (env, client, super_admin) = setup_with_init();
+    let treasury = Address::generate(&env);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            sponsor_threshold: 100,
+            sponsor_amount: 5,
+            sponsor_budget: 50,
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env));
+    token::StellarAssetClient::new(&env, &token.address).mint(&treasury, &50);
+    client.fund_sponsor_pool(&treasury, &token.address, &50);
+    let (project_id, _) = project(&env, &client, &super_admin, &token.address);
+    let donor = Address::generate(&env);
+    fund(&env, &client, project_id, &donor, &token.address, 80);
+    pass_deadline(&env);
+
+    client.claim_refund(&project_id, &donor, &token.address);
+    assert_eq!(token.balance(&donor), 85);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_refund_before_deadline_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    let (project_id, _) = project(&env, &client, &super_admin, &token.address);
+    let donor = Address::generate(&env);
+    fund(&env, &client, project_id, &donor, &token.address, 100);
+
+    client.claim_refund(&project_id, &donor, &token.address);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_refund_claimed_once() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    let (project_id, _) = project(&env, &client, &super_admin, &token.address);
+    let donor = Address::generate(&env);
+    fund(&env, &client, project_id, &donor, &token.address, 100);
+    pass_deadline(&env);
+
+    client.claim_refund(&project_id, &donor, &token.address);
+    client.claim_refund(&project_id, &donor, &token.address);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_completed_project_not_refundable() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    let (project_id, _) = project(&env, &client, &super_admin, &token.address);
+    let donor = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    fund(&env, &client, project_id, &donor, &token.address, 100);
+    client.verify_and_release(
+        &oracle,
+        &project_id,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+    );
+    pass_deadline(&env);
+
+    client.claim_refund(&project_id, &donor, &token.address);
+}
+
+#[test]
+fn test_anyone_can_expire_after_deadline() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    let (project_id, creator) = project(&env, &client, &super_admin, &token.address);
+    let donor = Address::generate(&env);
+    fund(&env, &client, project_id, &donor, &token.address, 100);
+    pass_deadline(&env);
+
+    client.expire_project(&project_id);
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("expired"), project_id).into_val(&env);
+    assert!(events.iter().any(|(_, t, _)| t == topics));
+    assert_eq!(
+        client.get_project_state(&project_id).status,
+        ProjectStatus::Expired
+    );
+    assert_eq!(client.get_creator_record(&creator).projects_expired, 1);
+
+    // Refunds work as usual once expired.
+    assert_eq!(
+        client.claim_refund(&project_id, &donor, &token.address),
+        100
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_expire_before_deadline_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    let (project_id, _) = project(&env, &client, &super_admin, &token.address);
+
+    client.expire_project(&project_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_expire_twice_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    let (project_id, _) = project(&env, &client, &super_admin, &token.address);
+    pass_deadline(&env);
+
+    client.expire_project(&project_id);
+    client.expire_project(&project_id);
+}
+
+#[test]
+fn test_grants_are_routed_instead_of_refunded() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    let (project_id, creator) = project(&env, &client, &super_admin, &token.address);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token.address).mint(&alice, &150);
+    token::StellarAssetClient::new(&env, &token.address).mint(&bob, &200);
+    fund(&env, &client, project_id, &alice, &token.address, 100);
+    client.deposit_grant(
+        &project_id,
+        &alice,
+        &token.address,
+        &150,
+        &GrantRoute::Creator,
+    );
+    client.deposit_grant(
+        &project_id,
+        &bob,
+        &token.address,
+        &200,
+        &GrantRoute::Commons,
+    );
+    assert_eq!(
+        client.get_grant(&project_id, &alice, &token.address),
+        Some(Grant {
+            amount: 150,
+            route: GrantRoute::Creator,
+        })
+    );
+    pass_deadline(&env);
+
+    client.expire_project(&project_id);
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("routed"), project_id).into_val(&env);
+    assert_eq!(events.iter().filter(|(_, t, _)| *t == topics).count(), 2);
+    assert_eq!(token.balance(&creator), 150);
+    assert_eq!(client.get_commons_pool(&token.address), 200);
+    assert_eq!(
+        client.get_token_flows(&project_id, &token.address).released,
+        150
+    );
+
+    // Only the refundable part comes back.
+    assert_eq!(
+        client.claim_refund(&project_id, &alice, &token.address),
+        100
+    );
+    let result = client.try_claim_refund(&project_id, &bob, &token.address);
+    assert!(result.is_err());
+    assert_eq!(
+        client
+            .get_balances(&project_id)
+            .balances
+            .get(0)
+            .unwrap()
+            .balance,
+        0
+    );
+
+    let recipient = Address::generate(&env);
+    client.disburse_commons(&super_admin, &token.address, &recipient, &120);
+    assert_eq!(token.balance(&recipient), 120);
+    assert_eq!(client.get_commons_pool(&token.address), 80);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn test_disburse_beyond_commons_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let token = create_token(&env, &Address::generate(&env));
+    client.disburse_commons(&super_admin, &token.address, &Address::generate(&env), &1);
+}
@@ -0,0 +1,142 @@
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env};
+
+use crate::{CreatorRecord, PifpProtocol, PifpProtocolClient, Role};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+#[test]
+fn test_creator_record_defaults_to_zero() {
+    let (env, client, _) = setup_with_init();
+    let nobody = Address::generate(&env);
+
+    assert_eq!(client.get_creator_record(&nobody), CreatorRecord::default());
+}
+
+#[test]
+fn test_creator_record_tracks_created_and_completed() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+
+    let tokens = soroban_sdk::vec![&env, token.address.clone()];
+    let deadline = env.ledger().timestamp() + 86400;
+    let first = client.register_project(
+        &creator,
+        &tokens,
+        &1000,
+        &proof_hash,
+        &deadline,
+        &1,
+        &vec![&env],
+    );
+    client.register_project(
+        &creator,
+        &tokens,
+        &2000,
+        &proof_hash,
+        &deadline,
+        &1,
+        &vec![&env],
+    );
+
+    client.verify_and_release(&oracle, &first.id, &proof_hash);
+
+    let record = client.get_creator_record(&creator);
+    assert_eq!(record.projects_created, 2);
+    assert_eq!(record.projects_completed, 1);
+    assert_eq!(record.fraud_flags, 0);
+}
+
+#[test]
+fn test_projects_listed_by_creator() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &other, &Role::ProjectManager, &None);
+    let tokens = vec![&env, create_token(&env, &Address::generate(&env)).address];
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let deadline = env.ledger().timestamp() + 86400;
+    let register = |creator: &Address| {
+        client
+            .register_project(
+                creator,
+                &tokens,
+                &1000,
+                &proof_hash,
+                &deadline,
+                &1,
+                &vec![&env],
+            )
+            .id
+    };
+
+    let first = register(&creator);
+    register(&other);
+    let third = register(&creator);
+    let salted = client.register_project_at(
+        &creator,
+        &tokens,
+        &1000,
+        &proof_hash,
+        &deadline,
+        &1,
+        &BytesN::from_array(&env, &[7u8; 32]),
+        &vec![&env],
+    );
+
+    assert_eq!(
+        client.get_projects_by_creator(&creator),
+        vec![&env, first, third, salted.id]
+    );
+    assert!(client
+        .get_projects_by_creator(&Address::generate(&env))
+        .is_empty());
+}
+
+#[test]
+fn test_admin_can_flag_creator() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+
+    client.flag_creator(&super_admin, &creator);
+    let record = client.flag_creator(&super_admin, &creator);
+
+    assert_eq!(record.fraud_flags, 2);
+    assert_eq!(client.get_creator_record(&creator).fraud_flags, 2);
+}
+
+#[test]
+#[should_panic]
+fn test_non_admin_cannot_flag_creator() {
+    let (env, client, _) = setup_with_init();
+    let nobody = Address::generate(&env);
+    let creator = Address::generate(&env);
+
+    client.flag_creator(&nobody, &creator);
+}
@@ -0,0 +1,438 @@
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env, Vec,
+};
+
+use crate::{
+    DisbursementTemplate, Milestone, MilestoneAmendment, PifpProtocol, PifpProtocolClient,
+    ProjectStatus, ProtocolConfig, Role, Tranche,
+};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+fn tranche(bps: u32, gated: bool) -> Tranche {
+    Tranche { bps, gated }
+}
+
+/// 20% upfront, then 40% on each of two verified gates.
+fn upfront_template(env: &Env) -> DisbursementTemplate {
+    DisbursementTemplate {
+        tranches: vec![
+            env,
+            tranche(2_000, false),
+            tranche(4_000, true),
+            tranche(4_000, true),
+        ],
+    }
+}
+
+#[test]
+fn test_template_releases_tranche_per_quorum() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    client.set_disbursement_template(&super_admin, &7, &upfront_template(&env));
+
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    client.use_disbursement_template(&vec![&env, creator.clone()], &project.id, &7);
+    assert_eq!(client.get_project_state(&project.id).released_bps, 2_000);
+
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        1000,
+    );
+    let proof = BytesN::from_array(&env, &[0xabu8; 32]);
+
+    client.verify_and_release(&oracle, &project.id, &proof);
+    let state = client.get_project_state(&project.id);
+    assert_eq!(state.released_bps, 6_000);
+    assert_eq!(state.status, ProjectStatus::Active);
+
+    // Attestations reset, so the same oracle can verify the next gate.
+    client.verify_and_release(&oracle, &project.id, &proof);
+    let state = client.get_project_state(&project.id);
+    assert_eq!(state.released_bps, 10_000);
+    assert_eq!(state.status, ProjectStatus::Completed);
+}
+
+#[test]
+fn test_template_copied_at_adoption() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_disbursement_template(&super_admin, &1, &upfront_template(&env));
+
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token]);
+    client.use_disbursement_template(&vec![&env, creator.clone()], &project.id, &1);
+
+    let replacement = DisbursementTemplate {
+        tranches: vec![&env, tranche(10_000, true)],
+    };
+    client.set_disbursement_template(&super_admin, &1, &replacement);
+    assert_eq!(
+        client.get_disbursement_schedule(&project.id),
+        Some(upfront_template(&env))
+    );
+}
+
+#[test]
+fn test_template_validation() {
+    let env = Env::default();
+    let valid = upfront_template(&env);
+    assert!(valid.is_valid());
+
+    let short = DisbursementTemplate {
+        tranches: vec![&env, tranche(5_000, true), tranche(4_000, true)],
+    };
+    assert!(!short.is_valid());
+
+    let ungated_last = DisbursementTemplate {
+        tranches: vec![&env, tranche(5_000, true), tranche(5_000, false)],
+    };
+    assert!(!ungated_last.is_valid());
+
+    let gates = DisbursementTemplate {
+        tranches: vec![
+            &env,
+            tranche(4_000, true),
+            tranche(1_000, false),
+            tranche(5_000, true),
+        ],
+    };
+    assert_eq!(gates.release_until_gate(0), 0);
+    assert_eq!(gates.release_next_gate(0), 5_000);
+    assert_eq!(gates.release_next_gate(5_000), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn test_invalid_template_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let template = DisbursementTemplate {
+        tranches: vec![&env, tranche(9_999, true)],
+    };
+    client.set_disbursement_template(&super_admin, &1, &template);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_unknown_template_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token]);
+
+    client.use_disbursement_template(&vec![&env, creator.clone()], &project.id, &42);
+}
+
+fn milestone(env: &Env, amount: i128, tag: u8) -> Milestone {
+    Milestone {
+        amount,
+        proof_hash: BytesN::from_array(env, &[tag; 32]),
+    }
+}
+
+/// A project whose 1000 goal splits into milestones of 300 and 700.
+fn milestone_project() -> (Env, PifpProtocolClient<'static>, u64, Address, Address) {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = client.register_project(
+        &creator,
+        &vec![&env, token.clone()],
+        &1000,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![&env, milestone(&env, 300, 1), milestone(&env, 700, 2)],
+    );
+    (env, client, project.id, creator, oracle)
+}
+
+#[test]
+fn test_milestones_release_in_turn() {
+    let (env, client, project_id, _, oracle) = milestone_project();
+    assert_eq!(client.get_milestones(&project_id).len(), 2);
+    let token = client
+        .get_project(&project_id)
+        .accepted_tokens
+        .get(0)
+        .unwrap();
+    fund(
+        &env,
+        &client,
+        project_id,
+        &Address::generate(&env),
+        &token,
+        1000,
+    );
+
+    let first = BytesN::from_array(&env, &[1u8; 32]);
+    client.verify_and_release(&oracle, &project_id, &first);
+    let state = client.get_project_state(&project_id);
+    assert_eq!(state.released_bps, 3_000);
+    assert_eq!(state.status, ProjectStatus::Active);
+    let creator = client.get_project(&project_id).creator;
+    assert_eq!(token::Client::new(&env, &token).balance(&creator), 300);
+
+    let second = BytesN::from_array(&env, &[2u8; 32]);
+    client.verify_and_release(&oracle, &project_id, &second);
+    let state = client.get_project_state(&project_id);
+    assert_eq!(state.released_bps, 10_000);
+    assert_eq!(state.status, ProjectStatus::Completed);
+    assert_eq!(token::Client::new(&env, &token).balance(&creator), 1000);
+}
+
+#[test]
+#[should_panic(expected = "proof verification failed")]
+fn test_milestone_requires_its_own_proof() {
+    let (env, client, project_id, _, oracle) = milestone_project();
+    let second = BytesN::from_array(&env, &[2u8; 32]);
+    client.verify_and_release(&oracle, &project_id, &second);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_milestones_must_sum_to_goal() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    client.register_project(
+        &creator,
+        &vec![&env, token],
+        &1000,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![&env, milestone(&env, 300, 1), milestone(&env, 600, 2)],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_milestone_project_rejects_template() {
+    let (env, client, project_id, creator, _) = milestone_project();
+    client.use_disbursement_template(&vec![&env, creator], &project_id, &1);
+}
+
+// ─── Milestone amendments ───────────────────────────────
+
+/// `milestone_project` funded 600/400 by two donors, returning them.
+fn funded_milestone_project() -> (
+    Env,
+    PifpProtocolClient<'static>,
+    u64,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    let (env, client, project_id, creator, oracle) = milestone_project();
+    let token = client
+        .get_project(&project_id)
+        .accepted_tokens
+        .get(0)
+        .unwrap();
+    let big = Address::generate(&env);
+    let small = Address::generate(&env);
+    fund(&env, &client, project_id, &big, &token, 600);
+    fund(&env, &client, project_id, &small, &token, 400);
+    (env, client, project_id, creator, oracle, big, small)
+}
+
+#[test]
+fn test_donor_majority_amends_milestones() {
+    let (env, client, project_id, creator, oracle, big, small) = funded_milestone_project();
+    client.verify_and_release(&oracle, &project_id, &BytesN::from_array(&env, &[1u8; 32]));
+
+    let milestones = vec![
+        &env,
+        milestone(&env, 300, 1),
+        milestone(&env, 200, 3),
+        milestone(&env, 500, 4),
+    ];
+    let proposal = client.propose_amendment(&vec![&env, creator], &project_id, &milestones);
+    assert_eq!(proposal.total_weight, 1000);
+    assert!(!client.back_amendment(&small, &project_id));
+    assert_eq!(client.get_milestones(&project_id).len(), 2);
+
+    assert!(client.back_amendment(&big, &project_id));
+    assert_eq!(client.get_milestones(&project_id), milestones);
+    assert_eq!(client.get_amendment(&project_id), None);
+    client.verify_and_release(&oracle, &project_id, &BytesN::from_array(&env, &[3u8; 32]));
+    assert_eq!(client.get_project_state(&project_id).released_bps, 5_000);
+}
+
+#[test]
+fn test_amendment_weight_counts_gross_contributions() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            treasury: Some(Address::generate(&env)),
+            fee_bps: 200,
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = client.register_project(
+        &creator,
+        &vec![&env, token.clone()],
+        &1000,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![&env, milestone(&env, 300, 1), milestone(&env, 700, 2)],
+    );
+    let half = Address::generate(&env);
+    fund(&env, &client, project.id, &half, &token, 500);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        520,
+    );
+
+    // 500 is a majority of the inflow left after the 2% fee, but not of
+    // the 1020 donors gave.
+    let split = vec![&env, milestone(&env, 500, 1), milestone(&env, 500, 2)];
+    let proposal = client.propose_amendment(&vec![&env, creator], &project.id, &split);
+    assert_eq!(proposal.total_weight, 1020);
+    assert!(!client.back_amendment(&half, &project.id));
+    assert_eq!(
+        client.get_milestones(&project.id).get(0).unwrap().amount,
+        300
+    );
+}
+
+#[test]
+fn test_admin_decides_amendment() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = client.register_project(
+        &creator,
+        &vec![&env, token],
+        &1000,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![&env, milestone(&env, 300, 1), milestone(&env, 700, 2)],
+    );
+    let signers = vec![&env, creator];
+    let split = vec![&env, milestone(&env, 500, 1), milestone(&env, 500, 2)];
+
+    client.propose_amendment(&signers, &project.id, &split);
+    client.resolve_amendment(&super_admin, &project.id, &false);
+    assert_eq!(client.get_amendment(&project.id), None);
+    assert_eq!(
+        client.get_milestones(&project.id).get(0).unwrap().amount,
+        300
+    );
+
+    client.propose_amendment(&signers, &project.id, &split);
+    client.resolve_amendment(&super_admin, &project.id, &true);
+    assert_eq!(client.get_milestones(&project.id), split);
+    let schedule = client.get_disbursement_schedule(&project.id).unwrap();
+    assert_eq!(schedule.tranches.get(0).unwrap().bps, 5_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_amendment_keeps_released_milestones() {
+    let (env, client, project_id, creator, oracle, _, _) = funded_milestone_project();
+    client.verify_and_release(&oracle, &project_id, &BytesN::from_array(&env, &[1u8; 32]));
+    client.propose_amendment(
+        &vec![&env, creator],
+        &project_id,
+        &vec![&env, milestone(&env, 400, 1), milestone(&env, 600, 2)],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #35)")]
+fn test_amendment_waits_for_open_proposal() {
+    let (env, client, project_id, creator, _, _, _) = funded_milestone_project();
+    let signers = vec![&env, creator];
+    let split = vec![&env, milestone(&env, 500, 1), milestone(&env, 500, 2)];
+    client.propose_amendment(&signers, &project_id, &split);
+    client.propose_amendment(&signers, &project_id, &split);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_lapsed_amendment_cannot_be_backed() {
+    let (env, client, project_id, creator, _, big, _) = funded_milestone_project();
+    let split = vec![&env, milestone(&env, 500, 1), milestone(&env, 500, 2)];
+    client.propose_amendment(&vec![&env, creator], &project_id, &split);
+    env.ledger()
+        .with_mut(|l| l.timestamp += MilestoneAmendment::VOTE_WINDOW);
+    client.back_amendment(&big, &project_id);
+}
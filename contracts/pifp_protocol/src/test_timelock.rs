@@ -0,0 +1,249 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, BytesN, Env, IntoVal, Val, Vec,
+};
+
+use crate::{PifpProtocol, PifpProtocolClient, ProtocolConfig, Role, TimelockedAction};
+
+const DELAY: u64 = 2 * 86400;
+
+fn setup_with_delay() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            action_delay: DELAY,
+            ..client.get_protocol_config()
+        },
+    );
+    (env, client, super_admin)
+}
+
+fn setup_council() -> (Env, PifpProtocolClient<'static>, [Address; 3]) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let members = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    client.init_council(&Vec::from_array(&env, members.clone()), &2);
+    (env, client, members)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|l| l.timestamp += seconds);
+}
+
+#[test]
+fn test_queued_fee_change_applies_after_delay() {
+    let (env, client, super_admin) = setup_with_delay();
+
+    let id = client.queue_action(&super_admin, &TimelockedAction::SetFees(150, 50));
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("act_queue"), id).into_val(&env);
+    assert!(events.iter().any(|(_, t, _)| t == topics));
+    let queued = client.get_queued_action(&id).unwrap();
+    assert_eq!(queued.eta, env.ledger().timestamp() + DELAY);
+
+    assert!(client.try_execute_action(&id).is_err());
+    assert_eq!(client.get_protocol_config().fee_bps, 0);
+
+    advance(&env, DELAY);
+    client.execute_action(&id);
+    let config = client.get_protocol_config();
+    assert_eq!(config.fee_bps, 150);
+    assert_eq!(config.release_fee_bps, 50);
+    assert_eq!(client.get_queued_action(&id), None);
+
+    // Executed actions cannot be replayed.
+    assert!(client.try_execute_action(&id).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn test_execute_before_eta_rejected() {
+    let (env, client, super_admin) = setup_with_delay();
+    let id = client.queue_action(&super_admin, &TimelockedAction::SetDelay(0));
+    advance(&env, DELAY - 1);
+    client.execute_action(&id);
+}
+
+#[test]
+fn test_cancelled_action_never_executes() {
+    let (env, client, super_admin) = setup_with_delay();
+    let id = client.queue_action(&super_admin, &TimelockedAction::SetDelay(0));
+
+    client.cancel_action(&super_admin, &id);
+    assert_eq!(client.get_queued_action(&id), None);
+    advance(&env, DELAY);
+    assert!(client.try_execute_action(&id).is_err());
+    assert_eq!(client.get_protocol_config().action_delay, DELAY);
+}
+
+#[test]
+fn test_direct_changes_blocked_while_delay_set() {
+    let (env, client, super_admin) = setup_with_delay();
+
+    assert!(client.try_set_release_fee_bps(&super_admin, &10).is_err());
+    assert!(client
+        .try_upgrade(&super_admin, &BytesN::from_array(&env, &[7u8; 32]))
+        .is_err());
+    assert!(client
+        .try_set_protocol_config(
+            &super_admin,
+            &ProtocolConfig {
+                fee_bps: 100,
+                ..client.get_protocol_config()
+            },
+        )
+        .is_err());
+    assert!(client
+        .try_set_protocol_config(
+            &super_admin,
+            &ProtocolConfig {
+                action_delay: 0,
+                ..client.get_protocol_config()
+            },
+        )
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_queue_requires_super_admin() {
+    let (env, client, super_admin) = setup_with_delay();
+    let admin = Address::generate(&env);
+    client.grant_role(&super_admin, &admin, &Role::Admin, &None);
+    client.queue_action(&admin, &TimelockedAction::SetDelay(0));
+}
+
+#[test]
+fn test_emergency_withdraw_limited_to_untracked_funds() {
+    let (env, client, super_admin) = setup_with_delay();
+    let token = create_token(&env, &super_admin);
+    let sac = token::StellarAssetClient::new(&env, &token.address);
+    let creator = Address::generate(&env);
+    let donator = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let project = client.register_project(
+        &creator,
+        &vec![&env, token.address.clone()],
+        &1000,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 30 * 86400),
+        &1,
+        &vec![&env],
+    );
+    sac.mint(&donator, &500);
+    client.deposit(&project.id, &donator, &token.address, &500, &false);
+    // Sent straight to the contract, outside any escrow.
+    sac.mint(&client.address, &70);
+
+    let too_much = client.queue_action(
+        &super_admin,
+        &TimelockedAction::EmergencyWithdraw(token.address.clone(), recipient.clone(), 71),
+    );
+    let exact = client.queue_action(
+        &super_admin,
+        &TimelockedAction::EmergencyWithdraw(token.address.clone(), recipient.clone(), 70),
+    );
+    advance(&env, DELAY);
+
+    assert!(client.try_execute_action(&too_much).is_err());
+    client.execute_action(&exact);
+    assert_eq!(token.balance(&recipient), 70);
+    assert_eq!(token.balance(&client.address), 500);
+}
+
+#[test]
+fn test_council_action_needs_threshold_approvals() {
+    let (env, client, members) = setup_council();
+    let admin = Address::generate(&env);
+    assert_eq!(client.get_council().unwrap().threshold, 2);
+    assert!(client.has_role(&client.address, &Role::SuperAdmin));
+
+    let id = client.queue_action(
+        &members[0],
+        &TimelockedAction::GrantRole(admin.clone(), Role::Admin),
+    );
+    // The proposer's own approval is not enough.
+    assert!(client.try_execute_action(&id).is_err());
+    assert!(client.try_approve_action(&members[0], &id).is_err());
+
+    assert_eq!(client.approve_action(&members[1], &id), 2);
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("act_appr"), id).into_val(&env);
+    assert!(events.iter().any(|(_, t, _)| t == topics));
+
+    client.execute_action(&id);
+    assert_eq!(client.role_of(&admin), Some(Role::Admin));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_council_execute_below_threshold_rejected() {
+    let (_, client, members) = setup_council();
+    let id = client.queue_action(&members[0], &TimelockedAction::SetDelay(60));
+    client.execute_action(&id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_non_member_cannot_approve() {
+    let (env, client, members) = setup_council();
+    let id = client.queue_action(&members[0], &TimelockedAction::SetDelay(60));
+    client.approve_action(&Address::generate(&env), &id);
+}
+
+#[test]
+fn test_init_council_rejects_bad_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let member = Address::generate(&env);
+
+    let one = Vec::from_array(&env, [member.clone()]);
+    assert!(client.try_init_council(&one, &2).is_err());
+    assert!(client.try_init_council(&one, &0).is_err());
+    let duplicated = Vec::from_array(&env, [member.clone(), member.clone()]);
+    assert!(client.try_init_council(&duplicated, &1).is_err());
+}
+
+#[test]
+fn test_super_admin_hands_over_to_council() {
+    let (env, client, super_admin) = setup_with_delay();
+    let members = Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
+
+    let id = client.queue_action(
+        &super_admin,
+        &TimelockedAction::SetCouncil(members.clone(), 2),
+    );
+    advance(&env, DELAY);
+    client.execute_action(&id);
+
+    assert!(!client.has_role(&super_admin, &Role::SuperAdmin));
+    assert!(client.has_role(&client.address, &Role::SuperAdmin));
+    assert_eq!(client.get_council().unwrap().members, members);
+    assert!(client
+        .try_queue_action(&super_admin, &TimelockedAction::SetDelay(0))
+        .is_err());
+}
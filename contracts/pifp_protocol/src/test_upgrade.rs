@@ -0,0 +1,171 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    token, vec, Address, BytesN, Env, IntoVal, String, Val, Vec,
+};
+
+use crate::storage::{self, DataKey, ProjectKey};
+use crate::upgrade::{ProjectConfigV1, ProjectStateV1};
+use crate::{ContractVersion, GoalUnit, PifpProtocol, PifpProtocolClient, ProjectStatus, Role};
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+/// Pretend storage was last written by a build at `schema`.
+fn set_stored_schema(env: &Env, client: &PifpProtocolClient<'static>, schema: u32) {
+    env.as_contract(&client.address, || {
+        storage::set_stored_version(
+            env,
+            &ContractVersion {
+                version: String::from_str(env, "0.0.0"),
+                commit: String::from_str(env, "unknown"),
+                schema,
+            },
+        );
+    });
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+/// Write `count` projects in the schema 1 layout, each holding `balance`
+/// of `token` in escrow, and record storage as schema 1.
+fn write_baseline_projects(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    token: &Address,
+    count: u64,
+    balance: i128,
+) {
+    env.as_contract(&client.address, || {
+        for id in 0..count {
+            let config = ProjectConfigV1 {
+                id,
+                creator: creator.clone(),
+                accepted_tokens: vec![env, token.clone()],
+                goal: 1000,
+                proof_hash: BytesN::from_array(env, &[0xabu8; 32]),
+                deadline: env.ledger().timestamp() + 86400,
+            };
+            let state = ProjectStateV1 {
+                status: ProjectStatus::Funding,
+                donation_count: 1,
+            };
+            let persistent = env.storage().persistent();
+            persistent.set(&ProjectKey::ProjConfig(id), &config);
+            persistent.set(&ProjectKey::ProjState(id), &state);
+            persistent.set(&ProjectKey::TokenBalance(id, token.clone()), &balance);
+        }
+        env.storage().instance().set(&DataKey::ProjectCount, &count);
+    });
+    token::StellarAssetClient::new(env, token).mint(&client.address, &(balance * count as i128));
+    set_stored_schema(env, client, 1);
+}
+
+#[test]
+fn test_init_records_current_version() {
+    let (_, client, _) = setup_with_init();
+    let stored = client.get_version();
+    assert_eq!(stored, client.version());
+    assert_eq!(stored.schema, ContractVersion::SCHEMA);
+}
+
+#[test]
+fn test_migrate_records_current_build() {
+    let (env, client, super_admin) = setup_with_init();
+    set_stored_schema(&env, &client, 1);
+
+    assert_eq!(client.migrate(&super_admin), ContractVersion::SCHEMA);
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("schema"),).into_val(&env);
+    assert!(events.iter().any(|(_, t, _)| t == topics));
+    assert_eq!(client.get_version(), client.version());
+
+    // Nothing left to do the second time.
+    assert_eq!(client.migrate(&super_admin), ContractVersion::SCHEMA);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_upgrade_blocked_until_migrated() {
+    let (env, client, super_admin) = setup_with_init();
+    set_stored_schema(&env, &client, 0);
+
+    client.upgrade(&super_admin, &BytesN::from_array(&env, &[7u8; 32]));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #32)")]
+fn test_migrate_from_newer_schema_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    set_stored_schema(&env, &client, ContractVersion::SCHEMA + 1);
+
+    client.migrate(&super_admin);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_upgrade_requires_super_admin() {
+    let (env, client, super_admin) = setup_with_init();
+    let admin = Address::generate(&env);
+    client.grant_role(&super_admin, &admin, &Role::Admin, &None);
+
+    client.upgrade(&admin, &BytesN::from_array(&env, &[7u8; 32]));
+}
+
+#[test]
+fn test_migrate_rewrites_baseline_projects() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.set_oracle(&super_admin, &oracle);
+    let token = create_token(&env, &Address::generate(&env));
+    write_baseline_projects(&env, &client, &creator, &token.address, 1, 700);
+
+    assert_eq!(client.migrate(&super_admin), ContractVersion::SCHEMA);
+    let project = client.get_project(&0);
+    assert_eq!(project.status, ProjectStatus::Funding);
+    assert_eq!(project.donation_count, 1);
+    assert_eq!(project.quorum, 1);
+    assert_eq!(project.goal_unit, GoalUnit::FirstToken);
+    assert_eq!(client.get_token_flows(&0, &token.address).inflow, 700);
+
+    // The migrated project takes deposits and releases its whole escrow.
+    let donor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token.address).mint(&donor, &300);
+    client.deposit(&0, &donor, &token.address, &300, &false);
+    client.verify_and_release(&oracle, &0, &BytesN::from_array(&env, &[0xabu8; 32]));
+    assert_eq!(client.get_project(&0).status, ProjectStatus::Completed);
+    assert_eq!(token.balance(&creator), 1000);
+}
+
+#[test]
+fn test_migrate_pages_through_baseline_projects() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let token = create_token(&env, &Address::generate(&env));
+    let count = storage::PROJECT_PAGE as u64 + 5;
+    write_baseline_projects(&env, &client, &creator, &token.address, count, 10);
+
+    // The first page leaves storage at schema 1, so upgrades stay blocked.
+    assert_eq!(client.migrate(&super_admin), 1);
+    assert_eq!(client.get_version().schema, 1);
+    assert_eq!(client.migrate(&super_admin), ContractVersion::SCHEMA);
+    assert_eq!(client.get_version(), client.version());
+
+    for id in 0..count {
+        assert_eq!(client.get_project(&id).quorum, 1);
+    }
+}
@@ -0,0 +1,936 @@
+extern crate std;
+
+use soroban_sdk::{
+    contract, contractimpl,
+    crypto::bls12_381::{Fr, G1Affine},
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, Bytes, BytesN, Env, IntoVal, Val, Vec, U256,
+};
+
+use crate::bls;
+
+use crate::{PifpProtocol, PifpProtocolClient, ProjectStatus, ProtocolConfig, Role};
+
+/// Test verifier that accepts a proof iff it equals the verifying-key hash.
+#[contract]
+struct MockVerifier;
+
+#[contractimpl]
+impl MockVerifier {
+    pub fn verify(
+        _env: Env,
+        vk_hash: BytesN<32>,
+        _public_input: BytesN<32>,
+        proof: BytesN<32>,
+    ) -> bool {
+        proof == vk_hash
+    }
+}
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    proof_hash: &BytesN<32>,
+    quorum: u32,
+) -> crate::Project {
+    let token = create_token(env, &Address::generate(env));
+    let tokens = soroban_sdk::vec![env, token.address.clone()];
+    client.register_project(
+        creator,
+        &tokens,
+        &1000,
+        proof_hash,
+        &(env.ledger().timestamp() + 86400),
+        &quorum,
+        &vec![env],
+    )
+}
+
+#[test]
+fn test_default_protocol_config() {
+    let (_env, client, _) = setup_with_init();
+    assert_eq!(
+        client.get_protocol_config().max_quorum,
+        ProtocolConfig::DEFAULT_MAX_QUORUM
+    );
+}
+
+#[test]
+fn test_quorum_requires_distinct_oracles() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle_a = Address::generate(&env);
+    let oracle_b = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle_a);
+    client.set_oracle(&super_admin, &oracle_b);
+
+    let project = register(&env, &client, &creator, &proof_hash, 2);
+    assert_eq!(project.quorum, 2);
+
+    client.verify_and_release(&oracle_a, &project.id, &proof_hash);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Funding
+    );
+
+    client.verify_and_release(&oracle_b, &project.id, &proof_hash);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_same_oracle_cannot_attest_twice() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+
+    let project = register(&env, &client, &creator, &proof_hash, 2);
+    client.verify_and_release(&oracle, &project.id, &proof_hash);
+    client.verify_and_release(&oracle, &project.id, &proof_hash);
+}
+
+#[test]
+fn test_verification_pays_creator_every_token() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+
+    let first = create_token(&env, &Address::generate(&env));
+    let second = create_token(&env, &Address::generate(&env));
+    let project = client.register_project(
+        &creator,
+        &vec![&env, first.address.clone(), second.address.clone()],
+        &1000,
+        &proof_hash,
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![&env],
+    );
+    for (token, amount) in [(&first, 700), (&second, 300)] {
+        let donor = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token.address).mint(&donor, &amount);
+        client.deposit(&project.id, &donor, &token.address, &amount, &false);
+    }
+
+    client.verify_and_release(&oracle, &project.id, &proof_hash);
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("released"), project.id).into_val(&env);
+    assert_eq!(events.iter().filter(|(_, t, _)| *t == topics).count(), 2);
+
+    assert_eq!(first.balance(&creator), 700);
+    assert_eq!(second.balance(&creator), 300);
+    assert_eq!(first.balance(&client.address), 0);
+    assert_eq!(
+        client.get_escrow_proof(&project.id, &first.address).balance,
+        0
+    );
+    let flows = client.get_token_flows(&project.id, &second.address);
+    assert_eq!((flows.outflow, flows.released), (300, 300));
+    assert_eq!(client.get_creator_released(&creator, &first.address), 700);
+    assert_eq!(client.get_creator_released(&creator, &second.address), 300);
+}
+
+#[test]
+#[should_panic]
+fn test_quorum_above_protocol_max_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            max_quorum: 3,
+            ..client.get_protocol_config()
+        },
+    );
+
+    register(&env, &client, &creator, &proof_hash, 4);
+}
+
+#[test]
+#[should_panic]
+fn test_zero_quorum_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    register(&env, &client, &creator, &proof_hash, 0);
+}
+
+#[test]
+fn test_project_verifier_replaces_default_check() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let vk_hash = BytesN::from_array(&env, &[0x11u8; 32]);
+    let verifier = env.register(MockVerifier, ());
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+    client.approve_verifier(&super_admin, &verifier);
+
+    let project = register(&env, &client, &creator, &proof_hash, 1);
+    client.set_project_verifier(
+        &vec![&env, creator.clone()],
+        &project.id,
+        &verifier,
+        &vk_hash,
+    );
+    assert_eq!(
+        client.get_project_verifier(&project.id).unwrap().verifier,
+        verifier
+    );
+
+    // The external verifier accepts the vk-bound proof, not the stored hash.
+    client.verify_and_release(&oracle, &project.id, &vk_hash);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_project_verifier_rejects_default_proof() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let vk_hash = BytesN::from_array(&env, &[0x11u8; 32]);
+    let verifier = env.register(MockVerifier, ());
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+    client.approve_verifier(&super_admin, &verifier);
+
+    let project = register(&env, &client, &creator, &proof_hash, 1);
+    client.set_project_verifier(
+        &vec![&env, creator.clone()],
+        &project.id,
+        &verifier,
+        &vk_hash,
+    );
+    client.verify_and_release(&oracle, &project.id, &proof_hash);
+}
+
+#[test]
+#[should_panic]
+fn test_unapproved_verifier_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let verifier = env.register(MockVerifier, ());
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let project = register(&env, &client, &creator, &proof_hash, 1);
+    client.set_project_verifier(
+        &vec![&env, creator.clone()],
+        &project.id,
+        &verifier,
+        &proof_hash,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_revoked_verifier_blocks_verification() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let vk_hash = BytesN::from_array(&env, &[0x11u8; 32]);
+    let verifier = env.register(MockVerifier, ());
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+    client.approve_verifier(&super_admin, &verifier);
+
+    let project = register(&env, &client, &creator, &proof_hash, 1);
+    client.set_project_verifier(
+        &vec![&env, creator.clone()],
+        &project.id,
+        &verifier,
+        &vk_hash,
+    );
+    client.revoke_verifier(&super_admin, &verifier);
+    client.verify_and_release(&oracle, &project.id, &vk_hash);
+}
+
+// ─── Oracle assignment ──────────────────────────────────
+
+#[test]
+fn test_assigned_oracle_can_verify() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let assigned = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &assigned);
+
+    let project = register(&env, &client, &creator, &proof_hash, 1);
+    let oracles = soroban_sdk::vec![&env, assigned.clone()];
+    client.set_project_oracles(&super_admin, &project.id, &oracles);
+    assert_eq!(client.get_project_oracles(&project.id), oracles);
+
+    client.verify_and_release(&assigned, &project.id, &proof_hash);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_unassigned_oracle_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let assigned = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &assigned);
+    client.set_oracle(&super_admin, &outsider);
+
+    let project = register(&env, &client, &creator, &proof_hash, 1);
+    client.set_project_oracles(
+        &super_admin,
+        &project.id,
+        &soroban_sdk::vec![&env, assigned],
+    );
+    client.verify_and_release(&outsider, &project.id, &proof_hash);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_fewer_assigned_oracles_than_quorum_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+
+    let project = register(&env, &client, &creator, &proof_hash, 2);
+    client.set_project_oracles(&super_admin, &project.id, &soroban_sdk::vec![&env, oracle]);
+}
+
+// ─── Dependencies ───────────────────────────────────────
+
+#[test]
+fn test_dependent_project_waits_for_dependency() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+
+    let phase1 = register(&env, &client, &creator, &proof_hash, 1);
+    let phase2 = register(&env, &client, &creator, &proof_hash, 1);
+    let deps = soroban_sdk::vec![&env, phase1.id];
+    client.set_dependencies(&vec![&env, creator.clone()], &phase2.id, &deps);
+    assert_eq!(client.get_dependencies(&phase2.id), deps);
+
+    assert!(client
+        .try_verify_and_release(&oracle, &phase2.id, &proof_hash)
+        .is_err());
+    client.verify_and_release(&oracle, &phase1.id, &proof_hash);
+    client.verify_and_release(&oracle, &phase2.id, &proof_hash);
+    assert_eq!(
+        client.get_project(&phase2.id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #29)")]
+fn test_unmet_dependency_blocks_verification() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+
+    let phase1 = register(&env, &client, &creator, &proof_hash, 1);
+    let phase2 = register(&env, &client, &creator, &proof_hash, 1);
+    client.set_dependencies(
+        &vec![&env, creator.clone()],
+        &phase2.id,
+        &soroban_sdk::vec![&env, phase1.id],
+    );
+    client.verify_and_release(&oracle, &phase2.id, &proof_hash);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_dependency_cycle_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let a = register(&env, &client, &creator, &proof_hash, 1);
+    let b = register(&env, &client, &creator, &proof_hash, 1);
+    let c = register(&env, &client, &creator, &proof_hash, 1);
+    client.set_dependencies(
+        &vec![&env, creator.clone()],
+        &b.id,
+        &soroban_sdk::vec![&env, a.id],
+    );
+    client.set_dependencies(
+        &vec![&env, creator.clone()],
+        &c.id,
+        &soroban_sdk::vec![&env, b.id],
+    );
+    client.set_dependencies(
+        &vec![&env, creator.clone()],
+        &a.id,
+        &soroban_sdk::vec![&env, c.id],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_unknown_dependency_rejected() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+
+    let project = register(&env, &client, &creator, &proof_hash, 1);
+    client.set_dependencies(
+        &vec![&env, creator.clone()],
+        &project.id,
+        &soroban_sdk::vec![&env, 999],
+    );
+}
+
+// ─── Emergency oracle replacement ───────────────────────
+
+#[test]
+fn test_emergency_replacement_cancels_pending_attestations() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let compromised = Address::generate(&env);
+    let honest = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &compromised);
+    client.set_oracle(&super_admin, &honest);
+
+    let project = register(&env, &client, &creator, &proof_hash, 2);
+    client.set_project_oracles(
+        &super_admin,
+        &project.id,
+        &soroban_sdk::vec![&env, compromised.clone(), honest.clone()],
+    );
+    client.verify_and_release(&compromised, &project.id, &proof_hash);
+
+    assert_eq!(
+        client.replace_oracle_emergency(&super_admin, &compromised, &replacement),
+        1
+    );
+    assert!(!client.has_role(&compromised, &Role::Oracle));
+    assert!(client.has_role(&replacement, &Role::Oracle));
+    assert_eq!(
+        client.get_project_oracles(&project.id),
+        soroban_sdk::vec![&env, replacement.clone(), honest.clone()]
+    );
+
+    // The discarded attestation no longer counts towards quorum.
+    client.verify_and_release(&honest, &project.id, &proof_hash);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Funding
+    );
+    client.verify_and_release(&replacement, &project.id, &proof_hash);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_admin_cannot_use_emergency_replacement() {
+    let (env, client, super_admin) = setup_with_init();
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &admin, &Role::Admin, &None);
+    client.set_oracle(&super_admin, &oracle);
+    client.replace_oracle_emergency(&admin, &oracle, &Address::generate(&env));
+}
+
+// ─── BLS aggregate attestations ─────────────────────────
+
+/// Uncompressed encoding of the BLS12-381 G1 generator.
+const G1_GENERATOR: [u8; 96] = [
+    0x17, 0xf1, 0xd3, 0xa7, 0x31, 0x97, 0xd7, 0x94, 0x26, 0x95, 0x63, 0x8c, 0x4f, 0xa9, 0xac, 0x0f,
+    0xc3, 0x68, 0x8c, 0x4f, 0x97, 0x74, 0xb9, 0x05, 0xa1, 0x4e, 0x3a, 0x3f, 0x17, 0x1b, 0xac, 0x58,
+    0x6c, 0x55, 0xe8, 0x3f, 0xf9, 0x7a, 0x1a, 0xef, 0xfb, 0x3a, 0xf0, 0x0a, 0xdb, 0x22, 0xc6, 0xbb,
+    0x08, 0xb3, 0xf4, 0x81, 0xe3, 0xaa, 0xa0, 0xf1, 0xa0, 0x9e, 0x30, 0xed, 0x74, 0x1d, 0x8a, 0xe4,
+    0xfc, 0xf5, 0xe0, 0x95, 0xd5, 0xd0, 0x0a, 0xf6, 0x00, 0xdb, 0x18, 0xcb, 0x2c, 0x04, 0xb3, 0xed,
+    0xd0, 0x3c, 0xc7, 0x44, 0xa2, 0x88, 0x8a, 0xe4, 0x0c, 0xaa, 0x23, 0x29, 0x46, 0xc5, 0xe7, 0xe1,
+];
+
+fn bls_public_key(env: &Env, secret: u32) -> BytesN<96> {
+    let generator = G1Affine::from_bytes(BytesN::from_array(env, &G1_GENERATOR));
+    let scalar = Fr::from_u256(U256::from_u32(env, secret));
+    env.crypto()
+        .bls12_381()
+        .g1_mul(&generator, &scalar)
+        .to_bytes()
+}
+
+fn bls_aggregate_sign(env: &Env, secrets: &[u32], payload: &Bytes) -> BytesN<192> {
+    let bls = env.crypto().bls12_381();
+    let message = bls.hash_to_g2(payload, &Bytes::from_slice(env, bls::DST));
+    let mut signature = None;
+    for secret in secrets {
+        let share = bls.g2_mul(&message, &Fr::from_u256(U256::from_u32(env, *secret)));
+        signature = Some(match signature {
+            Some(acc) => bls.g2_add(&acc, &share),
+            None => share,
+        });
+    }
+    signature.unwrap().to_bytes()
+}
+
+fn setup_bls_oracles(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    super_admin: &Address,
+    secrets: &[u32],
+) -> soroban_sdk::Vec<Address> {
+    let mut oracles = soroban_sdk::Vec::new(env);
+    for secret in secrets {
+        let oracle = Address::generate(env);
+        client.set_oracle(super_admin, &oracle);
+        client.set_oracle_bls_key(super_admin, &oracle, &bls_public_key(env, *secret));
+        oracles.push_back(oracle);
+    }
+    oracles
+}
+
+#[test]
+fn test_verify_aggregate_meets_quorum() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let secrets = [7u32, 11, 13];
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let oracles = setup_bls_oracles(&env, &client, &super_admin, &secrets);
+    let project = register(&env, &client, &creator, &proof_hash, 3);
+
+    let payload = bls::attestation_payload(&env, &client.address, project.id, &proof_hash);
+    assert_eq!(
+        client.get_attestation_payload(&project.id, &proof_hash),
+        payload
+    );
+    let signature = bls_aggregate_sign(&env, &secrets, &payload);
+
+    client.verify_aggregate(
+        &oracles.get(0).unwrap(),
+        &project.id,
+        &proof_hash,
+        &oracles,
+        &signature,
+    );
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_verify_aggregate_rejects_missing_signer() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let oracles = setup_bls_oracles(&env, &client, &super_admin, &[7, 11]);
+    let project = register(&env, &client, &creator, &proof_hash, 2);
+
+    // Only the first oracle actually signed, but both are claimed as signers.
+    let payload = bls::attestation_payload(&env, &client.address, project.id, &proof_hash);
+    let signature = bls_aggregate_sign(&env, &[7], &payload);
+
+    client.verify_aggregate(
+        &oracles.get(0).unwrap(),
+        &project.id,
+        &proof_hash,
+        &oracles,
+        &signature,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_verify_aggregate_rejects_other_deployment_payload() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let secrets = [7u32, 11];
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let oracles = setup_bls_oracles(&env, &client, &super_admin, &secrets);
+    let project = register(&env, &client, &creator, &proof_hash, 2);
+
+    // Signed for the same project ID on a different deployment.
+    let fork = Address::generate(&env);
+    let payload = bls::attestation_payload(&env, &fork, project.id, &proof_hash);
+    let signature = bls_aggregate_sign(&env, &secrets, &payload);
+
+    client.verify_aggregate(
+        &oracles.get(0).unwrap(),
+        &project.id,
+        &proof_hash,
+        &oracles,
+        &signature,
+    );
+}
+
+// ─── Ongoing projects ───────────────────────────────────
+
+#[test]
+fn test_ongoing_project_requires_periodic_reattestation() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let period = 90 * 86_400;
+    env.ledger().with_mut(|l| l.timestamp = 1_700_000_000);
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+
+    let project = register(&env, &client, &creator, &proof_hash, 1);
+    client.set_ongoing(&vec![&env, creator.clone()], &project.id, &period);
+    assert!(!client.is_stream_active(&project.id));
+
+    client.verify_and_release(&oracle, &project.id, &proof_hash);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+    assert!(client.is_stream_active(&project.id));
+
+    // Missing the next attestation pauses the stream.
+    env.ledger().with_mut(|l| l.timestamp += period + 1);
+    assert!(!client.is_stream_active(&project.id));
+
+    // A fresh round from the same oracle resumes it.
+    client.verify_and_release(&oracle, &project.id, &proof_hash);
+    assert!(client.is_stream_active(&project.id));
+    assert_eq!(
+        client.get_cadence(&project.id).unwrap().last_attested,
+        env.ledger().timestamp()
+    );
+}
+
+#[test]
+fn test_ongoing_rounds_release_while_stream_active() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let period = 30 * 86_400;
+    env.ledger().with_mut(|l| l.timestamp = 1_700_000_000);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+    let token = create_token(&env, &Address::generate(&env));
+    let project = client.register_project(
+        &creator,
+        &vec![&env, token.address.clone()],
+        &1000,
+        &proof_hash,
+        &(env.ledger().timestamp() + 365 * 86_400),
+        &1,
+        &vec![&env],
+    );
+    client.set_ongoing(&vec![&env, creator.clone()], &project.id, &period);
+    let fund = |amount: i128| {
+        let donor = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token.address).mint(&donor, &amount);
+        client.deposit(&project.id, &donor, &token.address, &amount, &false);
+    };
+
+    fund(300);
+    client.verify_and_release(&oracle, &project.id, &proof_hash);
+    assert_eq!(token.balance(&creator), 300);
+
+    // A missed round pauses the stream: the late round pays nothing.
+    fund(200);
+    env.ledger().with_mut(|l| l.timestamp += period + 1);
+    client.verify_and_release(&oracle, &project.id, &proof_hash);
+    assert_eq!(token.balance(&creator), 300);
+    assert_eq!(token.balance(&client.address), 200);
+
+    // The next on-time round releases everything held since.
+    fund(100);
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    client.verify_and_release(&oracle, &project.id, &proof_hash);
+    assert_eq!(token.balance(&creator), 600);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Active
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_set_ongoing_rejected_after_attestation() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle_a = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle_a);
+
+    let project = register(&env, &client, &creator, &proof_hash, 2);
+    client.verify_and_release(&oracle_a, &project.id, &proof_hash);
+    client.set_ongoing(&vec![&env, creator.clone()], &project.id, &86_400);
+}
+
+// ─── Release throttling ─────────────────────────────────
+
+#[test]
+fn test_release_past_cap_queues_until_next_window() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    env.ledger().with_mut(|l| l.timestamp = 1_700_000_000);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            release_cap: 1_000,
+            ..client.get_protocol_config()
+        },
+    );
+
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let tokens = soroban_sdk::vec![&env, token.clone()];
+    let deadline = env.ledger().timestamp() + 86400;
+    let first = client.register_project(
+        &creator,
+        &tokens,
+        &1000,
+        &proof_hash,
+        &deadline,
+        &1,
+        &vec![&env],
+    );
+    let second = client.register_project(
+        &creator,
+        &tokens,
+        &1000,
+        &proof_hash,
+        &deadline,
+        &1,
+        &vec![&env],
+    );
+    for id in [first.id, second.id] {
+        let donor = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&donor, &800);
+        client.deposit(&id, &donor, &token, &800, &false);
+    }
+
+    client.verify_and_release(&oracle, &first.id, &proof_hash);
+    assert_eq!(
+        client.get_project(&first.id).status,
+        ProjectStatus::Completed
+    );
+
+    // 800 + 800 exceeds the window's cap of 1_000, so the second waits.
+    client.verify_and_release(&oracle, &second.id, &proof_hash);
+    assert_eq!(
+        client.get_project(&second.id).status,
+        ProjectStatus::Funding
+    );
+    assert_eq!(client.get_release_queue().len(), 1);
+    assert_eq!(client.process_release_queue(), 0);
+
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    assert_eq!(client.process_release_queue(), 1);
+    assert_eq!(
+        client.get_project(&second.id).status,
+        ProjectStatus::Completed
+    );
+    assert!(client.get_release_queue().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")]
+fn test_queued_release_blocks_further_attestation() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            release_cap: 100,
+            ..client.get_protocol_config()
+        },
+    );
+
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let tokens = soroban_sdk::vec![&env, token.clone()];
+    let deadline = env.ledger().timestamp() + 86400;
+    let first = client.register_project(
+        &creator,
+        &tokens,
+        &1000,
+        &proof_hash,
+        &deadline,
+        &1,
+        &vec![&env],
+    );
+    let second = client.register_project(
+        &creator,
+        &tokens,
+        &1000,
+        &proof_hash,
+        &deadline,
+        &1,
+        &vec![&env],
+    );
+    for id in [first.id, second.id] {
+        let donor = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&donor, &500);
+        client.deposit(&id, &donor, &token, &500, &false);
+    }
+
+    // The first release alone exceeds the cap but fits an unused window.
+    client.verify_and_release(&oracle, &first.id, &proof_hash);
+    client.verify_and_release(&oracle, &second.id, &proof_hash);
+    client.verify_and_release(&oracle, &second.id, &proof_hash);
+}
+
+// ─── Proof submissions ───────────────────────────────────
+
+#[test]
+fn test_submitted_proof_approved_by_quorum() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle_a = Address::generate(&env);
+    let oracle_b = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle_a);
+    client.set_oracle(&super_admin, &oracle_b);
+    let project = register(&env, &client, &creator, &proof_hash, 2);
+
+    client.submit_proof(&vec![&env, creator.clone()], &project.id, &proof_hash);
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("proof_sub"), project.id).into_val(&env);
+    assert!(events.iter().any(|(_, t, _)| t == topics));
+    assert_eq!(
+        client.get_pending_proof(&project.id).unwrap().proof_hash,
+        proof_hash
+    );
+
+    client.approve_proof(&oracle_a, &project.id);
+    assert!(client.get_pending_proof(&project.id).is_some());
+    client.approve_proof(&oracle_b, &project.id);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+    assert_eq!(client.get_pending_proof(&project.id), None);
+}
+
+#[test]
+fn test_rejected_proof_can_be_resubmitted() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    let wrong = BytesN::from_array(&env, &[0xcdu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.set_oracle(&super_admin, &oracle);
+    let project = register(&env, &client, &creator, &proof_hash, 1);
+
+    client.submit_proof(&vec![&env, creator.clone()], &project.id, &wrong);
+    // Approval still checks the evidence.
+    assert!(client.try_approve_proof(&oracle, &project.id).is_err());
+    client.reject_proof(&oracle, &project.id, &BytesN::from_array(&env, &[1u8; 32]));
+    let events = env.events().all();
+    let topics: Vec<Val> = (symbol_short!("proof_rej"), project.id).into_val(&env);
+    assert!(events.iter().any(|(_, t, _)| t == topics));
+    assert_eq!(client.get_pending_proof(&project.id), None);
+    assert!(client.try_approve_proof(&oracle, &project.id).is_err());
+
+    client.submit_proof(&vec![&env, creator.clone()], &project.id, &proof_hash);
+    client.approve_proof(&oracle, &project.id);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_only_creator_submits_proof() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let proof_hash = BytesN::from_array(&env, &[0xabu8; 32]);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    let project = register(&env, &client, &creator, &proof_hash, 1);
+
+    client.submit_proof(
+        &vec![&env, Address::generate(&env)],
+        &project.id,
+        &proof_hash,
+    );
+}
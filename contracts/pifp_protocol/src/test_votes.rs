@@ -0,0 +1,661 @@
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env, Vec,
+};
+
+use crate::{
+    ActionableKind, DisbursementTemplate, PifpProtocol, PifpProtocolClient, ProjectStatus,
+    ProtocolConfig, Role, Tranche,
+};
+
+fn setup() -> (Env, PifpProtocolClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(PifpProtocol, ());
+    let client = PifpProtocolClient::new(&env, &contract_id);
+    (env, client)
+}
+
+fn setup_with_init() -> (Env, PifpProtocolClient<'static>, Address) {
+    let (env, client) = setup();
+    let super_admin = Address::generate(&env);
+    client.init(&super_admin);
+    (env, client, super_admin)
+}
+
+fn create_token<'a>(env: &Env, admin: &Address) -> token::Client<'a> {
+    let addr = env.register_stellar_asset_contract_v2(admin.clone());
+    token::Client::new(env, &addr.address())
+}
+
+fn register(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    creator: &Address,
+    tokens: &Vec<Address>,
+) -> crate::Project {
+    client.register_project(
+        creator,
+        tokens,
+        &1000,
+        &BytesN::from_array(env, &[0xabu8; 32]),
+        &(env.ledger().timestamp() + 86400),
+        &1,
+        &vec![env],
+    )
+}
+
+fn fund(
+    env: &Env,
+    client: &PifpProtocolClient<'static>,
+    project_id: u64,
+    donator: &Address,
+    token: &Address,
+    amount: i128,
+) {
+    token::StellarAssetClient::new(env, token).mint(donator, &amount);
+    client.deposit(&project_id, donator, token, &amount, &false);
+}
+
+/// Charge a 2% protocol fee, so escrows hold less than donors gave.
+fn enable_fee(env: &Env, client: &PifpProtocolClient<'static>, super_admin: &Address) {
+    client.set_protocol_config(
+        super_admin,
+        &ProtocolConfig {
+            treasury: Some(Address::generate(env)),
+            fee_bps: 200,
+            ..client.get_protocol_config()
+        },
+    );
+}
+
+struct Fixture {
+    env: Env,
+    client: PifpProtocolClient<'static>,
+    super_admin: Address,
+    oracle: Address,
+    project_id: u64,
+}
+
+/// A project with a 1-day, 50% objection vote funded 600/400 by two donors.
+fn fixture() -> (Fixture, Address, Address) {
+    let (env, client, super_admin) = setup_with_init();
+    env.ledger().with_mut(|l| l.timestamp = 1_700_000_000);
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    client.set_donor_vote(&vec![&env, creator.clone()], &project.id, &86_400, &5_000);
+
+    let big = Address::generate(&env);
+    let small = Address::generate(&env);
+    fund(&env, &client, project.id, &big, &token, 600);
+    fund(&env, &client, project.id, &small, &token, 400);
+    let f = Fixture {
+        env,
+        client,
+        super_admin,
+        oracle,
+        project_id: project.id,
+    };
+    (f, big, small)
+}
+
+fn verify(f: &Fixture) {
+    f.client.verify_and_release(
+        &f.oracle,
+        &f.project_id,
+        &BytesN::from_array(&f.env, &[0xabu8; 32]),
+    );
+}
+
+fn end_window(f: &Fixture) {
+    f.env.ledger().with_mut(|l| l.timestamp += 86_400);
+}
+
+#[test]
+fn test_unopposed_vote_completes_project() {
+    let (f, _, small) = fixture();
+    verify(&f);
+    let vote = f.client.get_donor_vote(&f.project_id).unwrap();
+    assert_eq!(vote.total_weight, 1000);
+    assert_eq!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Funding
+    );
+
+    assert_eq!(f.client.object_to_release(&small, &f.project_id), 400);
+    end_window(&f);
+    assert!(!f
+        .client
+        .close_donor_vote(&Address::generate(&f.env), &f.project_id));
+    assert_eq!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Completed
+    );
+    assert_eq!(f.client.get_donor_vote(&f.project_id), None);
+}
+
+#[test]
+fn test_vote_weight_counts_gross_contributions() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    enable_fee(&env, &client, &super_admin);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    client.set_donor_vote(&vec![&env, creator.clone()], &project.id, &86_400, &6_000);
+    let big = Address::generate(&env);
+    fund(&env, &client, project.id, &big, &token, 600);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        420,
+    );
+
+    client.verify_and_release(
+        &oracle,
+        &project.id,
+        &BytesN::from_array(&env, &[0xabu8; 32]),
+    );
+    // The escrow holds 2% less than was given; the vote still weighs the
+    // 1020 donors gave, so 600 objecting falls short of 60%.
+    assert_eq!(
+        client.get_donor_vote(&project.id).unwrap().total_weight,
+        1020
+    );
+    client.object_to_release(&big, &project.id);
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    assert!(!client.close_donor_vote(&Address::generate(&env), &project.id));
+}
+
+#[test]
+fn test_objection_threshold_contests_release() {
+    let (f, big, _) = fixture();
+    verify(&f);
+    f.client.object_to_release(&big, &f.project_id);
+    end_window(&f);
+
+    assert!(f
+        .client
+        .close_donor_vote(&Address::generate(&f.env), &f.project_id));
+    assert_eq!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Funding
+    );
+
+    f.client
+        .resolve_contest(&f.super_admin, &f.project_id, &true);
+    assert_eq!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+fn test_rejected_contest_requires_reverification() {
+    let (f, big, _) = fixture();
+    verify(&f);
+    f.client.object_to_release(&big, &f.project_id);
+    end_window(&f);
+    f.client
+        .close_donor_vote(&Address::generate(&f.env), &f.project_id);
+
+    f.client
+        .resolve_contest(&f.super_admin, &f.project_id, &false);
+    assert_eq!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Funding
+    );
+    assert_eq!(f.client.get_donor_vote(&f.project_id), None);
+
+    // The same oracle may attest again, opening a fresh vote.
+    verify(&f);
+    assert!(f.client.get_donor_vote(&f.project_id).is_some());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #25)")]
+fn test_attestation_rejected_while_vote_open() {
+    let (f, _, _) = fixture();
+    verify(&f);
+    verify(&f);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_objection_after_window_rejected() {
+    let (f, big, _) = fixture();
+    verify(&f);
+    end_window(&f);
+    f.client.object_to_release(&big, &f.project_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_donor_objects_once() {
+    let (f, big, _) = fixture();
+    verify(&f);
+    f.client.object_to_release(&big, &f.project_id);
+    f.client.object_to_release(&big, &f.project_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_close_before_window_end_rejected() {
+    let (f, _, _) = fixture();
+    verify(&f);
+    f.client
+        .close_donor_vote(&Address::generate(&f.env), &f.project_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_non_donor_cannot_object() {
+    let (f, _, _) = fixture();
+    verify(&f);
+    f.client
+        .object_to_release(&Address::generate(&f.env), &f.project_id);
+}
+
+// ─── Deadline extensions ────────────────────────────────
+
+fn miss_deadline(f: &Fixture) -> u64 {
+    let deadline = f.client.get_project(&f.project_id).deadline;
+    f.env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+    deadline
+}
+
+#[test]
+fn test_donor_majority_extends_deadline() {
+    let (f, big, small) = fixture();
+    let old = miss_deadline(&f);
+    let new_deadline = old + 30 * 86_400;
+
+    assert!(!f
+        .client
+        .back_extension(&small, &f.project_id, &new_deadline));
+    let vote = f.client.get_extension_vote(&f.project_id).unwrap();
+    assert_eq!(vote.support, 400);
+    assert_eq!(vote.total_weight, 1000);
+    assert_eq!(f.client.get_project(&f.project_id).deadline, old);
+
+    assert!(f.client.back_extension(&big, &f.project_id, &new_deadline));
+    assert_eq!(f.client.get_project(&f.project_id).deadline, new_deadline);
+    assert_eq!(f.client.get_extension_vote(&f.project_id), None);
+    assert_eq!(f.client.get_extension_count(&f.project_id), 1);
+}
+
+#[test]
+fn test_extension_weight_counts_gross_contributions() {
+    let (env, client, super_admin) = setup_with_init();
+    env.ledger().with_mut(|l| l.timestamp = 1_700_000_000);
+    let creator = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    enable_fee(&env, &client, &super_admin);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    let half = Address::generate(&env);
+    fund(&env, &client, project.id, &half, &token, 500);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        520,
+    );
+    env.ledger()
+        .with_mut(|l| l.timestamp = project.deadline + 1);
+
+    // 500 is a majority of the escrow left after fees, but not of the
+    // 1020 donors gave.
+    let new_deadline = project.deadline + 30 * 86_400;
+    assert!(!client.back_extension(&half, &project.id, &new_deadline));
+    let vote = client.get_extension_vote(&project.id).unwrap();
+    assert_eq!(vote.total_weight, 1020);
+    assert_eq!(client.get_project(&project.id).deadline, project.deadline);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_extension_requires_missed_deadline() {
+    let (f, big, _) = fixture();
+    let deadline = f.client.get_project(&f.project_id).deadline;
+    f.client
+        .back_extension(&big, &f.project_id, &(deadline + 86_400));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_extension_backers_must_agree_on_deadline() {
+    let (f, big, small) = fixture();
+    let old = miss_deadline(&f);
+    f.client
+        .back_extension(&small, &f.project_id, &(old + 86_400));
+    f.client
+        .back_extension(&big, &f.project_id, &(old + 2 * 86_400));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_donor_backs_extension_once() {
+    let (f, _, small) = fixture();
+    let old = miss_deadline(&f);
+    f.client
+        .back_extension(&small, &f.project_id, &(old + 86_400));
+    f.client
+        .back_extension(&small, &f.project_id, &(old + 86_400));
+}
+
+#[test]
+fn test_creator_extends_deadline_within_limit() {
+    let (f, _, _) = fixture();
+    let creator = f.client.get_project(&f.project_id).creator;
+    let old = f.client.get_project(&f.project_id).deadline;
+
+    assert_eq!(
+        f.client.extend_deadline(
+            &vec![&f.env, creator.clone()],
+            &f.project_id,
+            &(old + 7 * 86_400)
+        ),
+        1
+    );
+    assert_eq!(
+        f.client.extend_deadline(
+            &vec![&f.env, creator.clone()],
+            &f.project_id,
+            &(old + 14 * 86_400)
+        ),
+        2
+    );
+    assert_eq!(
+        f.client.get_project(&f.project_id).deadline,
+        old + 14 * 86_400
+    );
+    assert_eq!(f.client.get_extension_count(&f.project_id), 2);
+
+    // The deadline only ever moves out.
+    assert!(f
+        .client
+        .try_extend_deadline(&vec![&f.env, creator.clone()], &f.project_id, &old)
+        .is_err());
+}
+
+#[test]
+fn test_long_extension_needs_admin() {
+    let (f, _, _) = fixture();
+    let creator = f.client.get_project(&f.project_id).creator;
+    let old = f.client.get_project(&f.project_id).deadline;
+    let far = old + ProtocolConfig::DEFAULT_MAX_DEADLINE_EXTENSION + 1;
+
+    assert!(f
+        .client
+        .try_extend_deadline(&vec![&f.env, creator.clone()], &f.project_id, &far)
+        .is_err());
+    f.client
+        .extend_deadline(&vec![&f.env, f.super_admin.clone()], &f.project_id, &far);
+    assert_eq!(f.client.get_project(&f.project_id).deadline, far);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_extension_beyond_horizon_rejected() {
+    let (f, _, _) = fixture();
+    let horizon = f.env.ledger().timestamp() + ProtocolConfig::MAX_DEADLINE_HORIZON;
+    f.client.extend_deadline(
+        &vec![&f.env, f.super_admin.clone()],
+        &f.project_id,
+        &(horizon + 1),
+    );
+}
+
+#[test]
+fn test_held_and_contested_releases_are_actionable() {
+    let (f, big, _) = fixture();
+    let queue = |kind: ActionableKind| f.client.get_actionable_projects(&kind, &0, &10);
+    verify(&f);
+    assert_eq!(
+        queue(ActionableKind::PendingApproval),
+        vec![&f.env, f.project_id]
+    );
+    assert!(queue(ActionableKind::AwaitingVerification).is_empty());
+
+    f.client.object_to_release(&big, &f.project_id);
+    end_window(&f);
+    f.client
+        .close_donor_vote(&Address::generate(&f.env), &f.project_id);
+    assert!(queue(ActionableKind::PendingApproval).is_empty());
+    assert_eq!(
+        queue(ActionableKind::DisputeOpen),
+        vec![&f.env, f.project_id]
+    );
+}
+
+// ─── Challenge window ───────────────────────────────────
+
+const CHALLENGE: u64 = 3 * 86_400;
+
+/// A funded, one-oracle project under a protocol-wide challenge period;
+/// returns the fixture and an auditor.
+fn challenge_fixture() -> (Fixture, Address) {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    client.grant_role(&super_admin, &auditor, &Role::Auditor, &None);
+    client.set_protocol_config(
+        &super_admin,
+        &ProtocolConfig {
+            challenge_period: CHALLENGE,
+            ..client.get_protocol_config()
+        },
+    );
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        1000,
+    );
+    let f = Fixture {
+        env,
+        client,
+        super_admin,
+        oracle,
+        project_id: project.id,
+    };
+    (f, auditor)
+}
+
+#[test]
+fn test_release_waits_out_challenge_window() {
+    let (f, _) = challenge_fixture();
+    verify(&f);
+    let challenge = f.client.get_challenge(&f.project_id).unwrap();
+    assert_eq!(challenge.released_bps, 10_000);
+    assert_eq!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Funding
+    );
+    let keeper = Address::generate(&f.env);
+    assert!(f
+        .client
+        .try_close_challenge(&keeper, &f.project_id)
+        .is_err());
+
+    f.env.ledger().with_mut(|l| l.timestamp += CHALLENGE);
+    f.client.close_challenge(&keeper, &f.project_id);
+    assert_eq!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Completed
+    );
+    assert_eq!(f.client.get_challenge(&f.project_id), None);
+}
+
+#[test]
+fn test_auditor_dispute_holds_release_for_admin() {
+    let (f, auditor) = challenge_fixture();
+    verify(&f);
+    let evidence = BytesN::from_array(&f.env, &[9u8; 32]);
+    f.client.raise_dispute(&auditor, &f.project_id, &evidence);
+    assert_eq!(
+        f.client
+            .get_actionable_projects(&ActionableKind::DisputeOpen, &0, &10),
+        vec![&f.env, f.project_id]
+    );
+    // A disputed release no longer clears on its own.
+    f.env.ledger().with_mut(|l| l.timestamp += CHALLENGE);
+    assert!(f
+        .client
+        .try_close_challenge(&Address::generate(&f.env), &f.project_id)
+        .is_err());
+
+    f.client
+        .resolve_dispute(&f.super_admin, &f.project_id, &false);
+    assert_eq!(f.client.get_challenge(&f.project_id), None);
+    assert_ne!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Completed
+    );
+    // The project can be verified again.
+    verify(&f);
+    assert!(f.client.get_challenge(&f.project_id).is_some());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #34)")]
+fn test_dispute_after_window_rejected() {
+    let (f, auditor) = challenge_fixture();
+    verify(&f);
+    f.env.ledger().with_mut(|l| l.timestamp += CHALLENGE);
+    f.client.raise_dispute(
+        &auditor,
+        &f.project_id,
+        &BytesN::from_array(&f.env, &[9u8; 32]),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_only_auditors_raise_disputes() {
+    let (f, _) = challenge_fixture();
+    verify(&f);
+    f.client.raise_dispute(
+        &Address::generate(&f.env),
+        &f.project_id,
+        &BytesN::from_array(&f.env, &[9u8; 32]),
+    );
+}
+
+#[test]
+fn test_approved_vote_opens_challenge_window() {
+    let (f, _, _) = fixture();
+    f.client.set_protocol_config(
+        &f.super_admin,
+        &ProtocolConfig {
+            challenge_period: CHALLENGE,
+            ..f.client.get_protocol_config()
+        },
+    );
+    verify(&f);
+    end_window(&f);
+    assert!(!f
+        .client
+        .close_donor_vote(&Address::generate(&f.env), &f.project_id));
+    // The donors approved, but the release still waits out the window.
+    assert_eq!(
+        f.client.get_challenge(&f.project_id).unwrap().released_bps,
+        10_000
+    );
+    assert_eq!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Funding
+    );
+
+    f.env.ledger().with_mut(|l| l.timestamp += CHALLENGE);
+    f.client
+        .close_challenge(&Address::generate(&f.env), &f.project_id);
+    assert_eq!(
+        f.client.get_project(&f.project_id).status,
+        ProjectStatus::Completed
+    );
+}
+
+#[test]
+fn test_vote_held_for_each_gated_tranche() {
+    let (env, client, super_admin) = setup_with_init();
+    let creator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.grant_role(&super_admin, &creator, &Role::ProjectManager, &None);
+    client.grant_role(&super_admin, &oracle, &Role::Oracle, &None);
+    let template = DisbursementTemplate {
+        tranches: vec![
+            &env,
+            Tranche {
+                bps: 4_000,
+                gated: true,
+            },
+            Tranche {
+                bps: 6_000,
+                gated: true,
+            },
+        ],
+    };
+    client.set_disbursement_template(&super_admin, &1, &template);
+    let token = create_token(&env, &Address::generate(&env)).address;
+    let project = register(&env, &client, &creator, &vec![&env, token.clone()]);
+    let signers = vec![&env, creator.clone()];
+    client.use_disbursement_template(&signers, &project.id, &1);
+    client.set_donor_vote(&signers, &project.id, &86_400, &5_000);
+    fund(
+        &env,
+        &client,
+        project.id,
+        &Address::generate(&env),
+        &token,
+        1000,
+    );
+    let proof = BytesN::from_array(&env, &[0xabu8; 32]);
+
+    // The first gate waits on the donors rather than releasing at once.
+    client.verify_and_release(&oracle, &project.id, &proof);
+    assert_eq!(
+        client.get_donor_vote(&project.id).unwrap().released_bps,
+        4_000
+    );
+    assert_eq!(client.get_project_state(&project.id).released_bps, 0);
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    assert!(!client.close_donor_vote(&Address::generate(&env), &project.id));
+    let state = client.get_project_state(&project.id);
+    assert_eq!(state.released_bps, 4_000);
+    assert_eq!(state.status, ProjectStatus::Active);
+
+    // So does the last, which completes the project.
+    client.verify_and_release(&oracle, &project.id, &proof);
+    assert_eq!(
+        client.get_donor_vote(&project.id).unwrap().released_bps,
+        10_000
+    );
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    client.close_donor_vote(&Address::generate(&env), &project.id);
+    assert_eq!(
+        client.get_project(&project.id).status,
+        ProjectStatus::Completed
+    );
+}
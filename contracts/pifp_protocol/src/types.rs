@@ -8,7 +8,8 @@
 //!
 //! A `Project` is internally stored as two separate ledger entries:
 //!
-//! - [`ProjectConfig`] — written once at registration; never mutated.
+//! - [`ProjectConfig`] — written at registration; afterwards only amended by
+//!   rare, explicit paths such as a donor-approved deadline extension.
 //! - [`ProjectState`] — written on every deposit and on verification.
 //!
 //! The public API exposes the reconstructed [`Project`] struct for convenience.
@@ -27,7 +28,10 @@
 //! Backward transitions and transitions out of terminal states (`Completed`,
 //! `Expired`) are rejected by `verify_and_release`.
 
-use soroban_sdk::{contracttype, Address, BytesN, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::fsm::{self, StatusEvent};
+use crate::rbac::Role;
 
 /// Current lifecycle state of a funding project.
 #[contracttype]
@@ -43,7 +47,98 @@ pub enum ProjectStatus {
     Expired,
 }
 
-/// Immutable project configuration, written once at registration.
+impl ProjectStatus {
+    /// Return `true` if `next` is a forward transition of the lifecycle FSM,
+    /// i.e. some [`StatusEvent`] moves a project from this status to `next`.
+    pub fn can_advance_to(&self, next: &ProjectStatus) -> bool {
+        self != next
+            && StatusEvent::reaching(next)
+                .and_then(|event| fsm::transition(self, event).ok())
+                .as_ref()
+                == Some(next)
+    }
+
+    /// Stable one-byte code used by the packed [`ProjectState`] encoding.
+    pub fn code(&self) -> u8 {
+        match self {
+            ProjectStatus::Funding => 0,
+            ProjectStatus::Active => 1,
+            ProjectStatus::Completed => 2,
+            ProjectStatus::Expired => 3,
+        }
+    }
+
+    /// Inverse of [`ProjectStatus::code`].
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(ProjectStatus::Funding),
+            1 => Some(ProjectStatus::Active),
+            2 => Some(ProjectStatus::Completed),
+            3 => Some(ProjectStatus::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// Unit a project's goal is denominated in.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GoalUnit {
+    /// The first accepted token's units; other tokens do not count.
+    FirstToken,
+    /// The protocol price feed's reference unit, summed across every
+    /// accepted token at current prices.
+    Reference,
+}
+
+/// Operational queue a project can be listed in by
+/// `get_actionable_projects`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ActionableKind {
+    /// A verified release is held by an open donor vote, a challenge
+    /// window or the release throttle.
+    PendingApproval,
+    /// The project has met its goal or released a tranche and is waiting
+    /// for its next attestation.
+    AwaitingVerification,
+    /// The deadline has passed but the project was never expired.
+    PastDeadline,
+    /// Donors contested a release and it awaits `resolve_contest`, or an
+    /// auditor disputed it and it awaits `resolve_dispute`.
+    DisputeOpen,
+}
+
+/// Protocol-wide health snapshot returned by `health_check`, for
+/// monitoring systems to poll.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolHealth {
+    /// Protocol paused, explicitly or by an active maintenance window.
+    pub paused: bool,
+    /// Wind-down mode has begun.
+    pub sunset: bool,
+    /// Of the tokens asked about, those with deposits suspended.
+    pub paused_tokens: Vec<Address>,
+    /// Unsettled projects in the page paused individually.
+    pub paused_projects: u32,
+    /// Addresses currently holding `Role::Oracle`.
+    pub oracle_count: u32,
+    /// Releases in the page contested by donors or disputed by an auditor,
+    /// awaiting an admin.
+    pub open_disputes: u32,
+    /// Unsettled projects in the page whose deadline has passed.
+    pub past_deadline: u32,
+    /// Sequentially numbered projects, to page the counts above through.
+    pub project_count: u64,
+    /// Treasury holdings of each token asked about; empty without a
+    /// treasury.
+    pub treasury_balances: Vec<TokenBalance>,
+    /// Build that last initialised or migrated storage.
+    pub version: ContractVersion,
+}
+
+/// Project configuration, written at registration and rarely amended.
 ///
 /// Stored separately from mutable state to reduce write costs on deposits
 /// and verification (only ~20 bytes for state vs ~150 bytes for the full struct).
@@ -56,16 +151,93 @@ pub struct ProjectConfig {
     pub goal: i128,
     pub proof_hash: BytesN<32>,
     pub deadline: u64,
+    pub quorum: u32,
+    pub goal_unit: GoalUnit,
+}
+
+impl ProjectConfig {
+    /// Upper bound on the number of projects one project may depend on.
+    pub const MAX_DEPENDENCIES: u32 = 5;
 }
 
 /// Mutable project state, updated on deposits and verification.
 ///
 /// Kept small (~20 bytes) so that frequent writes (deposits) are cheap.
+///
+/// Stored packed into a single `u128` (see [`ProjectState::pack`]) rather than
+/// as a field map, which keeps the ledger entry at a fixed 20 bytes of XDR.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ProjectState {
     pub status: ProjectStatus,
     pub donation_count: u32,
+    /// Per-project boolean flags; see the `FLAG_*` constants.
+    pub flags: u32,
+    /// Share of the escrow released so far, in basis points.
+    pub released_bps: u32,
+}
+
+impl ProjectState {
+    /// Layout version stored in the top byte of the packed representation.
+    pub const ENCODING_VERSION: u8 = 1;
+
+    /// Deposits and releases are frozen by an admin (`pause_project`).
+    pub const FLAG_PAUSED: u32 = 1 << 0;
+    /// Quorum was met but completion awaits a donor vote or challenge
+    /// window, its resolution, or room under the release throttle.
+    pub const FLAG_RELEASE_PENDING: u32 = 1 << 1;
+    /// The donor vote reached its objection threshold, or an auditor
+    /// disputed the release; awaiting an admin.
+    pub const FLAG_CONTESTED: u32 = 1 << 2;
+    /// The project opted into donor protection and pays a premium on each
+    /// deposit into the insurance pool.
+    pub const FLAG_INSURED: u32 = 1 << 3;
+    /// The first deposit narrows the project's accepted tokens to the token
+    /// it was made in.
+    pub const FLAG_CURRENCY_LOCK: u32 = 1 << 4;
+    /// The project's escrow was moved to a successor contract; it is
+    /// `Expired` here but refunds are handled by the successor.
+    pub const FLAG_MIGRATED: u32 = 1 << 5;
+    /// Only donors on the project's allowlist may deposit.
+    pub const FLAG_DONOR_ALLOWLIST: u32 = 1 << 6;
+
+    /// Return `true` if every bit of `flag` is set.
+    pub fn has_flag(&self, flag: u32) -> bool {
+        self.flags & flag == flag
+    }
+
+    /// Set or clear `flag`.
+    pub fn set_flag(&mut self, flag: u32, on: bool) {
+        if on {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+    }
+
+    /// Pack into a `u128` laid out (most significant first) as
+    /// `version:8 | status:8 | reserved:16 | flags:32 | released_bps:32 | donation_count:32`.
+    pub fn pack(&self) -> u128 {
+        ((Self::ENCODING_VERSION as u128) << 120)
+            | ((self.status.code() as u128) << 112)
+            | ((self.flags as u128) << 64)
+            | ((self.released_bps as u128) << 32)
+            | self.donation_count as u128
+    }
+
+    /// Inverse of [`ProjectState::pack`]. Returns `None` if the version byte
+    /// or status code is not recognised.
+    pub fn unpack(packed: u128) -> Option<Self> {
+        if (packed >> 120) as u8 != Self::ENCODING_VERSION {
+            return None;
+        }
+        Some(ProjectState {
+            status: ProjectStatus::from_code((packed >> 112) as u8)?,
+            flags: (packed >> 64) as u32,
+            released_bps: (packed >> 32) as u32,
+            donation_count: packed as u32,
+        })
+    }
 }
 
 /// Full on-chain representation of a funding project.
@@ -81,15 +253,19 @@ pub struct Project {
     pub creator: Address,
     /// Ordered list of SAC token addresses this project accepts.
     /// Set once at registration; cannot be changed after creation.
-    /// Length: 1–10 tokens.
+    /// Length: 1 to `ProtocolConfig::max_accepted_tokens` tokens.
     pub accepted_tokens: soroban_sdk::Vec<Address>,
-    /// Funding goal expressed in the *first* accepted token's units.
-    /// Used as a reference denominator; cross-token goals require off-chain logic.
+    /// Funding goal, in the unit given by `goal_unit`.
     pub goal: i128,
     /// Content hash (e.g. IPFS CID digest) of proof artifacts.
     pub proof_hash: soroban_sdk::BytesN<32>,
     /// Ledger timestamp by which the project must be completed.
     pub deadline: u64,
+    /// Number of distinct oracle attestations required before release.
+    /// Bounded at registration by [`ProtocolConfig::max_quorum`].
+    pub quorum: u32,
+    /// Unit `goal` is denominated in.
+    pub goal_unit: GoalUnit,
     /// Current lifecycle state.
     pub status: ProjectStatus,
     /// Count of unique (token, donator) pairs that have donated.
@@ -109,6 +285,625 @@ impl Project {
     }
 }
 
+/// Why and for how long the protocol is paused — returned by
+/// `get_pause_info`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseInfo {
+    /// Admin who paused the protocol.
+    pub by: Address,
+    /// Hash of the off-chain incident report or announcement.
+    pub reason_hash: BytesN<32>,
+    /// Ledger timestamp the pause began.
+    pub paused_at: u64,
+    /// Timestamp the admin expects to unpause at, if announced. Advisory
+    /// only: the pause lasts until `unpause` is called.
+    pub expected_resume: Option<u64>,
+}
+
+/// Planned pause announced ahead of time — returned by `get_maintenance`.
+///
+/// The protocol behaves as paused while `start <= now < end`, without an
+/// admin having to call `pause` and `unpause`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaintenanceWindow {
+    /// Admin who scheduled the window.
+    pub by: Address,
+    pub start: u64,
+    pub end: u64,
+    /// Hash of the off-chain maintenance announcement.
+    pub reason_hash: BytesN<32>,
+}
+
+impl MaintenanceWindow {
+    /// Return `true` if `now` falls inside the window.
+    pub fn is_active(&self, now: u64) -> bool {
+        self.start <= now && now < self.end
+    }
+}
+
+/// Guardians able to rotate a lost SuperAdmin key — returned by
+/// `get_guardians`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianSet {
+    /// Distinct guardian addresses; at most [`GuardianSet::MAX_GUARDIANS`].
+    pub guardians: Vec<Address>,
+    /// Approvals needed before a recovery may be executed.
+    pub threshold: u32,
+    /// Seconds between the first approval and execution, during which the
+    /// current SuperAdmin can cancel.
+    pub delay: u64,
+}
+
+impl GuardianSet {
+    /// Upper bound on the size of a guardian set.
+    pub const MAX_GUARDIANS: u32 = 10;
+    /// Shortest allowed recovery delay (7 days).
+    pub const MIN_DELAY: u64 = 7 * 24 * 60 * 60;
+
+    /// Return `true` if the set is non-empty, duplicate-free, within
+    /// [`GuardianSet::MAX_GUARDIANS`], `threshold` lies in
+    /// `1..=guardians.len()` and `delay` is at least
+    /// [`GuardianSet::MIN_DELAY`].
+    pub fn is_valid(&self) -> bool {
+        let n = self.guardians.len();
+        if n == 0 || n > Self::MAX_GUARDIANS || self.threshold == 0 || self.threshold > n {
+            return false;
+        }
+        if self.delay < Self::MIN_DELAY {
+            return false;
+        }
+        for i in 0..n {
+            let guardian = self.guardians.get_unchecked(i);
+            if self.guardians.last_index_of(&guardian) != Some(i) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A guardian-initiated SuperAdmin rotation waiting for approvals and its
+/// delay — returned by `get_recovery`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRequest {
+    pub new_super_admin: Address,
+    /// Guardians that have approved this rotation.
+    pub approvals: Vec<Address>,
+    /// Earliest ledger timestamp at which the rotation may be executed.
+    pub eta: u64,
+}
+
+/// Successor arrangement that hands the SuperAdmin role over if the
+/// current holder stops signing — returned by `get_dead_man_switch`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeadManSwitch {
+    /// Address allowed to claim the role.
+    pub successor: Address,
+    /// Seconds without a SuperAdmin heartbeat after which the successor may
+    /// announce a claim.
+    pub period: u64,
+    /// Seconds between the announcement and the claim.
+    pub announce_delay: u64,
+    /// Ledger timestamp of the last SuperAdmin-signed call.
+    pub last_heartbeat: u64,
+    /// Earliest timestamp the announced claim may complete, if announced.
+    pub claim_eta: Option<u64>,
+}
+
+impl DeadManSwitch {
+    /// Return `true` if no heartbeat has occurred for longer than `period`.
+    pub fn is_lapsed(&self, now: u64) -> bool {
+        now > self.last_heartbeat.saturating_add(self.period)
+    }
+}
+
+/// One entry of the administrative audit log — returned by
+/// `get_admin_log`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminAction {
+    /// Position in the log; consecutive across every action ever recorded.
+    pub seq: u64,
+    pub timestamp: u64,
+    pub actor: Address,
+    /// Short identifier of the action, matching its event topic where one
+    /// exists (e.g. `role_set`, `pause`, `forced`).
+    pub action: Symbol,
+    /// Address the action applied to, if any.
+    pub target: Option<Address>,
+    /// Project the action applied to, if any.
+    pub project_id: Option<u64>,
+}
+
+/// Build metadata of the deployed contract — returned by `version`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractVersion {
+    /// Semantic version of the `pifp_protocol` crate.
+    pub version: String,
+    /// Abbreviated git commit the Wasm was built from, or `unknown`.
+    pub commit: String,
+    /// Storage layout version the build reads and writes.
+    pub schema: u32,
+}
+
+impl ContractVersion {
+    /// Semantic version compiled into this build.
+    pub const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+    /// Git commit compiled into this build.
+    #[cfg(not(test))]
+    pub const COMMIT: &'static str = env!("PIFP_GIT_COMMIT");
+    /// Test builds pin the commit so recorded snapshots do not change
+    /// with every checkout.
+    #[cfg(test)]
+    pub const COMMIT: &'static str = "test";
+    /// Storage layout version of this build; see [`crate::upgrade`].
+    pub const SCHEMA: u32 = 2;
+
+    /// Metadata of the running build.
+    pub fn current(env: &Env) -> Self {
+        ContractVersion {
+            version: String::from_str(env, Self::VERSION),
+            commit: String::from_str(env, Self::COMMIT),
+            schema: Self::SCHEMA,
+        }
+    }
+}
+
+/// Protocol-wide tunables, managed by Admin via `set_protocol_config`.
+///
+/// Stored in instance storage; until an admin writes one, the defaults from
+/// [`ProtocolConfig::default_config`] apply.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolConfig {
+    /// Upper bound on the per-project verification quorum.
+    pub max_quorum: u32,
+    /// Upper bound on the number of accepted tokens per project.
+    /// May not exceed [`ProtocolConfig::MAX_ACCEPTED_TOKENS_LIMIT`].
+    pub max_accepted_tokens: u32,
+    /// Basis points of a project's escrow paid to a keeper for each paid
+    /// `bump_project` call. `0` disables rent self-funding.
+    /// May not exceed [`ProtocolConfig::MAX_RENT_SLICE_BPS`].
+    pub rent_slice_bps: u32,
+    /// Minimum seconds between two paid bumps of the same project.
+    pub rent_bump_interval: u64,
+    /// Seconds a queued `force_status` must wait before it can be applied.
+    pub force_status_delay: u64,
+    /// SEP-40 price oracle used to value deposits in a reference unit.
+    /// `None` disables valuation.
+    pub price_feed: Option<Address>,
+    /// Length in seconds of the periods a donor's giving streak counts.
+    /// Must be non-zero.
+    pub streak_period: u64,
+    /// Premium, in basis points of each deposit, that insured projects pay
+    /// into the insurance pool. Locked per project when it opts in.
+    /// May not exceed [`ProtocolConfig::MAX_INSURANCE_PREMIUM_BPS`].
+    pub insurance_premium_bps: u32,
+    /// Maximum number of addresses holding `Role::Admin` at once. Only the
+    /// SuperAdmin may raise it.
+    pub max_admins: u32,
+    /// Token keeper rewards are paid in. `None` disables keeper rewards.
+    pub keeper_reward_token: Option<Address>,
+    /// Reward paid from the keeper pool for each crank call that does work
+    /// (e.g. a `cleanup` that removes entries). Must not be negative.
+    pub keeper_reward: i128,
+    /// Destination of swept dust. `None` until an admin sets one.
+    pub treasury: Option<Address>,
+    /// Largest per-token balance a settled project may still hold and be
+    /// cleaned up; the remainder is moved to the dust ledger. `0` requires
+    /// balances to be fully drained. Must not be negative.
+    pub dust_threshold: i128,
+    /// Protocol fee, in basis points of each deposit, sent to the treasury.
+    /// Only charged while a treasury is set; individual projects may be
+    /// given an override. May not exceed [`ProtocolConfig::MAX_FEE_BPS`].
+    pub fee_bps: u32,
+    /// Most of any one token that releases may unlock per `release_window`;
+    /// releases past it queue for a later window. `0` disables throttling.
+    /// Must not be negative.
+    pub release_cap: i128,
+    /// Length in seconds of the release throttling window. Must be non-zero.
+    pub release_window: u64,
+    /// Largest refund claim whose cost is reimbursed from the sponsorship
+    /// pool. `0` disables sponsorship. Must not be negative.
+    pub sponsor_threshold: i128,
+    /// Reimbursement paid, in the refunded token, per sponsored claim.
+    /// Must not be negative.
+    pub sponsor_amount: i128,
+    /// Most the sponsorship pool of one token pays out per `sponsor_period`.
+    /// Must not be negative.
+    pub sponsor_budget: i128,
+    /// Length in seconds of the sponsorship budget period. Must be non-zero.
+    pub sponsor_period: u64,
+    /// Seconds a project's data must stay on chain after it reaches a
+    /// terminal state before `cleanup` may remove it. Only the SuperAdmin
+    /// may lower it.
+    pub retention_period: u64,
+    /// If set, registration calls `decimals()` and `name()` on every
+    /// accepted token and rejects addresses that do not answer like a token
+    /// contract, instead of letting them trap deposits later.
+    pub strict_tokens: bool,
+    /// If set, registration rejects accepted tokens that are not on the
+    /// list managed with `add_allowed_token` / `remove_allowed_token`.
+    /// Projects registered earlier keep their tokens.
+    pub token_allowlist: bool,
+    /// Most seconds a creator may push their project's deadline out by in
+    /// one `extend_deadline` call; longer extensions need an Admin.
+    pub max_deadline_extension: u64,
+    /// Seconds a verified release is held for auditors to `raise_dispute`
+    /// before `close_challenge` may apply it. `0` releases immediately.
+    /// Projects with a donor vote use the vote instead.
+    pub challenge_period: u64,
+    /// If set, releases credit the creator's claimable balance instead of
+    /// transferring to them, and creators collect with `withdraw`. A token
+    /// that reverts transfers to the creator then cannot block a release.
+    pub pull_payments: bool,
+    /// Contract `migrate_escrow` may move projects to. `None` disables
+    /// migration. Only the SuperAdmin may change it.
+    pub successor: Option<Address>,
+    /// Fee, in basis points of each release, held for the treasury. Only
+    /// charged while a treasury is set. Only the SuperAdmin may change it.
+    /// May not exceed [`ProtocolConfig::MAX_FEE_BPS`].
+    pub release_fee_bps: u32,
+    /// Seconds a `queue_action` must wait before it can be executed. While
+    /// non-zero, fee changes and upgrades must be queued rather than made
+    /// directly. Only the SuperAdmin may change it, and lowering it must
+    /// itself be queued.
+    pub action_delay: u64,
+    /// Token fee revenue is converted into before reaching the treasury.
+    /// `None` sends fees to the treasury in the token they were paid in.
+    /// Requires `dex_router` and `price_feed`.
+    pub reserve_token: Option<Address>,
+    /// Swap adapter fee conversions go through; see [`crate::dex`].
+    pub dex_router: Option<Address>,
+    /// Fees of one token that must be held before `convert_fees` swaps
+    /// them. Must not be negative.
+    pub conversion_threshold: i128,
+    /// Largest shortfall against the price feed's quote, in basis points,
+    /// a fee conversion accepts.
+    /// May not exceed [`ProtocolConfig::MAX_SLIPPAGE_BPS`].
+    pub max_slippage_bps: u32,
+}
+
+impl ProtocolConfig {
+    /// Default maximum number of oracle attestations a project may require.
+    pub const DEFAULT_MAX_QUORUM: u32 = 5;
+    /// Default maximum number of accepted tokens per project.
+    pub const DEFAULT_MAX_ACCEPTED_TOKENS: u32 = 10;
+    /// Hard ceiling on `max_accepted_tokens`. Full balance reads beyond this
+    /// size must go through `get_balances_page`.
+    pub const MAX_ACCEPTED_TOKENS_LIMIT: u32 = 50;
+    /// Hard ceiling on `rent_slice_bps` (1% of escrow per paid bump).
+    pub const MAX_RENT_SLICE_BPS: u32 = 100;
+    /// Default minimum interval between paid bumps (30 days).
+    pub const DEFAULT_RENT_BUMP_INTERVAL: u64 = 30 * 86_400;
+    /// Default timelock on forced status transitions (2 days).
+    pub const DEFAULT_FORCE_STATUS_DELAY: u64 = 2 * 86_400;
+    /// Default donor streak period (30 days).
+    pub const DEFAULT_STREAK_PERIOD: u64 = 30 * 86_400;
+    /// Default insurance premium (1% of each deposit).
+    pub const DEFAULT_INSURANCE_PREMIUM_BPS: u32 = 100;
+    /// Hard ceiling on `insurance_premium_bps` (10% of each deposit).
+    pub const MAX_INSURANCE_PREMIUM_BPS: u32 = 1_000;
+    /// Default cap on simultaneous Admin role holders.
+    pub const DEFAULT_MAX_ADMINS: u32 = 5;
+    /// Default release throttling window (1 day).
+    pub const DEFAULT_RELEASE_WINDOW: u64 = 86_400;
+    /// Default sponsorship budget period (30 days).
+    pub const DEFAULT_SPONSOR_PERIOD: u64 = 30 * 86_400;
+    /// Hard ceiling on `fee_bps` and per-project fee overrides (10%).
+    pub const MAX_FEE_BPS: u32 = 1_000;
+    /// Default slippage bound on fee conversions (1%).
+    pub const DEFAULT_MAX_SLIPPAGE_BPS: u32 = 100;
+    /// Hard ceiling on `max_slippage_bps` (10%).
+    pub const MAX_SLIPPAGE_BPS: u32 = 1_000;
+    /// Maximum number of tokens on the registration allowlist.
+    pub const MAX_ALLOWED_TOKENS: u32 = 50;
+    /// Default creator deadline extension limit (30 days).
+    pub const DEFAULT_MAX_DEADLINE_EXTENSION: u64 = 30 * 86_400;
+    /// Furthest a project deadline may lie in the future (5 years).
+    pub const MAX_DEADLINE_HORIZON: u64 = 5 * 365 * 86_400;
+
+    /// Configuration in effect before any admin override.
+    pub fn default_config() -> Self {
+        ProtocolConfig {
+            max_quorum: Self::DEFAULT_MAX_QUORUM,
+            max_accepted_tokens: Self::DEFAULT_MAX_ACCEPTED_TOKENS,
+            rent_slice_bps: 0,
+            rent_bump_interval: Self::DEFAULT_RENT_BUMP_INTERVAL,
+            force_status_delay: Self::DEFAULT_FORCE_STATUS_DELAY,
+            price_feed: None,
+            streak_period: Self::DEFAULT_STREAK_PERIOD,
+            insurance_premium_bps: Self::DEFAULT_INSURANCE_PREMIUM_BPS,
+            max_admins: Self::DEFAULT_MAX_ADMINS,
+            keeper_reward_token: None,
+            keeper_reward: 0,
+            treasury: None,
+            dust_threshold: 0,
+            fee_bps: 0,
+            release_cap: 0,
+            release_window: Self::DEFAULT_RELEASE_WINDOW,
+            sponsor_threshold: 0,
+            sponsor_amount: 0,
+            sponsor_budget: 0,
+            sponsor_period: Self::DEFAULT_SPONSOR_PERIOD,
+            retention_period: 0,
+            strict_tokens: false,
+            token_allowlist: false,
+            max_deadline_extension: Self::DEFAULT_MAX_DEADLINE_EXTENSION,
+            challenge_period: 0,
+            pull_payments: false,
+            successor: None,
+            release_fee_bps: 0,
+            action_delay: 0,
+            reserve_token: None,
+            dex_router: None,
+            conversion_threshold: 0,
+            max_slippage_bps: Self::DEFAULT_MAX_SLIPPAGE_BPS,
+        }
+    }
+}
+
+/// External verifier registered for a single project.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectVerifier {
+    /// Allowlisted contract implementing `VerifierInterface`.
+    pub verifier: Address,
+    /// Hash of the verifying key the verifier must use for this project.
+    pub vk_hash: BytesN<32>,
+}
+
+/// Re-attestation schedule for an ongoing (perpetual) project.
+///
+/// Ongoing projects never move to `Completed`; each time the oracle quorum is
+/// met the project becomes (or stays) `Active` and `last_attested` is renewed.
+/// The project's stream is considered active only while
+/// `now <= last_attested + period`, and a round releases the escrow to the
+/// creator only if it renews a stream that is still active.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OngoingCadence {
+    /// Maximum number of seconds between successful attestations.
+    pub period: u64,
+    /// Ledger timestamp of the last attestation round that met quorum,
+    /// or `0` if none has yet.
+    pub last_attested: u64,
+}
+
+impl OngoingCadence {
+    /// Returns `true` if the last attestation is still within its period.
+    pub fn is_active(&self, now: u64) -> bool {
+        self.last_attested != 0 && now <= self.last_attested.saturating_add(self.period)
+    }
+}
+
+/// Per-creator aggregates across every project they have registered.
+///
+/// Updated in place as each of the creator's projects moves through its
+/// lifecycle, so admins and donors can read a creator's track record without
+/// replaying events.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CreatorRecord {
+    /// Number of projects registered by this creator.
+    pub projects_created: u32,
+    /// Number of projects that reached `Completed`.
+    pub projects_completed: u32,
+    /// Number of projects that reached `Expired`.
+    pub projects_expired: u32,
+    /// Number of projects cancelled before completion.
+    pub projects_cancelled: u32,
+    /// Number of times an admin has flagged this creator for fraud.
+    pub fraud_flags: u32,
+}
+
+/// Per-donor giving counters across every project.
+///
+/// Updated on each deposit so loyalty features can read a donor's record
+/// without an indexer. Periods are `ProtocolConfig::streak_period` long and
+/// counted from the Unix epoch.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DonorStats {
+    /// Number of deposits made, across projects and tokens.
+    pub deposit_count: u32,
+    /// Consecutive periods, ending with `last_period`, containing a deposit.
+    pub current_streak: u32,
+    /// Longest streak this donor has ever reached.
+    pub longest_streak: u32,
+    /// Index of the period of the donor's most recent deposit.
+    pub last_period: u64,
+    /// Sum of the deposit-time reference value of every priced deposit.
+    pub total_value: i128,
+}
+
+impl DonorStats {
+    /// Record one deposit made in `period`, valued at `value` (`0` if
+    /// unpriced).
+    pub fn record(&mut self, period: u64, value: i128) {
+        if self.deposit_count == 0 || period > self.last_period.saturating_add(1) {
+            self.current_streak = 1;
+        } else if period == self.last_period.saturating_add(1) {
+            self.current_streak += 1;
+        }
+        if self.current_streak > self.longest_streak {
+            self.longest_streak = self.current_streak;
+        }
+        self.last_period = period;
+        self.deposit_count = self.deposit_count.saturating_add(1);
+        self.total_value = self.total_value.saturating_add(value);
+    }
+}
+
+/// What a [`LedgerEntry`] records.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LedgerKind {
+    /// Tokens the donor deposited, before fees and premiums.
+    Deposit,
+    /// Tokens refunded to the donor, including insurance cover.
+    Refund,
+    /// Part of a deposit tagged as eligible for matching.
+    Match,
+}
+
+/// One timestamped movement in a donor's contribution ledger.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LedgerEntry {
+    /// Ledger timestamp of the movement.
+    pub at: u64,
+    pub project_id: u64,
+    pub token: Address,
+    pub kind: LedgerKind,
+    pub amount: i128,
+}
+
+/// A donor's totals in one token over a statement period.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatementLine {
+    pub token: Address,
+    /// Sum of deposits made in the period.
+    pub deposited: i128,
+    /// Sum of refunds received in the period.
+    pub refunded: i128,
+    /// Sum of deposits tagged for matching in the period.
+    pub matched: i128,
+}
+
+/// A donor's activity between `from_ts` and `to_ts`, inclusive, with one
+/// line per token they moved in that period.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonorStatement {
+    pub donor: Address,
+    pub from_ts: u64,
+    pub to_ts: u64,
+    pub lines: Vec<StatementLine>,
+}
+
+/// A donor's privacy choices. Every flag defaults to `false`, i.e. visible.
+///
+/// Features that rank, list or reward donors must consult these before
+/// including an address.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DonorPreferences {
+    /// Leave the donor out of leaderboards and other rankings.
+    pub hide_from_leaderboards: bool,
+    /// Leave the donor out of donor listings such as `get_project_donors`.
+    pub hide_from_listings: bool,
+    /// Do not mint donation certificates to the donor.
+    pub no_certificates: bool,
+}
+
+/// Where a donor's non-refundable giving goes if the project expires.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GrantRoute {
+    /// Paid to the project creator, who keeps it despite missing the goal.
+    Creator,
+    /// Added to the protocol's commons pool for the admins to disburse.
+    Commons,
+}
+
+/// A donor's non-refundable giving to a project in one token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grant {
+    /// Part of the donor's contribution that is never refunded.
+    pub amount: i128,
+    /// Where that part goes if the project expires.
+    pub route: GrantRoute,
+}
+
+/// One token's line in a [`ProjectReport`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenReport {
+    pub token: Address,
+    /// Total deposited by donors, before insurance premiums.
+    pub raised: i128,
+    /// Share of the escrow released to the creator.
+    pub released: i128,
+    /// Amount returned to donors.
+    pub refunded: i128,
+    /// Escrow neither released nor refunded.
+    pub outstanding: i128,
+}
+
+/// Running totals of one token entering and leaving a project's escrow —
+/// returned by `get_token_flows`.
+///
+/// Every path that moves project funds out checks that `outflow` never
+/// exceeds `inflow`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TokenFlows {
+    /// Credited to the escrow by deposits, net of premiums and fees.
+    pub inflow: i128,
+    /// Taken out of the escrow by releases, refunds, grants, rent and dust.
+    pub outflow: i128,
+    /// Part of `outflow` paid to the creator by releases and grants,
+    /// including the release fee skimmed from it.
+    pub released: i128,
+    /// Part of `outflow` returned to donors by refunds.
+    pub refunded: i128,
+    /// Ledger sequence of the last inflow or outflow; `0` if none yet.
+    pub updated_ledger: u32,
+}
+
+/// Fee settings returned by `get_fee_config`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    /// Recipient of fee revenue; no fees are charged while `None`.
+    pub treasury: Option<Address>,
+    /// Fee on deposits, before per-project overrides.
+    pub deposit_fee_bps: u32,
+    /// Fee on releases.
+    pub release_fee_bps: u32,
+}
+
+/// Snapshot of one token's escrow for a project — returned by
+/// `get_escrow_proof` so composing contracts can judge escrow health in a
+/// single read.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowProof {
+    pub project_id: u64,
+    pub token: Address,
+    /// Escrowed balance currently recorded.
+    pub balance: i128,
+    /// Ledger sequence the balance last changed in; `0` if it never has.
+    pub updated_ledger: u32,
+    pub inflow: i128,
+    pub outflow: i128,
+}
+
+/// Consolidated funding picture of a project — returned by
+/// `get_project_report`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectReport {
+    pub project_id: u64,
+    pub status: ProjectStatus,
+    /// One entry per accepted token, in accepted-token order.
+    pub tokens: Vec<TokenReport>,
+    /// Sum of the deposit-time reference value of every priced deposit.
+    pub raised_value: i128,
+    /// Reference value of the outstanding escrow at current prices; tokens
+    /// the feed cannot price count as zero.
+    pub outstanding_value: i128,
+}
+
 /// Snapshot of all balances for a project — returned by `get_balances`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -124,3 +919,397 @@ pub struct ProjectBalances {
     pub project_id: u64,
     pub balances: Vec<TokenBalance>,
 }
+
+/// One storage entry in a [`StorageReport`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageEntry {
+    /// Short name of the `ProjectKey` variant, e.g. `config` or `balance`.
+    pub kind: Symbol,
+    /// Token the entry belongs to, for per-token keys.
+    pub token: Option<Address>,
+    /// Whether the entry currently exists.
+    pub present: bool,
+    /// XDR size of the key plus value in bytes; `0` when absent.
+    pub size: u32,
+}
+
+/// Per-project storage footprint returned by `storage_report`.
+///
+/// Soroban does not expose remaining TTLs to contracts, so
+/// `last_rent_bump` (the last escrow-funded full extension) is the closest
+/// on-chain signal of archival risk.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageReport {
+    pub project_id: u64,
+    pub entries: Vec<StorageEntry>,
+    /// Number of `Contribution` entries (one per donor and token).
+    pub contributions: u32,
+    /// Sum of `size` over `entries`; contributions are not included.
+    pub total_size: u32,
+    pub last_rent_bump: Option<u64>,
+}
+
+/// Proof of impact a creator submitted for the oracles to adjudicate.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingProof {
+    pub proof_hash: BytesN<32>,
+    /// Ledger timestamp of the submission.
+    pub submitted_at: u64,
+}
+
+impl PendingProof {
+    /// Ledgers a submission stays available for review (~14 days); after
+    /// that it lapses and must be resubmitted.
+    pub const REVIEW_LEDGERS: u32 = 14 * 17_280;
+}
+
+/// A SuperAdmin status override waiting out its timelock.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingStatusChange {
+    pub new_status: ProjectStatus,
+    /// Hash of the off-chain justification.
+    pub reason_hash: BytesN<32>,
+    /// Earliest ledger timestamp at which the change may be applied.
+    pub eta: u64,
+}
+
+/// A sensitive change that, once queued with `queue_action`, waits out
+/// `ProtocolConfig::action_delay` before `execute_action` applies it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimelockedAction {
+    /// Set the deposit and release fee rates, in basis points.
+    SetFees(u32, u32),
+    /// Replace the contract's Wasm, as `upgrade` does.
+    Upgrade(BytesN<32>),
+    /// Pay `amount` of `token` held outside every escrow and pool to
+    /// `recipient`: `(token, recipient, amount)`.
+    EmergencyWithdraw(Address, Address, i128),
+    /// Change `ProtocolConfig::action_delay`.
+    SetDelay(u64),
+    /// Grant a role other than `SuperAdmin`: `(target, role)`.
+    GrantRole(Address, Role),
+    /// Revoke whatever role `target` holds.
+    RevokeRole(Address),
+    /// Replace the council with `(members, threshold)`. On a single-key
+    /// deployment this hands the SuperAdmin role over to the council.
+    SetCouncil(Vec<Address>, u32),
+}
+
+/// A timelocked action waiting to be executed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedAction {
+    pub action: TimelockedAction,
+    /// SuperAdmin or council member that queued the action.
+    pub proposer: Address,
+    /// Earliest ledger timestamp at which the action may be executed.
+    pub eta: u64,
+    /// Council members that approved the action, the proposer first.
+    pub approvals: Vec<Address>,
+}
+
+/// Council that holds the SuperAdmin role in place of a single key.
+///
+/// Its members queue and approve timelocked actions; one executes once
+/// `threshold` of them have approved it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Council {
+    pub members: Vec<Address>,
+    /// Approvals an action needs before it can execute.
+    pub threshold: u32,
+}
+
+impl Council {
+    /// Upper bound on council size, keeping approval checks cheap.
+    pub const MAX_MEMBERS: u32 = 10;
+}
+
+/// A SuperAdmin escrow migration waiting out its timelock.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingMigration {
+    pub successor: Address,
+    /// Earliest ledger timestamp at which the migration may be applied.
+    pub eta: u64,
+}
+
+/// A migrated project as handed to the successor's `intake_project`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationRecord {
+    /// The project as it stood before migrating.
+    pub project: Project,
+    /// Share of the escrow already released to the creator, in basis points.
+    pub released_bps: u32,
+    /// Milestones the project registered with; empty if none.
+    pub milestones: Vec<Milestone>,
+    /// Amount of each accepted token transferred with the project.
+    pub balances: Vec<TokenBalance>,
+}
+
+/// M-of-N set of addresses controlling a project in place of its creator.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectOwners {
+    /// Distinct owner addresses; at most [`ProjectOwners::MAX_OWNERS`].
+    pub owners: Vec<Address>,
+    /// Number of owners that must sign a controlled action.
+    pub threshold: u32,
+}
+
+impl ProjectOwners {
+    /// Upper bound on the size of an owner set.
+    pub const MAX_OWNERS: u32 = 10;
+
+    /// Return `true` if the set is non-empty, duplicate-free, within
+    /// [`ProjectOwners::MAX_OWNERS`], and `threshold` lies in `1..=owners.len()`.
+    pub fn is_valid(&self) -> bool {
+        let n = self.owners.len();
+        if n == 0 || n > Self::MAX_OWNERS || self.threshold == 0 || self.threshold > n {
+            return false;
+        }
+        for i in 0..n {
+            let owner = self.owners.get_unchecked(i);
+            if self.owners.last_index_of(&owner) != Some(i) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Opt-in donor vote held after verification, before completion.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonorVoteConfig {
+    /// Seconds donors have to object once quorum is met.
+    pub window: u64,
+    /// Share of contribution weight, in basis points, whose objection
+    /// contests the release.
+    pub objection_bps: u32,
+}
+
+/// A donor vote in progress or awaiting resolution.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonorVote {
+    /// Oracle whose attestation met quorum.
+    pub oracle: Address,
+    pub proof_hash: BytesN<32>,
+    /// Share of the escrow released if the vote passes, in basis points;
+    /// `10_000` completes the project.
+    pub released_bps: u32,
+    /// Ledger timestamp at which objections close.
+    pub ends_at: u64,
+    /// Contribution weight that has objected so far.
+    pub objections: i128,
+    /// Gross contributions of every donor in the first accepted token,
+    /// fixed when the vote opens.
+    pub total_weight: i128,
+}
+
+/// Outcome of a deposit, returned by `deposit` and `deposit_grant`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DepositReceipt {
+    /// Amount transferred from the donor.
+    pub transferred: i128,
+    /// Part of it tagged eligible for matching under the project's match
+    /// cap; `0` if the project has none.
+    pub matched: i128,
+}
+
+/// Sponsor funds matching donor deposits to one project.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchPool {
+    pub sponsor: Address,
+    pub project_id: u64,
+    /// Token the pool holds and matches deposits in.
+    pub token: Address,
+    /// Amount matched per deposit, in basis points of the deposit;
+    /// `10_000` matches one to one.
+    pub ratio_bps: u32,
+    /// Most the pool will ever match in total.
+    pub cap: i128,
+    /// Funds left to match with.
+    pub balance: i128,
+    /// Total matched so far.
+    pub matched: i128,
+}
+
+impl MatchPool {
+    /// Upper bound on `ratio_bps`, a ten-to-one match.
+    pub const MAX_RATIO_BPS: u32 = 100_000;
+    /// Upper bound on the number of pools matching one project.
+    pub const MAX_PER_PROJECT: u32 = 5;
+}
+
+/// A verified release held open for auditors to dispute.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Challenge {
+    /// Oracle whose attestation met quorum.
+    pub oracle: Address,
+    pub proof_hash: BytesN<32>,
+    /// Share of the escrow released once the challenge clears, in basis
+    /// points; `10_000` completes the project.
+    pub released_bps: u32,
+    /// Ledger timestamp at which the challenge window closes.
+    pub ends_at: u64,
+    /// Auditor that disputed the release, if any.
+    pub disputed_by: Option<Address>,
+    /// Hash of the evidence the auditor raised.
+    pub evidence_hash: Option<BytesN<32>>,
+}
+
+/// A release held back by the release throttle, in queue order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedRelease {
+    pub project_id: u64,
+    /// Share of escrow the project will have released once this applies;
+    /// `10_000` completes it.
+    pub released_bps: u32,
+    /// Oracle whose attestation met quorum.
+    pub oracle: Address,
+    pub proof_hash: BytesN<32>,
+}
+
+/// Display metadata of a project, for explorers rendering project pages.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectMetadata {
+    /// Short display name, at most [`ProjectMetadata::MAX_NAME_LEN`] bytes.
+    pub name: String,
+    /// Hash of the off-chain metadata document (description, images, links).
+    pub metadata_hash: BytesN<32>,
+}
+
+impl ProjectMetadata {
+    /// Longest accepted `name`, in bytes.
+    pub const MAX_NAME_LEN: u32 = 64;
+}
+
+/// Donor proposal to extend a project's missed deadline.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtensionVote {
+    /// Deadline the project missed; a new proposal starts if it changes.
+    pub old_deadline: u64,
+    pub new_deadline: u64,
+    /// Contribution weight supporting the extension so far.
+    pub support: i128,
+    /// Gross contributions of every donor in the first accepted token,
+    /// fixed when the proposal opens.
+    pub total_weight: i128,
+}
+
+/// A creator's proposal to replace the milestones of a project, held until
+/// donors back it or an admin decides it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MilestoneAmendment {
+    /// Full replacement list; milestones already released are unchanged.
+    pub milestones: Vec<Milestone>,
+    /// Ledger timestamp after which the proposal can no longer be backed
+    /// or approved.
+    pub ends_at: u64,
+    /// Contribution weight backing the proposal so far.
+    pub support: i128,
+    /// Gross contributions of every donor in the first accepted token,
+    /// fixed when the proposal opens.
+    pub total_weight: i128,
+}
+
+impl MilestoneAmendment {
+    /// Seconds donors have to back a proposal.
+    pub const VOTE_WINDOW: u64 = 7 * 86_400;
+}
+
+/// One step of a disbursement schedule.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tranche {
+    /// Share of the escrow this tranche releases, in basis points.
+    pub bps: u32,
+    /// `true` if the tranche waits for an attestation quorum; ungated
+    /// tranches release as soon as every earlier tranche has.
+    pub gated: bool,
+}
+
+/// A slice of a project's goal released once its proof is verified.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Milestone {
+    /// Share of the goal this milestone releases, in goal units.
+    pub amount: i128,
+    /// Proof oracles must submit to verify this milestone.
+    pub proof_hash: BytesN<32>,
+}
+
+/// Admin-defined reusable release schedule, e.g. 20% upfront then 40% on
+/// each of two verified milestones.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisbursementTemplate {
+    pub tranches: Vec<Tranche>,
+}
+
+impl DisbursementTemplate {
+    /// Upper bound on the number of tranches in a template.
+    pub const MAX_TRANCHES: u32 = 10;
+
+    /// Return `true` if the template has `1..=MAX_TRANCHES` non-empty
+    /// tranches summing to 10 000 bps and ends with a gated tranche, so that
+    /// completion always requires verification.
+    pub fn is_valid(&self) -> bool {
+        let n = self.tranches.len();
+        if n == 0 || n > Self::MAX_TRANCHES {
+            return false;
+        }
+        let mut total = 0u32;
+        for tranche in self.tranches.iter() {
+            if tranche.bps == 0 {
+                return false;
+            }
+            total = total.saturating_add(tranche.bps);
+        }
+        total == 10_000 && self.tranches.get_unchecked(n - 1).gated
+    }
+
+    /// Cumulative share released once every tranche before the next gated
+    /// one after `released_bps` has been paid out.
+    ///
+    /// With `released_bps == 0` this is the upfront share; after a quorum it
+    /// is called with the share including the tranche just verified.
+    pub fn release_until_gate(&self, released_bps: u32) -> u32 {
+        let mut cumulative = 0u32;
+        for tranche in self.tranches.iter() {
+            if cumulative >= released_bps && tranche.gated {
+                break;
+            }
+            cumulative += tranche.bps;
+        }
+        cumulative.max(released_bps)
+    }
+
+    /// Cumulative share released after the next gated tranche is verified,
+    /// including any ungated tranches that follow it.
+    pub fn release_next_gate(&self, released_bps: u32) -> u32 {
+        let mut cumulative = 0u32;
+        for tranche in self.tranches.iter() {
+            cumulative += tranche.bps;
+            if cumulative > released_bps && tranche.gated {
+                break;
+            }
+        }
+        self.release_until_gate(cumulative)
+    }
+}
@@ -0,0 +1,89 @@
+//! # Upgrades
+//!
+//! `upgrade` swaps the contract's Wasm in place; `migrate` then brings
+//! storage written by older builds up to the layout this build expects.
+//!
+//! The layout is versioned by [`ContractVersion::SCHEMA`]. The version of
+//! the build that last initialised or migrated storage is recorded in
+//! instance storage and returned by `get_version`; deployments that predate
+//! the record are treated as schema 1. `upgrade` refuses to run while the
+//! recorded schema differs from the running build's, so no migration step
+//! can be skipped by upgrading twice.
+//!
+//! To change the storage layout, raise `SCHEMA` and append one step to
+//! [`MIGRATIONS`]; the compile-time check below keeps the two in lockstep.
+//!
+//! Steps that rewrite per-project entries page through projects
+//! [`storage::PROJECT_PAGE`] at a time, keeping their place in
+//! `MigrationCursor`, so a deployment with many projects migrates over
+//! several `migrate` calls instead of exceeding one invocation's budget.
+//!
+//! | Schema | Layout change                                                  |
+//! |--------|----------------------------------------------------------------|
+//! | 1 → 2  | `ProjConfig` gains `quorum` and `goal_unit`; `ProjState` is packed into a `u128` with flags and released share; `Flows` and `EscrowTotal` are seeded from `TokenBalance` |
+
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+use crate::storage;
+use crate::types::{ContractVersion, ProjectStatus};
+
+/// A one-time storage migration from one schema to the next.
+///
+/// Returns `true` once the step has finished; a paged step returns `false`
+/// and resumes from its cursor on the next `migrate`.
+pub type Migration = fn(&Env) -> bool;
+
+/// `MIGRATIONS[n]` moves storage from schema `n + 1` to `n + 2`.
+pub const MIGRATIONS: &[Migration] = &[v1_pack_projects];
+
+const _: () = assert!(MIGRATIONS.len() as u32 + 1 == ContractVersion::SCHEMA);
+
+/// Run the migration steps from schema `from` towards the current schema,
+/// stopping at the first step that has not finished. Returns the schema
+/// storage has reached.
+pub fn run(env: &Env, from: u32) -> u32 {
+    let mut reached = from.max(1);
+    for step in MIGRATIONS.iter().skip(from.saturating_sub(1) as usize) {
+        if !step(env) {
+            return reached;
+        }
+        reached += 1;
+    }
+    reached
+}
+
+/// `ProjConfig` as written by schema 1.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectConfigV1 {
+    pub id: u64,
+    pub creator: Address,
+    pub accepted_tokens: Vec<Address>,
+    pub goal: i128,
+    pub proof_hash: BytesN<32>,
+    pub deadline: u64,
+}
+
+/// `ProjState` as written by schema 1, a field map rather than packed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProjectStateV1 {
+    pub status: ProjectStatus,
+    pub donation_count: u32,
+}
+
+/// Schema 1 → 2: rewrite the next page of projects in the current layout.
+fn v1_pack_projects(env: &Env) -> bool {
+    let count = storage::get_project_count(env);
+    let start = storage::get_migration_cursor(env);
+    let end = count.min(start.saturating_add(storage::PROJECT_PAGE as u64));
+    for id in start..end {
+        storage::migrate_project_v1(env, id);
+    }
+    if end < count {
+        storage::set_migration_cursor(env, Some(end));
+        return false;
+    }
+    storage::set_migration_cursor(env, None);
+    true
+}
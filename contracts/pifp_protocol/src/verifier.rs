@@ -0,0 +1,52 @@
+//! # Verifier
+//!
+//! Pluggable proof verification for `verify_and_release`.
+//!
+//! By default a submission is checked by comparing it to the project's stored
+//! `proof_hash` (the mocked verification path). A project may instead register
+//! its own verifier contract together with a verifying-key hash; submissions
+//! are then forwarded to that contract through [`VerifierInterface`].
+//!
+//! Only verifier contracts on the admin-managed allowlist may be registered,
+//! and a verifier that is removed from the allowlist stops being accepted for
+//! verification immediately.
+
+use soroban_sdk::{contractclient, BytesN, Env};
+
+use crate::storage;
+use crate::types::ProjectConfig;
+use crate::Error;
+
+/// Interface every external verifier contract must implement.
+#[contractclient(name = "VerifierClient")]
+pub trait VerifierInterface {
+    /// Return `true` if `proof` is valid for `public_input` under the
+    /// verifying key identified by `vk_hash`.
+    fn verify(env: Env, vk_hash: BytesN<32>, public_input: BytesN<32>, proof: BytesN<32>) -> bool;
+}
+
+/// Check `submitted` against the project's verification path.
+///
+/// `expected` is the proof currently due: the project's `proof_hash`, or
+/// that of its next milestone. Uses the project's registered external
+/// verifier if one exists, otherwise falls back to hash equality.
+pub fn verify_proof(
+    env: &Env,
+    config: &ProjectConfig,
+    expected: &BytesN<32>,
+    submitted: &BytesN<32>,
+) -> bool {
+    match storage::get_project_verifier(env, config.id) {
+        Some(project_verifier) => {
+            if !storage::is_verifier_approved(env, &project_verifier.verifier) {
+                soroban_sdk::panic_with_error!(env, Error::VerifierNotApproved);
+            }
+            VerifierClient::new(env, &project_verifier.verifier).verify(
+                &project_verifier.vk_hash,
+                expected,
+                submitted,
+            )
+        }
+        None => submitted == expected,
+    }
+}